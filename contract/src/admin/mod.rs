@@ -0,0 +1,279 @@
+//! Contract Administration Module
+//!
+//! This module tracks who administers a deployment and lets that admin
+//! upgrade the contract WASM, hand the role over, tune storage TTLs, set
+//! the platform fee and migrate stored records between schema versions.
+//!
+//! # Features
+//! - Admin recorded once at initialization
+//! - Two-step admin transfer (nominate, then accept)
+//! - WASM upgrades guarded by the admin
+//! - Schema versioning with a `migrate` hook run after upgrades
+//! - Reconciliation of bounty escrow the migration could not account for
+//!
+//! The admin is an ordinary `Address`, so a platform-wide multisig account
+//! contract can hold the role and authorize upgrades with its own policy.
+
+pub mod storage;
+pub mod types;
+
+use soroban_sdk::{Address, BytesN, Env, Symbol};
+
+use crate::admin::storage::{
+    clear_pending_admin, get_admin, get_pending_admin, get_schema_version, set_admin,
+    set_pending_admin, set_platform_fee as store_platform_fee, set_schema_version,
+};
+use crate::admin::types::{
+    AdminTransferStartedEvent, AdminTransferredEvent, ContractUpgradedEvent, EscrowReconciledEvent,
+    PlatformFee, PlatformFeeUpdatedEvent, SchemaMigratedEvent,
+};
+use crate::ttl::{self, TtlConfig};
+
+pub use types::AdminError;
+
+/// Schema version written by this release
+///
+/// - 1: every guild, membership, proposal, vote and delegation kept in a few
+///   contract-wide maps; counters in persistent storage
+/// - 2: per-entity keys with paginated indexes; counters in instance storage
+/// - 3: per-bounty escrow state and per-token escrow totals
+pub const SCHEMA_VERSION: u32 = 3;
+
+/// Highest platform fee the admin can set, in basis points (10%)
+pub const MAX_PLATFORM_FEE_BPS: u32 = 1_000;
+
+/// Record the contract admin on a fresh deployment
+///
+/// The admin must authorize the call, so a front-running caller cannot
+/// name an arbitrary address before the deployer does.
+///
+/// # Errors
+/// - `AdminError::AlreadyInitialized` if an admin has already been recorded
+pub fn initialize(env: &Env, admin: &Address) -> Result<(), AdminError> {
+    if get_admin(env).is_some() {
+        return Err(AdminError::AlreadyInitialized);
+    }
+    admin.require_auth();
+
+    set_admin(env, admin);
+    set_schema_version(env, SCHEMA_VERSION);
+    Ok(())
+}
+
+/// Load the admin and require its authorization
+///
+/// # Errors
+/// - `AdminError::NotInitialized` if no admin has been recorded
+pub fn require_admin(env: &Env) -> Result<Address, AdminError> {
+    let admin = get_admin(env).ok_or(AdminError::NotInitialized)?;
+    admin.require_auth();
+    Ok(admin)
+}
+
+/// Nominate a new admin; the role moves once they accept
+///
+/// # Errors
+/// - `AdminError::NotInitialized` if no admin has been recorded
+pub fn transfer_admin(env: &Env, new_admin: Address) -> Result<bool, AdminError> {
+    let admin = require_admin(env)?;
+
+    set_pending_admin(env, &new_admin);
+
+    env.events().publish(
+        (
+            Symbol::new(env, "admin"),
+            Symbol::new(env, "transfer_started"),
+        ),
+        AdminTransferStartedEvent {
+            current_admin: admin,
+            pending_admin: new_admin,
+        },
+    );
+
+    Ok(true)
+}
+
+/// Accept a pending admin nomination
+///
+/// # Errors
+/// - `AdminError::NotInitialized` if no admin has been recorded
+/// - `AdminError::NoPendingAdmin` if nobody has been nominated
+pub fn accept_admin(env: &Env) -> Result<bool, AdminError> {
+    let previous_admin = get_admin(env).ok_or(AdminError::NotInitialized)?;
+    let pending = get_pending_admin(env).ok_or(AdminError::NoPendingAdmin)?;
+    pending.require_auth();
+
+    set_admin(env, &pending);
+    clear_pending_admin(env);
+
+    env.events().publish(
+        (Symbol::new(env, "admin"), Symbol::new(env, "transferred")),
+        AdminTransferredEvent {
+            previous_admin,
+            new_admin: pending,
+        },
+    );
+
+    Ok(true)
+}
+
+/// Replace the contract WASM
+///
+/// Run `migrate` afterwards so stored records match the new code.
+///
+/// # Errors
+/// - `AdminError::NotInitialized` if no admin has been recorded
+pub fn upgrade(env: &Env, new_wasm_hash: BytesN<32>) -> Result<bool, AdminError> {
+    require_admin(env)?;
+
+    env.deployer()
+        .update_current_contract_wasm(new_wasm_hash.clone());
+
+    env.events().publish(
+        (Symbol::new(env, "admin"), Symbol::new(env, "upgraded")),
+        ContractUpgradedEvent { new_wasm_hash },
+    );
+
+    Ok(true)
+}
+
+/// Bring stored data up to `SCHEMA_VERSION`, one version at a time
+///
+/// Steps that touch an unbounded number of records only prepare the move;
+/// the records themselves are then carried over in batches by
+/// `migrate_storage`.
+///
+/// # Returns
+/// The schema version now in effect
+///
+/// # Errors
+/// - `AdminError::NotInitialized` if no admin has been recorded
+pub fn migrate(env: &Env) -> Result<u32, AdminError> {
+    require_admin(env)?;
+
+    let from_version = get_schema_version(env);
+    let mut version = from_version;
+    while version < SCHEMA_VERSION {
+        if version == 1 {
+            migrate_counters(env);
+        }
+        if version == 2 {
+            crate::bounty::storage::migrate_escrow_balances(env);
+        }
+        version += 1;
+    }
+
+    if version != from_version {
+        set_schema_version(env, version);
+
+        env.events().publish(
+            (Symbol::new(env, "admin"), Symbol::new(env, "migrated")),
+            SchemaMigratedEvent {
+                from_version,
+                to_version: version,
+            },
+        );
+    }
+
+    Ok(version)
+}
+
+/// Record whether a bounty's escrow is still held, for bounties the schema 3
+/// migration could not tell
+///
+/// Bounties left `Completed` by older releases may already have been paid
+/// out, so the migration leaves their escrow unrecorded and they can be
+/// neither released nor refunded. After checking the token transfers on
+/// the ledger, the admin records the outcome here: held escrow can then be
+/// released as usual, and escrow already paid out closes the bounty as
+/// `Paid`.
+///
+/// # Errors
+/// - `AdminError::NotInitialized` if no admin has been recorded
+/// - `AdminError::NothingToReconcile` if the bounty doesn't exist, holds no
+///   escrow or already has a recorded escrow state
+pub fn reconcile_bounty_escrow(env: &Env, bounty_id: u64, held: bool) -> Result<bool, AdminError> {
+    require_admin(env)?;
+
+    let amount = crate::bounty::escrow::reconcile_escrow(env, bounty_id, held)
+        .ok_or(AdminError::NothingToReconcile)?;
+
+    env.events().publish(
+        (
+            Symbol::new(env, "admin"),
+            Symbol::new(env, "escrow_reconciled"),
+        ),
+        EscrowReconciledEvent {
+            bounty_id,
+            amount,
+            held,
+        },
+    );
+
+    Ok(true)
+}
+
+/// Move every module's ID counter into instance storage
+pub fn migrate_counters(env: &Env) {
+    crate::guild::storage::migrate_legacy_counters(env);
+    crate::governance::storage::migrate_legacy_counters(env);
+    crate::bounty::storage::migrate_legacy_counters(env);
+    crate::treasury::storage::migrate_legacy_counters(env);
+    crate::milestone::storage::migrate_legacy_counters(env);
+    crate::payment::storage::migrate_legacy_counters(env);
+}
+
+/// Replace the TTL thresholds applied to stored entries
+///
+/// # Errors
+/// - `AdminError::NotInitialized` if no admin has been recorded
+/// - `AdminError::InvalidTtlConfig` if a threshold is not below its extension
+///   target or a target exceeds the network's maximum TTL
+pub fn set_ttl_config(env: &Env, config: TtlConfig) -> Result<bool, AdminError> {
+    require_admin(env)?;
+
+    let max_ttl = env.storage().max_ttl();
+    if config.persistent_threshold >= config.persistent_extend_to
+        || config.instance_threshold >= config.instance_extend_to
+        || config.persistent_extend_to > max_ttl
+        || config.instance_extend_to > max_ttl
+    {
+        return Err(AdminError::InvalidTtlConfig);
+    }
+
+    ttl::set_config(env, &config);
+    Ok(true)
+}
+
+/// Set the fee the platform takes from every bounty and milestone payout
+///
+/// A fee of zero turns the platform fee off.
+///
+/// # Errors
+/// - `AdminError::NotInitialized` if no admin has been recorded
+/// - `AdminError::InvalidFee` if the fee exceeds `MAX_PLATFORM_FEE_BPS`
+pub fn set_platform_fee(env: &Env, fee_bps: u32, recipient: Address) -> Result<bool, AdminError> {
+    require_admin(env)?;
+
+    if fee_bps > MAX_PLATFORM_FEE_BPS {
+        return Err(AdminError::InvalidFee);
+    }
+
+    store_platform_fee(
+        env,
+        &PlatformFee {
+            fee_bps,
+            recipient: recipient.clone(),
+        },
+    );
+
+    env.events().publish(
+        (Symbol::new(env, "admin"), Symbol::new(env, "platform_fee")),
+        PlatformFeeUpdatedEvent { fee_bps, recipient },
+    );
+
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests;
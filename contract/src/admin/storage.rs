@@ -0,0 +1,65 @@
+use soroban_sdk::{contracttype, Address, Env};
+
+use crate::admin::types::PlatformFee;
+use crate::ttl;
+
+/// Storage keys for contract administration, all kept in instance storage
+#[contracttype]
+pub enum AdminStorageKey {
+    Admin,
+    PendingAdmin,
+    SchemaVersion,
+    PlatformFee,
+}
+
+/// Schema version assumed for deployments that never recorded one
+pub const LEGACY_SCHEMA_VERSION: u32 = 1;
+
+pub fn get_admin(env: &Env) -> Option<Address> {
+    env.storage().instance().get(&AdminStorageKey::Admin)
+}
+
+pub fn set_admin(env: &Env, admin: &Address) {
+    env.storage().instance().set(&AdminStorageKey::Admin, admin);
+    ttl::extend_instance(env);
+}
+
+pub fn get_pending_admin(env: &Env) -> Option<Address> {
+    env.storage().instance().get(&AdminStorageKey::PendingAdmin)
+}
+
+pub fn set_pending_admin(env: &Env, pending: &Address) {
+    env.storage()
+        .instance()
+        .set(&AdminStorageKey::PendingAdmin, pending);
+    ttl::extend_instance(env);
+}
+
+pub fn clear_pending_admin(env: &Env) {
+    env.storage().instance().remove(&AdminStorageKey::PendingAdmin);
+}
+
+pub fn get_schema_version(env: &Env) -> u32 {
+    env.storage()
+        .instance()
+        .get(&AdminStorageKey::SchemaVersion)
+        .unwrap_or(LEGACY_SCHEMA_VERSION)
+}
+
+pub fn set_schema_version(env: &Env, version: u32) {
+    env.storage()
+        .instance()
+        .set(&AdminStorageKey::SchemaVersion, &version);
+    ttl::extend_instance(env);
+}
+
+pub fn get_platform_fee(env: &Env) -> Option<PlatformFee> {
+    env.storage().instance().get(&AdminStorageKey::PlatformFee)
+}
+
+pub fn set_platform_fee(env: &Env, fee: &PlatformFee) {
+    env.storage()
+        .instance()
+        .set(&AdminStorageKey::PlatformFee, fee);
+    ttl::extend_instance(env);
+}
@@ -0,0 +1,428 @@
+//! Contract Administration Tests
+//!
+//! Coverage for admin initialization, two-step admin transfer, schema
+//! migration, TTL configuration and the platform fee.
+
+use crate::admin::storage::AdminStorageKey;
+use crate::admin::{MAX_PLATFORM_FEE_BPS, SCHEMA_VERSION};
+use crate::bounty::storage::BountyStorageKey;
+use crate::bounty::types::{AssignmentMode, BountyStatus, EscrowState};
+use crate::ttl::TtlConfig;
+use crate::StellarGuildsContract;
+use crate::StellarGuildsContractClient;
+use soroban_sdk::testutils::Address as _;
+use soroban_sdk::{symbol_short, token, Address, BytesN, Env, String};
+
+// ============ Test Helpers ============
+
+fn setup_env() -> Env {
+    let env = Env::default();
+    env.budget().reset_unlimited();
+    env
+}
+
+fn register_and_init_contract(env: &Env, admin: &Address) -> Address {
+    let contract_id = env.register_contract(None, StellarGuildsContract);
+    let client = StellarGuildsContractClient::new(env, &contract_id);
+    client.mock_all_auths().initialize(admin);
+    contract_id
+}
+
+// ============ Initialization Tests ============
+
+#[test]
+fn test_initialize_records_admin_and_schema() {
+    let env = setup_env();
+    let admin = Address::generate(&env);
+    let contract_id = register_and_init_contract(&env, &admin);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+    assert_eq!(client.get_admin(), admin);
+    assert_eq!(client.get_pending_admin(), None);
+    assert_eq!(client.get_schema_version(), SCHEMA_VERSION);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #600)")] // AlreadyInitialized
+fn test_initialize_twice_fails() {
+    let env = setup_env();
+    let admin = Address::generate(&env);
+    let contract_id = register_and_init_contract(&env, &admin);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+    client.mock_all_auths().initialize(&Address::generate(&env));
+}
+
+#[test]
+#[should_panic(expected = "Error(Auth, InvalidAction)")]
+fn test_initialize_requires_admin_auth() {
+    let env = setup_env();
+    let contract_id = env.register_contract(None, StellarGuildsContract);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+    // Without the admin's signature nobody can claim the role
+    client.initialize(&Address::generate(&env));
+}
+
+// ============ Admin Transfer Tests ============
+
+#[test]
+fn test_two_step_admin_transfer() {
+    let env = setup_env();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let new_admin = Address::generate(&env);
+    let contract_id = register_and_init_contract(&env, &admin);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+    client.transfer_admin(&new_admin);
+
+    // Nothing changes until the nominee accepts
+    assert_eq!(client.get_admin(), admin);
+    assert_eq!(client.get_pending_admin(), Some(new_admin.clone()));
+
+    client.accept_admin();
+
+    assert_eq!(client.get_admin(), new_admin);
+    assert_eq!(client.get_pending_admin(), None);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #602)")] // NoPendingAdmin
+fn test_accept_admin_without_nomination_fails() {
+    let env = setup_env();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let contract_id = register_and_init_contract(&env, &admin);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+    client.accept_admin();
+}
+
+#[test]
+#[should_panic]
+fn test_transfer_admin_requires_admin_auth() {
+    let env = setup_env();
+    let admin = Address::generate(&env);
+    let contract_id = register_and_init_contract(&env, &admin);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+    // No auths mocked: the admin's signature is missing
+    client.transfer_admin(&Address::generate(&env));
+}
+
+#[test]
+#[should_panic]
+fn test_upgrade_requires_admin_auth() {
+    let env = setup_env();
+    let admin = Address::generate(&env);
+    let contract_id = register_and_init_contract(&env, &admin);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+    client.upgrade(&BytesN::from_array(&env, &[0u8; 32]));
+}
+
+// ============ Migration Tests ============
+
+#[test]
+fn test_migrate_is_noop_on_current_schema() {
+    let env = setup_env();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let contract_id = register_and_init_contract(&env, &admin);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+    assert_eq!(client.migrate(), SCHEMA_VERSION);
+    assert_eq!(client.get_schema_version(), SCHEMA_VERSION);
+}
+
+#[test]
+fn test_migrate_from_legacy_schema_moves_counters() {
+    let env = setup_env();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let contract_id = register_and_init_contract(&env, &admin);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+    // Pretend this deployment predates schema versioning
+    env.as_contract(&contract_id, || {
+        env.storage()
+            .instance()
+            .remove(&AdminStorageKey::SchemaVersion);
+        env.storage()
+            .persistent()
+            .set(&symbol_short!("guild_cnt"), &41u64);
+    });
+    assert_eq!(client.get_schema_version(), 1);
+
+    assert_eq!(client.migrate(), SCHEMA_VERSION);
+    assert_eq!(client.get_schema_version(), SCHEMA_VERSION);
+
+    let name = String::from_str(&env, "Guild");
+    let description = String::from_str(&env, "Description");
+    assert_eq!(client.create_guild(&name, &description, &owner), 42);
+}
+
+#[test]
+fn test_migrate_records_escrow_of_funded_bounties() {
+    let env = setup_env();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let claimer = Address::generate(&env);
+    let contract_id = register_and_init_contract(&env, &admin);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+    let guild_id = client.create_guild(
+        &String::from_str(&env, "Guild"),
+        &String::from_str(&env, "Description"),
+        &owner,
+    );
+    let token = env
+        .register_stellar_asset_contract_v2(admin.clone())
+        .address();
+    token::StellarAssetClient::new(&env, &token).mint(&owner, &100);
+    let bounty_id = client.create_bounty(
+        &guild_id,
+        &owner,
+        &String::from_str(&env, "Task"),
+        &String::from_str(&env, "Description"),
+        &100i128,
+        &token,
+        &(env.ledger().timestamp() + 1000),
+        &AssignmentMode::FirstCome,
+    );
+    client.fund_bounty(&bounty_id, &owner, &100i128);
+
+    // Pretend the bounty was funded before escrow was tracked
+    env.as_contract(&contract_id, || {
+        env.storage()
+            .instance()
+            .set(&AdminStorageKey::SchemaVersion, &2u32);
+        env.storage()
+            .persistent()
+            .remove(&BountyStorageKey::EscrowBalance(token.clone()));
+        env.storage()
+            .persistent()
+            .remove(&BountyStorageKey::EscrowState(bounty_id));
+    });
+
+    assert_eq!(client.migrate(), SCHEMA_VERSION);
+    assert_eq!(
+        client.get_bounty_escrow_state(&bounty_id),
+        Some(EscrowState::Locked)
+    );
+
+    client.claim_bounty(&bounty_id, &claimer);
+    client.submit_work(
+        &bounty_id,
+        &String::from_str(&env, "https://github.com/pr/1"),
+    );
+    client.approve_completion(&bounty_id, &owner);
+    client.release_escrow(&bounty_id);
+    assert_eq!(token::Client::new(&env, &token).balance(&claimer), 100);
+}
+
+/// Drive a funded bounty to `Completed`, then roll storage back to how
+/// schema 2 left it: no escrow state and no escrow total
+fn setup_legacy_completed_bounty(
+    env: &Env,
+    client: &StellarGuildsContractClient<'_>,
+    contract_id: &Address,
+    admin: &Address,
+    claimer: &Address,
+) -> (u64, Address) {
+    let owner = Address::generate(env);
+    let guild_id = client.create_guild(
+        &String::from_str(env, "Guild"),
+        &String::from_str(env, "Description"),
+        &owner,
+    );
+    let token = env
+        .register_stellar_asset_contract_v2(admin.clone())
+        .address();
+    token::StellarAssetClient::new(env, &token).mint(&owner, &100);
+    let bounty_id = client.create_bounty(
+        &guild_id,
+        &owner,
+        &String::from_str(env, "Task"),
+        &String::from_str(env, "Description"),
+        &100i128,
+        &token,
+        &(env.ledger().timestamp() + 1000),
+        &AssignmentMode::FirstCome,
+    );
+    client.fund_bounty(&bounty_id, &owner, &100i128);
+    client.claim_bounty(&bounty_id, claimer);
+    client.submit_work(
+        &bounty_id,
+        &String::from_str(env, "https://github.com/pr/1"),
+    );
+    client.approve_completion(&bounty_id, &owner);
+
+    env.as_contract(contract_id, || {
+        env.storage()
+            .instance()
+            .set(&AdminStorageKey::SchemaVersion, &2u32);
+        env.storage()
+            .persistent()
+            .remove(&BountyStorageKey::EscrowBalance(token.clone()));
+        env.storage()
+            .persistent()
+            .remove(&BountyStorageKey::EscrowState(bounty_id));
+    });
+
+    (bounty_id, token)
+}
+
+#[test]
+fn test_migrate_leaves_completed_bounty_escrow_unrecorded() {
+    let env = setup_env();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let claimer = Address::generate(&env);
+    let contract_id = register_and_init_contract(&env, &admin);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+    let (bounty_id, token) =
+        setup_legacy_completed_bounty(&env, &client, &contract_id, &admin, &claimer);
+
+    assert_eq!(client.migrate(), SCHEMA_VERSION);
+
+    // Older releases may already have paid this bounty out
+    assert_eq!(client.get_bounty_escrow_state(&bounty_id), None);
+    assert!(client.try_release_escrow(&bounty_id).is_err());
+
+    // The admin confirms the escrow is still held
+    client.reconcile_bounty_escrow(&bounty_id, &true);
+    assert_eq!(
+        client.get_bounty_escrow_state(&bounty_id),
+        Some(EscrowState::Locked)
+    );
+
+    client.release_escrow(&bounty_id);
+    assert_eq!(token::Client::new(&env, &token).balance(&claimer), 100);
+}
+
+#[test]
+fn test_reconcile_paid_out_escrow_closes_bounty() {
+    let env = setup_env();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let claimer = Address::generate(&env);
+    let contract_id = register_and_init_contract(&env, &admin);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+    let (bounty_id, _token) =
+        setup_legacy_completed_bounty(&env, &client, &contract_id, &admin, &claimer);
+
+    client.migrate();
+    client.reconcile_bounty_escrow(&bounty_id, &false);
+
+    let bounty = client.get_bounty(&bounty_id);
+    assert_eq!(bounty.status, BountyStatus::Paid);
+    assert_eq!(bounty.funded_amount, 0);
+    assert_eq!(
+        client.get_bounty_escrow_state(&bounty_id),
+        Some(EscrowState::Released)
+    );
+    assert!(client.try_release_escrow(&bounty_id).is_err());
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #605)")] // NothingToReconcile
+fn test_reconcile_recorded_escrow_fails() {
+    let env = setup_env();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let claimer = Address::generate(&env);
+    let contract_id = register_and_init_contract(&env, &admin);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+    let (bounty_id, _token) =
+        setup_legacy_completed_bounty(&env, &client, &contract_id, &admin, &claimer);
+
+    client.migrate();
+    client.reconcile_bounty_escrow(&bounty_id, &true);
+    client.reconcile_bounty_escrow(&bounty_id, &false);
+}
+
+// ============ TTL Config Tests ============
+
+#[test]
+fn test_set_ttl_config() {
+    let env = setup_env();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let contract_id = register_and_init_contract(&env, &admin);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+    let config = TtlConfig {
+        persistent_threshold: 1_000,
+        persistent_extend_to: 5_000,
+        instance_threshold: 1_000,
+        instance_extend_to: 5_000,
+    };
+    client.set_ttl_config(&config);
+
+    assert_eq!(client.get_ttl_config(), config);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #603)")] // InvalidTtlConfig
+fn test_set_ttl_config_threshold_above_target_fails() {
+    let env = setup_env();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let contract_id = register_and_init_contract(&env, &admin);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+    client.set_ttl_config(&TtlConfig {
+        persistent_threshold: 5_000,
+        persistent_extend_to: 1_000,
+        instance_threshold: 1_000,
+        instance_extend_to: 5_000,
+    });
+}
+
+// ============ Platform Fee Tests ============
+
+#[test]
+fn test_set_platform_fee() {
+    let env = setup_env();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let contract_id = register_and_init_contract(&env, &admin);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+    assert_eq!(client.get_platform_fee(), None);
+
+    let recipient = Address::generate(&env);
+    client.set_platform_fee(&250u32, &recipient);
+
+    let fee = client.get_platform_fee().unwrap();
+    assert_eq!(fee.fee_bps, 250);
+    assert_eq!(fee.recipient, recipient);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #604)")] // InvalidFee
+fn test_set_platform_fee_above_max_fails() {
+    let env = setup_env();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let contract_id = register_and_init_contract(&env, &admin);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+    client.set_platform_fee(&(MAX_PLATFORM_FEE_BPS + 1), &Address::generate(&env));
+}
@@ -0,0 +1,77 @@
+use soroban_sdk::{contracterror, contracttype, Address, BytesN};
+
+/// Error types for contract administration
+///
+/// Codes live in the 600-699 range so they never collide with the errors of
+/// other modules when surfaced to clients.
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum AdminError {
+    AlreadyInitialized = 600,
+    NotInitialized = 601,
+    NoPendingAdmin = 602,
+    InvalidTtlConfig = 603,
+    InvalidFee = 604,
+    NothingToReconcile = 605,
+}
+
+/// Event emitted when the admin nominates a successor
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AdminTransferStartedEvent {
+    pub current_admin: Address,
+    pub pending_admin: Address,
+}
+
+/// Event emitted when the nominated successor accepts the admin role
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AdminTransferredEvent {
+    pub previous_admin: Address,
+    pub new_admin: Address,
+}
+
+/// Event emitted when the contract WASM is replaced
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ContractUpgradedEvent {
+    pub new_wasm_hash: BytesN<32>,
+}
+
+/// Event emitted when stored data is migrated to a newer schema
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SchemaMigratedEvent {
+    pub from_version: u32,
+    pub to_version: u32,
+}
+
+/// Contract-wide fee taken from bounty and milestone payouts
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PlatformFee {
+    /// Fee in basis points of each payout
+    pub fee_bps: u32,
+    /// Address receiving the fee
+    pub recipient: Address,
+}
+
+/// Event emitted when the admin changes the platform fee
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PlatformFeeUpdatedEvent {
+    pub fee_bps: u32,
+    pub recipient: Address,
+}
+
+/// Event emitted when the admin records the outcome of an escrow the schema
+/// migration could not determine
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EscrowReconciledEvent {
+    pub bounty_id: u64,
+    pub amount: i128,
+    /// Whether the escrow is still held by the contract
+    pub held: bool,
+}
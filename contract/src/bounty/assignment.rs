@@ -0,0 +1,198 @@
+//! Application-based bounty assignment
+//!
+//! Bounties created in `AssignmentMode::Application` cannot be claimed.
+//! Contributors apply instead, and the creator or a holder of
+//! `ManageBounties` assigns the bounty to one applicant; everyone else is told
+//! through an event.
+
+use crate::bounty::claims::start_work_window;
+use crate::bounty::storage::{
+    get_applications, get_assignment_mode, get_bounty, store_applications, store_bounty,
+};
+use crate::bounty::types::{
+    Application, ApplicationDeclinedEvent, ApplicationSubmittedEvent, AssignmentMode,
+    BountyAssignedEvent, BountyError, BountyStatus,
+};
+use crate::guild::roles::has_guild_permission;
+use crate::guild::storage::is_banned;
+use crate::guild::types::Permission;
+use soroban_sdk::{Address, Env, String, Symbol, Vec};
+
+/// Maximum open applications per bounty, bounding the cost of assignment
+pub const MAX_APPLICATIONS: u32 = 50;
+
+const MAX_PITCH_LEN: u32 = 1024;
+
+/// Apply to work on an application-mode bounty
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `bounty_id` - The ID of the bounty
+/// * `applicant` - Address of the contributor applying
+/// * `pitch` - Pitch text or a URL to one
+/// * `estimated_delivery` - Timestamp by which the applicant expects to deliver
+///
+/// # Returns
+/// `true` if the application was recorded
+///
+/// # Errors
+/// - `BountyError::BountyNotFound` if bounty is not found
+/// - `BountyError::Unauthorized` if the applicant created the bounty or is
+///   banned from the guild
+/// - `BountyError::NotApplicationMode` if the bounty is first-come
+/// - `BountyError::NotOpen` if bounty is not open
+/// - `BountyError::BountyExpired` if the bounty has expired
+/// - `BountyError::InvalidApplication` if the pitch is empty or too long, or
+///   the delivery estimate is in the past
+/// - `BountyError::AlreadyApplied` if the applicant has an open application
+/// - `BountyError::TooManyApplications` if the bounty has no room for more
+pub fn apply_for_bounty(
+    env: &Env,
+    bounty_id: u64,
+    applicant: Address,
+    pitch: String,
+    estimated_delivery: u64,
+) -> Result<bool, BountyError> {
+    applicant.require_auth();
+
+    let bounty = get_bounty(env, bounty_id).ok_or(BountyError::BountyNotFound)?;
+
+    // The creator approves the work, so they may not also deliver it
+    if applicant == bounty.creator || is_banned(env, bounty.guild_id, &applicant) {
+        return Err(BountyError::Unauthorized);
+    }
+    if get_assignment_mode(env, bounty_id) != AssignmentMode::Application {
+        return Err(BountyError::NotApplicationMode);
+    }
+    if bounty.status != BountyStatus::Open {
+        return Err(BountyError::NotOpen);
+    }
+
+    let now = env.ledger().timestamp();
+    if now > bounty.expires_at {
+        return Err(BountyError::BountyExpired);
+    }
+    if pitch.is_empty() || pitch.len() > MAX_PITCH_LEN || estimated_delivery <= now {
+        return Err(BountyError::InvalidApplication);
+    }
+
+    let mut applications = get_applications(env, bounty_id);
+    if applications.iter().any(|a| a.applicant == applicant) {
+        return Err(BountyError::AlreadyApplied);
+    }
+    if applications.len() >= MAX_APPLICATIONS {
+        return Err(BountyError::TooManyApplications);
+    }
+
+    applications.push_back(Application {
+        applicant: applicant.clone(),
+        pitch,
+        estimated_delivery,
+        applied_at: now,
+    });
+    store_applications(env, bounty_id, &applications);
+
+    env.events().publish(
+        (Symbol::new(env, "bounty"), Symbol::new(env, "applied")),
+        ApplicationSubmittedEvent {
+            bounty_id,
+            applicant,
+            estimated_delivery,
+        },
+    );
+
+    Ok(true)
+}
+
+/// Assign an application-mode bounty to one of its applicants
+///
+/// The bounty moves to `Claimed` with the applicant as claimer. Every other
+/// applicant receives an `app_declined` event and the application list is
+/// cleared.
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `bounty_id` - The ID of the bounty
+/// * `assigner` - The bounty creator or a holder of `ManageBounties`
+/// * `applicant` - Address of the chosen applicant
+///
+/// # Returns
+/// `true` if the bounty was assigned
+///
+/// # Errors
+/// - `BountyError::BountyNotFound` if bounty is not found
+/// - `BountyError::Unauthorized` if assigner is neither creator nor holds `ManageBounties`,
+///   or the applicant created the bounty or is banned from the guild
+/// - `BountyError::NotApplicationMode` if the bounty is first-come
+/// - `BountyError::NotOpen` if bounty is not open
+/// - `BountyError::ApplicationNotFound` if the address has not applied
+pub fn assign_bounty(
+    env: &Env,
+    bounty_id: u64,
+    assigner: Address,
+    applicant: Address,
+) -> Result<bool, BountyError> {
+    assigner.require_auth();
+
+    let mut bounty = get_bounty(env, bounty_id).ok_or(BountyError::BountyNotFound)?;
+
+    let is_creator = bounty.creator == assigner;
+    if !is_creator
+        && !has_guild_permission(env, bounty.guild_id, assigner.clone(), Permission::ManageBounties)
+    {
+        return Err(BountyError::Unauthorized);
+    }
+    // An applicant banned after applying can no longer be picked
+    if applicant == bounty.creator || is_banned(env, bounty.guild_id, &applicant) {
+        return Err(BountyError::Unauthorized);
+    }
+    if get_assignment_mode(env, bounty_id) != AssignmentMode::Application {
+        return Err(BountyError::NotApplicationMode);
+    }
+    if bounty.status != BountyStatus::Open {
+        return Err(BountyError::NotOpen);
+    }
+
+    let applications = get_applications(env, bounty_id);
+    if !applications.iter().any(|a| a.applicant == applicant) {
+        return Err(BountyError::ApplicationNotFound);
+    }
+
+    bounty.status = BountyStatus::Claimed;
+    bounty.claimer = Some(applicant.clone());
+    store_bounty(env, &bounty);
+    store_applications(env, bounty_id, &Vec::new(env));
+    start_work_window(env, bounty_id);
+
+    for application in applications.iter() {
+        if application.applicant != applicant {
+            env.events().publish(
+                (Symbol::new(env, "bounty"), Symbol::new(env, "app_declined")),
+                ApplicationDeclinedEvent {
+                    bounty_id,
+                    applicant: application.applicant,
+                },
+            );
+        }
+    }
+
+    env.events().publish(
+        (Symbol::new(env, "bounty"), Symbol::new(env, "assigned")),
+        BountyAssignedEvent {
+            bounty_id,
+            assignee: applicant,
+            assigner,
+        },
+    );
+
+    Ok(true)
+}
+
+/// Get the open applications for a bounty, oldest first
+///
+/// # Errors
+/// `BountyError::BountyNotFound` if bounty is not found
+pub fn get_bounty_applications(env: &Env, bounty_id: u64) -> Result<Vec<Application>, BountyError> {
+    get_bounty(env, bounty_id).ok_or(BountyError::BountyNotFound)?;
+    Ok(get_applications(env, bounty_id))
+}
@@ -0,0 +1,203 @@
+//! Claim work windows and abandonment
+//!
+//! Every claim (or assignment) starts a work window, seven days unless the
+//! bounty sets its own. The claimer can release the bounty with
+//! `abandon_bounty`; once the window has passed without a submission anyone
+//! can reopen it with `reclaim_stale`, which also costs the claimer
+//! reputation. Both outcomes are kept in the claimer's abandonment record.
+
+use crate::bounty::storage::{
+    get_abandonments, get_bounty, get_claim_deadline, get_index_ids, get_work_window,
+    index_len, remove_team, store_abandonments, store_bounty, store_claim_deadline,
+    store_work_window,
+};
+use crate::bounty::types::{
+    AbandonmentRecord, Bounty, BountyAbandonedEvent, BountyError, BountyFilter, BountyStatus,
+};
+use crate::guild::roles::has_guild_permission;
+use crate::guild::types::Permission;
+use crate::reputation;
+use soroban_sdk::{Address, Env, Symbol};
+
+/// Work window applied when a bounty does not set its own (7 days)
+pub const DEFAULT_WORK_WINDOW_SECONDS: u64 = 7 * 24 * 60 * 60;
+
+/// Longest work window a bounty may set (365 days)
+pub const MAX_WORK_WINDOW_SECONDS: u64 = 365 * 24 * 60 * 60;
+
+/// Set how long claimers of a bounty have to submit work
+///
+/// Applies from the next claim or revision request onwards.
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `bounty_id` - The ID of the bounty
+/// * `caller` - The bounty creator or a holder of `ManageBounties`
+/// * `seconds` - Length of the work window
+///
+/// # Errors
+/// - `BountyError::BountyNotFound` if bounty is not found
+/// - `BountyError::Unauthorized` if caller is neither creator nor holds `ManageBounties`
+/// - `BountyError::InvalidWorkWindow` if the window is zero or over a year
+pub fn set_work_window(
+    env: &Env,
+    bounty_id: u64,
+    caller: Address,
+    seconds: u64,
+) -> Result<bool, BountyError> {
+    caller.require_auth();
+
+    let bounty = get_bounty(env, bounty_id).ok_or(BountyError::BountyNotFound)?;
+
+    let is_creator = bounty.creator == caller;
+    if !is_creator
+        && !has_guild_permission(env, bounty.guild_id, caller, Permission::ManageBounties)
+    {
+        return Err(BountyError::Unauthorized);
+    }
+    if seconds == 0 || seconds > MAX_WORK_WINDOW_SECONDS {
+        return Err(BountyError::InvalidWorkWindow);
+    }
+
+    store_work_window(env, bounty_id, seconds);
+    Ok(true)
+}
+
+/// Release a claimed bounty so others can take it
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `bounty_id` - The ID of the claimed bounty
+/// * `claimer` - The current claimer
+///
+/// # Errors
+/// - `BountyError::BountyNotFound` if bounty is not found
+/// - `BountyError::NotClaimed` if bounty is not in Claimed status
+/// - `BountyError::NotClaimer` if the caller is not the current claimer
+pub fn abandon_bounty(env: &Env, bounty_id: u64, claimer: Address) -> Result<bool, BountyError> {
+    claimer.require_auth();
+
+    let mut bounty = get_bounty(env, bounty_id).ok_or(BountyError::BountyNotFound)?;
+
+    if bounty.status != BountyStatus::Claimed {
+        return Err(BountyError::NotClaimed);
+    }
+    if bounty.claimer.as_ref() != Some(&claimer) {
+        return Err(BountyError::NotClaimer);
+    }
+
+    reopen(env, &mut bounty);
+    store_bounty(env, &bounty);
+    record_abandonment(env, &bounty, &claimer, false);
+
+    Ok(true)
+}
+
+/// Reopen a bounty whose claimer missed their work window
+///
+/// Callable by anyone. The claimer loses reputation.
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `bounty_id` - The ID of the claimed bounty
+///
+/// # Errors
+/// - `BountyError::BountyNotFound` if bounty is not found
+/// - `BountyError::NotClaimed` if bounty is not in Claimed status
+/// - `BountyError::ClaimNotStale` if the work window is still running
+pub fn reclaim_stale(env: &Env, bounty_id: u64) -> Result<bool, BountyError> {
+    let mut bounty = get_bounty(env, bounty_id).ok_or(BountyError::BountyNotFound)?;
+
+    if bounty.status != BountyStatus::Claimed {
+        return Err(BountyError::NotClaimed);
+    }
+    let claimer = bounty.claimer.clone().ok_or(BountyError::NoClaimer)?;
+
+    let deadline = get_claim_deadline(env, bounty_id).ok_or(BountyError::ClaimNotStale)?;
+    if env.ledger().timestamp() <= deadline {
+        return Err(BountyError::ClaimNotStale);
+    }
+
+    reopen(env, &mut bounty);
+    store_bounty(env, &bounty);
+    record_abandonment(env, &bounty, &claimer, true);
+    reputation::record_bounty_abandoned(env, bounty.guild_id, &claimer);
+
+    Ok(true)
+}
+
+/// Reopen every bounty of a guild claimed by an address that left it
+///
+/// Counted as a voluntary abandonment; work already submitted for review is
+/// left alone.
+pub(crate) fn release_member_claims(env: &Env, guild_id: u64, claimer: &Address) {
+    let index = BountyFilter::Claimer(claimer.clone());
+    let ids = get_index_ids(env, &index, 0, index_len(env, &index));
+
+    for bounty_id in ids.iter() {
+        let mut bounty = match get_bounty(env, bounty_id) {
+            Some(bounty) => bounty,
+            None => continue,
+        };
+        if bounty.guild_id != guild_id || bounty.status != BountyStatus::Claimed {
+            continue;
+        }
+
+        reopen(env, &mut bounty);
+        store_bounty(env, &bounty);
+        record_abandonment(env, &bounty, claimer, false);
+    }
+}
+
+/// Get the abandonment record of a contributor
+pub fn get_abandonment_record(env: &Env, address: &Address) -> AbandonmentRecord {
+    get_abandonments(env, address)
+}
+
+/// Get the timestamp by which the current claimer of a bounty must submit
+///
+/// # Errors
+/// `BountyError::BountyNotFound` if bounty is not found
+pub fn get_bounty_claim_deadline(env: &Env, bounty_id: u64) -> Result<Option<u64>, BountyError> {
+    get_bounty(env, bounty_id).ok_or(BountyError::BountyNotFound)?;
+    Ok(get_claim_deadline(env, bounty_id))
+}
+
+/// Start the current claimer's work window
+pub(crate) fn start_work_window(env: &Env, bounty_id: u64) {
+    let window = get_work_window(env, bounty_id).unwrap_or(DEFAULT_WORK_WINDOW_SECONDS);
+    store_claim_deadline(env, bounty_id, env.ledger().timestamp() + window);
+}
+
+/// Clear the claim on a bounty, disband its team and make it available again
+pub(crate) fn reopen(env: &Env, bounty: &mut Bounty) {
+    remove_team(env, bounty.id);
+    bounty.claimer = None;
+    bounty.submission_url = None;
+    bounty.status = if bounty.funded_amount >= bounty.reward_amount {
+        BountyStatus::Open
+    } else {
+        BountyStatus::AwaitingFunds
+    };
+}
+
+fn record_abandonment(env: &Env, bounty: &Bounty, claimer: &Address, stale: bool) {
+    let mut record = get_abandonments(env, claimer);
+    if stale {
+        record.stale += 1;
+    } else {
+        record.voluntary += 1;
+    }
+    record.last_bounty_id = bounty.id;
+    record.last_abandoned_at = env.ledger().timestamp();
+    store_abandonments(env, claimer, &record);
+
+    env.events().publish(
+        (Symbol::new(env, "bounty"), Symbol::new(env, "abandoned")),
+        BountyAbandonedEvent {
+            bounty_id: bounty.id,
+            claimer: claimer.clone(),
+            stale,
+        },
+    );
+}
@@ -0,0 +1,244 @@
+//! Contest bounties
+//!
+//! A bounty created with `AssignmentMode::Contest` is never claimed. Any
+//! address other than the creator may enter one submission until the
+//! contest deadline. Afterwards the creator or a holder of `ApproveWork`
+//! ranks the entries, and the escrow is paid out along the prize table.
+//! Tiers left without a winner are refunded to the funders.
+
+use crate::bounty::escrow::{close_escrow, pay_workers, refund_funders};
+use crate::bounty::review::record_submission;
+use crate::bounty::storage::{
+    get_assignment_mode, get_bounty, get_submissions, store_bounty, store_submissions,
+};
+use crate::bounty::types::{
+    AssignmentMode, BountyError, BountyStatus, ContestJudgedEvent, ContestTerms, EscrowState,
+    SubmissionOutcome, WorkSubmittedEvent,
+};
+use crate::guild::roles::has_guild_permission;
+use crate::guild::storage::is_banned;
+use crate::guild::types::Permission;
+use crate::reputation;
+use soroban_sdk::{Address, Env, String, Symbol, Vec};
+
+/// Maximum prize tiers in a contest
+pub const MAX_PRIZE_TIERS: u32 = 10;
+
+/// Maximum entries per contest, bounding the cost of judging
+pub const MAX_CONTEST_ENTRIES: u32 = 100;
+
+/// Check a contest's terms against the lifetime of its bounty
+pub(crate) fn validate_contest_terms(
+    terms: &ContestTerms,
+    created_at: u64,
+    expires_at: u64,
+) -> Result<(), BountyError> {
+    if terms.submission_deadline <= created_at || terms.submission_deadline >= expires_at {
+        return Err(BountyError::InvalidPrizeTable);
+    }
+
+    let tiers = terms.prize_percentages.len();
+    if tiers == 0 || tiers > MAX_PRIZE_TIERS {
+        return Err(BountyError::InvalidPrizeTable);
+    }
+    if terms.prize_percentages.iter().any(|p| p == 0) {
+        return Err(BountyError::InvalidPrizeTable);
+    }
+    if terms.prize_percentages.iter().sum::<u32>() != 100 {
+        return Err(BountyError::InvalidPrizeTable);
+    }
+
+    Ok(())
+}
+
+/// Enter a contest bounty
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `bounty_id` - The ID of the contest bounty
+/// * `entrant` - Address submitting the entry
+/// * `submission_url` - URL or reference to the submitted work
+///
+/// # Returns
+/// `true` if the entry was recorded
+///
+/// # Errors
+/// - `BountyError::BountyNotFound` if bounty is not found
+/// - `BountyError::NotContest` if the bounty is not a contest
+/// - `BountyError::NotOpen` if the contest is not funded and open
+/// - `BountyError::ContestClosed` if the submission deadline has passed
+/// - `BountyError::Unauthorized` if the entrant created the bounty or is banned
+///   from the guild
+/// - `BountyError::InvalidSubmissionUrl` if the URL is empty or too long
+/// - `BountyError::AlreadySubmitted` if the entrant has already entered
+/// - `BountyError::TooManyEntries` if the contest has no room for more entries
+pub fn submit_contest_entry(
+    env: &Env,
+    bounty_id: u64,
+    entrant: Address,
+    submission_url: String,
+) -> Result<bool, BountyError> {
+    entrant.require_auth();
+
+    let bounty = get_bounty(env, bounty_id).ok_or(BountyError::BountyNotFound)?;
+    let terms = contest_terms(env, bounty_id)?;
+
+    if bounty.status != BountyStatus::Open {
+        return Err(BountyError::NotOpen);
+    }
+    if env.ledger().timestamp() > terms.submission_deadline {
+        return Err(BountyError::ContestClosed);
+    }
+    if entrant == bounty.creator || is_banned(env, bounty.guild_id, &entrant) {
+        return Err(BountyError::Unauthorized);
+    }
+    if submission_url.is_empty() || submission_url.len() > 512 {
+        return Err(BountyError::InvalidSubmissionUrl);
+    }
+
+    let entries = get_submissions(env, bounty_id);
+    if entries.iter().any(|e| e.claimer == entrant) {
+        return Err(BountyError::AlreadySubmitted);
+    }
+    if entries.len() >= MAX_CONTEST_ENTRIES {
+        return Err(BountyError::TooManyEntries);
+    }
+
+    record_submission(env, bounty_id, &entrant, &submission_url);
+
+    env.events().publish(
+        (Symbol::new(env, "bounty"), Symbol::new(env, "submitted")),
+        WorkSubmittedEvent {
+            bounty_id,
+            claimer: entrant,
+            submission_url,
+        },
+    );
+
+    Ok(true)
+}
+
+/// Rank a contest's entries and pay out its prizes
+///
+/// `ranking` lists winning entrants from first place down and may be shorter
+/// than the prize table. Guild and platform fees are withheld from each
+/// prize, as in `release_escrow`. Unawarded tiers are refunded to the funders.
+/// The bounty ends `Paid`, or `Cancelled` when nobody was ranked.
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `bounty_id` - The ID of the contest bounty
+/// * `judge` - The bounty creator or a holder of `ApproveWork`
+/// * `ranking` - Winning entrants, best first
+///
+/// # Returns
+/// `true` if the contest was judged
+///
+/// # Errors
+/// - `BountyError::BountyNotFound` if bounty is not found
+/// - `BountyError::Unauthorized` if judge is neither creator nor holds `ApproveWork`
+/// - `BountyError::NotContest` if the bounty is not a contest
+/// - `BountyError::NotOpen` if the contest was already settled
+/// - `BountyError::ContestNotEnded` if the submission deadline has not passed
+/// - `BountyError::InvalidRanking` if the ranking names a non-entrant, repeats
+///   an entrant, or has more places than the prize table
+pub fn judge_contest(
+    env: &Env,
+    bounty_id: u64,
+    judge: Address,
+    ranking: Vec<Address>,
+) -> Result<bool, BountyError> {
+    judge.require_auth();
+
+    let mut bounty = get_bounty(env, bounty_id).ok_or(BountyError::BountyNotFound)?;
+
+    let is_creator = bounty.creator == judge;
+    if !is_creator
+        && !has_guild_permission(env, bounty.guild_id, judge.clone(), Permission::ApproveWork)
+    {
+        return Err(BountyError::Unauthorized);
+    }
+
+    let terms = contest_terms(env, bounty_id)?;
+    if bounty.status != BountyStatus::Open {
+        return Err(BountyError::NotOpen);
+    }
+    if env.ledger().timestamp() <= terms.submission_deadline {
+        return Err(BountyError::ContestNotEnded);
+    }
+
+    let mut entries = get_submissions(env, bounty_id);
+    if ranking.len() > terms.prize_percentages.len() {
+        return Err(BountyError::InvalidRanking);
+    }
+    for (i, winner) in ranking.iter().enumerate() {
+        let repeated = ranking.first_index_of(&winner) != Some(i as u32);
+        if repeated || !entries.iter().any(|e| e.claimer == winner) {
+            return Err(BountyError::InvalidRanking);
+        }
+    }
+
+    // Pay each ranked entrant their tier of the escrow, less fees
+    let mut prizes = Vec::new(env);
+    let mut payouts = Vec::new(env);
+    let mut awarded: i128 = 0;
+    for (winner, percent) in ranking.iter().zip(terms.prize_percentages.iter()) {
+        let prize = bounty.funded_amount * percent as i128 / 100;
+        reputation::record_bounty_completed(env, bounty.guild_id, &winner);
+        payouts.push_back((winner, prize));
+        prizes.push_back(prize);
+        awarded += prize;
+    }
+    let fees = pay_workers(env, &bounty, &payouts);
+
+    let refunded = bounty.funded_amount - awarded;
+    refund_funders(env, &bounty, refunded);
+
+    // Close out every entry
+    let now = env.ledger().timestamp();
+    for i in 0..entries.len() {
+        let mut entry = entries.get_unchecked(i);
+        entry.outcome = if ranking.contains(&entry.claimer) {
+            SubmissionOutcome::Approved
+        } else {
+            SubmissionOutcome::Rejected
+        };
+        entry.reviewed_at = Some(now);
+        entries.set(i, entry);
+    }
+    store_submissions(env, bounty_id, &entries);
+
+    if ranking.is_empty() {
+        close_escrow(env, &mut bounty, EscrowState::Refunded)?;
+        bounty.status = BountyStatus::Cancelled;
+    } else {
+        close_escrow(env, &mut bounty, EscrowState::Released)?;
+        bounty.status = BountyStatus::Paid;
+    }
+    store_bounty(env, &bounty);
+
+    env.events().publish(
+        (
+            Symbol::new(env, "bounty"),
+            Symbol::new(env, "contest_judged"),
+        ),
+        ContestJudgedEvent {
+            bounty_id,
+            judge,
+            winners: ranking,
+            prizes,
+            refunded,
+            guild_fee: fees.guild_fee,
+            platform_fee: fees.platform_fee,
+        },
+    );
+
+    Ok(true)
+}
+
+fn contest_terms(env: &Env, bounty_id: u64) -> Result<ContestTerms, BountyError> {
+    match get_assignment_mode(env, bounty_id) {
+        AssignmentMode::Contest(terms) => Ok(terms),
+        _ => Err(BountyError::NotContest),
+    }
+}
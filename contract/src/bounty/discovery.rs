@@ -0,0 +1,142 @@
+//! Bounty discovery
+//!
+//! Bounties can be labelled with tags and a difficulty level, and listed a
+//! page at a time by guild, status, token, tag, claimer or creator. Every
+//! listing except the per-guild one is served from a secondary index kept
+//! up to date by `storage::store_bounty`, so a page costs the same however
+//! many bounties exist. Listing by `BountyStatus::Open` gives the open
+//! bounties of every guild.
+
+use crate::bounty::storage::{
+    count_guild_bounties, get_bounties, get_bounty, get_guild_bounty_ids, get_index_ids,
+    get_metadata, index_add, index_len, index_remove, store_metadata,
+};
+use crate::bounty::types::{
+    BountyError, BountyFilter, BountyMetadata, BountyMetadataUpdatedEvent, BountyPage, Difficulty,
+};
+use crate::guild::roles::has_guild_permission;
+use crate::guild::types::Permission;
+use crate::paging;
+use soroban_sdk::{Address, Env, Symbol, Vec};
+
+/// Maximum tags per bounty
+pub const MAX_TAGS: u32 = 5;
+
+/// Maximum bounties returned by one discovery query
+pub const MAX_PAGE_LIMIT: u32 = paging::PAGE_SIZE;
+
+/// Set the tags and difficulty of a bounty
+///
+/// Replaces any earlier tags; the bounty is moved between tag indexes
+/// accordingly.
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `bounty_id` - The ID of the bounty
+/// * `caller` - The bounty creator or a holder of `ManageBounties`
+/// * `tags` - Skill or topic labels, at most `MAX_TAGS`
+/// * `difficulty` - How demanding the work is
+///
+/// # Errors
+/// - `BountyError::BountyNotFound` if bounty is not found
+/// - `BountyError::Unauthorized` if caller is neither the creator nor holds `ManageBounties`
+/// - `BountyError::InvalidTags` if there are too many tags or a tag repeats
+pub fn set_bounty_metadata(
+    env: &Env,
+    bounty_id: u64,
+    caller: Address,
+    tags: Vec<Symbol>,
+    difficulty: Difficulty,
+) -> Result<bool, BountyError> {
+    caller.require_auth();
+
+    let bounty = get_bounty(env, bounty_id).ok_or(BountyError::BountyNotFound)?;
+    if caller != bounty.creator
+        && !has_guild_permission(env, bounty.guild_id, caller, Permission::ManageBounties)
+    {
+        return Err(BountyError::Unauthorized);
+    }
+
+    if tags.len() > MAX_TAGS {
+        return Err(BountyError::InvalidTags);
+    }
+    for (i, tag) in tags.iter().enumerate() {
+        if tags.iter().skip(i + 1).any(|other| other == tag) {
+            return Err(BountyError::InvalidTags);
+        }
+    }
+
+    if let Some(previous) = get_metadata(env, bounty_id) {
+        for tag in previous.tags.iter() {
+            if !tags.contains(&tag) {
+                index_remove(env, &BountyFilter::Tag(tag), bounty_id);
+            }
+        }
+    }
+    for tag in tags.iter() {
+        index_add(env, &BountyFilter::Tag(tag), bounty_id);
+    }
+
+    store_metadata(
+        env,
+        bounty_id,
+        &BountyMetadata {
+            tags: tags.clone(),
+            difficulty,
+        },
+    );
+
+    env.events().publish(
+        (Symbol::new(env, "bounty"), Symbol::new(env, "metadata")),
+        BountyMetadataUpdatedEvent {
+            bounty_id,
+            tags,
+            difficulty,
+        },
+    );
+
+    Ok(true)
+}
+
+/// Get the tags and difficulty of a bounty, if they were set
+///
+/// # Errors
+/// `BountyError::BountyNotFound` if bounty is not found
+pub fn get_bounty_metadata(
+    env: &Env,
+    bounty_id: u64,
+) -> Result<Option<BountyMetadata>, BountyError> {
+    get_bounty(env, bounty_id).ok_or(BountyError::BountyNotFound)?;
+    Ok(get_metadata(env, bounty_id))
+}
+
+/// List a page of the bounties matching a filter
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `filter` - Which bounties to list
+/// * `cursor` - Position to start at: 0, or the `next_cursor` of the previous page
+/// * `limit` - Bounties per page, capped at `MAX_PAGE_LIMIT`
+///
+/// Guild listings are in creation order. Other listings are unordered and,
+/// as bounties enter and leave them, may shift between calls.
+pub fn list_bounties(env: &Env, filter: BountyFilter, cursor: u32, limit: u32) -> BountyPage {
+    let limit = limit.clamp(1, MAX_PAGE_LIMIT);
+
+    let (ids, total) = match &filter {
+        BountyFilter::Guild(guild_id) => (
+            get_guild_bounty_ids(env, *guild_id, cursor, limit),
+            count_guild_bounties(env, *guild_id),
+        ),
+        _ => (
+            get_index_ids(env, &filter, cursor, limit),
+            index_len(env, &filter),
+        ),
+    };
+
+    let next = cursor.saturating_add(ids.len());
+    BountyPage {
+        bounties: get_bounties(env, &ids),
+        next_cursor: if next < total { Some(next) } else { None },
+    }
+}
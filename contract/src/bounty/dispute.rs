@@ -0,0 +1,290 @@
+//! Bounty dispute resolution
+//!
+//! Once work is under review, the claimer or the creator can dispute it.
+//! Escrow stays locked while the guild's arbiter panel rules on how the
+//! funded amount is split; a majority of the panel agreeing on the same split
+//! settles the dispute. Guilds without a panel (or with a deadlocked one) can
+//! settle through a `DisputeResolution` governance proposal instead.
+
+use crate::bounty::escrow::{close_escrow, pay_workers, refund_funders};
+use crate::bounty::storage::{
+    get_arbiters, get_bounty, get_dispute, store_arbiters, store_bounty, store_dispute,
+};
+use crate::bounty::team::worker_payouts;
+use crate::bounty::types::{
+    Bounty, BountyError, BountyStatus, Dispute, DisputeOpenedEvent, DisputeResolvedEvent,
+    DisputeRulingEvent, EscrowState,
+};
+use crate::guild::roles::has_guild_permission;
+use crate::guild::types::Permission;
+use crate::treasury::types::PayoutFees;
+use soroban_sdk::{Address, Env, Map, String, Symbol, Vec};
+
+const MAX_REASON_LEN: u32 = 1024;
+
+/// Appoint the arbiter panel that rules on a guild's bounty disputes
+///
+/// Replaces the current panel. Disputes that are already open keep the panel
+/// they were opened with.
+///
+/// # Errors
+/// - `BountyError::Unauthorized` if caller lacks `ManageGuild`
+/// - `BountyError::InvalidArbiters` if the panel lists an address twice
+pub fn set_arbiters(
+    env: &Env,
+    guild_id: u64,
+    caller: Address,
+    arbiters: Vec<Address>,
+) -> Result<bool, BountyError> {
+    caller.require_auth();
+
+    if !has_guild_permission(env, guild_id, caller, Permission::ManageGuild) {
+        return Err(BountyError::Unauthorized);
+    }
+
+    for (i, arbiter) in arbiters.iter().enumerate() {
+        if arbiters.first_index_of(&arbiter) != Some(i as u32) {
+            return Err(BountyError::InvalidArbiters);
+        }
+    }
+
+    store_arbiters(env, guild_id, &arbiters);
+    Ok(true)
+}
+
+/// Get the arbiter panel of a guild
+pub fn get_guild_arbiters(env: &Env, guild_id: u64) -> Vec<Address> {
+    get_arbiters(env, guild_id)
+}
+
+/// Dispute the work submitted for a bounty
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `bounty_id` - The ID of the bounty under review
+/// * `caller` - The bounty's claimer or creator
+/// * `reason` - Why the review outcome is contested
+///
+/// # Errors
+/// - `BountyError::BountyNotFound` if bounty is not found
+/// - `BountyError::NotDisputable` if bounty is not under review
+/// - `BountyError::Unauthorized` if caller is neither claimer nor creator
+/// - `BountyError::InvalidReason` if reason is empty or too long
+pub fn open_dispute(
+    env: &Env,
+    bounty_id: u64,
+    caller: Address,
+    reason: String,
+) -> Result<bool, BountyError> {
+    caller.require_auth();
+
+    let mut bounty = get_bounty(env, bounty_id).ok_or(BountyError::BountyNotFound)?;
+
+    if bounty.status != BountyStatus::UnderReview {
+        return Err(BountyError::NotDisputable);
+    }
+
+    let claimer = bounty.claimer.clone().ok_or(BountyError::NoClaimer)?;
+    if caller != claimer && caller != bounty.creator {
+        return Err(BountyError::Unauthorized);
+    }
+
+    if reason.is_empty() || reason.len() > MAX_REASON_LEN {
+        return Err(BountyError::InvalidReason);
+    }
+
+    // Parties to the dispute cannot rule on it
+    let mut arbiters = Vec::new(env);
+    for arbiter in get_arbiters(env, bounty.guild_id).iter() {
+        if arbiter != claimer && arbiter != bounty.creator {
+            arbiters.push_back(arbiter);
+        }
+    }
+
+    let dispute = Dispute {
+        bounty_id,
+        opened_by: caller.clone(),
+        reason: reason.clone(),
+        opened_at: env.ledger().timestamp(),
+        arbiters,
+        rulings: Map::new(env),
+        claimer_percent: None,
+        resolved_at: None,
+    };
+    store_dispute(env, &dispute);
+
+    bounty.status = BountyStatus::Disputed;
+    store_bounty(env, &bounty);
+
+    env.events().publish(
+        (Symbol::new(env, "bounty"), Symbol::new(env, "disputed")),
+        DisputeOpenedEvent {
+            bounty_id,
+            opened_by: caller,
+            reason,
+        },
+    );
+
+    Ok(true)
+}
+
+/// Record an arbiter's ruling on a dispute
+///
+/// `claimer_percent` is the share of the escrowed funds awarded to the
+/// claimer; the rest is refunded to the funders. The dispute settles as soon
+/// as a majority of the panel has ruled for the same share.
+///
+/// # Returns
+/// `true` if this ruling settled the dispute
+///
+/// # Errors
+/// - `BountyError::BountyNotFound` if bounty is not found
+/// - `BountyError::NotDisputed` if bounty is not in dispute
+/// - `BountyError::NotArbiter` if arbiter is not on the dispute's panel
+/// - `BountyError::AlreadyRuled` if arbiter has already ruled
+/// - `BountyError::InvalidSplit` if `claimer_percent` exceeds 100
+pub fn rule_dispute(
+    env: &Env,
+    bounty_id: u64,
+    arbiter: Address,
+    claimer_percent: u32,
+) -> Result<bool, BountyError> {
+    arbiter.require_auth();
+
+    let bounty = get_bounty(env, bounty_id).ok_or(BountyError::BountyNotFound)?;
+    if bounty.status != BountyStatus::Disputed {
+        return Err(BountyError::NotDisputed);
+    }
+
+    let mut dispute = get_dispute(env, bounty_id).ok_or(BountyError::DisputeNotFound)?;
+
+    if !dispute.arbiters.contains(&arbiter) {
+        return Err(BountyError::NotArbiter);
+    }
+    if dispute.rulings.contains_key(arbiter.clone()) {
+        return Err(BountyError::AlreadyRuled);
+    }
+    if claimer_percent > 100 {
+        return Err(BountyError::InvalidSplit);
+    }
+
+    dispute.rulings.set(arbiter.clone(), claimer_percent);
+
+    env.events().publish(
+        (Symbol::new(env, "bounty"), Symbol::new(env, "ruling")),
+        DisputeRulingEvent {
+            bounty_id,
+            arbiter,
+            claimer_percent,
+        },
+    );
+
+    let agreeing = dispute
+        .rulings
+        .values()
+        .iter()
+        .filter(|p| *p == claimer_percent)
+        .count() as u32;
+
+    if agreeing * 2 > dispute.arbiters.len() {
+        settle(env, bounty, &mut dispute, claimer_percent)?;
+        return Ok(true);
+    }
+
+    store_dispute(env, &dispute);
+    Ok(false)
+}
+
+/// Settle a dispute on behalf of the guild, following a governance vote
+///
+/// # Errors
+/// - `BountyError::BountyNotFound` if bounty is not found or belongs to
+///   another guild
+/// - `BountyError::NotDisputed` if bounty is not in dispute
+/// - `BountyError::InvalidSplit` if `claimer_percent` exceeds 100
+pub fn resolve_dispute_by_governance(
+    env: &Env,
+    guild_id: u64,
+    bounty_id: u64,
+    claimer_percent: u32,
+) -> Result<(), BountyError> {
+    let bounty = get_bounty(env, bounty_id)
+        .filter(|b| b.guild_id == guild_id)
+        .ok_or(BountyError::BountyNotFound)?;
+    if bounty.status != BountyStatus::Disputed {
+        return Err(BountyError::NotDisputed);
+    }
+    if claimer_percent > 100 {
+        return Err(BountyError::InvalidSplit);
+    }
+
+    let mut dispute = get_dispute(env, bounty_id).ok_or(BountyError::DisputeNotFound)?;
+    settle(env, bounty, &mut dispute, claimer_percent)
+}
+
+/// Get the dispute opened over a bounty
+///
+/// # Errors
+/// `BountyError::DisputeNotFound` if the bounty was never disputed
+pub fn get_dispute_data(env: &Env, bounty_id: u64) -> Result<Dispute, BountyError> {
+    get_dispute(env, bounty_id).ok_or(BountyError::DisputeNotFound)
+}
+
+/// Split the escrow between claimer (or their team) and funders and close
+/// the bounty
+///
+/// Guild and platform fees are withheld from the claimer's award, as in
+/// `release_escrow`. The bounty ends `Paid` if the claimer was awarded
+/// anything and `Cancelled` otherwise.
+///
+/// # Errors
+/// - `BountyError::NoClaimer` or `BountyError::InvalidTeamSplit` if the
+///   award cannot be split among the workers
+/// - `BountyError::InsufficientEscrow` if the contract holds less escrow for
+///   the token than the bounty is owed
+fn settle(
+    env: &Env,
+    mut bounty: Bounty,
+    dispute: &mut Dispute,
+    claimer_percent: u32,
+) -> Result<(), BountyError> {
+    let claimer_amount = bounty.funded_amount * claimer_percent as i128 / 100;
+    let funder_amount = bounty.funded_amount - claimer_amount;
+
+    let mut fees = PayoutFees::default();
+    if claimer_amount > 0 {
+        let payouts = worker_payouts(env, &bounty, claimer_amount)?;
+        fees = pay_workers(env, &bounty, &payouts);
+    }
+    refund_funders(env, &bounty, funder_amount);
+
+    let (state, status) = if claimer_percent > 0 {
+        (EscrowState::Released, BountyStatus::Paid)
+    } else {
+        (EscrowState::Refunded, BountyStatus::Cancelled)
+    };
+    close_escrow(env, &mut bounty, state)?;
+    bounty.status = status;
+    store_bounty(env, &bounty);
+
+    dispute.claimer_percent = Some(claimer_percent);
+    dispute.resolved_at = Some(env.ledger().timestamp());
+    store_dispute(env, dispute);
+
+    env.events().publish(
+        (
+            Symbol::new(env, "bounty"),
+            Symbol::new(env, "dispute_resolved"),
+        ),
+        DisputeResolvedEvent {
+            bounty_id: bounty.id,
+            claimer_percent,
+            claimer_amount,
+            funder_amount,
+            guild_fee: fees.guild_fee,
+            platform_fee: fees.platform_fee,
+        },
+    );
+
+    Ok(())
+}
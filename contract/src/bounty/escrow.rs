@@ -1,5 +1,16 @@
-use soroban_sdk::{Address, Env};
+use crate::bounty::storage::{
+    get_bounty, get_escrow_balance, get_escrow_state, get_funders, set_escrow_balance,
+    store_bounty, store_escrow_state,
+};
+use crate::bounty::types::{
+    Bounty, BountyError, BountyStatus, EscrowReleasedEvent, EscrowState, FunderRefundedEvent,
+    FundingSource,
+};
+use crate::treasury;
+use crate::treasury::fees::{collect_fees, quote_fees};
+use crate::treasury::types::PayoutFees;
 use soroban_sdk::token::Client as TokenClient;
+use soroban_sdk::{panic_with_error, Address, Env, Symbol, Vec};
 
 /// Transfer funds from funder to contract
 pub fn lock_funds(env: &Env, token: &Address, funder: &Address, amount: i128) {
@@ -14,3 +25,172 @@ pub fn release_funds(env: &Env, token: &Address, recipient: &Address, amount: i1
     // Transfer tokens from this contract to recipient
     client.transfer(&env.current_contract_address(), recipient, &amount);
 }
+
+/// Pay workers their share of a bounty's escrow
+///
+/// Guild and platform fees are withheld from each payment and handed over
+/// once everyone is paid, so every payout path charges the same fees.
+/// Returns the fees withheld in total.
+pub fn pay_workers(env: &Env, bounty: &Bounty, payouts: &Vec<(Address, i128)>) -> PayoutFees {
+    let mut withheld = PayoutFees::default();
+    for (recipient, amount) in payouts.iter() {
+        if amount <= 0 {
+            continue;
+        }
+        let fees = quote_fees(env, bounty.guild_id, amount);
+        let net_amount = amount - fees.total();
+        release_funds(env, &bounty.token, &recipient, net_amount);
+        withheld.guild_fee += fees.guild_fee;
+        withheld.platform_fee += fees.platform_fee;
+
+        env.events().publish(
+            (Symbol::new(env, "bounty"), Symbol::new(env, "released")),
+            EscrowReleasedEvent {
+                bounty_id: bounty.id,
+                recipient,
+                amount: net_amount,
+                token: bounty.token.clone(),
+                guild_fee: fees.guild_fee,
+                platform_fee: fees.platform_fee,
+            },
+        );
+    }
+
+    let token = Some(bounty.token.clone());
+    if let Err(e) = collect_fees(env, bounty.guild_id, &token, &withheld) {
+        panic_with_error!(env, e);
+    }
+    withheld
+}
+
+/// Record tokens entering a bounty's escrow
+///
+/// Every bounty's escrow is held by the same contract, so a per-token total
+/// is kept alongside each bounty's `funded_amount`; a payout can never take
+/// more than the bounties hold between them.
+pub fn credit_escrow(env: &Env, bounty: &Bounty, amount: i128) {
+    let balance = get_escrow_balance(env, &bounty.token);
+    set_escrow_balance(env, &bounty.token, balance + amount);
+    store_escrow_state(env, bounty.id, &EscrowState::Locked);
+}
+
+/// Settle a bounty's escrow once it has been paid out or refunded
+///
+/// Takes the bounty's `funded_amount` off the per-token total and clears it.
+///
+/// # Errors
+/// - `BountyError::InsufficientEscrow` if the bounty's escrow is not recorded
+///   as `Locked`, or the total held for the token is smaller than it
+pub fn close_escrow(env: &Env, bounty: &mut Bounty, state: EscrowState) -> Result<(), BountyError> {
+    if bounty.funded_amount <= 0 {
+        return Ok(());
+    }
+    if get_escrow_state(env, bounty.id) != Some(EscrowState::Locked) {
+        return Err(BountyError::InsufficientEscrow);
+    }
+
+    let balance = get_escrow_balance(env, &bounty.token);
+    if balance < bounty.funded_amount {
+        return Err(BountyError::InsufficientEscrow);
+    }
+    set_escrow_balance(env, &bounty.token, balance - bounty.funded_amount);
+    store_escrow_state(env, bounty.id, &state);
+    bounty.funded_amount = 0;
+
+    Ok(())
+}
+
+/// Record whether the escrow of a bounty the schema migration left
+/// unrecorded is still held by the contract
+///
+/// Held escrow is counted and marked `Locked`, so the bounty can be paid out
+/// as usual. Otherwise the escrow is marked `Released` and a `Completed`
+/// bounty moves to `Paid`.
+///
+/// # Returns
+/// The bounty's escrow, or `None` if the bounty doesn't exist, holds no
+/// escrow or already has a recorded escrow state
+pub(crate) fn reconcile_escrow(env: &Env, bounty_id: u64, held: bool) -> Option<i128> {
+    let mut bounty = get_bounty(env, bounty_id)?;
+    if bounty.funded_amount <= 0 || get_escrow_state(env, bounty_id).is_some() {
+        return None;
+    }
+
+    let amount = bounty.funded_amount;
+    if held {
+        credit_escrow(env, &bounty, amount);
+    } else {
+        store_escrow_state(env, bounty_id, &EscrowState::Released);
+        bounty.funded_amount = 0;
+        if bounty.status == BountyStatus::Completed {
+            bounty.status = BountyStatus::Paid;
+        }
+        store_bounty(env, &bounty);
+    }
+
+    Some(amount)
+}
+
+/// Return `amount` of a bounty's escrow to its funders, pro rata to what each
+/// contributed
+///
+/// Shares funded by a guild treasury are credited back to that treasury's
+/// balance; the tokens never leave the contract. Escrow deposited before contributions were recorded is attributed to the
+/// creator. Rounding dust goes to the last recipient so the full amount is
+/// always paid out. Must be called before `funded_amount` is cleared.
+pub fn refund_funders(env: &Env, bounty: &Bounty, amount: i128) {
+    if amount <= 0 {
+        return;
+    }
+
+    let funders = get_funders(env, bounty.id);
+    let recorded: i128 = funders.iter().map(|f| f.amount).sum();
+    let unrecorded = (bounty.funded_amount - recorded).max(0);
+    let total = recorded + unrecorded;
+
+    let mut remaining = amount;
+    let last = funders.len().saturating_sub(1);
+    for (i, entry) in funders.iter().enumerate() {
+        let share = if i as u32 == last && unrecorded == 0 {
+            remaining
+        } else {
+            amount * entry.amount / total
+        };
+        remaining -= share;
+        refund(env, bounty, entry.source, share);
+    }
+
+    if remaining > 0 {
+        refund(
+            env,
+            bounty,
+            FundingSource::Account(bounty.creator.clone()),
+            remaining,
+        );
+    }
+}
+
+fn refund(env: &Env, bounty: &Bounty, source: FundingSource, amount: i128) {
+    if amount <= 0 {
+        return;
+    }
+    match source {
+        FundingSource::Account(ref funder) => {
+            release_funds(env, &bounty.token, funder, amount);
+        }
+        FundingSource::Treasury(treasury_id) => {
+            if let Err(e) = treasury::credit_bounty_refund(env, treasury_id, &bounty.token, amount)
+            {
+                panic_with_error!(env, e);
+            }
+        }
+    }
+    env.events().publish(
+        (Symbol::new(env, "bounty"), Symbol::new(env, "refunded")),
+        FunderRefundedEvent {
+            bounty_id: bounty.id,
+            source,
+            amount,
+        },
+    );
+}
@@ -6,49 +6,88 @@
 //!
 //! # Features
 //! - Bounty creation with metadata and rewards
-//! - Escrow funding from any address
-//! - First-come-first-served bounty claiming
-//! - Work submission and approval flow
-//! - Automatic escrow release on completion
+//! - Escrow funding from any address, with per-funder pro-rata refunds
+//! - First-come-first-served claiming, or assignment from applications
+//! - Contests with ranked prizes
+//! - Claim work windows, abandonment and reclaiming of stalled claims
+//! - Work submission and review: approval, revision requests and rejection
+//! - Escrow release on completion, once per bounty, optionally on approval
 //! - Cancellation with refund support
 //! - Expiration handling
-
+//! - Disputes ruled by an arbiter panel or a governance vote
+//! - Tags, difficulty levels and paginated discovery queries
+
+pub mod assignment;
+pub mod claims;
+pub mod contest;
+pub mod discovery;
+pub mod dispute;
 pub mod escrow;
+pub mod review;
 pub mod storage;
+pub mod team;
 pub mod types;
 
-use crate::bounty::escrow::{lock_funds, release_funds};
-use crate::bounty::storage::{get_bounty, get_guild_bounties, get_next_bounty_id, store_bounty};
+use crate::bounty::claims::start_work_window;
+use crate::bounty::contest::validate_contest_terms;
+use crate::bounty::escrow::{close_escrow, credit_escrow, lock_funds, pay_workers, refund_funders};
+use crate::bounty::review::{mark_latest_submission, record_submission};
+use crate::bounty::team::{bounty_workers, ensure_team_confirmed, worker_payouts};
+use crate::bounty::storage::{
+    get_assignment_mode, get_auto_release, get_bounty, get_escrow_state, get_funders,
+    get_guild_bounties, get_next_bounty_id, store_assignment_mode, store_auto_release,
+    store_bounty, store_funders,
+};
 use crate::bounty::types::{
-    BountyApprovedEvent, BountyCancelledEvent, BountyClaimedEvent, BountyCreatedEvent,
-    BountyExpiredEvent, BountyFundedEvent, EscrowReleasedEvent, WorkSubmittedEvent,
+    AutoReleaseUpdatedEvent, BountyApprovedEvent, BountyCancelledEvent, BountyClaimedEvent,
+    BountyCreatedEvent, BountyExpiredEvent, BountyFundedEvent, BountyTreasuryFundedEvent,
+    EscrowState, SubmissionOutcome, WorkSubmittedEvent,
 };
-use crate::guild::membership::has_permission;
-use crate::guild::types::Role;
+use crate::guild::roles::has_guild_permission;
+use crate::guild::storage::is_banned;
+use crate::guild::rules::get_rules;
+use crate::guild::types::Permission;
+use crate::keeper;
+use crate::keeper::types::{DueItem, DueOutcome};
+use crate::reputation;
 use soroban_sdk::{Address, Env, String, Symbol, Vec};
 
 // Re-export types for external use
-pub use types::{Bounty, BountyStatus};
+pub use types::{
+    AssignmentMode, Bounty, BountyError, BountyFunder, BountyStatus, FundingSource,
+};
+
+/// Maximum distinct funders per bounty, bounding the cost of refunds
+const MAX_FUNDERS: u32 = 50;
 
 /// Create a new bounty
 ///
 /// # Arguments
 /// * `env` - The contract environment
 /// * `guild_id` - The ID of the guild creating the bounty
-/// * `creator` - Address of the bounty creator (must be guild admin/owner)
+/// * `creator` - Address of the bounty creator (must hold the guild's
+///   `bounty_creator_role`, Admin by default)
 /// * `title` - Short title for the bounty
 /// * `description` - Detailed description of the task
 /// * `reward_amount` - Amount of tokens as reward
 /// * `token` - Address of the token contract (XLM wrapped or custom Stellar asset)
-/// * `expiry` - Absolute timestamp when the bounty expires
+/// * `expiry` - Absolute timestamp when the bounty expires, or 0 to use the
+///   guild's `default_bounty_expiry` rule
+/// * `assignment_mode` - `FirstCome` for open claiming, `Application` to pick
+///   the claimer from applications, or `Contest` to rank open entries
 ///
 /// # Returns
 /// The ID of the newly created bounty
 ///
-/// # Panics
-/// - If creator is not a guild admin or owner
-/// - If reward_amount is negative
-/// - If expiry is in the past
+/// # Errors
+/// - `BountyError::Unauthorized` if creator lacks `Permission::CreateBounty`
+/// - `BountyError::InvalidRewardAmount` if reward_amount is negative or below
+///   the guild's `min_bounty_reward` rule
+/// - `BountyError::InvalidExpiry` if expiry is in the past
+/// - `BountyError::InvalidPrizeTable` if contest terms have a deadline outside
+///   the bounty's lifetime or prizes that do not sum to 100
+/// - `BountyError::InvalidTitle` / `BountyError::InvalidDescription` on bad metadata
+#[allow(clippy::too_many_arguments)]
 pub fn create_bounty(
     env: &Env,
     guild_id: u64,
@@ -58,30 +97,42 @@ pub fn create_bounty(
     reward_amount: i128,
     token: Address,
     expiry: u64,
-) -> u64 {
+    assignment_mode: AssignmentMode,
+) -> Result<u64, BountyError> {
     creator.require_auth();
 
-    // Verify creator has Admin or Owner permissions in the guild
-    if !has_permission(env, guild_id, creator.clone(), Role::Admin) {
-        panic!("Unauthorized: Creator must be a guild admin or owner");
+    let rules = get_rules(env, guild_id);
+
+    // Built-in roles get `CreateBounty` per the guild's `bounty_creator_role`
+    if !has_guild_permission(env, guild_id, creator.clone(), Permission::CreateBounty) {
+        return Err(BountyError::Unauthorized);
     }
 
     // Validate inputs
-    if reward_amount < 0 {
-        panic!("Invalid reward amount: must be non-negative");
+    if reward_amount < 0 || reward_amount < rules.min_bounty_reward {
+        return Err(BountyError::InvalidRewardAmount);
     }
 
     let created_at = env.ledger().timestamp();
+    let expiry = if expiry == 0 {
+        created_at + rules.default_bounty_expiry
+    } else {
+        expiry
+    };
     if expiry <= created_at {
-        panic!("Expiry must be in the future");
+        return Err(BountyError::InvalidExpiry);
+    }
+
+    if let AssignmentMode::Contest(terms) = &assignment_mode {
+        validate_contest_terms(terms, created_at, expiry)?;
     }
 
-    if title.len() == 0 || title.len() > 256 {
-        panic!("Title must be between 1 and 256 characters");
+    if title.is_empty() || title.len() > 256 {
+        return Err(BountyError::InvalidTitle);
     }
 
-    if description.len() > 2048 {
-        panic!("Description must be at most 2048 characters");
+    if description.len() > rules.max_bounty_description_len {
+        return Err(BountyError::InvalidDescription);
     }
 
     let bounty_id = get_next_bounty_id(env);
@@ -110,6 +161,8 @@ pub fn create_bounty(
     };
 
     store_bounty(env, &bounty);
+    store_assignment_mode(env, bounty_id, &assignment_mode);
+    keeper::storage::schedule(env, DueItem::Bounty(bounty_id), expiry);
 
     // Emit creation event
     env.events().publish(
@@ -121,10 +174,11 @@ pub fn create_bounty(
             reward_amount,
             token,
             expires_at: expiry,
+            assignment_mode,
         },
     );
 
-    bounty_id
+    Ok(bounty_id)
 }
 
 /// Fund a bounty with tokens
@@ -133,55 +187,44 @@ pub fn create_bounty(
 /// * `env` - The contract environment
 /// * `bounty_id` - The ID of the bounty to fund
 /// * `funder` - Address providing the funds
-/// * `amount` - Amount of tokens to fund
+/// * `amount` - Amount of tokens to fund; only the part still needed to
+///   reach `reward_amount` is taken
 ///
 /// # Returns
 /// `true` if funding was successful
 ///
-/// # Panics
-/// - If bounty is not found
-/// - If amount is not positive
-/// - If bounty is not in a fundable state
-pub fn fund_bounty(env: &Env, bounty_id: u64, funder: Address, amount: i128) -> bool {
+/// # Errors
+/// - `BountyError::BountyNotFound` if bounty is not found
+/// - `BountyError::InvalidAmount` if amount is not positive
+/// - `BountyError::BountyExpired` if the bounty has expired
+/// - `BountyError::NotFundable` if bounty is not in a fundable state
+/// - `BountyError::AlreadyFullyFunded` if the reward is already covered
+/// - `BountyError::TooManyFunders` if the bounty has no room for a new funder
+pub fn fund_bounty(
+    env: &Env,
+    bounty_id: u64,
+    funder: Address,
+    amount: i128,
+) -> Result<bool, BountyError> {
     funder.require_auth();
 
     if amount <= 0 {
-        panic!("Amount must be positive");
+        return Err(BountyError::InvalidAmount);
     }
 
-    let mut bounty = get_bounty(env, bounty_id).expect("Bounty not found");
-
-    // Check for expiration
-    let now = env.ledger().timestamp();
-    if now > bounty.expires_at {
-        bounty.status = BountyStatus::Expired;
-        store_bounty(env, &bounty);
-        env.events().publish(
-            (Symbol::new(env, "bounty"), Symbol::new(env, "expired")),
-            BountyExpiredEvent { bounty_id },
-        );
-        panic!("Bounty has expired");
-    }
+    let mut bounty = get_bounty(env, bounty_id).ok_or(BountyError::BountyNotFound)?;
 
-    // Can only fund if awaiting funds or open (partial funding support)
-    match bounty.status {
-        BountyStatus::AwaitingFunds | BountyStatus::Open => {}
-        _ => panic!("Bounty cannot be funded in current status"),
-    }
+    let amount = add_funding(
+        env,
+        &mut bounty,
+        FundingSource::Account(funder.clone()),
+        amount,
+    )?;
 
     // Transfer tokens to contract (escrow)
     lock_funds(env, &bounty.token, &funder, amount);
-
-    // Update funded amount
-    bounty.funded_amount += amount;
-
     let is_fully_funded = bounty.funded_amount >= bounty.reward_amount;
 
-    // Transition to Open if fully funded
-    if is_fully_funded && bounty.status == BountyStatus::AwaitingFunds {
-        bounty.status = BountyStatus::Open;
-    }
-
     store_bounty(env, &bounty);
 
     // Emit funding event
@@ -196,7 +239,119 @@ pub fn fund_bounty(env: &Env, bounty_id: u64, funder: Address, amount: i128) ->
         },
     );
 
-    true
+    Ok(true)
+}
+
+/// Fund a bounty with tokens already held by a guild treasury
+///
+/// Called when a `BountyFunding` treasury transaction executes. The tokens
+/// are already in the contract, so only the escrow accounting moves.
+///
+/// # Returns
+/// The amount accepted, capped at what the reward still needs
+///
+/// # Errors
+/// - `BountyError::BountyNotFound` if bounty is not found
+/// - `BountyError::InvalidAmount` if amount is not positive
+/// - `BountyError::BountyExpired` if the bounty has expired
+/// - `BountyError::NotFundable` if bounty is not in a fundable state
+/// - `BountyError::AlreadyFullyFunded` if the reward is already covered
+/// - `BountyError::TooManyFunders` if the bounty has no room for a new funder
+pub fn fund_from_treasury(
+    env: &Env,
+    bounty_id: u64,
+    treasury_id: u64,
+    amount: i128,
+) -> Result<i128, BountyError> {
+    if amount <= 0 {
+        return Err(BountyError::InvalidAmount);
+    }
+
+    let mut bounty = get_bounty(env, bounty_id).ok_or(BountyError::BountyNotFound)?;
+    let amount = add_funding(
+        env,
+        &mut bounty,
+        FundingSource::Treasury(treasury_id),
+        amount,
+    )?;
+    let is_fully_funded = bounty.funded_amount >= bounty.reward_amount;
+
+    store_bounty(env, &bounty);
+
+    env.events().publish(
+        (
+            Symbol::new(env, "bounty"),
+            Symbol::new(env, "treasury_funded"),
+        ),
+        BountyTreasuryFundedEvent {
+            bounty_id,
+            treasury_id,
+            amount,
+            total_funded: bounty.funded_amount,
+            is_fully_funded,
+        },
+    );
+
+    Ok(amount)
+}
+
+/// Record a contribution to a bounty's escrow
+///
+/// Only what the reward still needs is accepted; the accepted amount is
+/// returned so the caller moves exactly that many tokens.
+fn add_funding(
+    env: &Env,
+    bounty: &mut Bounty,
+    source: FundingSource,
+    amount: i128,
+) -> Result<i128, BountyError> {
+    // Check for expiration
+    let now = env.ledger().timestamp();
+    if now > bounty.expires_at {
+        return Err(BountyError::BountyExpired);
+    }
+
+    // Can only fund if awaiting funds or open (partial funding support)
+    match bounty.status {
+        BountyStatus::AwaitingFunds | BountyStatus::Open => {}
+        _ => return Err(BountyError::NotFundable),
+    }
+
+    // Only accept what the reward still needs; the excess never leaves the
+    // funder
+    let needed = bounty.reward_amount - bounty.funded_amount;
+    if needed <= 0 {
+        return Err(BountyError::AlreadyFullyFunded);
+    }
+    let amount = amount.min(needed);
+
+    // Record the contribution so refunds go back to its source
+    let mut funders = get_funders(env, bounty.id);
+    match funders.iter().position(|f| f.source == source) {
+        Some(i) => {
+            let mut entry = funders.get_unchecked(i as u32);
+            entry.amount += amount;
+            funders.set(i as u32, entry);
+        }
+        None => {
+            if funders.len() >= MAX_FUNDERS {
+                return Err(BountyError::TooManyFunders);
+            }
+            funders.push_back(BountyFunder { source, amount });
+        }
+    }
+    store_funders(env, bounty.id, &funders);
+
+    bounty.funded_amount += amount;
+    credit_escrow(env, bounty, amount);
+
+    // Transition to Open if fully funded
+    if bounty.funded_amount >= bounty.reward_amount && bounty.status == BountyStatus::AwaitingFunds
+    {
+        bounty.status = BountyStatus::Open;
+    }
+
+    Ok(amount)
 }
 
 /// Claim a bounty (first-come-first-served)
@@ -209,14 +364,24 @@ pub fn fund_bounty(env: &Env, bounty_id: u64, funder: Address, amount: i128) ->
 /// # Returns
 /// `true` if claiming was successful
 ///
-/// # Panics
-/// - If bounty is not found
-/// - If bounty is not open for claiming
-/// - If bounty has expired
-pub fn claim_bounty(env: &Env, bounty_id: u64, claimer: Address) -> bool {
+/// # Errors
+/// - `BountyError::BountyNotFound` if bounty is not found
+/// - `BountyError::Unauthorized` if the claimer created the bounty or is
+///   banned from the guild
+/// - `BountyError::BountyExpired` if bounty has expired
+/// - `BountyError::NotOpen` if bounty is not open for claiming
+/// - `BountyError::NotClaimable` if the bounty is assigned from applications
+///   or run as a contest
+pub fn claim_bounty(env: &Env, bounty_id: u64, claimer: Address) -> Result<bool, BountyError> {
     claimer.require_auth();
 
-    let mut bounty = get_bounty(env, bounty_id).expect("Bounty not found");
+    let mut bounty = get_bounty(env, bounty_id).ok_or(BountyError::BountyNotFound)?;
+
+    // The creator approves the work, and addresses banned from the guild
+    // cannot take on its bounties
+    if claimer == bounty.creator || is_banned(env, bounty.guild_id, &claimer) {
+        return Err(BountyError::Unauthorized);
+    }
 
     // Check for expiration
     let now = env.ledger().timestamp();
@@ -227,12 +392,17 @@ pub fn claim_bounty(env: &Env, bounty_id: u64, claimer: Address) -> bool {
             (Symbol::new(env, "bounty"), Symbol::new(env, "expired")),
             BountyExpiredEvent { bounty_id },
         );
-        panic!("Bounty has expired");
+        return Err(BountyError::BountyExpired);
     }
 
     // Must be Open to claim
     if bounty.status != BountyStatus::Open {
-        panic!("Bounty is not open for claiming");
+        return Err(BountyError::NotOpen);
+    }
+
+    // Application and contest bounties are never claimed
+    if get_assignment_mode(env, bounty_id) != AssignmentMode::FirstCome {
+        return Err(BountyError::NotClaimable);
     }
 
     // Update bounty state
@@ -240,6 +410,7 @@ pub fn claim_bounty(env: &Env, bounty_id: u64, claimer: Address) -> bool {
     bounty.claimer = Some(claimer.clone());
 
     store_bounty(env, &bounty);
+    start_work_window(env, bounty_id);
 
     // Emit claim event
     env.events().publish(
@@ -247,7 +418,7 @@ pub fn claim_bounty(env: &Env, bounty_id: u64, claimer: Address) -> bool {
         BountyClaimedEvent { bounty_id, claimer },
     );
 
-    true
+    Ok(true)
 }
 
 /// Submit work for a claimed bounty
@@ -260,32 +431,37 @@ pub fn claim_bounty(env: &Env, bounty_id: u64, claimer: Address) -> bool {
 /// # Returns
 /// `true` if submission was successful
 ///
-/// # Panics
-/// - If bounty is not found
-/// - If caller is not the claimer
-/// - If bounty is not in Claimed status
-pub fn submit_work(env: &Env, bounty_id: u64, submission_url: String) -> bool {
-    let mut bounty = get_bounty(env, bounty_id).expect("Bounty not found");
+/// # Errors
+/// - `BountyError::BountyNotFound` if bounty is not found
+/// - `BountyError::NoClaimer` if nobody has claimed the bounty
+/// - `BountyError::NotClaimed` if bounty is not in Claimed status
+/// - `BountyError::InvalidSubmissionUrl` if the URL is empty or too long
+pub fn submit_work(env: &Env, bounty_id: u64, submission_url: String) -> Result<bool, BountyError> {
+    let mut bounty = get_bounty(env, bounty_id).ok_or(BountyError::BountyNotFound)?;
 
     // Verify claimer
-    let claimer = bounty.claimer.clone().expect("No claimer for this bounty");
+    let claimer = bounty.claimer.clone().ok_or(BountyError::NoClaimer)?;
     claimer.require_auth();
 
     // Must be in Claimed status
     if bounty.status != BountyStatus::Claimed {
-        panic!("Bounty is not in claimed status");
+        return Err(BountyError::NotClaimed);
     }
 
     // Validate submission URL
-    if submission_url.len() == 0 || submission_url.len() > 512 {
-        panic!("Submission URL must be between 1 and 512 characters");
+    if submission_url.is_empty() || submission_url.len() > 512 {
+        return Err(BountyError::InvalidSubmissionUrl);
     }
 
+    // A team must agree on its split before handing in work
+    ensure_team_confirmed(env, bounty_id)?;
+
     // Update status
     bounty.status = BountyStatus::UnderReview;
     bounty.submission_url = Some(submission_url.clone());
 
     store_bounty(env, &bounty);
+    record_submission(env, bounty_id, &claimer, &submission_url);
 
     // Emit submission event
     env.events().publish(
@@ -297,42 +473,55 @@ pub fn submit_work(env: &Env, bounty_id: u64, submission_url: String) -> bool {
         },
     );
 
-    true
+    Ok(true)
 }
 
 /// Approve completion of a bounty
 ///
+/// If automatic release is on for the bounty, its escrow is released in the
+/// same call.
+///
 /// # Arguments
 /// * `env` - The contract environment
 /// * `bounty_id` - The ID of the bounty to approve
-/// * `approver` - Address of the approver (must be guild admin/owner)
+/// * `approver` - Address of the approver (must hold `ApproveWork`)
 ///
 /// # Returns
 /// `true` if approval was successful
 ///
-/// # Panics
-/// - If bounty is not found
-/// - If approver is not a guild admin/owner
-/// - If bounty is not under review
-pub fn approve_completion(env: &Env, bounty_id: u64, approver: Address) -> bool {
+/// # Errors
+/// - `BountyError::BountyNotFound` if bounty is not found
+/// - `BountyError::Unauthorized` if approver lacks `ApproveWork`
+/// - `BountyError::NotUnderReview` if bounty is not under review
+/// - Any error of `release_escrow` if automatic release is on
+pub fn approve_completion(
+    env: &Env,
+    bounty_id: u64,
+    approver: Address,
+) -> Result<bool, BountyError> {
     approver.require_auth();
 
-    let mut bounty = get_bounty(env, bounty_id).expect("Bounty not found");
+    let mut bounty = get_bounty(env, bounty_id).ok_or(BountyError::BountyNotFound)?;
 
     // Verify approver permissions
-    if !has_permission(env, bounty.guild_id, approver.clone(), Role::Admin) {
-        panic!("Unauthorized: Approver must be a guild admin or owner");
+    if !has_guild_permission(env, bounty.guild_id, approver.clone(), Permission::ApproveWork) {
+        return Err(BountyError::Unauthorized);
     }
 
     // Must be under review
     if bounty.status != BountyStatus::UnderReview {
-        panic!("Bounty is not under review");
+        return Err(BountyError::NotUnderReview);
     }
 
     // Update status
     bounty.status = BountyStatus::Completed;
 
     store_bounty(env, &bounty);
+    mark_latest_submission(env, bounty_id, SubmissionOutcome::Approved, None);
+
+    for worker in bounty_workers(env, &bounty).iter() {
+        reputation::record_bounty_completed(env, bounty.guild_id, &worker);
+    }
 
     // Emit approval event
     env.events().publish(
@@ -343,11 +532,55 @@ pub fn approve_completion(env: &Env, bounty_id: u64, approver: Address) -> bool
         },
     );
 
-    true
+    if get_auto_release(env, bounty_id) {
+        release_escrow(env, bounty_id)?;
+    }
+
+    Ok(true)
+}
+
+/// Turn automatic release of a bounty's escrow on approval on or off
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `bounty_id` - The ID of the bounty
+/// * `caller` - The bounty creator or a holder of `ManageBounties`
+/// * `enabled` - Whether `approve_completion` also releases the escrow
+///
+/// # Errors
+/// - `BountyError::BountyNotFound` if bounty is not found
+/// - `BountyError::Unauthorized` if caller is neither the creator nor holds `ManageBounties`
+pub fn set_auto_release(
+    env: &Env,
+    bounty_id: u64,
+    caller: Address,
+    enabled: bool,
+) -> Result<bool, BountyError> {
+    caller.require_auth();
+
+    let bounty = get_bounty(env, bounty_id).ok_or(BountyError::BountyNotFound)?;
+    if caller != bounty.creator
+        && !has_guild_permission(env, bounty.guild_id, caller, Permission::ManageBounties)
+    {
+        return Err(BountyError::Unauthorized);
+    }
+
+    store_auto_release(env, bounty_id, enabled);
+
+    env.events().publish(
+        (Symbol::new(env, "bounty"), Symbol::new(env, "auto_release")),
+        AutoReleaseUpdatedEvent { bounty_id, enabled },
+    );
+
+    Ok(true)
 }
 
 /// Release escrow funds to the bounty claimer
 ///
+/// A bounty worked by a team pays each member their agreed share. Guild and
+/// platform fees are withheld from each payment. The bounty then moves to
+/// `Paid`, so its escrow can only be released once.
+///
 /// # Arguments
 /// * `env` - The contract environment
 /// * `bounty_id` - The ID of the completed bounty
@@ -355,37 +588,34 @@ pub fn approve_completion(env: &Env, bounty_id: u64, approver: Address) -> bool
 /// # Returns
 /// `true` if release was successful
 ///
-/// # Panics
-/// - If bounty is not found
-/// - If bounty is not completed
-/// - If no claimer exists
-pub fn release_escrow(env: &Env, bounty_id: u64) -> bool {
-    let bounty = get_bounty(env, bounty_id).expect("Bounty not found");
-
-    // Must be completed
+/// # Errors
+/// - `BountyError::BountyNotFound` if bounty is not found
+/// - `BountyError::NotCompleted` if bounty is not completed or was already
+///   paid
+/// - `BountyError::NoClaimer` if no claimer exists
+/// - `BountyError::InsufficientEscrow` if the bounty's escrow is not recorded
+///   as held, or the contract holds less escrow for the token than the bounty
+///   is owed
+pub fn release_escrow(env: &Env, bounty_id: u64) -> Result<bool, BountyError> {
+    let mut bounty = get_bounty(env, bounty_id).ok_or(BountyError::BountyNotFound)?;
+
+    // Must be completed; a paid bounty has already been released
     if bounty.status != BountyStatus::Completed {
-        panic!("Bounty is not completed");
+        return Err(BountyError::NotCompleted);
     }
 
-    let claimer = bounty.claimer.clone().expect("No claimer for this bounty");
+    let payouts = worker_payouts(env, &bounty, bounty.funded_amount)?;
 
-    // Release funds to claimer
+    // Release funds to claimer, or to each team member, less fees
     if bounty.funded_amount > 0 {
-        release_funds(env, &bounty.token, &claimer, bounty.funded_amount);
-
-        // Emit release event
-        env.events().publish(
-            (Symbol::new(env, "bounty"), Symbol::new(env, "released")),
-            EscrowReleasedEvent {
-                bounty_id,
-                recipient: claimer,
-                amount: bounty.funded_amount,
-                token: bounty.token,
-            },
-        );
+        pay_workers(env, &bounty, &payouts);
     }
 
-    true
+    close_escrow(env, &mut bounty, EscrowState::Released)?;
+    bounty.status = BountyStatus::Paid;
+    store_bounty(env, &bounty);
+
+    Ok(true)
 }
 
 /// Cancel a bounty and refund escrowed funds
@@ -393,43 +623,47 @@ pub fn release_escrow(env: &Env, bounty_id: u64) -> bool {
 /// # Arguments
 /// * `env` - The contract environment
 /// * `bounty_id` - The ID of the bounty to cancel
-/// * `canceller` - Address attempting to cancel (must be creator or guild admin)
+/// * `canceller` - Address attempting to cancel (creator or holder of `ManageBounties`)
 ///
 /// # Returns
 /// `true` if cancellation was successful
 ///
-/// # Panics
-/// - If bounty is not found
-/// - If canceller is not authorized
-/// - If bounty is already completed or cancelled
-pub fn cancel_bounty(env: &Env, bounty_id: u64, canceller: Address) -> bool {
+/// # Errors
+/// - `BountyError::BountyNotFound` if bounty is not found
+/// - `BountyError::NotCancellable` if bounty is already completed or
+///   cancelled, or is in dispute
+/// - `BountyError::Unauthorized` if canceller is not authorized
+pub fn cancel_bounty(env: &Env, bounty_id: u64, canceller: Address) -> Result<bool, BountyError> {
     canceller.require_auth();
 
-    let mut bounty = get_bounty(env, bounty_id).expect("Bounty not found");
-
-    // Cannot cancel completed or already cancelled bounties
-    match bounty.status {
-        BountyStatus::Completed | BountyStatus::Cancelled => {
-            panic!("Bounty cannot be cancelled in current status");
-        }
-        _ => {}
+    let mut bounty = get_bounty(env, bounty_id).ok_or(BountyError::BountyNotFound)?;
+
+    // Cannot cancel completed, paid, already cancelled or disputed bounties
+    if matches!(
+        bounty.status,
+        BountyStatus::Completed
+            | BountyStatus::Paid
+            | BountyStatus::Cancelled
+            | BountyStatus::Disputed
+    ) {
+        return Err(BountyError::NotCancellable);
     }
 
-    // Authorization: creator or guild admin can cancel
+    // Authorization: creator or a holder of `ManageBounties` can cancel
     let is_creator = bounty.creator == canceller;
-    let is_admin = has_permission(env, bounty.guild_id, canceller.clone(), Role::Admin);
+    let is_manager =
+        has_guild_permission(env, bounty.guild_id, canceller.clone(), Permission::ManageBounties);
 
-    if !is_creator && !is_admin {
-        panic!("Unauthorized: Only creator or guild admin can cancel");
+    if !is_creator && !is_manager {
+        return Err(BountyError::Unauthorized);
     }
 
     let refund_amount = bounty.funded_amount;
-    let refund_recipient = bounty.creator.clone();
 
-    // Refund escrowed funds to creator
+    // Return escrowed funds to the funders
     if refund_amount > 0 {
-        release_funds(env, &bounty.token, &refund_recipient, refund_amount);
-        bounty.funded_amount = 0;
+        refund_funders(env, &bounty, refund_amount);
+        close_escrow(env, &mut bounty, EscrowState::Refunded)?;
     }
 
     bounty.status = BountyStatus::Cancelled;
@@ -442,11 +676,10 @@ pub fn cancel_bounty(env: &Env, bounty_id: u64, canceller: Address) -> bool {
             bounty_id,
             canceller,
             refund_amount,
-            refund_recipient,
         },
     );
 
-    true
+    Ok(true)
 }
 
 /// Handle expired bounty - refund funds and update status
@@ -457,26 +690,40 @@ pub fn cancel_bounty(env: &Env, bounty_id: u64, canceller: Address) -> bool {
 ///
 /// # Returns
 /// `true` if bounty was expired and refunded
-pub fn expire_bounty(env: &Env, bounty_id: u64) -> bool {
-    let mut bounty = get_bounty(env, bounty_id).expect("Bounty not found");
-
-    // Already expired or completed
+///
+/// # Errors
+/// - `BountyError::BountyNotFound` if bounty is not found
+/// - `BountyError::InsufficientEscrow` if the contract holds less escrow for
+///   the token than the bounty is owed
+pub fn expire_bounty(env: &Env, bounty_id: u64) -> Result<bool, BountyError> {
+    let mut bounty = get_bounty(env, bounty_id).ok_or(BountyError::BountyNotFound)?;
+
+    // Already expired or completed; disputed escrow waits for the ruling
     if bounty.status == BountyStatus::Expired
         || bounty.status == BountyStatus::Completed
+        || bounty.status == BountyStatus::Paid
         || bounty.status == BountyStatus::Cancelled
+        || bounty.status == BountyStatus::Disputed
     {
-        return false;
+        return Ok(false);
     }
 
     let now = env.ledger().timestamp();
     if now <= bounty.expires_at {
-        return false; // Not expired yet
+        return Ok(false); // Not expired yet
     }
 
-    // Refund escrowed funds
+    // Return escrowed funds to the funders
     if bounty.funded_amount > 0 {
-        release_funds(env, &bounty.token, &bounty.creator, bounty.funded_amount);
-        bounty.funded_amount = 0;
+        refund_funders(env, &bounty, bounty.funded_amount);
+        close_escrow(env, &mut bounty, EscrowState::Refunded)?;
+    }
+
+    // A claimer who let the bounty lapse loses reputation
+    if matches!(bounty.status, BountyStatus::Claimed | BountyStatus::UnderReview) {
+        if let Some(claimer) = bounty.claimer.as_ref() {
+            reputation::record_bounty_expired(env, bounty.guild_id, claimer);
+        }
     }
 
     bounty.status = BountyStatus::Expired;
@@ -488,11 +735,42 @@ pub fn expire_bounty(env: &Env, bounty_id: u64) -> bool {
         BountyExpiredEvent { bounty_id },
     );
 
-    true
+    Ok(true)
+}
+
+/// Expire a bounty taken off the keeper's deadline index
+pub(crate) fn expire_due_bounty(env: &Env, bounty_id: u64) -> DueOutcome {
+    let Some(bounty) = get_bounty(env, bounty_id) else {
+        return DueOutcome::Settled;
+    };
+    if bounty.status != BountyStatus::Disputed && env.ledger().timestamp() <= bounty.expires_at {
+        return DueOutcome::Pending(bounty.expires_at);
+    }
+
+    match expire_bounty(env, bounty_id) {
+        Ok(true) => DueOutcome::Expired,
+        _ => DueOutcome::Settled,
+    }
 }
 
 // ============ Query Functions ============
 
+/// Get where a bounty's escrow stands
+///
+/// # Returns
+/// The escrow state, or `None` if the bounty was never funded or its escrow
+/// awaits `reconcile_bounty_escrow` after the schema migration
+///
+/// # Errors
+/// - `BountyError::BountyNotFound` if bounty is not found
+pub fn get_bounty_escrow_state(
+    env: &Env,
+    bounty_id: u64,
+) -> Result<Option<EscrowState>, BountyError> {
+    get_bounty(env, bounty_id).ok_or(BountyError::BountyNotFound)?;
+    Ok(get_escrow_state(env, bounty_id))
+}
+
 /// Get bounty by ID
 ///
 /// # Arguments
@@ -502,10 +780,26 @@ pub fn expire_bounty(env: &Env, bounty_id: u64) -> bool {
 /// # Returns
 /// The Bounty struct
 ///
-/// # Panics
-/// If bounty is not found
-pub fn get_bounty_data(env: &Env, bounty_id: u64) -> Bounty {
-    get_bounty(env, bounty_id).expect("Bounty not found")
+/// # Errors
+/// `BountyError::BountyNotFound` if bounty is not found
+pub fn get_bounty_data(env: &Env, bounty_id: u64) -> Result<Bounty, BountyError> {
+    get_bounty(env, bounty_id).ok_or(BountyError::BountyNotFound)
+}
+
+/// Get the contributions made to a bounty's escrow
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `bounty_id` - The ID of the bounty
+///
+/// # Returns
+/// Each funder and the amount they contributed, in funding order
+///
+/// # Errors
+/// `BountyError::BountyNotFound` if bounty is not found
+pub fn get_bounty_funders(env: &Env, bounty_id: u64) -> Result<Vec<BountyFunder>, BountyError> {
+    get_bounty(env, bounty_id).ok_or(BountyError::BountyNotFound)?;
+    Ok(get_funders(env, bounty_id))
 }
 
 /// Get all bounties for a guild
@@ -520,10 +814,5 @@ pub fn get_guild_bounties_list(env: &Env, guild_id: u64) -> Vec<Bounty> {
     get_guild_bounties(env, guild_id)
 }
 
-// Legacy function name for compatibility
-pub fn cancel_bounty_auth(env: &Env, bounty_id: u64, canceller: Address) -> bool {
-    cancel_bounty(env, bounty_id, canceller)
-}
-
 #[cfg(test)]
 mod tests;
@@ -0,0 +1,217 @@
+//! Review of bounty submissions
+//!
+//! Reviewers can approve work (see `approve_completion`), send it back to the
+//! claimer for a bounded number of revision rounds, or reject it outright,
+//! which releases the claim and reopens the bounty. Every submission is kept
+//! with its outcome so the review trail survives resubmission.
+
+use crate::bounty::claims::{reopen, start_work_window};
+use crate::bounty::storage::{get_bounty, get_submissions, store_bounty, store_submissions};
+use crate::bounty::types::{
+    BountyError, BountyStatus, ChangesRequestedEvent, Submission, SubmissionOutcome,
+    SubmissionRejectedEvent,
+};
+use crate::guild::roles::has_guild_permission;
+use crate::guild::types::Permission;
+use soroban_sdk::{Address, Env, String, Symbol, Vec};
+
+/// Revision rounds a claimer gets before the reviewer must approve or reject
+pub const MAX_REVISION_ROUNDS: u32 = 3;
+
+const MAX_FEEDBACK_LEN: u32 = 1024;
+
+/// Send submitted work back to the claimer
+///
+/// The claimer gets a fresh work window for the revision.
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `bounty_id` - The ID of the bounty under review
+/// * `reviewer` - Address of the reviewer (must hold `ApproveWork`)
+/// * `feedback` - What needs to change
+///
+/// # Returns
+/// `true` if the bounty was returned to `Claimed`
+///
+/// # Errors
+/// - `BountyError::BountyNotFound` if bounty is not found
+/// - `BountyError::Unauthorized` if reviewer lacks `ApproveWork`
+/// - `BountyError::NotUnderReview` if bounty is not under review
+/// - `BountyError::InvalidFeedback` if feedback is empty or too long
+/// - `BountyError::RevisionLimitReached` if the claimer has used every round
+pub fn request_changes(
+    env: &Env,
+    bounty_id: u64,
+    reviewer: Address,
+    feedback: String,
+) -> Result<bool, BountyError> {
+    reviewer.require_auth();
+
+    let mut bounty = get_bounty(env, bounty_id).ok_or(BountyError::BountyNotFound)?;
+
+    if !has_guild_permission(env, bounty.guild_id, reviewer.clone(), Permission::ApproveWork) {
+        return Err(BountyError::Unauthorized);
+    }
+    if bounty.status != BountyStatus::UnderReview {
+        return Err(BountyError::NotUnderReview);
+    }
+    validate_feedback(&feedback)?;
+
+    let claimer = bounty.claimer.clone().ok_or(BountyError::NoClaimer)?;
+    let round = revision_rounds(env, bounty_id, &claimer) + 1;
+    if round > MAX_REVISION_ROUNDS {
+        return Err(BountyError::RevisionLimitReached);
+    }
+
+    mark_latest_submission(
+        env,
+        bounty_id,
+        SubmissionOutcome::ChangesRequested,
+        Some(feedback.clone()),
+    );
+
+    bounty.status = BountyStatus::Claimed;
+    bounty.submission_url = None;
+    store_bounty(env, &bounty);
+    start_work_window(env, bounty_id);
+
+    env.events().publish(
+        (
+            Symbol::new(env, "bounty"),
+            Symbol::new(env, "changes_requested"),
+        ),
+        ChangesRequestedEvent {
+            bounty_id,
+            reviewer,
+            feedback,
+            round,
+        },
+    );
+
+    Ok(true)
+}
+
+/// Reject submitted work and release the claim
+///
+/// The bounty reopens for other contributors; escrow is untouched.
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `bounty_id` - The ID of the bounty under review
+/// * `reviewer` - Address of the reviewer (must hold `ApproveWork`)
+/// * `feedback` - Why the work was rejected
+///
+/// # Returns
+/// `true` if the bounty was reopened
+///
+/// # Errors
+/// - `BountyError::BountyNotFound` if bounty is not found
+/// - `BountyError::Unauthorized` if reviewer lacks `ApproveWork`
+/// - `BountyError::NotUnderReview` if bounty is not under review
+/// - `BountyError::InvalidFeedback` if feedback is empty or too long
+pub fn reject_submission(
+    env: &Env,
+    bounty_id: u64,
+    reviewer: Address,
+    feedback: String,
+) -> Result<bool, BountyError> {
+    reviewer.require_auth();
+
+    let mut bounty = get_bounty(env, bounty_id).ok_or(BountyError::BountyNotFound)?;
+
+    if !has_guild_permission(env, bounty.guild_id, reviewer.clone(), Permission::ApproveWork) {
+        return Err(BountyError::Unauthorized);
+    }
+    if bounty.status != BountyStatus::UnderReview {
+        return Err(BountyError::NotUnderReview);
+    }
+    validate_feedback(&feedback)?;
+
+    let claimer = bounty.claimer.clone().ok_or(BountyError::NoClaimer)?;
+
+    mark_latest_submission(
+        env,
+        bounty_id,
+        SubmissionOutcome::Rejected,
+        Some(feedback.clone()),
+    );
+
+    reopen(env, &mut bounty);
+    store_bounty(env, &bounty);
+
+    env.events().publish(
+        (Symbol::new(env, "bounty"), Symbol::new(env, "rejected")),
+        SubmissionRejectedEvent {
+            bounty_id,
+            reviewer,
+            claimer,
+            feedback,
+        },
+    );
+
+    Ok(true)
+}
+
+/// Get every submission made for a bounty, oldest first
+///
+/// # Errors
+/// `BountyError::BountyNotFound` if bounty is not found
+pub fn get_submission_history(env: &Env, bounty_id: u64) -> Result<Vec<Submission>, BountyError> {
+    get_bounty(env, bounty_id).ok_or(BountyError::BountyNotFound)?;
+    Ok(get_submissions(env, bounty_id))
+}
+
+/// Append a pending submission to a bounty's history
+pub(crate) fn record_submission(
+    env: &Env,
+    bounty_id: u64,
+    claimer: &Address,
+    submission_url: &String,
+) {
+    let mut submissions = get_submissions(env, bounty_id);
+    submissions.push_back(Submission {
+        claimer: claimer.clone(),
+        submission_url: submission_url.clone(),
+        submitted_at: env.ledger().timestamp(),
+        outcome: SubmissionOutcome::Pending,
+        feedback: None,
+        reviewed_at: None,
+    });
+    store_submissions(env, bounty_id, &submissions);
+}
+
+/// Record the review outcome of a bounty's most recent submission
+///
+/// Bounties submitted before the history existed have nothing to update.
+pub(crate) fn mark_latest_submission(
+    env: &Env,
+    bounty_id: u64,
+    outcome: SubmissionOutcome,
+    feedback: Option<String>,
+) {
+    let mut submissions = get_submissions(env, bounty_id);
+    let Some(mut latest) = submissions.last() else {
+        return;
+    };
+
+    latest.outcome = outcome;
+    latest.feedback = feedback;
+    latest.reviewed_at = Some(env.ledger().timestamp());
+    submissions.set(submissions.len() - 1, latest);
+    store_submissions(env, bounty_id, &submissions);
+}
+
+/// Count the revision rounds already spent by a claimer on a bounty
+fn revision_rounds(env: &Env, bounty_id: u64, claimer: &Address) -> u32 {
+    get_submissions(env, bounty_id)
+        .iter()
+        .filter(|s| &s.claimer == claimer && s.outcome == SubmissionOutcome::ChangesRequested)
+        .count() as u32
+}
+
+fn validate_feedback(feedback: &String) -> Result<(), BountyError> {
+    if feedback.is_empty() || feedback.len() > MAX_FEEDBACK_LEN {
+        return Err(BountyError::InvalidFeedback);
+    }
+    Ok(())
+}
@@ -1,52 +1,76 @@
-use crate::bounty::types::Bounty;
-use soroban_sdk::{symbol_short, Env, Map, Symbol, Vec};
+use crate::bounty::types::{
+    AbandonmentRecord, Application, AssignmentMode, Bounty, BountyFilter, BountyFunder,
+    BountyMetadata, BountyStatus, BountyTeam, Dispute, EscrowState, Submission,
+};
+use crate::paging;
+use crate::ttl;
+use soroban_sdk::{contracttype, symbol_short, Address, Env, Map, Symbol, Vec};
 
 // Storage keys
 const BOUNTIES_KEY: Symbol = symbol_short!("bounties");
 const BOUNTY_CNT_KEY: Symbol = symbol_short!("b_cnt");
 const GUILD_BOUNTIES_KEY: Symbol = symbol_short!("g_bnties");
 
+/// Keys for per-bounty assignment, claim, team, funding, escrow,
+/// submission, dispute and discovery state, per-guild arbiters,
+/// per-contributor abandonment records, per-token escrow totals and the
+/// secondary indexes used by discovery queries
+#[contracttype]
+#[derive(Clone)]
+pub enum BountyStorageKey {
+    Dispute(u64),
+    Arbiters(u64),
+    Funders(u64),
+    Submissions(u64),
+    AssignmentMode(u64),
+    Applications(u64),
+    WorkWindow(u64),
+    ClaimDeadline(u64),
+    Abandonments(Address),
+    Team(u64),
+    Metadata(u64),
+    EscrowState(u64),
+    AutoRelease(u64),
+    EscrowBalance(Address), // tokens held in escrow for all bounties
+    IndexLen(BountyFilter),       // number of bounty IDs in a secondary index
+    IndexPage(BountyFilter, u32), // Vec<u64>
+    IndexSlot(BountyFilter, u64), // position of the bounty in a secondary index
+}
+
 /// Initialize bounty storage
 pub fn initialize(env: &Env) {
-    if !env.storage().persistent().has(&BOUNTY_CNT_KEY) {
-        env.storage().persistent().set(&BOUNTY_CNT_KEY, &0u64);
+    if !env.storage().instance().has(&BOUNTY_CNT_KEY) {
+        ttl::set_counter(env, &BOUNTY_CNT_KEY, 0);
     }
 }
 
 /// Get the next bounty ID and increment
 pub fn get_next_bounty_id(env: &Env) -> u64 {
-    let counter: u64 = env
-        .storage()
-        .persistent()
-        .get(&BOUNTY_CNT_KEY)
-        .unwrap_or(0u64);
+    let counter = ttl::get_counter(env, &BOUNTY_CNT_KEY, 0);
 
     let next_id = counter + 1;
-    env.storage().persistent().set(&BOUNTY_CNT_KEY, &next_id);
+    ttl::set_counter(env, &BOUNTY_CNT_KEY, next_id);
 
     next_id
 }
 
-/// Store a bounty and update the guild index
+/// Store a bounty and update the guild and secondary indexes
 pub fn store_bounty(env: &Env, bounty: &Bounty) {
     // 1. Save to main bounties map
-    let mut bounties: Map<u64, Bounty> = env
-        .storage()
-        .persistent()
-        .get(&BOUNTIES_KEY)
-        .unwrap_or_else(|| Map::new(env));
+    let mut bounties: Map<u64, Bounty> =
+        ttl::get_persistent(env, &BOUNTIES_KEY).unwrap_or_else(|| Map::new(env));
 
-    let is_new = !bounties.contains_key(bounty.id);
+    let previous = bounties.get(bounty.id);
+    let is_new = previous.is_none();
     bounties.set(bounty.id, bounty.clone());
-    env.storage().persistent().set(&BOUNTIES_KEY, &bounties);
+    ttl::set_persistent(env, &BOUNTIES_KEY, &bounties);
+
+    update_indexes(env, previous.as_ref(), bounty);
 
     // 2. Update guild index if it's a new bounty
     if is_new {
-        let mut guild_bounties: Map<u64, Vec<u64>> = env
-            .storage()
-            .persistent()
-            .get(&GUILD_BOUNTIES_KEY)
-            .unwrap_or_else(|| Map::new(env));
+        let mut guild_bounties: Map<u64, Vec<u64>> =
+            ttl::get_persistent(env, &GUILD_BOUNTIES_KEY).unwrap_or_else(|| Map::new(env));
 
         let mut list = guild_bounties
             .get(bounty.guild_id)
@@ -54,40 +78,43 @@ pub fn store_bounty(env: &Env, bounty: &Bounty) {
 
         list.push_back(bounty.id);
         guild_bounties.set(bounty.guild_id, list);
-        env.storage()
-            .persistent()
-            .set(&GUILD_BOUNTIES_KEY, &guild_bounties);
+        ttl::set_persistent(env, &GUILD_BOUNTIES_KEY, &guild_bounties);
     }
 }
 
 /// Get a bounty by ID
 pub fn get_bounty(env: &Env, bounty_id: u64) -> Option<Bounty> {
-    let bounties: Map<u64, Bounty> = env
-        .storage()
-        .persistent()
-        .get(&BOUNTIES_KEY)
-        .unwrap_or_else(|| Map::new(env));
+    let bounties: Map<u64, Bounty> =
+        ttl::get_persistent(env, &BOUNTIES_KEY).unwrap_or_else(|| Map::new(env));
 
     bounties.get(bounty_id)
 }
 
+/// Get the bounties with the given IDs, in order, skipping unknown IDs
+pub fn get_bounties(env: &Env, bounty_ids: &Vec<u64>) -> Vec<Bounty> {
+    let bounties_map: Map<u64, Bounty> =
+        ttl::get_persistent(env, &BOUNTIES_KEY).unwrap_or_else(|| Map::new(env));
+
+    let mut result = Vec::new(env);
+    for id in bounty_ids.iter() {
+        if let Some(b) = bounties_map.get(id) {
+            result.push_back(b);
+        }
+    }
+    result
+}
+
 /// Get all bounties for a guild
 pub fn get_guild_bounties(env: &Env, guild_id: u64) -> Vec<Bounty> {
-    let guild_bounties: Map<u64, Vec<u64>> = env
-        .storage()
-        .persistent()
-        .get(&GUILD_BOUNTIES_KEY)
-        .unwrap_or_else(|| Map::new(env));
+    let guild_bounties: Map<u64, Vec<u64>> =
+        ttl::get_persistent(env, &GUILD_BOUNTIES_KEY).unwrap_or_else(|| Map::new(env));
 
     let bounty_ids = guild_bounties
         .get(guild_id)
         .unwrap_or_else(|| Vec::new(env));
 
-    let bounties_map: Map<u64, Bounty> = env
-        .storage()
-        .persistent()
-        .get(&BOUNTIES_KEY)
-        .unwrap_or_else(|| Map::new(env));
+    let bounties_map: Map<u64, Bounty> =
+        ttl::get_persistent(env, &BOUNTIES_KEY).unwrap_or_else(|| Map::new(env));
 
     let mut result = Vec::new(env);
     for id in bounty_ids.iter() {
@@ -97,3 +124,369 @@ pub fn get_guild_bounties(env: &Env, guild_id: u64) -> Vec<Bounty> {
     }
     result
 }
+
+/// Get up to `limit` of a guild's bounty IDs, starting at position `cursor`
+pub fn get_guild_bounty_ids(env: &Env, guild_id: u64, cursor: u32, limit: u32) -> Vec<u64> {
+    let guild_bounties: Map<u64, Vec<u64>> =
+        ttl::get_persistent(env, &GUILD_BOUNTIES_KEY).unwrap_or_else(|| Map::new(env));
+
+    let ids = guild_bounties
+        .get(guild_id)
+        .unwrap_or_else(|| Vec::new(env));
+    let end = cursor.saturating_add(limit).min(ids.len());
+    if cursor >= end {
+        return Vec::new(env);
+    }
+    ids.slice(cursor..end)
+}
+
+/// Number of bounties in a guild
+pub fn count_guild_bounties(env: &Env, guild_id: u64) -> u32 {
+    let guild_bounties: Map<u64, Vec<u64>> =
+        ttl::get_persistent(env, &GUILD_BOUNTIES_KEY).unwrap_or_else(|| Map::new(env));
+
+    guild_bounties.get(guild_id).map_or(0, |ids| ids.len())
+}
+
+/// Keep the status, claimer, creator and token indexes in step with a write
+///
+/// Creator and token never change, so they are indexed once. A bounty is
+/// moved between status indexes and claimer indexes as those fields change.
+fn update_indexes(env: &Env, previous: Option<&Bounty>, bounty: &Bounty) {
+    match previous {
+        None => {
+            index_add(
+                env,
+                &BountyFilter::Creator(bounty.creator.clone()),
+                bounty.id,
+            );
+            index_add(env, &BountyFilter::Token(bounty.token.clone()), bounty.id);
+        }
+        Some(prev) => {
+            if prev.status != bounty.status {
+                index_remove(env, &BountyFilter::Status(prev.status), bounty.id);
+            }
+            if prev.claimer != bounty.claimer {
+                if let Some(claimer) = prev.claimer.clone() {
+                    index_remove(env, &BountyFilter::Claimer(claimer), bounty.id);
+                }
+            }
+        }
+    }
+
+    index_add(env, &BountyFilter::Status(bounty.status), bounty.id);
+    if let Some(claimer) = bounty.claimer.clone() {
+        index_add(env, &BountyFilter::Claimer(claimer), bounty.id);
+    }
+}
+
+/// Add a bounty to a secondary index; does nothing if it is already listed
+pub fn index_add(env: &Env, index: &BountyFilter, bounty_id: u64) {
+    let slot_key = BountyStorageKey::IndexSlot(index.clone(), bounty_id);
+    if env.storage().persistent().has(&slot_key) {
+        return;
+    }
+
+    let slot = paging::push(
+        env,
+        &BountyStorageKey::IndexLen(index.clone()),
+        |page| BountyStorageKey::IndexPage(index.clone(), page),
+        bounty_id,
+    );
+    ttl::set_persistent(env, &slot_key, &slot);
+}
+
+/// Remove a bounty from a secondary index, if it is listed
+pub fn index_remove(env: &Env, index: &BountyFilter, bounty_id: u64) {
+    let slot_key = BountyStorageKey::IndexSlot(index.clone(), bounty_id);
+    let Some(slot) = ttl::get_persistent::<_, u32>(env, &slot_key) else {
+        return;
+    };
+
+    let moved: Option<u64> = paging::swap_remove(
+        env,
+        &BountyStorageKey::IndexLen(index.clone()),
+        |page| BountyStorageKey::IndexPage(index.clone(), page),
+        slot,
+    );
+    if let Some(moved) = moved {
+        ttl::set_persistent(
+            env,
+            &BountyStorageKey::IndexSlot(index.clone(), moved),
+            &slot,
+        );
+    }
+    env.storage().persistent().remove(&slot_key);
+}
+
+/// Number of bounties in a secondary index
+pub fn index_len(env: &Env, index: &BountyFilter) -> u32 {
+    paging::len(env, &BountyStorageKey::IndexLen(index.clone()))
+}
+
+/// Get up to `limit` bounty IDs of a secondary index, starting at `cursor`
+pub fn get_index_ids(env: &Env, index: &BountyFilter, cursor: u32, limit: u32) -> Vec<u64> {
+    paging::get_range(
+        env,
+        &BountyStorageKey::IndexLen(index.clone()),
+        |page| BountyStorageKey::IndexPage(index.clone(), page),
+        cursor,
+        limit,
+    )
+}
+
+/// Store the tags and difficulty of a bounty
+pub fn store_metadata(env: &Env, bounty_id: u64, metadata: &BountyMetadata) {
+    ttl::set_persistent(env, &BountyStorageKey::Metadata(bounty_id), metadata);
+}
+
+/// Get the tags and difficulty of a bounty, if they were set
+pub fn get_metadata(env: &Env, bounty_id: u64) -> Option<BountyMetadata> {
+    ttl::get_persistent(env, &BountyStorageKey::Metadata(bounty_id))
+}
+
+/// Store how a bounty gets its claimer
+pub fn store_assignment_mode(env: &Env, bounty_id: u64, mode: &AssignmentMode) {
+    ttl::set_persistent(env, &BountyStorageKey::AssignmentMode(bounty_id), mode);
+}
+
+/// Get how a bounty gets its claimer; bounties created before assignment
+/// modes existed are first-come
+pub fn get_assignment_mode(env: &Env, bounty_id: u64) -> AssignmentMode {
+    ttl::get_persistent(env, &BountyStorageKey::AssignmentMode(bounty_id))
+        .unwrap_or(AssignmentMode::FirstCome)
+}
+
+/// Store the open applications for a bounty
+pub fn store_applications(env: &Env, bounty_id: u64, applications: &Vec<Application>) {
+    ttl::set_persistent(env, &BountyStorageKey::Applications(bounty_id), applications);
+}
+
+/// Get the open applications for a bounty, oldest first
+pub fn get_applications(env: &Env, bounty_id: u64) -> Vec<Application> {
+    ttl::get_persistent(env, &BountyStorageKey::Applications(bounty_id))
+        .unwrap_or_else(|| Vec::new(env))
+}
+
+/// Store how long a claimer of the bounty has to submit work
+pub fn store_work_window(env: &Env, bounty_id: u64, seconds: u64) {
+    ttl::set_persistent(env, &BountyStorageKey::WorkWindow(bounty_id), &seconds);
+}
+
+/// Get how long a claimer of the bounty has to submit work, if customised
+pub fn get_work_window(env: &Env, bounty_id: u64) -> Option<u64> {
+    ttl::get_persistent(env, &BountyStorageKey::WorkWindow(bounty_id))
+}
+
+/// Store the timestamp by which the current claimer must submit
+pub fn store_claim_deadline(env: &Env, bounty_id: u64, deadline: u64) {
+    ttl::set_persistent(env, &BountyStorageKey::ClaimDeadline(bounty_id), &deadline);
+}
+
+/// Get the timestamp by which the current claimer must submit; claims made
+/// before work windows existed have none
+pub fn get_claim_deadline(env: &Env, bounty_id: u64) -> Option<u64> {
+    ttl::get_persistent(env, &BountyStorageKey::ClaimDeadline(bounty_id))
+}
+
+/// Store the abandonment record of a contributor
+pub fn store_abandonments(env: &Env, address: &Address, record: &AbandonmentRecord) {
+    ttl::set_persistent(env, &BountyStorageKey::Abandonments(address.clone()), record);
+}
+
+/// Get the abandonment record of a contributor
+pub fn get_abandonments(env: &Env, address: &Address) -> AbandonmentRecord {
+    ttl::get_persistent(env, &BountyStorageKey::Abandonments(address.clone())).unwrap_or_default()
+}
+
+/// Store the team working a bounty
+pub fn store_team(env: &Env, bounty_id: u64, team: &BountyTeam) {
+    ttl::set_persistent(env, &BountyStorageKey::Team(bounty_id), team);
+}
+
+/// Get the team working a bounty, if the claimer formed one
+pub fn get_team(env: &Env, bounty_id: u64) -> Option<BountyTeam> {
+    ttl::get_persistent(env, &BountyStorageKey::Team(bounty_id))
+}
+
+/// Disband the team working a bounty
+pub fn remove_team(env: &Env, bounty_id: u64) {
+    env.storage()
+        .persistent()
+        .remove(&BountyStorageKey::Team(bounty_id));
+}
+
+/// Store where a bounty's escrow stands
+pub fn store_escrow_state(env: &Env, bounty_id: u64, state: &EscrowState) {
+    ttl::set_persistent(env, &BountyStorageKey::EscrowState(bounty_id), state);
+}
+
+/// Get where a bounty's escrow stands, if it was ever funded
+pub fn get_escrow_state(env: &Env, bounty_id: u64) -> Option<EscrowState> {
+    ttl::get_persistent(env, &BountyStorageKey::EscrowState(bounty_id))
+}
+
+/// Store whether approving a bounty also releases its escrow
+pub fn store_auto_release(env: &Env, bounty_id: u64, enabled: bool) {
+    ttl::set_persistent(env, &BountyStorageKey::AutoRelease(bounty_id), &enabled);
+}
+
+/// Get whether approving a bounty also releases its escrow
+pub fn get_auto_release(env: &Env, bounty_id: u64) -> bool {
+    ttl::get_persistent(env, &BountyStorageKey::AutoRelease(bounty_id)).unwrap_or(false)
+}
+
+/// Store how many tokens of `token` are held in escrow across all bounties
+pub fn set_escrow_balance(env: &Env, token: &Address, amount: i128) {
+    ttl::set_persistent(env, &BountyStorageKey::EscrowBalance(token.clone()), &amount);
+}
+
+/// Get how many tokens of `token` are held in escrow across all bounties
+pub fn get_escrow_balance(env: &Env, token: &Address) -> i128 {
+    ttl::get_persistent(env, &BountyStorageKey::EscrowBalance(token.clone())).unwrap_or(0)
+}
+
+/// Store the contributions made to a bounty's escrow
+pub fn store_funders(env: &Env, bounty_id: u64, funders: &Vec<BountyFunder>) {
+    ttl::set_persistent(env, &BountyStorageKey::Funders(bounty_id), funders);
+}
+
+/// Get the contributions made to a bounty's escrow, in funding order
+pub fn get_funders(env: &Env, bounty_id: u64) -> Vec<BountyFunder> {
+    ttl::get_persistent(env, &BountyStorageKey::Funders(bounty_id))
+        .unwrap_or_else(|| Vec::new(env))
+}
+
+/// Store the submission history of a bounty
+pub fn store_submissions(env: &Env, bounty_id: u64, submissions: &Vec<Submission>) {
+    ttl::set_persistent(env, &BountyStorageKey::Submissions(bounty_id), submissions);
+}
+
+/// Get the submission history of a bounty, oldest first
+pub fn get_submissions(env: &Env, bounty_id: u64) -> Vec<Submission> {
+    ttl::get_persistent(env, &BountyStorageKey::Submissions(bounty_id))
+        .unwrap_or_else(|| Vec::new(env))
+}
+
+/// Store the dispute opened over a bounty
+pub fn store_dispute(env: &Env, dispute: &Dispute) {
+    ttl::set_persistent(env, &BountyStorageKey::Dispute(dispute.bounty_id), dispute);
+}
+
+/// Get the dispute opened over a bounty, if any
+pub fn get_dispute(env: &Env, bounty_id: u64) -> Option<Dispute> {
+    ttl::get_persistent(env, &BountyStorageKey::Dispute(bounty_id))
+}
+
+/// Store a guild's arbiter panel
+pub fn store_arbiters(env: &Env, guild_id: u64, arbiters: &Vec<Address>) {
+    ttl::set_persistent(env, &BountyStorageKey::Arbiters(guild_id), arbiters);
+}
+
+/// Get a guild's arbiter panel
+pub fn get_arbiters(env: &Env, guild_id: u64) -> Vec<Address> {
+    ttl::get_persistent(env, &BountyStorageKey::Arbiters(guild_id)).unwrap_or_else(|| Vec::new(env))
+}
+
+/// Extend the TTL of the entries holding bounties and the guild's arbiter
+/// panel, and of everything stored for the bounties at positions
+/// `cursor..cursor + limit` of the guild's bounty index: its escrow and the
+/// token's escrow total, funders, assignment, applications, deadlines, team,
+/// submissions, dispute, metadata and the discovery index pages listing it
+///
+/// # Returns
+/// Whether the guild has bounties past the batch
+pub fn bump_guild_bounties(env: &Env, guild_id: u64, cursor: u32, limit: u32) -> bool {
+    ttl::extend_persistent_if_present(env, &BOUNTIES_KEY);
+    ttl::extend_persistent_if_present(env, &GUILD_BOUNTIES_KEY);
+    ttl::extend_persistent_if_present(env, &BountyStorageKey::Arbiters(guild_id));
+
+    let ids = get_guild_bounty_ids(env, guild_id, cursor, limit);
+    for bounty in get_bounties(env, &ids).iter() {
+        let id = bounty.id;
+        for key in [
+            BountyStorageKey::Dispute(id),
+            BountyStorageKey::Funders(id),
+            BountyStorageKey::Submissions(id),
+            BountyStorageKey::AssignmentMode(id),
+            BountyStorageKey::Applications(id),
+            BountyStorageKey::WorkWindow(id),
+            BountyStorageKey::ClaimDeadline(id),
+            BountyStorageKey::Team(id),
+            BountyStorageKey::Metadata(id),
+            BountyStorageKey::EscrowState(id),
+            BountyStorageKey::AutoRelease(id),
+            BountyStorageKey::EscrowBalance(bounty.token.clone()),
+        ] {
+            ttl::extend_persistent_if_present(env, &key);
+        }
+
+        let mut indexes = Vec::from_array(
+            env,
+            [
+                BountyFilter::Status(bounty.status),
+                BountyFilter::Token(bounty.token.clone()),
+                BountyFilter::Creator(bounty.creator.clone()),
+            ],
+        );
+        if let Some(claimer) = bounty.claimer.clone() {
+            indexes.push_back(BountyFilter::Claimer(claimer));
+        }
+        if let Some(metadata) = get_metadata(env, id) {
+            for tag in metadata.tags.iter() {
+                indexes.push_back(BountyFilter::Tag(tag));
+            }
+        }
+        for index in indexes.iter() {
+            bump_index_entry(env, &index, id);
+        }
+    }
+
+    count_guild_bounties(env, guild_id) > cursor.saturating_add(limit)
+}
+
+/// Extend the TTL of a bounty's slot in a secondary index and of the page
+/// and length entries it is listed under
+fn bump_index_entry(env: &Env, index: &BountyFilter, bounty_id: u64) {
+    let slot_key = BountyStorageKey::IndexSlot(index.clone(), bounty_id);
+    if let Some(slot) = ttl::get_persistent::<_, u32>(env, &slot_key) {
+        ttl::extend_persistent_if_present(env, &BountyStorageKey::IndexLen(index.clone()));
+        ttl::extend_persistent_if_present(
+            env,
+            &BountyStorageKey::IndexPage(index.clone(), slot / paging::PAGE_SIZE),
+        );
+    }
+}
+
+/// Record the escrow totals of bounties funded before they were tracked
+///
+/// Older releases left a bounty `Completed` whether or not its escrow had
+/// been released, so the escrow of a `Completed` bounty is neither counted
+/// nor marked `Locked`; until the admin records its outcome with
+/// `reconcile_bounty_escrow`, it cannot be paid out or refunded.
+pub fn migrate_escrow_balances(env: &Env) {
+    let bounties: Map<u64, Bounty> =
+        ttl::get_persistent(env, &BOUNTIES_KEY).unwrap_or_else(|| Map::new(env));
+
+    let mut totals: Map<Address, i128> = Map::new(env);
+    for (id, bounty) in bounties.iter() {
+        if bounty.funded_amount <= 0
+            || matches!(bounty.status, BountyStatus::Completed | BountyStatus::Paid)
+        {
+            continue;
+        }
+        let total = totals.get(bounty.token.clone()).unwrap_or(0);
+        totals.set(bounty.token.clone(), total + bounty.funded_amount);
+        if get_escrow_state(env, id).is_none() {
+            store_escrow_state(env, id, &EscrowState::Locked);
+        }
+    }
+    for (token, total) in totals.iter() {
+        set_escrow_balance(env, &token, total);
+    }
+}
+
+/// Move the bounty counter written by older releases into instance storage
+pub fn migrate_legacy_counters(env: &Env) {
+    ttl::promote_counter(env, &BOUNTY_CNT_KEY, &BOUNTY_CNT_KEY);
+}
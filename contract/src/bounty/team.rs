@@ -0,0 +1,211 @@
+//! Team bounties
+//!
+//! The claimer of a bounty can bring in collaborators by proposing a team
+//! with a percentage split of the payout. Every member has to accept the
+//! split before work can be submitted, and `release_escrow` then pays each
+//! member their share. Reopening the bounty disbands the team.
+
+use crate::bounty::storage::{get_bounty, get_team, store_team};
+use crate::bounty::types::{
+    Bounty, BountyError, BountyStatus, BountyTeam, TeamProposedEvent, TeamSplitAcceptedEvent,
+};
+use crate::guild::storage::is_banned;
+use crate::payment::distribution::{percentage_amount, validate_percentage_shares};
+use crate::payment::types::Recipient;
+use soroban_sdk::{Address, Env, Symbol, Vec};
+
+/// Maximum members in a bounty team, claimer included
+pub const MAX_TEAM_SIZE: u32 = 10;
+
+/// Propose the team working a claimed bounty and how its payout is split
+///
+/// Replaces any earlier proposal; every member has to accept again. The
+/// claimer accepts by proposing.
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `bounty_id` - The ID of the claimed bounty
+/// * `claimer` - The current claimer
+/// * `members` - Members and their percentage shares, claimer included
+///
+/// # Errors
+/// - `BountyError::BountyNotFound` if bounty is not found
+/// - `BountyError::NotClaimed` if bounty is not in Claimed status
+/// - `BountyError::NotClaimer` if the caller is not the current claimer
+/// - `BountyError::Unauthorized` if a member is banned from the guild
+/// - `BountyError::InvalidTeamSplit` if the shares do not sum to 100, an
+///   address repeats, the claimer is missing, the creator is listed, or the
+///   team is too large
+pub fn propose_team(
+    env: &Env,
+    bounty_id: u64,
+    claimer: Address,
+    members: Vec<Recipient>,
+) -> Result<bool, BountyError> {
+    claimer.require_auth();
+
+    let bounty = get_bounty(env, bounty_id).ok_or(BountyError::BountyNotFound)?;
+
+    if bounty.status != BountyStatus::Claimed {
+        return Err(BountyError::NotClaimed);
+    }
+    if bounty.claimer.as_ref() != Some(&claimer) {
+        return Err(BountyError::NotClaimer);
+    }
+    // Addresses banned from the guild cannot share in its bounties
+    if members.iter().any(|m| is_banned(env, bounty.guild_id, &m.address)) {
+        return Err(BountyError::Unauthorized);
+    }
+
+    validate_percentage_shares(&members).map_err(|_| BountyError::InvalidTeamSplit)?;
+    if members.len() > MAX_TEAM_SIZE
+        || !members.iter().any(|m| m.address == claimer)
+        || members.iter().any(|m| m.address == bounty.creator)
+    {
+        return Err(BountyError::InvalidTeamSplit);
+    }
+
+    let mut accepted = Vec::new(env);
+    accepted.push_back(claimer.clone());
+    store_team(
+        env,
+        bounty_id,
+        &BountyTeam {
+            members: members.clone(),
+            accepted,
+            proposed_at: env.ledger().timestamp(),
+        },
+    );
+
+    env.events().publish(
+        (
+            Symbol::new(env, "bounty"),
+            Symbol::new(env, "team_proposed"),
+        ),
+        TeamProposedEvent {
+            bounty_id,
+            claimer,
+            members,
+        },
+    );
+
+    Ok(true)
+}
+
+/// Accept the proposed split of a bounty's payout
+///
+/// # Returns
+/// `true` once every member has accepted
+///
+/// # Errors
+/// - `BountyError::BountyNotFound` if bounty is not found
+/// - `BountyError::NotClaimed` if bounty is not in Claimed status
+/// - `BountyError::Unauthorized` if the member is banned from the guild
+/// - `BountyError::NotTeamMember` if no team is proposed or the member is not
+///   part of it
+/// - `BountyError::AlreadyAccepted` if the member has already accepted
+pub fn accept_team_split(env: &Env, bounty_id: u64, member: Address) -> Result<bool, BountyError> {
+    member.require_auth();
+
+    let bounty = get_bounty(env, bounty_id).ok_or(BountyError::BountyNotFound)?;
+    if bounty.status != BountyStatus::Claimed {
+        return Err(BountyError::NotClaimed);
+    }
+    // A member banned after the proposal can no longer join the team
+    if is_banned(env, bounty.guild_id, &member) {
+        return Err(BountyError::Unauthorized);
+    }
+
+    let mut team = get_team(env, bounty_id).ok_or(BountyError::NotTeamMember)?;
+    if !team.members.iter().any(|m| m.address == member) {
+        return Err(BountyError::NotTeamMember);
+    }
+    if team.accepted.contains(&member) {
+        return Err(BountyError::AlreadyAccepted);
+    }
+
+    team.accepted.push_back(member.clone());
+    store_team(env, bounty_id, &team);
+
+    let confirmed = team.is_confirmed();
+    env.events().publish(
+        (
+            Symbol::new(env, "bounty"),
+            Symbol::new(env, "team_accepted"),
+        ),
+        TeamSplitAcceptedEvent {
+            bounty_id,
+            member,
+            confirmed,
+        },
+    );
+
+    Ok(confirmed)
+}
+
+/// Get the team working a bounty, if one was proposed
+///
+/// # Errors
+/// `BountyError::BountyNotFound` if bounty is not found
+pub fn get_bounty_team(env: &Env, bounty_id: u64) -> Result<Option<BountyTeam>, BountyError> {
+    get_bounty(env, bounty_id).ok_or(BountyError::BountyNotFound)?;
+    Ok(get_team(env, bounty_id))
+}
+
+/// Check that every member of a bounty's team, if any, accepted the split
+pub(crate) fn ensure_team_confirmed(env: &Env, bounty_id: u64) -> Result<(), BountyError> {
+    match get_team(env, bounty_id) {
+        Some(team) if !team.is_confirmed() => Err(BountyError::TeamNotConfirmed),
+        _ => Ok(()),
+    }
+}
+
+/// Everyone credited with a bounty's work: the confirmed team, or the claimer
+pub(crate) fn bounty_workers(env: &Env, bounty: &Bounty) -> Vec<Address> {
+    let mut workers = Vec::new(env);
+    match get_team(env, bounty.id).filter(|t| t.is_confirmed()) {
+        Some(team) => {
+            for member in team.members.iter() {
+                workers.push_back(member.address);
+            }
+        }
+        None => {
+            if let Some(claimer) = bounty.claimer.clone() {
+                workers.push_back(claimer);
+            }
+        }
+    }
+    workers
+}
+
+/// Split a payout of a bounty among its confirmed team
+///
+/// Without a team the claimer receives everything. Rounding dust goes to the
+/// last member so the full amount is always paid out.
+pub(crate) fn worker_payouts(
+    env: &Env,
+    bounty: &Bounty,
+    amount: i128,
+) -> Result<Vec<(Address, i128)>, BountyError> {
+    let mut payouts = Vec::new(env);
+
+    let Some(team) = get_team(env, bounty.id).filter(|t| t.is_confirmed()) else {
+        let claimer = bounty.claimer.clone().ok_or(BountyError::NoClaimer)?;
+        payouts.push_back((claimer, amount));
+        return Ok(payouts);
+    };
+
+    let mut remaining = amount;
+    let last = team.members.len() - 1;
+    for (i, member) in team.members.iter().enumerate() {
+        let share = if i as u32 == last {
+            remaining
+        } else {
+            percentage_amount(amount, member.share).map_err(|_| BountyError::InvalidTeamSplit)?
+        };
+        remaining -= share;
+        payouts.push_back((member.address, share));
+    }
+
+    Ok(payouts)
+}
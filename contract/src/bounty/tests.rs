@@ -6,12 +6,20 @@
 //! NOTE: These tests use the contract client to test through the main lib.rs
 //! contract interface, ensuring proper contract context execution.
 
-use crate::bounty::types::BountyStatus;
+use crate::bounty::claims::DEFAULT_WORK_WINDOW_SECONDS;
+use crate::bounty::review::MAX_REVISION_ROUNDS;
+use crate::bounty::storage::store_bounty;
+use crate::bounty::types::{
+    AssignmentMode, BountyFilter, BountyStatus, ContestTerms, Difficulty, EscrowState,
+    SubmissionOutcome,
+};
 use crate::guild::types::Role;
+use crate::payment::types::Recipient;
+use crate::ttl;
 use crate::StellarGuildsContract;
 use crate::StellarGuildsContractClient;
 use soroban_sdk::testutils::{Address as _, Ledger, LedgerInfo};
-use soroban_sdk::{token, Address, Env, String};
+use soroban_sdk::{symbol_short, token, vec, Address, Env, String};
 
 // ============ Test Helpers ============
 
@@ -37,7 +45,7 @@ fn set_ledger_timestamp(env: &Env, timestamp: u64) {
 fn register_and_init_contract(env: &Env) -> Address {
     let contract_id = env.register_contract(None, StellarGuildsContract);
     let client = StellarGuildsContractClient::new(env, &contract_id);
-    client.initialize();
+    client.mock_all_auths().initialize(&Address::generate(env));
     contract_id
 }
 
@@ -91,6 +99,7 @@ fn test_create_bounty_success() {
         &reward_amount,
         &token,
         &expiry,
+        &AssignmentMode::FirstCome,
     );
 
     assert_eq!(bounty_id, 1);
@@ -131,6 +140,7 @@ fn test_create_bounty_zero_reward_is_open() {
         &0i128,
         &token,
         &2000u64,
+        &AssignmentMode::FirstCome,
     );
 
     let bounty = client.get_bounty(&bounty_id);
@@ -138,7 +148,7 @@ fn test_create_bounty_zero_reward_is_open() {
 }
 
 #[test]
-#[should_panic(expected = "Unauthorized")]
+#[should_panic(expected = "Error(Contract, #201)")] // Unauthorized
 fn test_create_bounty_non_admin_fails() {
     let env = setup_env();
     let owner = Address::generate(&env);
@@ -165,11 +175,12 @@ fn test_create_bounty_non_admin_fails() {
         &100i128,
         &token,
         &2000u64,
+        &AssignmentMode::FirstCome,
     );
 }
 
 #[test]
-#[should_panic(expected = "Invalid reward amount")]
+#[should_panic(expected = "Error(Contract, #202)")] // InvalidRewardAmount
 fn test_create_bounty_negative_reward_fails() {
     let env = setup_env();
     let owner = Address::generate(&env);
@@ -194,11 +205,12 @@ fn test_create_bounty_negative_reward_fails() {
         &-100i128,
         &token,
         &2000u64,
+        &AssignmentMode::FirstCome,
     );
 }
 
 #[test]
-#[should_panic(expected = "Expiry must be in the future")]
+#[should_panic(expected = "Error(Contract, #203)")] // InvalidExpiry
 fn test_create_bounty_past_expiry_fails() {
     let env = setup_env();
     let owner = Address::generate(&env);
@@ -223,11 +235,12 @@ fn test_create_bounty_past_expiry_fails() {
         &100i128,
         &token,
         &1000u64, // Past expiry
+        &AssignmentMode::FirstCome,
     );
 }
 
 #[test]
-#[should_panic(expected = "Title must be between 1 and 256 characters")]
+#[should_panic(expected = "Error(Contract, #204)")] // InvalidTitle
 fn test_create_bounty_empty_title_fails() {
     let env = setup_env();
     let owner = Address::generate(&env);
@@ -252,6 +265,7 @@ fn test_create_bounty_empty_title_fails() {
         &100i128,
         &token,
         &2000u64,
+        &AssignmentMode::FirstCome,
     );
 }
 
@@ -285,11 +299,12 @@ fn test_fund_bounty_success() {
         &100i128,
         &token,
         &2000u64,
+        &AssignmentMode::FirstCome,
     );
 
     // Fund the bounty
     let result = client.fund_bounty(&bounty_id, &funder, &100i128);
-    assert_eq!(result, true);
+    assert!(result);
 
     let bounty = client.get_bounty(&bounty_id);
     assert_eq!(bounty.funded_amount, 100);
@@ -323,6 +338,7 @@ fn test_fund_bounty_partial_funding() {
         &100i128,
         &token,
         &2000u64,
+        &AssignmentMode::FirstCome,
     );
 
     // Partial fund
@@ -339,7 +355,7 @@ fn test_fund_bounty_partial_funding() {
 }
 
 #[test]
-#[should_panic(expected = "Amount must be positive")]
+#[should_panic(expected = "Error(Contract, #206)")] // InvalidAmount
 fn test_fund_bounty_zero_amount_fails() {
     let env = setup_env();
     let owner = Address::generate(&env);
@@ -364,11 +380,128 @@ fn test_fund_bounty_zero_amount_fails() {
         &100i128,
         &token,
         &2000u64,
+        &AssignmentMode::FirstCome,
     );
 
     client.fund_bounty(&bounty_id, &funder, &0i128);
 }
 
+#[test]
+fn test_fund_bounty_excess_stays_with_funder() {
+    let env = setup_env();
+    let owner = Address::generate(&env);
+    let funder = Address::generate(&env);
+    let token = create_mock_token(&env, &owner);
+
+    set_ledger_timestamp(&env, 1000);
+    env.mock_all_auths();
+
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+    let guild_id = setup_guild(&client, &env, &owner);
+
+    mint_tokens(&env, &token, &funder, 1000);
+
+    let bounty_id = client.create_bounty(
+        &guild_id,
+        &owner,
+        &String::from_str(&env, "Task"),
+        &String::from_str(&env, "Description"),
+        &100i128,
+        &token,
+        &2000u64,
+        &AssignmentMode::FirstCome,
+    );
+
+    client.fund_bounty(&bounty_id, &funder, &60i128);
+    client.fund_bounty(&bounty_id, &funder, &500i128);
+
+    let bounty = client.get_bounty(&bounty_id);
+    assert_eq!(bounty.funded_amount, 100);
+    assert_eq!(bounty.status, BountyStatus::Open);
+    assert_eq!(get_token_balance(&env, &token, &funder), 900);
+
+    let funders = client.get_bounty_funders(&bounty_id);
+    assert_eq!(funders.len(), 1);
+    assert_eq!(funders.get(0).unwrap().amount, 100);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #224)")] // AlreadyFullyFunded
+fn test_fund_fully_funded_bounty_fails() {
+    let env = setup_env();
+    let owner = Address::generate(&env);
+    let funder = Address::generate(&env);
+    let token = create_mock_token(&env, &owner);
+
+    set_ledger_timestamp(&env, 1000);
+    env.mock_all_auths();
+
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+    let guild_id = setup_guild(&client, &env, &owner);
+
+    mint_tokens(&env, &token, &funder, 1000);
+
+    let bounty_id = client.create_bounty(
+        &guild_id,
+        &owner,
+        &String::from_str(&env, "Task"),
+        &String::from_str(&env, "Description"),
+        &100i128,
+        &token,
+        &2000u64,
+        &AssignmentMode::FirstCome,
+    );
+
+    client.fund_bounty(&bounty_id, &funder, &100i128);
+    client.fund_bounty(&bounty_id, &funder, &1i128);
+}
+
+#[test]
+fn test_cancel_crowdfunded_bounty_refunds_each_funder() {
+    let env = setup_env();
+    let owner = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let token = create_mock_token(&env, &owner);
+
+    set_ledger_timestamp(&env, 1000);
+    env.mock_all_auths();
+
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+    let guild_id = setup_guild(&client, &env, &owner);
+
+    mint_tokens(&env, &token, &alice, 1000);
+    mint_tokens(&env, &token, &bob, 1000);
+
+    let bounty_id = client.create_bounty(
+        &guild_id,
+        &owner,
+        &String::from_str(&env, "Task"),
+        &String::from_str(&env, "Description"),
+        &100i128,
+        &token,
+        &2000u64,
+        &AssignmentMode::FirstCome,
+    );
+
+    client.fund_bounty(&bounty_id, &alice, &70i128);
+    client.fund_bounty(&bounty_id, &bob, &30i128);
+    assert_eq!(client.get_bounty_funders(&bounty_id).len(), 2);
+
+    client.cancel_bounty(&bounty_id, &owner);
+
+    assert_eq!(get_token_balance(&env, &token, &alice), 1000);
+    assert_eq!(get_token_balance(&env, &token, &bob), 1000);
+    assert_eq!(get_token_balance(&env, &token, &owner), 0);
+    assert_eq!(get_token_balance(&env, &token, &contract_id), 0);
+}
+
 // ============ Bounty Claiming Tests ============
 
 #[test]
@@ -399,13 +532,14 @@ fn test_claim_bounty_success() {
         &100i128,
         &token,
         &2000u64,
+        &AssignmentMode::FirstCome,
     );
 
     client.fund_bounty(&bounty_id, &funder, &100i128);
 
     // Claim the bounty
     let result = client.claim_bounty(&bounty_id, &claimer);
-    assert_eq!(result, true);
+    assert!(result);
 
     let bounty = client.get_bounty(&bounty_id);
     assert_eq!(bounty.status, BountyStatus::Claimed);
@@ -413,7 +547,7 @@ fn test_claim_bounty_success() {
 }
 
 #[test]
-#[should_panic(expected = "Bounty is not open for claiming")]
+#[should_panic(expected = "Error(Contract, #209)")] // NotOpen
 fn test_claim_bounty_not_open_fails() {
     let env = setup_env();
     let owner = Address::generate(&env);
@@ -438,6 +572,7 @@ fn test_claim_bounty_not_open_fails() {
         &100i128,
         &token,
         &2000u64,
+        &AssignmentMode::FirstCome,
     );
 
     // Try to claim without funding
@@ -445,7 +580,7 @@ fn test_claim_bounty_not_open_fails() {
 }
 
 #[test]
-#[should_panic(expected = "Bounty is not open for claiming")]
+#[should_panic(expected = "Error(Contract, #209)")] // NotOpen
 fn test_claim_bounty_already_claimed_fails() {
     let env = setup_env();
     let owner = Address::generate(&env);
@@ -474,6 +609,7 @@ fn test_claim_bounty_already_claimed_fails() {
         &100i128,
         &token,
         &2000u64,
+        &AssignmentMode::FirstCome,
     );
 
     client.fund_bounty(&bounty_id, &funder, &100i128);
@@ -513,6 +649,7 @@ fn test_submit_work_success() {
         &100i128,
         &token,
         &2000u64,
+        &AssignmentMode::FirstCome,
     );
 
     client.fund_bounty(&bounty_id, &funder, &100i128);
@@ -520,7 +657,7 @@ fn test_submit_work_success() {
 
     let submission = String::from_str(&env, "https://github.com/pr/123");
     let result = client.submit_work(&bounty_id, &submission);
-    assert_eq!(result, true);
+    assert!(result);
 
     let bounty = client.get_bounty(&bounty_id);
     assert_eq!(bounty.status, BountyStatus::UnderReview);
@@ -528,7 +665,7 @@ fn test_submit_work_success() {
 }
 
 #[test]
-#[should_panic(expected = "No claimer for this bounty")]
+#[should_panic(expected = "Error(Contract, #210)")] // NoClaimer
 fn test_submit_work_no_claimer_fails() {
     let env = setup_env();
     let owner = Address::generate(&env);
@@ -555,6 +692,7 @@ fn test_submit_work_no_claimer_fails() {
         &100i128,
         &token,
         &2000u64,
+        &AssignmentMode::FirstCome,
     );
 
     client.fund_bounty(&bounty_id, &funder, &100i128);
@@ -594,6 +732,7 @@ fn test_approve_completion_success() {
         &100i128,
         &token,
         &2000u64,
+        &AssignmentMode::FirstCome,
     );
 
     client.fund_bounty(&bounty_id, &funder, &100i128);
@@ -603,14 +742,14 @@ fn test_approve_completion_success() {
     client.submit_work(&bounty_id, &submission);
 
     let result = client.approve_completion(&bounty_id, &owner);
-    assert_eq!(result, true);
+    assert!(result);
 
     let bounty = client.get_bounty(&bounty_id);
     assert_eq!(bounty.status, BountyStatus::Completed);
 }
 
 #[test]
-#[should_panic(expected = "Unauthorized")]
+#[should_panic(expected = "Error(Contract, #201)")] // Unauthorized
 fn test_approve_completion_non_admin_fails() {
     let env = setup_env();
     let owner = Address::generate(&env);
@@ -639,6 +778,7 @@ fn test_approve_completion_non_admin_fails() {
         &100i128,
         &token,
         &2000u64,
+        &AssignmentMode::FirstCome,
     );
 
     client.fund_bounty(&bounty_id, &funder, &100i128);
@@ -652,7 +792,7 @@ fn test_approve_completion_non_admin_fails() {
 }
 
 #[test]
-#[should_panic(expected = "Bounty is not under review")]
+#[should_panic(expected = "Error(Contract, #213)")] // NotUnderReview
 fn test_approve_completion_wrong_status_fails() {
     let env = setup_env();
     let owner = Address::generate(&env);
@@ -680,6 +820,7 @@ fn test_approve_completion_wrong_status_fails() {
         &100i128,
         &token,
         &2000u64,
+        &AssignmentMode::FirstCome,
     );
 
     client.fund_bounty(&bounty_id, &funder, &100i128);
@@ -719,6 +860,7 @@ fn test_release_escrow_success() {
         &100i128,
         &token,
         &2000u64,
+        &AssignmentMode::FirstCome,
     );
 
     client.fund_bounty(&bounty_id, &funder, &100i128);
@@ -730,7 +872,7 @@ fn test_release_escrow_success() {
 
     // Release escrow
     let result = client.release_escrow(&bounty_id);
-    assert_eq!(result, true);
+    assert!(result);
 
     // Claimer should have received the funds
     let claimer_balance = get_token_balance(&env, &token, &claimer);
@@ -738,7 +880,7 @@ fn test_release_escrow_success() {
 }
 
 #[test]
-#[should_panic(expected = "Bounty is not completed")]
+#[should_panic(expected = "Error(Contract, #214)")] // NotCompleted
 fn test_release_escrow_not_completed_fails() {
     let env = setup_env();
     let owner = Address::generate(&env);
@@ -766,6 +908,7 @@ fn test_release_escrow_not_completed_fails() {
         &100i128,
         &token,
         &2000u64,
+        &AssignmentMode::FirstCome,
     );
 
     client.fund_bounty(&bounty_id, &funder, &100i128);
@@ -804,24 +947,25 @@ fn test_cancel_bounty_by_creator() {
         &100i128,
         &token,
         &2000u64,
+        &AssignmentMode::FirstCome,
     );
 
     client.fund_bounty(&bounty_id, &funder, &100i128);
 
     let result = client.cancel_bounty(&bounty_id, &owner);
-    assert_eq!(result, true);
+    assert!(result);
 
     let bounty = client.get_bounty(&bounty_id);
     assert_eq!(bounty.status, BountyStatus::Cancelled);
     assert_eq!(bounty.funded_amount, 0);
 
-    // Creator should have received the refund
-    let creator_balance = get_token_balance(&env, &token, &owner);
-    assert_eq!(creator_balance, 100);
+    // Funder should have received the refund
+    assert_eq!(get_token_balance(&env, &token, &funder), 1000);
+    assert_eq!(get_token_balance(&env, &token, &owner), 0);
 }
 
 #[test]
-fn test_cancel_bounty_after_claim_refunds_creator() {
+fn test_cancel_bounty_after_claim_refunds_funder() {
     let env = setup_env();
     let owner = Address::generate(&env);
     let funder = Address::generate(&env);
@@ -848,6 +992,7 @@ fn test_cancel_bounty_after_claim_refunds_creator() {
         &100i128,
         &token,
         &2000u64,
+        &AssignmentMode::FirstCome,
     );
 
     client.fund_bounty(&bounty_id, &funder, &100i128);
@@ -855,17 +1000,17 @@ fn test_cancel_bounty_after_claim_refunds_creator() {
 
     // Owner cancels even after claim
     let result = client.cancel_bounty(&bounty_id, &owner);
-    assert_eq!(result, true);
+    assert!(result);
 
-    // Funds go to creator, not claimer
-    let creator_balance = get_token_balance(&env, &token, &owner);
+    // Funds go back to the funder, not the claimer
+    let funder_balance = get_token_balance(&env, &token, &funder);
     let claimer_balance = get_token_balance(&env, &token, &claimer);
-    assert_eq!(creator_balance, 100);
+    assert_eq!(funder_balance, 1000);
     assert_eq!(claimer_balance, 0);
 }
 
 #[test]
-#[should_panic(expected = "Unauthorized")]
+#[should_panic(expected = "Error(Contract, #201)")] // Unauthorized
 fn test_cancel_bounty_non_creator_non_admin_fails() {
     let env = setup_env();
     let owner = Address::generate(&env);
@@ -890,6 +1035,7 @@ fn test_cancel_bounty_non_creator_non_admin_fails() {
         &100i128,
         &token,
         &2000u64,
+        &AssignmentMode::FirstCome,
     );
 
     // Random user tries to cancel
@@ -897,7 +1043,7 @@ fn test_cancel_bounty_non_creator_non_admin_fails() {
 }
 
 #[test]
-#[should_panic(expected = "Bounty cannot be cancelled")]
+#[should_panic(expected = "Error(Contract, #215)")] // NotCancellable
 fn test_cancel_bounty_completed_fails() {
     let env = setup_env();
     let owner = Address::generate(&env);
@@ -925,6 +1071,7 @@ fn test_cancel_bounty_completed_fails() {
         &100i128,
         &token,
         &2000u64,
+        &AssignmentMode::FirstCome,
     );
 
     client.fund_bounty(&bounty_id, &funder, &100i128);
@@ -967,6 +1114,7 @@ fn test_expire_bounty_success() {
         &100i128,
         &token,
         &1500u64,
+        &AssignmentMode::FirstCome,
     );
 
     client.fund_bounty(&bounty_id, &funder, &100i128);
@@ -975,15 +1123,15 @@ fn test_expire_bounty_success() {
     set_ledger_timestamp(&env, 2000);
 
     let result = client.expire_bounty(&bounty_id);
-    assert_eq!(result, true);
+    assert!(result);
 
     let bounty = client.get_bounty(&bounty_id);
     assert_eq!(bounty.status, BountyStatus::Expired);
     assert_eq!(bounty.funded_amount, 0);
 
-    // Creator should have received the refund
-    let creator_balance = get_token_balance(&env, &token, &owner);
-    assert_eq!(creator_balance, 100);
+    // Funder should have received the refund
+    assert_eq!(get_token_balance(&env, &token, &funder), 1000);
+    assert_eq!(get_token_balance(&env, &token, &owner), 0);
 }
 
 #[test]
@@ -1013,13 +1161,14 @@ fn test_expire_bounty_not_expired_yet() {
         &100i128,
         &token,
         &2000u64,
+        &AssignmentMode::FirstCome,
     );
 
     client.fund_bounty(&bounty_id, &funder, &100i128);
 
     // Still before expiry
     let result = client.expire_bounty(&bounty_id);
-    assert_eq!(result, false);
+    assert!(!result);
 
     let bounty = client.get_bounty(&bounty_id);
     assert_eq!(bounty.status, BountyStatus::Open);
@@ -1055,6 +1204,7 @@ fn test_get_guild_bounties() {
         &100i128,
         &token,
         &2000u64,
+        &AssignmentMode::FirstCome,
     );
     client.create_bounty(
         &guild_id,
@@ -1064,6 +1214,7 @@ fn test_get_guild_bounties() {
         &100i128,
         &token,
         &2000u64,
+        &AssignmentMode::FirstCome,
     );
     client.create_bounty(
         &guild_id,
@@ -1073,6 +1224,7 @@ fn test_get_guild_bounties() {
         &100i128,
         &token,
         &2000u64,
+        &AssignmentMode::FirstCome,
     );
 
     let bounties = client.get_guild_bounties(&guild_id);
@@ -1111,6 +1263,7 @@ fn test_full_bounty_lifecycle() {
         &100i128,
         &token,
         &5000u64,
+        &AssignmentMode::FirstCome,
     );
 
     let bounty = client.get_bounty(&bounty_id);
@@ -1173,26 +1326,31 @@ fn test_multiple_bounties_per_guild() {
     let title1 = String::from_str(&env, "Task 1");
     let bounty_id_1 = client.create_bounty(
         &guild_id, &owner, &title1, &desc, &100i128, &token, &2000u64,
+        &AssignmentMode::FirstCome,
     );
 
     let title2 = String::from_str(&env, "Task 2");
     let bounty_id_2 = client.create_bounty(
         &guild_id, &owner, &title2, &desc, &200i128, &token, &2000u64,
+        &AssignmentMode::FirstCome,
     );
 
     let title3 = String::from_str(&env, "Task 3");
     let bounty_id_3 = client.create_bounty(
         &guild_id, &owner, &title3, &desc, &300i128, &token, &2000u64,
+        &AssignmentMode::FirstCome,
     );
 
     let title4 = String::from_str(&env, "Task 4");
     client.create_bounty(
         &guild_id, &owner, &title4, &desc, &400i128, &token, &2000u64,
+        &AssignmentMode::FirstCome,
     );
 
     let title5 = String::from_str(&env, "Task 5");
     client.create_bounty(
         &guild_id, &owner, &title5, &desc, &500i128, &token, &2000u64,
+        &AssignmentMode::FirstCome,
     );
 
     // Fund some bounties
@@ -1247,6 +1405,7 @@ fn test_admin_can_approve_bounty() {
         &100i128,
         &token,
         &2000u64,
+        &AssignmentMode::FirstCome,
     );
 
     client.fund_bounty(&bounty_id, &funder, &100i128);
@@ -1257,7 +1416,7 @@ fn test_admin_can_approve_bounty() {
 
     // Admin approves
     let result = client.approve_completion(&bounty_id, &admin);
-    assert_eq!(result, true);
+    assert!(result);
 
     let bounty = client.get_bounty(&bounty_id);
     assert_eq!(bounty.status, BountyStatus::Completed);
@@ -1294,14 +1453,1547 @@ fn test_admin_can_cancel_bounty() {
         &100i128,
         &token,
         &2000u64,
+        &AssignmentMode::FirstCome,
     );
 
     client.fund_bounty(&bounty_id, &funder, &100i128);
 
     // Admin cancels
     let result = client.cancel_bounty(&bounty_id, &admin);
-    assert_eq!(result, true);
+    assert!(result);
 
     let bounty = client.get_bounty(&bounty_id);
     assert_eq!(bounty.status, BountyStatus::Cancelled);
 }
+
+// ============ Guild Rules Tests ============
+
+#[test]
+fn test_create_bounty_uses_default_expiry_rule() {
+    let env = setup_env();
+    let owner = Address::generate(&env);
+    let token = create_mock_token(&env, &owner);
+
+    set_ledger_timestamp(&env, 1000);
+    env.mock_all_auths();
+
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+    let guild_id = setup_guild(&client, &env, &owner);
+
+    let mut rules = client.get_guild_rules(&guild_id);
+    rules.default_bounty_expiry = 5000;
+    client.set_guild_rules(&guild_id, &owner, &rules);
+
+    let bounty_id = client.create_bounty(
+        &guild_id,
+        &owner,
+        &String::from_str(&env, "Task"),
+        &String::from_str(&env, "Description"),
+        &100i128,
+        &token,
+        &0u64,
+        &AssignmentMode::FirstCome,
+    );
+
+    assert_eq!(client.get_bounty(&bounty_id).expires_at, 6000);
+}
+
+#[test]
+fn test_bounty_creator_role_rule_allows_members() {
+    let env = setup_env();
+    let owner = Address::generate(&env);
+    let member = Address::generate(&env);
+    let token = create_mock_token(&env, &owner);
+
+    set_ledger_timestamp(&env, 1000);
+    env.mock_all_auths();
+
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+    let guild_id = setup_guild(&client, &env, &owner);
+    client.add_member(&guild_id, &member, &Role::Member, &owner);
+
+    let mut rules = client.get_guild_rules(&guild_id);
+    rules.bounty_creator_role = Role::Member;
+    client.set_guild_rules(&guild_id, &owner, &rules);
+
+    let bounty_id = client.create_bounty(
+        &guild_id,
+        &member,
+        &String::from_str(&env, "Task"),
+        &String::from_str(&env, "Description"),
+        &100i128,
+        &token,
+        &2000u64,
+        &AssignmentMode::FirstCome,
+    );
+
+    assert_eq!(client.get_bounty(&bounty_id).creator, member);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #202)")] // InvalidRewardAmount
+fn test_create_bounty_below_min_reward_rule_fails() {
+    let env = setup_env();
+    let owner = Address::generate(&env);
+    let token = create_mock_token(&env, &owner);
+
+    set_ledger_timestamp(&env, 1000);
+    env.mock_all_auths();
+
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+    let guild_id = setup_guild(&client, &env, &owner);
+
+    let mut rules = client.get_guild_rules(&guild_id);
+    rules.min_bounty_reward = 50;
+    client.set_guild_rules(&guild_id, &owner, &rules);
+
+    client.create_bounty(
+        &guild_id,
+        &owner,
+        &String::from_str(&env, "Task"),
+        &String::from_str(&env, "Description"),
+        &10i128,
+        &token,
+        &2000u64,
+        &AssignmentMode::FirstCome,
+    );
+}
+
+// ============ Dispute Tests ============
+
+/// Create a funded bounty and drive it to `UnderReview`
+fn setup_under_review(
+    client: &StellarGuildsContractClient<'_>,
+    env: &Env,
+    guild_id: u64,
+    owner: &Address,
+    token: &Address,
+    claimer: &Address,
+) -> u64 {
+    mint_tokens(env, token, owner, 100);
+
+    let bounty_id = client.create_bounty(
+        &guild_id,
+        owner,
+        &String::from_str(env, "Task"),
+        &String::from_str(env, "Description"),
+        &100i128,
+        token,
+        &2000u64,
+        &AssignmentMode::FirstCome,
+    );
+    client.fund_bounty(&bounty_id, owner, &100i128);
+    client.claim_bounty(&bounty_id, claimer);
+    client.submit_work(&bounty_id, &String::from_str(env, "https://github.com/pr/1"));
+
+    bounty_id
+}
+
+#[test]
+fn test_panel_majority_splits_disputed_escrow() {
+    let env = setup_env();
+    let owner = Address::generate(&env);
+    let claimer = Address::generate(&env);
+    let token = create_mock_token(&env, &owner);
+
+    set_ledger_timestamp(&env, 1000);
+    env.mock_all_auths();
+
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+    let guild_id = setup_guild(&client, &env, &owner);
+    let bounty_id = setup_under_review(&client, &env, guild_id, &owner, &token, &claimer);
+
+    let arbiters = soroban_sdk::vec![
+        &env,
+        Address::generate(&env),
+        Address::generate(&env),
+        Address::generate(&env),
+    ];
+    client.set_bounty_arbiters(&guild_id, &owner, &arbiters);
+
+    client.open_bounty_dispute(
+        &bounty_id,
+        &claimer,
+        &String::from_str(&env, "Work was delivered as specified"),
+    );
+    assert_eq!(client.get_bounty(&bounty_id).status, BountyStatus::Disputed);
+
+    // Split rulings do not settle the dispute
+    assert!(!client.rule_bounty_dispute(&bounty_id, &arbiters.get(0).unwrap(), &60u32));
+    assert!(!client.rule_bounty_dispute(&bounty_id, &arbiters.get(1).unwrap(), &100u32));
+    assert_eq!(get_token_balance(&env, &token, &contract_id), 100);
+
+    assert!(client.rule_bounty_dispute(&bounty_id, &arbiters.get(2).unwrap(), &60u32));
+
+    assert_eq!(get_token_balance(&env, &token, &claimer), 60);
+    assert_eq!(get_token_balance(&env, &token, &owner), 40);
+
+    let bounty = client.get_bounty(&bounty_id);
+    assert_eq!(bounty.status, BountyStatus::Paid);
+    assert_eq!(bounty.funded_amount, 0);
+
+    let dispute = client.get_bounty_dispute(&bounty_id);
+    assert_eq!(dispute.claimer_percent, Some(60));
+    assert_eq!(dispute.resolved_at, Some(1000));
+}
+
+#[test]
+fn test_dispute_ruled_for_creator_cancels_bounty() {
+    let env = setup_env();
+    let owner = Address::generate(&env);
+    let claimer = Address::generate(&env);
+    let arbiter = Address::generate(&env);
+    let token = create_mock_token(&env, &owner);
+
+    set_ledger_timestamp(&env, 1000);
+    env.mock_all_auths();
+
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+    let guild_id = setup_guild(&client, &env, &owner);
+    let bounty_id = setup_under_review(&client, &env, guild_id, &owner, &token, &claimer);
+
+    // The creator sits on the panel but cannot rule on their own dispute
+    let arbiters = soroban_sdk::vec![&env, owner.clone(), arbiter.clone()];
+    client.set_bounty_arbiters(&guild_id, &owner, &arbiters);
+    client.open_bounty_dispute(&bounty_id, &owner, &String::from_str(&env, "Incomplete"));
+
+    let dispute = client.get_bounty_dispute(&bounty_id);
+    assert_eq!(dispute.arbiters, soroban_sdk::vec![&env, arbiter.clone()]);
+
+    assert!(client.rule_bounty_dispute(&bounty_id, &arbiter, &0u32));
+
+    assert_eq!(get_token_balance(&env, &token, &owner), 100);
+    assert_eq!(client.get_bounty(&bounty_id).status, BountyStatus::Cancelled);
+}
+
+#[test]
+fn test_dispute_award_that_cannot_be_split_is_not_settled() {
+    let env = setup_env();
+    let owner = Address::generate(&env);
+    let claimer = Address::generate(&env);
+    let arbiter = Address::generate(&env);
+    let token = create_mock_token(&env, &owner);
+
+    set_ledger_timestamp(&env, 1000);
+    env.mock_all_auths();
+
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+    let guild_id = setup_guild(&client, &env, &owner);
+    let bounty_id = setup_under_review(&client, &env, guild_id, &owner, &token, &claimer);
+    client.set_bounty_arbiters(&guild_id, &owner, &soroban_sdk::vec![&env, arbiter.clone()]);
+    client.open_bounty_dispute(&bounty_id, &claimer, &String::from_str(&env, "Delivered"));
+
+    // A record without a claimer leaves nobody to pay the award to
+    let mut bounty = client.get_bounty(&bounty_id);
+    bounty.claimer = None;
+    env.as_contract(&contract_id, || store_bounty(&env, &bounty));
+
+    assert!(client.try_rule_bounty_dispute(&bounty_id, &arbiter, &60u32).is_err());
+
+    // Nothing was paid out or refunded, and the escrow is still locked
+    let bounty = client.get_bounty(&bounty_id);
+    assert_eq!(bounty.status, BountyStatus::Disputed);
+    assert_eq!(bounty.funded_amount, 100);
+    assert_eq!(client.get_bounty_escrow_state(&bounty_id), Some(EscrowState::Locked));
+    assert_eq!(get_token_balance(&env, &token, &contract_id), 100);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #215)")] // NotCancellable
+fn test_cancel_disputed_bounty_fails() {
+    let env = setup_env();
+    let owner = Address::generate(&env);
+    let claimer = Address::generate(&env);
+    let token = create_mock_token(&env, &owner);
+
+    set_ledger_timestamp(&env, 1000);
+    env.mock_all_auths();
+
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+    let guild_id = setup_guild(&client, &env, &owner);
+    let bounty_id = setup_under_review(&client, &env, guild_id, &owner, &token, &claimer);
+
+    client.open_bounty_dispute(&bounty_id, &claimer, &String::from_str(&env, "Ignored"));
+    client.cancel_bounty(&bounty_id, &owner);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #216)")] // NotDisputable
+fn test_open_dispute_before_submission_fails() {
+    let env = setup_env();
+    let owner = Address::generate(&env);
+    let claimer = Address::generate(&env);
+    let token = create_mock_token(&env, &owner);
+
+    set_ledger_timestamp(&env, 1000);
+    env.mock_all_auths();
+
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+    let guild_id = setup_guild(&client, &env, &owner);
+    let bounty_id = client.create_bounty(
+        &guild_id,
+        &owner,
+        &String::from_str(&env, "Task"),
+        &String::from_str(&env, "Description"),
+        &0i128,
+        &token,
+        &2000u64,
+        &AssignmentMode::FirstCome,
+    );
+    client.claim_bounty(&bounty_id, &claimer);
+
+    client.open_bounty_dispute(&bounty_id, &claimer, &String::from_str(&env, "Early"));
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #219)")] // NotArbiter
+fn test_rule_dispute_non_arbiter_fails() {
+    let env = setup_env();
+    let owner = Address::generate(&env);
+    let claimer = Address::generate(&env);
+    let token = create_mock_token(&env, &owner);
+
+    set_ledger_timestamp(&env, 1000);
+    env.mock_all_auths();
+
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+    let guild_id = setup_guild(&client, &env, &owner);
+    let bounty_id = setup_under_review(&client, &env, guild_id, &owner, &token, &claimer);
+
+    let arbiters = soroban_sdk::vec![&env, Address::generate(&env)];
+    client.set_bounty_arbiters(&guild_id, &owner, &arbiters);
+    client.open_bounty_dispute(&bounty_id, &claimer, &String::from_str(&env, "Ignored"));
+
+    client.rule_bounty_dispute(&bounty_id, &Address::generate(&env), &100u32);
+}
+
+#[test]
+fn test_dispute_refunds_funders_pro_rata() {
+    let env = setup_env();
+    let owner = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let claimer = Address::generate(&env);
+    let arbiter = Address::generate(&env);
+    let token = create_mock_token(&env, &owner);
+
+    set_ledger_timestamp(&env, 1000);
+    env.mock_all_auths();
+
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+    let guild_id = setup_guild(&client, &env, &owner);
+
+    mint_tokens(&env, &token, &alice, 75);
+    mint_tokens(&env, &token, &bob, 25);
+
+    let bounty_id = client.create_bounty(
+        &guild_id,
+        &owner,
+        &String::from_str(&env, "Task"),
+        &String::from_str(&env, "Description"),
+        &100i128,
+        &token,
+        &2000u64,
+        &AssignmentMode::FirstCome,
+    );
+    client.fund_bounty(&bounty_id, &alice, &75i128);
+    client.fund_bounty(&bounty_id, &bob, &25i128);
+    client.claim_bounty(&bounty_id, &claimer);
+    client.submit_work(&bounty_id, &String::from_str(&env, "https://github.com/pr/1"));
+
+    let arbiters = soroban_sdk::vec![&env, arbiter.clone()];
+    client.set_bounty_arbiters(&guild_id, &owner, &arbiters);
+    client.open_bounty_dispute(&bounty_id, &claimer, &String::from_str(&env, "Ignored"));
+    client.rule_bounty_dispute(&bounty_id, &arbiter, &20u32);
+
+    assert_eq!(get_token_balance(&env, &token, &claimer), 20);
+    assert_eq!(get_token_balance(&env, &token, &alice), 60);
+    assert_eq!(get_token_balance(&env, &token, &bob), 20);
+}
+
+// ============ Review Tests ============
+
+#[test]
+fn test_request_changes_keeps_submission_history() {
+    let env = setup_env();
+    let owner = Address::generate(&env);
+    let claimer = Address::generate(&env);
+    let token = create_mock_token(&env, &owner);
+
+    set_ledger_timestamp(&env, 1000);
+    env.mock_all_auths();
+
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+    let guild_id = setup_guild(&client, &env, &owner);
+    let bounty_id = setup_under_review(&client, &env, guild_id, &owner, &token, &claimer);
+
+    client.request_changes(&bounty_id, &owner, &String::from_str(&env, "Add tests"));
+
+    let bounty = client.get_bounty(&bounty_id);
+    assert_eq!(bounty.status, BountyStatus::Claimed);
+    assert_eq!(bounty.claimer, Some(claimer.clone()));
+
+    set_ledger_timestamp(&env, 1100);
+    client.submit_work(&bounty_id, &String::from_str(&env, "https://github.com/pr/2"));
+    client.approve_completion(&bounty_id, &owner);
+
+    let history = client.get_bounty_submissions(&bounty_id);
+    assert_eq!(history.len(), 2);
+
+    let first = history.get(0).unwrap();
+    assert_eq!(first.submission_url, String::from_str(&env, "https://github.com/pr/1"));
+    assert_eq!(first.outcome, SubmissionOutcome::ChangesRequested);
+    assert_eq!(first.feedback, Some(String::from_str(&env, "Add tests")));
+
+    let second = history.get(1).unwrap();
+    assert_eq!(second.submitted_at, 1100);
+    assert_eq!(second.outcome, SubmissionOutcome::Approved);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #227)")] // RevisionLimitReached
+fn test_request_changes_beyond_limit_fails() {
+    let env = setup_env();
+    let owner = Address::generate(&env);
+    let claimer = Address::generate(&env);
+    let token = create_mock_token(&env, &owner);
+
+    set_ledger_timestamp(&env, 1000);
+    env.mock_all_auths();
+
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+    let guild_id = setup_guild(&client, &env, &owner);
+    let bounty_id = setup_under_review(&client, &env, guild_id, &owner, &token, &claimer);
+
+    let feedback = String::from_str(&env, "Not yet");
+    let url = String::from_str(&env, "https://github.com/pr/1");
+    for _ in 0..MAX_REVISION_ROUNDS {
+        client.request_changes(&bounty_id, &owner, &feedback);
+        client.submit_work(&bounty_id, &url);
+    }
+
+    client.request_changes(&bounty_id, &owner, &feedback);
+}
+
+#[test]
+fn test_reject_submission_reopens_bounty() {
+    let env = setup_env();
+    let owner = Address::generate(&env);
+    let claimer = Address::generate(&env);
+    let other = Address::generate(&env);
+    let token = create_mock_token(&env, &owner);
+
+    set_ledger_timestamp(&env, 1000);
+    env.mock_all_auths();
+
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+    let guild_id = setup_guild(&client, &env, &owner);
+    let bounty_id = setup_under_review(&client, &env, guild_id, &owner, &token, &claimer);
+
+    client.reject_submission(&bounty_id, &owner, &String::from_str(&env, "Off topic"));
+
+    let bounty = client.get_bounty(&bounty_id);
+    assert_eq!(bounty.status, BountyStatus::Open);
+    assert!(bounty.claimer.is_none());
+    assert!(bounty.submission_url.is_none());
+    assert_eq!(bounty.funded_amount, 100);
+
+    let history = client.get_bounty_submissions(&bounty_id);
+    assert_eq!(history.get(0).unwrap().outcome, SubmissionOutcome::Rejected);
+
+    client.claim_bounty(&bounty_id, &other);
+    assert_eq!(client.get_bounty(&bounty_id).claimer, Some(other));
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #201)")] // Unauthorized
+fn test_reject_submission_non_admin_fails() {
+    let env = setup_env();
+    let owner = Address::generate(&env);
+    let claimer = Address::generate(&env);
+    let token = create_mock_token(&env, &owner);
+
+    set_ledger_timestamp(&env, 1000);
+    env.mock_all_auths();
+
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+    let guild_id = setup_guild(&client, &env, &owner);
+    let bounty_id = setup_under_review(&client, &env, guild_id, &owner, &token, &claimer);
+
+    client.reject_submission(&bounty_id, &claimer, &String::from_str(&env, "Self-reject"));
+}
+
+// ============ Assignment Tests ============
+
+fn create_application_bounty(
+    client: &StellarGuildsContractClient<'_>,
+    env: &Env,
+    guild_id: u64,
+    owner: &Address,
+    token: &Address,
+) -> u64 {
+    client.create_bounty(
+        &guild_id,
+        owner,
+        &String::from_str(env, "Audit"),
+        &String::from_str(env, "Review the escrow contract"),
+        &0i128,
+        token,
+        &5000u64,
+        &AssignmentMode::Application,
+    )
+}
+
+#[test]
+fn test_assign_bounty_from_applications() {
+    let env = setup_env();
+    let owner = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let token = create_mock_token(&env, &owner);
+
+    set_ledger_timestamp(&env, 1000);
+    env.mock_all_auths();
+
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+    let guild_id = setup_guild(&client, &env, &owner);
+    let bounty_id = create_application_bounty(&client, &env, guild_id, &owner, &token);
+
+    let pitch = String::from_str(&env, "Did three audits");
+    client.apply_for_bounty(&bounty_id, &alice, &pitch, &3000u64);
+    let pitch = String::from_str(&env, "https://bob.dev");
+    client.apply_for_bounty(&bounty_id, &bob, &pitch, &4000u64);
+
+    let applications = client.get_bounty_applications(&bounty_id);
+    assert_eq!(applications.len(), 2);
+    assert_eq!(applications.get(1).unwrap().estimated_delivery, 4000);
+
+    client.assign_bounty(&bounty_id, &owner, &bob);
+
+    let bounty = client.get_bounty(&bounty_id);
+    assert_eq!(bounty.status, BountyStatus::Claimed);
+    assert_eq!(bounty.claimer, Some(bob));
+    assert_eq!(client.get_bounty_applications(&bounty_id).len(), 0);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #228)")] // NotClaimable
+fn test_claim_application_bounty_fails() {
+    let env = setup_env();
+    let owner = Address::generate(&env);
+    let claimer = Address::generate(&env);
+    let token = create_mock_token(&env, &owner);
+
+    set_ledger_timestamp(&env, 1000);
+    env.mock_all_auths();
+
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+    let guild_id = setup_guild(&client, &env, &owner);
+    let bounty_id = create_application_bounty(&client, &env, guild_id, &owner, &token);
+
+    client.claim_bounty(&bounty_id, &claimer);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #229)")] // NotApplicationMode
+fn test_apply_for_first_come_bounty_fails() {
+    let env = setup_env();
+    let owner = Address::generate(&env);
+    let applicant = Address::generate(&env);
+    let token = create_mock_token(&env, &owner);
+
+    set_ledger_timestamp(&env, 1000);
+    env.mock_all_auths();
+
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+    let guild_id = setup_guild(&client, &env, &owner);
+    let bounty_id = client.create_bounty(
+        &guild_id,
+        &owner,
+        &String::from_str(&env, "Task"),
+        &String::from_str(&env, "Description"),
+        &0i128,
+        &token,
+        &2000u64,
+        &AssignmentMode::FirstCome,
+    );
+
+    client.apply_for_bounty(&bounty_id, &applicant, &String::from_str(&env, "Me"), &1500u64);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #231)")] // ApplicationNotFound
+fn test_assign_bounty_to_non_applicant_fails() {
+    let env = setup_env();
+    let owner = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let token = create_mock_token(&env, &owner);
+
+    set_ledger_timestamp(&env, 1000);
+    env.mock_all_auths();
+
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+    let guild_id = setup_guild(&client, &env, &owner);
+    let bounty_id = create_application_bounty(&client, &env, guild_id, &owner, &token);
+
+    client.apply_for_bounty(&bounty_id, &alice, &String::from_str(&env, "Pick me"), &3000u64);
+    client.assign_bounty(&bounty_id, &owner, &Address::generate(&env));
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #201)")] // Unauthorized
+fn test_creator_cannot_apply_for_own_bounty() {
+    let env = setup_env();
+    let owner = Address::generate(&env);
+    let token = create_mock_token(&env, &owner);
+
+    set_ledger_timestamp(&env, 1000);
+    env.mock_all_auths();
+
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+    let guild_id = setup_guild(&client, &env, &owner);
+    let bounty_id = create_application_bounty(&client, &env, guild_id, &owner, &token);
+
+    client.apply_for_bounty(&bounty_id, &owner, &String::from_str(&env, "Mine"), &3000u64);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #201)")] // Unauthorized
+fn test_creator_cannot_claim_own_bounty() {
+    let env = setup_env();
+    let owner = Address::generate(&env);
+    let token = create_mock_token(&env, &owner);
+
+    set_ledger_timestamp(&env, 1000);
+    env.mock_all_auths();
+
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+    let guild_id = setup_guild(&client, &env, &owner);
+    let bounty_id = create_open_bounty(&client, &env, guild_id, &owner, &token);
+
+    client.claim_bounty(&bounty_id, &owner);
+}
+
+// ============ Contest Tests ============
+
+fn create_contest_bounty(
+    client: &StellarGuildsContractClient<'_>,
+    env: &Env,
+    guild_id: u64,
+    owner: &Address,
+    token: &Address,
+) -> u64 {
+    mint_tokens(env, token, owner, 1000);
+
+    let terms = ContestTerms {
+        submission_deadline: 2000,
+        prize_percentages: soroban_sdk::vec![env, 50u32, 30u32, 20u32],
+    };
+    let bounty_id = client.create_bounty(
+        &guild_id,
+        owner,
+        &String::from_str(env, "Hackathon"),
+        &String::from_str(env, "Build the best dashboard"),
+        &1000i128,
+        token,
+        &5000u64,
+        &AssignmentMode::Contest(terms),
+    );
+    client.fund_bounty(&bounty_id, owner, &1000i128);
+    bounty_id
+}
+
+#[test]
+fn test_contest_pays_ranked_winners_and_refunds_unawarded_tiers() {
+    let env = setup_env();
+    let owner = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let carol = Address::generate(&env);
+    let token = create_mock_token(&env, &owner);
+
+    set_ledger_timestamp(&env, 1000);
+    env.mock_all_auths();
+
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+    let guild_id = setup_guild(&client, &env, &owner);
+    let bounty_id = create_contest_bounty(&client, &env, guild_id, &owner, &token);
+
+    let url = String::from_str(&env, "https://github.com/entry");
+    client.submit_contest_entry(&bounty_id, &alice, &url);
+    client.submit_contest_entry(&bounty_id, &bob, &url);
+    client.submit_contest_entry(&bounty_id, &carol, &url);
+
+    set_ledger_timestamp(&env, 2001);
+    let ranking = soroban_sdk::vec![&env, bob.clone(), alice.clone()];
+    client.judge_contest(&bounty_id, &owner, &ranking);
+
+    assert_eq!(get_token_balance(&env, &token, &bob), 500);
+    assert_eq!(get_token_balance(&env, &token, &alice), 300);
+    assert_eq!(get_token_balance(&env, &token, &carol), 0);
+    // Third prize had no winner and goes back to the funder
+    assert_eq!(get_token_balance(&env, &token, &owner), 200);
+
+    let bounty = client.get_bounty(&bounty_id);
+    assert_eq!(bounty.status, BountyStatus::Paid);
+    assert_eq!(bounty.funded_amount, 0);
+
+    let entries = client.get_bounty_submissions(&bounty_id);
+    assert_eq!(entries.get(1).unwrap().outcome, SubmissionOutcome::Approved);
+    assert_eq!(entries.get(2).unwrap().outcome, SubmissionOutcome::Rejected);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #235)")] // ContestClosed
+fn test_contest_entry_after_deadline_fails() {
+    let env = setup_env();
+    let owner = Address::generate(&env);
+    let token = create_mock_token(&env, &owner);
+
+    set_ledger_timestamp(&env, 1000);
+    env.mock_all_auths();
+
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+    let guild_id = setup_guild(&client, &env, &owner);
+    let bounty_id = create_contest_bounty(&client, &env, guild_id, &owner, &token);
+
+    set_ledger_timestamp(&env, 2001);
+    let url = String::from_str(&env, "https://github.com/late");
+    client.submit_contest_entry(&bounty_id, &Address::generate(&env), &url);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #236)")] // ContestNotEnded
+fn test_judge_contest_before_deadline_fails() {
+    let env = setup_env();
+    let owner = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let token = create_mock_token(&env, &owner);
+
+    set_ledger_timestamp(&env, 1000);
+    env.mock_all_auths();
+
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+    let guild_id = setup_guild(&client, &env, &owner);
+    let bounty_id = create_contest_bounty(&client, &env, guild_id, &owner, &token);
+
+    client.submit_contest_entry(&bounty_id, &alice, &String::from_str(&env, "https://a.dev"));
+    client.judge_contest(&bounty_id, &owner, &soroban_sdk::vec![&env, alice]);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #238)")] // InvalidRanking
+fn test_judge_contest_ranking_non_entrant_fails() {
+    let env = setup_env();
+    let owner = Address::generate(&env);
+    let token = create_mock_token(&env, &owner);
+
+    set_ledger_timestamp(&env, 1000);
+    env.mock_all_auths();
+
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+    let guild_id = setup_guild(&client, &env, &owner);
+    let bounty_id = create_contest_bounty(&client, &env, guild_id, &owner, &token);
+
+    set_ledger_timestamp(&env, 2001);
+    let ranking = soroban_sdk::vec![&env, Address::generate(&env)];
+    client.judge_contest(&bounty_id, &owner, &ranking);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #234)")] // InvalidPrizeTable
+fn test_create_contest_with_bad_prize_table_fails() {
+    let env = setup_env();
+    let owner = Address::generate(&env);
+    let token = create_mock_token(&env, &owner);
+
+    set_ledger_timestamp(&env, 1000);
+    env.mock_all_auths();
+
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+    let guild_id = setup_guild(&client, &env, &owner);
+
+    let terms = ContestTerms {
+        submission_deadline: 2000,
+        prize_percentages: soroban_sdk::vec![&env, 50u32, 30u32],
+    };
+    client.create_bounty(
+        &guild_id,
+        &owner,
+        &String::from_str(&env, "Hackathon"),
+        &String::from_str(&env, "Prizes do not add up"),
+        &1000i128,
+        &token,
+        &5000u64,
+        &AssignmentMode::Contest(terms),
+    );
+}
+
+// ============ Claim Window Tests ============
+
+fn create_open_bounty(
+    client: &StellarGuildsContractClient<'_>,
+    env: &Env,
+    guild_id: u64,
+    owner: &Address,
+    token: &Address,
+) -> u64 {
+    client.create_bounty(
+        &guild_id,
+        owner,
+        &String::from_str(env, "Task"),
+        &String::from_str(env, "Description"),
+        &0i128,
+        token,
+        &(1000 + 30 * 24 * 60 * 60),
+        &AssignmentMode::FirstCome,
+    )
+}
+
+#[test]
+fn test_abandon_bounty_reopens_and_records() {
+    let env = setup_env();
+    let owner = Address::generate(&env);
+    let claimer = Address::generate(&env);
+    let token = create_mock_token(&env, &owner);
+
+    set_ledger_timestamp(&env, 1000);
+    env.mock_all_auths();
+
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+    let guild_id = setup_guild(&client, &env, &owner);
+    let bounty_id = create_open_bounty(&client, &env, guild_id, &owner, &token);
+
+    client.claim_bounty(&bounty_id, &claimer);
+    assert_eq!(
+        client.get_bounty_claim_deadline(&bounty_id),
+        Some(1000 + DEFAULT_WORK_WINDOW_SECONDS)
+    );
+
+    client.abandon_bounty(&bounty_id, &claimer);
+
+    let bounty = client.get_bounty(&bounty_id);
+    assert_eq!(bounty.status, BountyStatus::Open);
+    assert!(bounty.claimer.is_none());
+
+    let record = client.get_abandonment_record(&claimer);
+    assert_eq!(record.voluntary, 1);
+    assert_eq!(record.stale, 0);
+    assert_eq!(record.last_bounty_id, bounty_id);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #241)")] // NotClaimer
+fn test_abandon_bounty_by_non_claimer_fails() {
+    let env = setup_env();
+    let owner = Address::generate(&env);
+    let claimer = Address::generate(&env);
+    let token = create_mock_token(&env, &owner);
+
+    set_ledger_timestamp(&env, 1000);
+    env.mock_all_auths();
+
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+    let guild_id = setup_guild(&client, &env, &owner);
+    let bounty_id = create_open_bounty(&client, &env, guild_id, &owner, &token);
+
+    client.claim_bounty(&bounty_id, &claimer);
+    client.abandon_bounty(&bounty_id, &owner);
+}
+
+#[test]
+fn test_reclaim_stale_after_work_window() {
+    let env = setup_env();
+    let owner = Address::generate(&env);
+    let claimer = Address::generate(&env);
+    let token = create_mock_token(&env, &owner);
+
+    set_ledger_timestamp(&env, 1000);
+    env.mock_all_auths();
+
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+    let guild_id = setup_guild(&client, &env, &owner);
+    let bounty_id = create_open_bounty(&client, &env, guild_id, &owner, &token);
+
+    client.set_bounty_work_window(&bounty_id, &owner, &3600u64);
+    client.claim_bounty(&bounty_id, &claimer);
+
+    set_ledger_timestamp(&env, 1000 + 3601);
+    client.reclaim_stale(&bounty_id);
+
+    let bounty = client.get_bounty(&bounty_id);
+    assert_eq!(bounty.status, BountyStatus::Open);
+    assert!(bounty.claimer.is_none());
+    assert_eq!(client.get_abandonment_record(&claimer).stale, 1);
+    let reputation = client.get_reputation_record(&guild_id, &claimer).unwrap();
+    assert_eq!(reputation.penalties, 1);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #242)")] // ClaimNotStale
+fn test_reclaim_stale_within_work_window_fails() {
+    let env = setup_env();
+    let owner = Address::generate(&env);
+    let claimer = Address::generate(&env);
+    let token = create_mock_token(&env, &owner);
+
+    set_ledger_timestamp(&env, 1000);
+    env.mock_all_auths();
+
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+    let guild_id = setup_guild(&client, &env, &owner);
+    let bounty_id = create_open_bounty(&client, &env, guild_id, &owner, &token);
+
+    client.claim_bounty(&bounty_id, &claimer);
+
+    set_ledger_timestamp(&env, 1000 + DEFAULT_WORK_WINDOW_SECONDS);
+    client.reclaim_stale(&bounty_id);
+}
+
+// ============ Team Bounty Tests ============
+
+fn create_claimed_bounty(
+    client: &StellarGuildsContractClient<'_>,
+    env: &Env,
+    owner: &Address,
+    claimer: &Address,
+    token: &Address,
+) -> u64 {
+    let guild_id = setup_guild(client, env, owner);
+    let funder = Address::generate(env);
+    mint_tokens(env, token, &funder, 1000);
+
+    let bounty_id = client.create_bounty(
+        &guild_id,
+        owner,
+        &String::from_str(env, "Task"),
+        &String::from_str(env, "Description"),
+        &1000i128,
+        token,
+        &(1000 + 30 * 24 * 60 * 60),
+        &AssignmentMode::FirstCome,
+    );
+    client.fund_bounty(&bounty_id, &funder, &1000i128);
+    client.claim_bounty(&bounty_id, claimer);
+    bounty_id
+}
+
+#[test]
+fn test_team_bounty_releases_each_share() {
+    let env = setup_env();
+    let owner = Address::generate(&env);
+    let claimer = Address::generate(&env);
+    let member = Address::generate(&env);
+    let token = create_mock_token(&env, &owner);
+
+    set_ledger_timestamp(&env, 1000);
+    env.mock_all_auths();
+
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+    let bounty_id = create_claimed_bounty(&client, &env, &owner, &claimer, &token);
+
+    let members = vec![
+        &env,
+        Recipient {
+            address: claimer.clone(),
+            share: 65,
+        },
+        Recipient {
+            address: member.clone(),
+            share: 35,
+        },
+    ];
+    client.propose_bounty_team(&bounty_id, &claimer, &members);
+
+    // The split is only final once every member agrees
+    let submission = String::from_str(&env, "https://github.com/stellar-guilds/pr/7");
+    assert!(client.try_submit_work(&bounty_id, &submission).is_err());
+
+    assert!(client.accept_team_split(&bounty_id, &member));
+    assert!(client.get_bounty_team(&bounty_id).unwrap().is_confirmed());
+
+    client.submit_work(&bounty_id, &submission);
+    client.approve_completion(&bounty_id, &owner);
+    client.release_escrow(&bounty_id);
+
+    assert_eq!(get_token_balance(&env, &token, &claimer), 650);
+    assert_eq!(get_token_balance(&env, &token, &member), 350);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #244)")] // InvalidTeamSplit
+fn test_team_split_must_total_100() {
+    let env = setup_env();
+    let owner = Address::generate(&env);
+    let claimer = Address::generate(&env);
+    let token = create_mock_token(&env, &owner);
+
+    set_ledger_timestamp(&env, 1000);
+    env.mock_all_auths();
+
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+    let bounty_id = create_claimed_bounty(&client, &env, &owner, &claimer, &token);
+
+    let members = vec![
+        &env,
+        Recipient {
+            address: claimer.clone(),
+            share: 50,
+        },
+        Recipient {
+            address: Address::generate(&env),
+            share: 40,
+        },
+    ];
+    client.propose_bounty_team(&bounty_id, &claimer, &members);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #245)")] // NotTeamMember
+fn test_outsider_cannot_accept_team_split() {
+    let env = setup_env();
+    let owner = Address::generate(&env);
+    let claimer = Address::generate(&env);
+    let token = create_mock_token(&env, &owner);
+
+    set_ledger_timestamp(&env, 1000);
+    env.mock_all_auths();
+
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+    let bounty_id = create_claimed_bounty(&client, &env, &owner, &claimer, &token);
+
+    let members = vec![
+        &env,
+        Recipient {
+            address: claimer.clone(),
+            share: 50,
+        },
+        Recipient {
+            address: Address::generate(&env),
+            share: 50,
+        },
+    ];
+    client.propose_bounty_team(&bounty_id, &claimer, &members);
+    client.accept_team_split(&bounty_id, &Address::generate(&env));
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #201)")] // Unauthorized
+fn test_team_with_banned_member_fails() {
+    let env = setup_env();
+    let owner = Address::generate(&env);
+    let claimer = Address::generate(&env);
+    let banned = Address::generate(&env);
+    let token = create_mock_token(&env, &owner);
+
+    set_ledger_timestamp(&env, 1000);
+    env.mock_all_auths();
+
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+    let bounty_id = create_claimed_bounty(&client, &env, &owner, &claimer, &token);
+    let guild_id = client.get_bounty(&bounty_id).guild_id;
+    client.ban_member(&guild_id, &banned, &owner);
+
+    let members = vec![
+        &env,
+        Recipient {
+            address: claimer.clone(),
+            share: 50,
+        },
+        Recipient {
+            address: banned.clone(),
+            share: 50,
+        },
+    ];
+    client.propose_bounty_team(&bounty_id, &claimer, &members);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #201)")] // Unauthorized
+fn test_banned_member_cannot_accept_team_split() {
+    let env = setup_env();
+    let owner = Address::generate(&env);
+    let claimer = Address::generate(&env);
+    let member = Address::generate(&env);
+    let token = create_mock_token(&env, &owner);
+
+    set_ledger_timestamp(&env, 1000);
+    env.mock_all_auths();
+
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+    let bounty_id = create_claimed_bounty(&client, &env, &owner, &claimer, &token);
+
+    let members = vec![
+        &env,
+        Recipient {
+            address: claimer.clone(),
+            share: 50,
+        },
+        Recipient {
+            address: member.clone(),
+            share: 50,
+        },
+    ];
+    client.propose_bounty_team(&bounty_id, &claimer, &members);
+
+    let guild_id = client.get_bounty(&bounty_id).guild_id;
+    client.ban_member(&guild_id, &member, &owner);
+    client.accept_team_split(&bounty_id, &member);
+}
+
+#[test]
+fn test_abandoning_bounty_disbands_team() {
+    let env = setup_env();
+    let owner = Address::generate(&env);
+    let claimer = Address::generate(&env);
+    let token = create_mock_token(&env, &owner);
+
+    set_ledger_timestamp(&env, 1000);
+    env.mock_all_auths();
+
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+    let bounty_id = create_claimed_bounty(&client, &env, &owner, &claimer, &token);
+
+    let members = vec![
+        &env,
+        Recipient {
+            address: claimer.clone(),
+            share: 70,
+        },
+        Recipient {
+            address: Address::generate(&env),
+            share: 30,
+        },
+    ];
+    client.propose_bounty_team(&bounty_id, &claimer, &members);
+    client.abandon_bounty(&bounty_id, &claimer);
+
+    assert_eq!(client.get_bounty_team(&bounty_id), None);
+}
+
+// ============ Discovery Tests ============
+
+#[test]
+fn test_list_open_bounties_pages_across_guilds() {
+    let env = setup_env();
+    let owner = Address::generate(&env);
+    let token = create_mock_token(&env, &owner);
+
+    set_ledger_timestamp(&env, 1000);
+    env.mock_all_auths();
+
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+    let guild_a = setup_guild(&client, &env, &owner);
+    let guild_b = setup_guild(&client, &env, &owner);
+
+    create_open_bounty(&client, &env, guild_a, &owner, &token);
+    create_open_bounty(&client, &env, guild_b, &owner, &token);
+    create_open_bounty(&client, &env, guild_b, &owner, &token);
+
+    let first = client.list_open_bounties(&0, &2);
+    assert_eq!(first.bounties.len(), 2);
+    assert_eq!(first.next_cursor, Some(2));
+
+    let second = client.list_open_bounties(&2, &2);
+    assert_eq!(second.bounties.len(), 1);
+    assert_eq!(second.next_cursor, None);
+
+    let guild_page = client.list_bounties(&BountyFilter::Guild(guild_b), &0, &50);
+    assert_eq!(guild_page.bounties.len(), 2);
+    assert!(guild_page.bounties.iter().all(|b| b.guild_id == guild_b));
+}
+
+#[test]
+fn test_status_and_claimer_indexes_follow_bounty() {
+    let env = setup_env();
+    let owner = Address::generate(&env);
+    let claimer = Address::generate(&env);
+    let token = create_mock_token(&env, &owner);
+
+    set_ledger_timestamp(&env, 1000);
+    env.mock_all_auths();
+
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+    let bounty_id = create_claimed_bounty(&client, &env, &owner, &claimer, &token);
+
+    let open = client.list_bounties(&BountyFilter::Status(BountyStatus::Open), &0, &50);
+    assert_eq!(open.bounties.len(), 0);
+    let claimed = client.list_bounties(&BountyFilter::Status(BountyStatus::Claimed), &0, &50);
+    assert_eq!(claimed.bounties.get(0).unwrap().id, bounty_id);
+    let by_claimer = client.list_bounties(&BountyFilter::Claimer(claimer.clone()), &0, &50);
+    assert_eq!(by_claimer.bounties.len(), 1);
+
+    client.abandon_bounty(&bounty_id, &claimer);
+
+    let open = client.list_bounties(&BountyFilter::Status(BountyStatus::Open), &0, &50);
+    assert_eq!(open.bounties.get(0).unwrap().id, bounty_id);
+    let claimed = client.list_bounties(&BountyFilter::Status(BountyStatus::Claimed), &0, &50);
+    assert_eq!(claimed.bounties.len(), 0);
+    let by_claimer = client.list_bounties(&BountyFilter::Claimer(claimer), &0, &50);
+    assert_eq!(by_claimer.bounties.len(), 0);
+    let by_creator = client.list_bounties(&BountyFilter::Creator(owner), &0, &50);
+    assert_eq!(by_creator.bounties.len(), 1);
+    let by_token = client.list_bounties(&BountyFilter::Token(token), &0, &50);
+    assert_eq!(by_token.bounties.len(), 1);
+}
+
+#[test]
+fn test_set_bounty_metadata_updates_tag_index() {
+    let env = setup_env();
+    let owner = Address::generate(&env);
+    let token = create_mock_token(&env, &owner);
+
+    set_ledger_timestamp(&env, 1000);
+    env.mock_all_auths();
+
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+    let guild_id = setup_guild(&client, &env, &owner);
+    let bounty_id = create_open_bounty(&client, &env, guild_id, &owner, &token);
+
+    let tags = vec![&env, symbol_short!("rust"), symbol_short!("soroban")];
+    client.set_bounty_metadata(&bounty_id, &owner, &tags, &Difficulty::Advanced);
+
+    let metadata = client.get_bounty_metadata(&bounty_id).unwrap();
+    assert_eq!(metadata.tags, tags);
+    assert_eq!(metadata.difficulty, Difficulty::Advanced);
+    let tagged = client.list_bounties(&BountyFilter::Tag(symbol_short!("rust")), &0, &50);
+    assert_eq!(tagged.bounties.len(), 1);
+
+    let tags = vec![&env, symbol_short!("soroban"), symbol_short!("docs")];
+    client.set_bounty_metadata(&bounty_id, &owner, &tags, &Difficulty::Beginner);
+
+    let tagged = client.list_bounties(&BountyFilter::Tag(symbol_short!("rust")), &0, &50);
+    assert_eq!(tagged.bounties.len(), 0);
+    let tagged = client.list_bounties(&BountyFilter::Tag(symbol_short!("docs")), &0, &50);
+    assert_eq!(tagged.bounties.len(), 1);
+    let tagged = client.list_bounties(&BountyFilter::Tag(symbol_short!("soroban")), &0, &50);
+    assert_eq!(tagged.bounties.len(), 1);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #248)")] // InvalidTags
+fn test_set_bounty_metadata_duplicate_tag_fails() {
+    let env = setup_env();
+    let owner = Address::generate(&env);
+    let token = create_mock_token(&env, &owner);
+
+    set_ledger_timestamp(&env, 1000);
+    env.mock_all_auths();
+
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+    let guild_id = setup_guild(&client, &env, &owner);
+    let bounty_id = create_open_bounty(&client, &env, guild_id, &owner, &token);
+
+    let tags = vec![&env, symbol_short!("rust"), symbol_short!("rust")];
+    client.set_bounty_metadata(&bounty_id, &owner, &tags, &Difficulty::Beginner);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #201)")] // Unauthorized
+fn test_set_bounty_metadata_non_admin_fails() {
+    let env = setup_env();
+    let owner = Address::generate(&env);
+    let outsider = Address::generate(&env);
+    let token = create_mock_token(&env, &owner);
+
+    set_ledger_timestamp(&env, 1000);
+    env.mock_all_auths();
+
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+    let guild_id = setup_guild(&client, &env, &owner);
+    let bounty_id = create_open_bounty(&client, &env, guild_id, &owner, &token);
+
+    let tags = vec![&env, symbol_short!("rust")];
+    client.set_bounty_metadata(&bounty_id, &outsider, &tags, &Difficulty::Beginner);
+}
+
+// ============ Escrow Lifecycle Tests ============
+
+#[test]
+#[should_panic(expected = "Error(Contract, #214)")] // NotCompleted
+fn test_release_escrow_twice_fails() {
+    let env = setup_env();
+    let owner = Address::generate(&env);
+    let claimer = Address::generate(&env);
+    let token = create_mock_token(&env, &owner);
+
+    set_ledger_timestamp(&env, 1000);
+    env.mock_all_auths();
+
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+    // A second bounty's escrow sits in the same contract
+    let other_claimer = Address::generate(&env);
+    let other_id = create_claimed_bounty(&client, &env, &owner, &other_claimer, &token);
+    let bounty_id = create_claimed_bounty(&client, &env, &owner, &claimer, &token);
+    let url = String::from_str(&env, "https://github.com/pr/1");
+    client.submit_work(&bounty_id, &url);
+    client.approve_completion(&bounty_id, &owner);
+
+    assert_eq!(
+        client.get_bounty_escrow_state(&bounty_id),
+        Some(EscrowState::Locked)
+    );
+    client.release_escrow(&bounty_id);
+
+    let bounty = client.get_bounty(&bounty_id);
+    assert_eq!(bounty.status, BountyStatus::Paid);
+    assert_eq!(bounty.funded_amount, 0);
+    assert_eq!(
+        client.get_bounty_escrow_state(&bounty_id),
+        Some(EscrowState::Released)
+    );
+    assert_eq!(get_token_balance(&env, &token, &claimer), 1000);
+    assert_eq!(get_token_balance(&env, &token, &contract_id), 1000);
+    assert_eq!(
+        client.get_bounty_escrow_state(&other_id),
+        Some(EscrowState::Locked)
+    );
+
+    client.release_escrow(&bounty_id);
+}
+
+#[test]
+fn test_auto_release_pays_on_approval() {
+    let env = setup_env();
+    let owner = Address::generate(&env);
+    let claimer = Address::generate(&env);
+    let token = create_mock_token(&env, &owner);
+
+    set_ledger_timestamp(&env, 1000);
+    env.mock_all_auths();
+
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+    let bounty_id = create_claimed_bounty(&client, &env, &owner, &claimer, &token);
+
+    client.set_bounty_auto_release(&bounty_id, &owner, &true);
+    let url = String::from_str(&env, "https://github.com/pr/1");
+    client.submit_work(&bounty_id, &url);
+    client.approve_completion(&bounty_id, &owner);
+
+    assert_eq!(client.get_bounty(&bounty_id).status, BountyStatus::Paid);
+    assert_eq!(get_token_balance(&env, &token, &claimer), 1000);
+    assert_eq!(get_token_balance(&env, &token, &contract_id), 0);
+}
+
+#[test]
+fn test_cancelled_bounty_escrow_is_refunded() {
+    let env = setup_env();
+    let owner = Address::generate(&env);
+    let token = create_mock_token(&env, &owner);
+
+    set_ledger_timestamp(&env, 1000);
+    env.mock_all_auths();
+
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+    let guild_id = setup_guild(&client, &env, &owner);
+
+    let bounty_id = create_open_bounty(&client, &env, guild_id, &owner, &token);
+    assert_eq!(client.get_bounty_escrow_state(&bounty_id), None);
+
+    let bounty_id = client.create_bounty(
+        &guild_id,
+        &owner,
+        &String::from_str(&env, "Task"),
+        &String::from_str(&env, "Description"),
+        &100i128,
+        &token,
+        &2000u64,
+        &AssignmentMode::FirstCome,
+    );
+    mint_tokens(&env, &token, &owner, 100);
+    client.fund_bounty(&bounty_id, &owner, &100i128);
+    client.cancel_bounty(&bounty_id, &owner);
+
+    assert_eq!(
+        client.get_bounty_escrow_state(&bounty_id),
+        Some(EscrowState::Refunded)
+    );
+    assert_eq!(get_token_balance(&env, &token, &owner), 100);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #201)")] // Unauthorized
+fn test_set_auto_release_non_admin_fails() {
+    let env = setup_env();
+    let owner = Address::generate(&env);
+    let claimer = Address::generate(&env);
+    let token = create_mock_token(&env, &owner);
+
+    set_ledger_timestamp(&env, 1000);
+    env.mock_all_auths();
+
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+    let bounty_id = create_claimed_bounty(&client, &env, &owner, &claimer, &token);
+
+    client.set_bounty_auto_release(&bounty_id, &claimer, &true);
+}
+
+// ============ Leave & Ban Tests ============
+
+#[test]
+#[should_panic(expected = "Error(Contract, #201)")] // Unauthorized
+fn test_banned_address_cannot_claim() {
+    let env = setup_env();
+    let owner = Address::generate(&env);
+    let claimer = Address::generate(&env);
+    let token = create_mock_token(&env, &owner);
+
+    set_ledger_timestamp(&env, 1000);
+    env.mock_all_auths();
+
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+    let guild_id = setup_guild(&client, &env, &owner);
+    let bounty_id = create_open_bounty(&client, &env, guild_id, &owner, &token);
+    client.ban_member(&guild_id, &claimer, &owner);
+
+    client.claim_bounty(&bounty_id, &claimer);
+}
+
+#[test]
+fn test_leaving_member_releases_claims() {
+    let env = setup_env();
+    let owner = Address::generate(&env);
+    let claimer = Address::generate(&env);
+    let token = create_mock_token(&env, &owner);
+
+    set_ledger_timestamp(&env, 1000);
+    env.mock_all_auths();
+
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+    let guild_id = setup_guild(&client, &env, &owner);
+    client.add_member(&guild_id, &claimer, &Role::Contributor, &owner);
+    let bounty_id = create_open_bounty(&client, &env, guild_id, &owner, &token);
+    client.claim_bounty(&bounty_id, &claimer);
+
+    client.leave_guild(&guild_id, &claimer);
+
+    let bounty = client.get_bounty(&bounty_id);
+    assert_eq!(bounty.status, BountyStatus::Open);
+    assert!(bounty.claimer.is_none());
+    assert_eq!(client.get_abandonment_record(&claimer).voluntary, 1);
+}
+
+// ============ Storage TTL Tests ============
+
+#[test]
+fn test_bump_guild_keeps_bounty_state_alive() {
+    let env = setup_env();
+    let owner = Address::generate(&env);
+    let claimer = Address::generate(&env);
+    let token = create_mock_token(&env, &owner);
+
+    set_ledger_timestamp(&env, 1000);
+    env.mock_all_auths();
+
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+    let bounty_id = create_claimed_bounty(&client, &env, &owner, &claimer, &token);
+    let guild_id = client.get_bounty(&bounty_id).guild_id;
+    let tags = vec![&env, symbol_short!("rust")];
+    client.set_bounty_metadata(&bounty_id, &owner, &tags, &Difficulty::Advanced);
+    client.submit_work(
+        &bounty_id,
+        &String::from_str(&env, "https://github.com/pr/1"),
+    );
+
+    // Well past the default TTL of every entry, bumping along the way
+    for _ in 0..6 {
+        env.ledger()
+            .with_mut(|li| li.sequence_number += 25 * ttl::DAY_IN_LEDGERS);
+        assert_eq!(client.bump_guild(&guild_id, &0, &10), None);
+    }
+
+    assert_eq!(
+        client.get_bounty_escrow_state(&bounty_id),
+        Some(EscrowState::Locked)
+    );
+    assert_eq!(client.get_bounty_funders(&bounty_id).len(), 1);
+    assert_eq!(client.get_bounty_submissions(&bounty_id).len(), 1);
+    assert_eq!(client.get_bounty_metadata(&bounty_id).unwrap().tags, tags);
+
+    let tagged = client.list_bounties(&BountyFilter::Tag(symbol_short!("rust")), &0, &50);
+    assert_eq!(tagged.bounties.len(), 1);
+    let claimed = client.list_bounties(&BountyFilter::Claimer(claimer), &0, &50);
+    assert_eq!(claimed.bounties.len(), 1);
+    let by_status = client.list_bounties(&BountyFilter::Status(BountyStatus::UnderReview), &0, &50);
+    assert_eq!(by_status.bounties.len(), 1);
+}
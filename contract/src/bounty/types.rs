@@ -1,4 +1,65 @@
-use soroban_sdk::{contracttype, Address, String};
+use crate::payment::types::Recipient;
+use soroban_sdk::{contracterror, contracttype, Address, Map, String, Symbol, Vec};
+
+/// Error types for bounty escrow operations
+///
+/// Codes live in the 200-299 range so they never collide with the errors of
+/// other modules when surfaced to clients.
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum BountyError {
+    BountyNotFound = 200,
+    Unauthorized = 201,
+    InvalidRewardAmount = 202,
+    InvalidExpiry = 203,
+    InvalidTitle = 204,
+    InvalidDescription = 205,
+    InvalidAmount = 206,
+    BountyExpired = 207,
+    NotFundable = 208,
+    NotOpen = 209,
+    NoClaimer = 210,
+    NotClaimed = 211,
+    InvalidSubmissionUrl = 212,
+    NotUnderReview = 213,
+    NotCompleted = 214,
+    NotCancellable = 215,
+    NotDisputable = 216,
+    NotDisputed = 217,
+    DisputeNotFound = 218,
+    NotArbiter = 219,
+    AlreadyRuled = 220,
+    InvalidSplit = 221,
+    InvalidReason = 222,
+    InvalidArbiters = 223,
+    AlreadyFullyFunded = 224,
+    TooManyFunders = 225,
+    InvalidFeedback = 226,
+    RevisionLimitReached = 227,
+    NotClaimable = 228,
+    NotApplicationMode = 229,
+    AlreadyApplied = 230,
+    ApplicationNotFound = 231,
+    InvalidApplication = 232,
+    TooManyApplications = 233,
+    InvalidPrizeTable = 234,
+    ContestClosed = 235,
+    ContestNotEnded = 236,
+    NotContest = 237,
+    InvalidRanking = 238,
+    AlreadySubmitted = 239,
+    TooManyEntries = 240,
+    NotClaimer = 241,
+    ClaimNotStale = 242,
+    InvalidWorkWindow = 243,
+    InvalidTeamSplit = 244,
+    NotTeamMember = 245,
+    TeamNotConfirmed = 246,
+    AlreadyAccepted = 247,
+    InvalidTags = 248,
+    InsufficientEscrow = 249,
+}
 
 /// Status of a bounty lifecycle
 #[contracttype]
@@ -11,6 +72,44 @@ pub enum BountyStatus {
     Cancelled = 4,
     Expired = 5,
     AwaitingFunds = 6,
+    Disputed = 7,
+    /// Completed and its escrow paid out
+    Paid = 8,
+}
+
+/// Where a bounty's escrowed funds stand
+#[contracttype]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EscrowState {
+    /// Held by the contract for the bounty
+    Locked = 0,
+    /// Paid out to the workers, possibly with part refunded
+    Released = 1,
+    /// Returned to the funders
+    Refunded = 2,
+}
+
+/// How a bounty gets its claimer
+#[contracttype]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum AssignmentMode {
+    /// First contributor to call `claim_bounty` gets it
+    FirstCome,
+    /// Contributors apply and the creator or an admin picks one
+    Application,
+    /// Anyone may enter until the deadline; judges rank entries for prizes
+    Contest(ContestTerms),
+}
+
+/// Deadline and prize table of a contest bounty
+#[contracttype]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ContestTerms {
+    /// Last timestamp at which entries are accepted
+    pub submission_deadline: u64,
+    /// Percent of the escrow paid to each rank, first place first; must sum
+    /// to 100
+    pub prize_percentages: Vec<u32>,
 }
 
 /// Bounty struct containing all bounty metadata and state
@@ -45,6 +144,112 @@ pub struct Bounty {
     pub expires_at: u64,
 }
 
+/// A contributor's application to work on a bounty
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct Application {
+    pub applicant: Address,
+    /// Pitch text or a URL to one
+    pub pitch: String,
+    /// Timestamp by which the applicant expects to deliver
+    pub estimated_delivery: u64,
+    pub applied_at: u64,
+}
+
+/// Team working a claimed bounty and the agreed payout split
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BountyTeam {
+    /// Members with their percentage of the payout; includes the claimer
+    pub members: Vec<Recipient>,
+    /// Members who have agreed to the split
+    pub accepted: Vec<Address>,
+    pub proposed_at: u64,
+}
+
+impl BountyTeam {
+    /// Whether every member has agreed to the split
+    pub fn is_confirmed(&self) -> bool {
+        self.members.iter().all(|m| self.accepted.contains(&m.address))
+    }
+}
+
+/// Claims a contributor has given up or let lapse, across all guilds
+#[contracttype]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct AbandonmentRecord {
+    /// Claims released by the claimer through `abandon_bounty`
+    pub voluntary: u32,
+    /// Claims reclaimed after the claimer missed their work window
+    pub stale: u32,
+    pub last_bounty_id: u64,
+    pub last_abandoned_at: u64,
+}
+
+/// Review outcome of a work submission
+#[contracttype]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SubmissionOutcome {
+    Pending = 0,
+    ChangesRequested = 1,
+    Rejected = 2,
+    Approved = 3,
+}
+
+/// A single work submission, kept alongside every earlier one
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct Submission {
+    pub claimer: Address,
+    pub submission_url: String,
+    pub submitted_at: u64,
+    pub outcome: SubmissionOutcome,
+    /// Reviewer feedback when changes were requested or the work was rejected
+    pub feedback: Option<String>,
+    pub reviewed_at: Option<u64>,
+}
+
+/// Where a contribution to a bounty's escrow came from, and where its
+/// refund goes
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum FundingSource {
+    /// Tokens transferred in by an address
+    Account(Address),
+    /// Tokens moved out of a guild treasury
+    Treasury(u64),
+}
+
+/// Amount a funding source has contributed to a bounty's escrow
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BountyFunder {
+    pub source: FundingSource,
+    pub amount: i128,
+}
+
+/// Dispute opened over a bounty's submitted work
+///
+/// Escrowed funds stay locked until the arbiter panel or a governance
+/// proposal rules on how `funded_amount` is split.
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct Dispute {
+    pub bounty_id: u64,
+    /// Claimer or creator who opened the dispute
+    pub opened_by: Address,
+    pub reason: String,
+    pub opened_at: u64,
+    /// Guild arbiters at the time the dispute was opened, excluding the
+    /// parties to it
+    pub arbiters: Vec<Address>,
+    /// Claimer share, in percent, proposed by each arbiter that has ruled
+    pub rulings: Map<Address, u32>,
+    /// Claimer share, in percent, of the final ruling
+    pub claimer_percent: Option<u32>,
+    pub resolved_at: Option<u64>,
+}
+
 // ============ Events ============
 
 /// Event emitted when a bounty is created
@@ -57,6 +262,7 @@ pub struct BountyCreatedEvent {
     pub reward_amount: i128,
     pub token: Address,
     pub expires_at: u64,
+    pub assignment_mode: AssignmentMode,
 }
 
 /// Event emitted when a bounty is funded
@@ -78,6 +284,80 @@ pub struct BountyClaimedEvent {
     pub claimer: Address,
 }
 
+/// Event emitted when a contributor applies for a bounty
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct ApplicationSubmittedEvent {
+    pub bounty_id: u64,
+    pub applicant: Address,
+    pub estimated_delivery: u64,
+}
+
+/// Event emitted when an applicant is assigned a bounty
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct BountyAssignedEvent {
+    pub bounty_id: u64,
+    pub assignee: Address,
+    pub assigner: Address,
+}
+
+/// Event emitted for each applicant passed over when a bounty is assigned
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct ApplicationDeclinedEvent {
+    pub bounty_id: u64,
+    pub applicant: Address,
+}
+
+/// Event emitted when a contest bounty is judged and prizes are paid
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct ContestJudgedEvent {
+    pub bounty_id: u64,
+    pub judge: Address,
+    /// Winners in rank order
+    pub winners: Vec<Address>,
+    /// Prize awarded to each winner, in rank order, before fees
+    pub prizes: Vec<i128>,
+    /// Escrow from unawarded tiers returned to funders
+    pub refunded: i128,
+    /// Guild fee withheld from the prizes
+    pub guild_fee: i128,
+    /// Platform fee withheld from the prizes
+    pub platform_fee: i128,
+}
+
+/// Event emitted when a claimer proposes a team split
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct TeamProposedEvent {
+    pub bounty_id: u64,
+    pub claimer: Address,
+    pub members: Vec<Recipient>,
+}
+
+/// Event emitted when a team member agrees to the split
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct TeamSplitAcceptedEvent {
+    pub bounty_id: u64,
+    pub member: Address,
+    /// `true` once every member has agreed
+    pub confirmed: bool,
+}
+
+/// Event emitted when a claim is abandoned or reclaimed as stale
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct BountyAbandonedEvent {
+    pub bounty_id: u64,
+    pub claimer: Address,
+    /// `true` when reclaimed after the work window, `false` when released by
+    /// the claimer
+    pub stale: bool,
+}
+
 /// Event emitted when work is submitted
 #[contracttype]
 #[derive(Clone, Debug)]
@@ -87,6 +367,26 @@ pub struct WorkSubmittedEvent {
     pub submission_url: String,
 }
 
+/// Event emitted when a reviewer sends work back to the claimer
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct ChangesRequestedEvent {
+    pub bounty_id: u64,
+    pub reviewer: Address,
+    pub feedback: String,
+    pub round: u32,
+}
+
+/// Event emitted when a submission is rejected and the bounty reopens
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct SubmissionRejectedEvent {
+    pub bounty_id: u64,
+    pub reviewer: Address,
+    pub claimer: Address,
+    pub feedback: String,
+}
+
 /// Event emitted when work is approved
 #[contracttype]
 #[derive(Clone, Debug)]
@@ -101,8 +401,11 @@ pub struct BountyApprovedEvent {
 pub struct EscrowReleasedEvent {
     pub bounty_id: u64,
     pub recipient: Address,
+    /// Amount received by the recipient, after fees
     pub amount: i128,
     pub token: Address,
+    pub guild_fee: i128,
+    pub platform_fee: i128,
 }
 
 /// Event emitted when a bounty is cancelled
@@ -112,7 +415,83 @@ pub struct BountyCancelledEvent {
     pub bounty_id: u64,
     pub canceller: Address,
     pub refund_amount: i128,
-    pub refund_recipient: Address,
+}
+
+/// Event emitted when escrow is returned to a funder
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct FunderRefundedEvent {
+    pub bounty_id: u64,
+    pub source: FundingSource,
+    pub amount: i128,
+}
+
+/// Event emitted when a treasury transaction funds a bounty
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct BountyTreasuryFundedEvent {
+    pub bounty_id: u64,
+    pub treasury_id: u64,
+    pub amount: i128,
+    pub total_funded: i128,
+    pub is_fully_funded: bool,
+}
+
+/// How demanding the work of a bounty is
+#[contracttype]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Difficulty {
+    Beginner = 0,
+    Intermediate = 1,
+    Advanced = 2,
+    Expert = 3,
+}
+
+/// Discovery labels of a bounty, kept apart from the bounty record
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct BountyMetadata {
+    /// Skill or topic labels, e.g. `rust` or `frontend`
+    pub tags: Vec<Symbol>,
+    pub difficulty: Difficulty,
+}
+
+/// Selects the bounties a discovery query pages through
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub enum BountyFilter {
+    Guild(u64),
+    Status(BountyStatus),
+    Token(Address),
+    Tag(Symbol),
+    Claimer(Address),
+    Creator(Address),
+}
+
+/// One page of a discovery query
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct BountyPage {
+    pub bounties: Vec<Bounty>,
+    /// Cursor to pass for the next page, `None` once the listing is exhausted
+    pub next_cursor: Option<u32>,
+}
+
+/// Event emitted when the tags or difficulty of a bounty change
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct BountyMetadataUpdatedEvent {
+    pub bounty_id: u64,
+    pub tags: Vec<Symbol>,
+    pub difficulty: Difficulty,
+}
+
+/// Event emitted when automatic release on approval is turned on or off
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct AutoReleaseUpdatedEvent {
+    pub bounty_id: u64,
+    pub enabled: bool,
 }
 
 /// Event emitted when a bounty expires
@@ -121,3 +500,36 @@ pub struct BountyCancelledEvent {
 pub struct BountyExpiredEvent {
     pub bounty_id: u64,
 }
+
+/// Event emitted when a dispute is opened
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct DisputeOpenedEvent {
+    pub bounty_id: u64,
+    pub opened_by: Address,
+    pub reason: String,
+}
+
+/// Event emitted when an arbiter rules on a dispute
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct DisputeRulingEvent {
+    pub bounty_id: u64,
+    pub arbiter: Address,
+    pub claimer_percent: u32,
+}
+
+/// Event emitted when a dispute is settled and escrow is paid out
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct DisputeResolvedEvent {
+    pub bounty_id: u64,
+    pub claimer_percent: u32,
+    /// Escrow awarded to the claimer or their team, before fees
+    pub claimer_amount: i128,
+    pub funder_amount: i128,
+    /// Guild fee withheld from the claimer's award
+    pub guild_fee: i128,
+    /// Platform fee withheld from the claimer's award
+    pub platform_fee: i128,
+}
@@ -1,72 +1,123 @@
-use soroban_sdk::{Address, Env, String, Symbol};
+use soroban_sdk::{panic_with_error, Env, String, Symbol};
 
+use crate::bounty::dispute::resolve_dispute_by_governance;
 use crate::governance::proposals::get_proposal as load_proposal;
-use crate::governance::storage::store_proposal;
+use crate::governance::storage::{get_config, set_config, store_proposal};
 use crate::governance::types::{
-    ExecutionPayload, Proposal, ProposalExecutedEvent, ProposalStatus, ProposalType,
+    ExecutionPayload, GovernanceConfig, GovernanceError, Proposal, ProposalExecutedEvent,
+    ProposalStatus, ProposalType, RuleChangeData, VotingMode,
 };
 use crate::governance::voting::finalize_proposal;
-use crate::guild::membership::{add_member, remove_member};
+use crate::guild::membership;
+use crate::guild::rules as guild_rules;
 use crate::guild::storage as guild_storage;
-use crate::guild::types::Role;
+use crate::guild::types::{GuildRules, Role};
+use crate::treasury::execute_governance_spend;
 
 const EXECUTION_DEADLINE_SECONDS: u64 = 3 * 24 * 60 * 60; // 3 days after passing
 
-fn get_guild_owner(env: &Env, guild_id: u64) -> Address {
-    let guild =
-        guild_storage::get_guild(env, guild_id).unwrap_or_else(|| panic!("guild not found"));
-    guild.owner
-}
-
-pub fn execute_proposal(env: &Env, proposal_id: u64) -> bool {
-    let mut proposal = load_proposal(env, proposal_id);
+pub fn execute_proposal(env: &Env, proposal_id: u64) -> Result<bool, GovernanceError> {
+    let mut proposal = load_proposal(env, proposal_id)?;
 
     // auto-finalize if still active and voting period ended
     let now = env.ledger().timestamp();
     if matches!(proposal.status, ProposalStatus::Active) && now >= proposal.voting_end {
-        let _status = finalize_proposal(env, proposal_id);
-        proposal = load_proposal(env, proposal_id);
+        // a roster too large to count in one call is left to finalize_proposal
+        if finalize_proposal(env, proposal_id)? == ProposalStatus::Active {
+            return Err(GovernanceError::TallyInProgress);
+        }
+        proposal = load_proposal(env, proposal_id)?;
         if !matches!(proposal.status, ProposalStatus::Passed) {
-            panic!("proposal not passed");
+            return Err(GovernanceError::ProposalNotPassed);
         }
     }
 
     if !matches!(proposal.status, ProposalStatus::Passed) {
-        panic!("only passed proposals can be executed");
+        return Err(GovernanceError::ProposalNotPassed);
     }
 
     if let Some(passed_at) = proposal.passed_at {
         if now > passed_at + EXECUTION_DEADLINE_SECONDS {
             proposal.status = ProposalStatus::Expired;
             store_proposal(env, &proposal);
-            panic!("execution window expired");
+            return Err(GovernanceError::ExecutionWindowExpired);
         }
     }
 
+    // Membership and treasury failures keep their own error codes so
+    // clients can tell them apart.
     let success = match (&proposal.proposal_type, &proposal.execution_payload) {
-        (ProposalType::AddMember, ExecutionPayload::AddMember) => {
-            // NOTE: With simplified ExecutionPayload, actual member data must be stored separately.
-            // For now, this is a signalling-only execution.
+        (ProposalType::AddMember, ExecutionPayload::AddMember(data)) => {
+            // The guild acts through its owner's authority
+            let guild = guild_storage::get_guild(env, proposal.guild_id)
+                .ok_or(GovernanceError::GuildNotFound)?;
+            if let Err(e) = membership::add_member(
+                env,
+                proposal.guild_id,
+                data.address.clone(),
+                data.role,
+                guild.owner,
+            ) {
+                panic_with_error!(env, e);
+            }
             true
         }
-        (ProposalType::RemoveMember, ExecutionPayload::RemoveMember) => {
-            // NOTE: With simplified ExecutionPayload, actual member data must be stored separately.
-            // For now, this is a signalling-only execution.
+        (ProposalType::RemoveMember, ExecutionPayload::RemoveMember(data)) => {
+            let guild = guild_storage::get_guild(env, proposal.guild_id)
+                .ok_or(GovernanceError::GuildNotFound)?;
+            if let Err(e) =
+                membership::remove_member(env, proposal.guild_id, data.address.clone(), guild.owner)
+            {
+                panic_with_error!(env, e);
+            }
             true
         }
-        (ProposalType::TreasurySpend, ExecutionPayload::TreasurySpend) => {
-            // For now, only record that governance approved the spend.
-            // Actual treasury movement should be done by a separate call using this payload.
+        (ProposalType::TreasurySpend, ExecutionPayload::TreasurySpend(data)) => {
+            if let Err(e) = execute_governance_spend(
+                env,
+                data.treasury_id,
+                proposal.guild_id,
+                data.token.clone(),
+                data.recipient.clone(),
+                data.amount,
+                data.reason.clone(),
+            ) {
+                panic_with_error!(env, e);
+            }
             true
         }
-        (ProposalType::RuleChange, ExecutionPayload::RuleChange) => {
-            // No concrete rule storage defined yet; treat as signalling and emit event only.
+        (ProposalType::RuleChange, ExecutionPayload::RuleChange(data)) => {
+            let mut config = get_config(env, proposal.guild_id);
+            let mut rules = guild_rules::get_rules(env, proposal.guild_id);
+            let (old_config, old_rules) = (config.clone(), rules.clone());
+
+            apply_rule_change(env, &mut config, &mut rules, data)?;
+
+            if config != old_config {
+                set_config(env, proposal.guild_id, &config);
+            }
+            if rules != old_rules {
+                if let Err(e) = guild_rules::set_rules(env, proposal.guild_id, rules) {
+                    panic_with_error!(env, e);
+                }
+            }
             true
         }
         (ProposalType::GeneralDecision, ExecutionPayload::GeneralDecision) => {
             // Signalling-only proposals, always succeed when executed.
             true
         }
+        (ProposalType::DisputeResolution, ExecutionPayload::DisputeResolution(data)) => {
+            if let Err(e) = resolve_dispute_by_governance(
+                env,
+                proposal.guild_id,
+                data.bounty_id,
+                data.claimer_percent,
+            ) {
+                panic_with_error!(env, e);
+            }
+            true
+        }
         _ => false,
     };
 
@@ -89,5 +140,118 @@ pub fn execute_proposal(env: &Env, proposal_id: u64) -> bool {
         event,
     );
 
-    success
+    Ok(success)
+}
+
+/// Write a rule change into a governance config or the guild's rules
+///
+/// Keys name a `GovernanceConfig` or `GuildRules` field. Values are decimal
+/// integers, except `bounty_creator_role` which takes a role name
+/// (`owner`, `admin`, `member` or `contributor`) and `voting_mode` which
+/// takes `role`, `one_member_one_vote` or `reputation`. Token-weighted voting
+/// names a token contract, so it can only be chosen through
+/// `update_governance_config`.
+pub(crate) fn apply_rule_change(
+    env: &Env,
+    config: &mut GovernanceConfig,
+    rules: &mut GuildRules,
+    change: &RuleChangeData,
+) -> Result<(), GovernanceError> {
+    let key = &change.key;
+
+    if *key == String::from_str(env, "bounty_creator_role") {
+        rules.bounty_creator_role =
+            parse_role(env, &change.value).ok_or(GovernanceError::InvalidRuleValue)?;
+        return Ok(());
+    }
+
+    if *key == String::from_str(env, "voting_mode") {
+        config.voting_mode =
+            parse_voting_mode(env, &change.value).ok_or(GovernanceError::InvalidRuleValue)?;
+        return Ok(());
+    }
+
+    let value = parse_u64(&change.value).ok_or(GovernanceError::InvalidRuleValue)?;
+    let as_u32 = || u32::try_from(value).map_err(|_| GovernanceError::InvalidRuleValue);
+
+    if *key == String::from_str(env, "quorum_percentage") {
+        let value = as_u32()?;
+        if value == 0 || value > 100 {
+            return Err(GovernanceError::InvalidQuorum);
+        }
+        config.quorum_percentage = value;
+    } else if *key == String::from_str(env, "approval_threshold") {
+        let value = as_u32()?;
+        if value == 0 || value > 100 {
+            return Err(GovernanceError::InvalidApprovalThreshold);
+        }
+        config.approval_threshold = value;
+    } else if *key == String::from_str(env, "voting_period_days") {
+        let value = as_u32()?;
+        if value == 0 {
+            return Err(GovernanceError::InvalidRuleValue);
+        }
+        config.voting_period_days = value;
+    } else if *key == String::from_str(env, "min_proposer_reputation") {
+        config.min_proposer_reputation = as_u32()?;
+    } else if *key == String::from_str(env, "max_members") {
+        rules.max_members = as_u32()?;
+    } else if *key == String::from_str(env, "default_bounty_expiry") {
+        rules.default_bounty_expiry = value;
+    } else if *key == String::from_str(env, "max_bounty_description_len") {
+        rules.max_bounty_description_len = as_u32()?;
+    } else if *key == String::from_str(env, "min_bounty_reward") {
+        rules.min_bounty_reward = value as i128;
+    } else if *key == String::from_str(env, "treasury_tx_expiry") {
+        rules.treasury_tx_expiry = value;
+    } else {
+        return Err(GovernanceError::UnknownRule);
+    }
+
+    guild_rules::validate_rules(rules).map_err(|_| GovernanceError::InvalidRuleValue)
+}
+
+fn parse_u64(value: &String) -> Option<u64> {
+    let len = value.len() as usize;
+    if len == 0 || len > 20 {
+        return None;
+    }
+
+    let mut buf = [0u8; 20];
+    value.copy_into_slice(&mut buf[..len]);
+
+    let mut result = 0u64;
+    for byte in &buf[..len] {
+        if !byte.is_ascii_digit() {
+            return None;
+        }
+        result = result.checked_mul(10)?.checked_add((byte - b'0') as u64)?;
+    }
+    Some(result)
+}
+
+fn parse_role(env: &Env, value: &String) -> Option<Role> {
+    if *value == String::from_str(env, "owner") {
+        Some(Role::Owner)
+    } else if *value == String::from_str(env, "admin") {
+        Some(Role::Admin)
+    } else if *value == String::from_str(env, "member") {
+        Some(Role::Member)
+    } else if *value == String::from_str(env, "contributor") {
+        Some(Role::Contributor)
+    } else {
+        None
+    }
+}
+
+fn parse_voting_mode(env: &Env, value: &String) -> Option<VotingMode> {
+    if *value == String::from_str(env, "role") {
+        Some(VotingMode::RoleWeighted)
+    } else if *value == String::from_str(env, "one_member_one_vote") {
+        Some(VotingMode::OneMemberOneVote)
+    } else if *value == String::from_str(env, "reputation") {
+        Some(VotingMode::ReputationWeighted)
+    } else {
+        None
+    }
 }
@@ -7,6 +7,7 @@ pub mod execution;
 pub use types::{
     ExecutionPayload,
     GovernanceConfig,
+    GovernanceError,
     Proposal,
     ProposalStatus,
     ProposalType,
@@ -17,6 +18,7 @@ pub use proposals::{
     cancel_proposal,
     create_proposal,
     get_active_proposals,
+    get_governance_config,
     get_proposal,
     update_governance_config,
 };
@@ -24,6 +26,7 @@ pub use proposals::{
 pub use voting::{
     delegate_vote,
     finalize_proposal,
+    get_vote_weight,
     undelegate_vote,
     vote,
 };
@@ -1,15 +1,21 @@
 use soroban_sdk::{Address, Env, String, Symbol, Vec};
 
+use crate::bounty::storage as bounty_storage;
+use crate::bounty::types::BountyStatus;
 use crate::governance::storage::{
     get_config, get_guild_proposals, get_next_proposal_id, get_proposal as load_proposal,
     set_config, store_proposal,
 };
 use crate::governance::types::{
-    ExecutionPayload, GovernanceConfig, GovernanceConfigUpdatedEvent, Proposal,
+    ExecutionPayload, GovernanceConfig, GovernanceConfigUpdatedEvent, GovernanceError, Proposal,
     ProposalCreatedEvent, ProposalStatus, ProposalType,
 };
+use crate::governance::execution::apply_rule_change;
+use crate::governance::voting::{ensure_supported_mode, member_reputation, take_snapshot};
+use crate::guild::rules as guild_rules;
 use crate::guild::storage as guild_storage;
 use crate::guild::types::Member;
+use crate::treasury::storage as treasury_storage;
 
 const EVENT_TOPIC_PROPOSAL_CREATED: &str = "proposal_created";
 const EVENT_TOPIC_CONFIG_UPDATED: &str = "gov_config_updated";
@@ -19,34 +25,50 @@ fn validate_execution_payload(
     guild_id: u64,
     proposal_type: &ProposalType,
     payload: &ExecutionPayload,
-) {
-    // Validate that payload type matches proposal type
+) -> Result<(), GovernanceError> {
+    // Ensure guild exists
+    guild_storage::get_guild(env, guild_id).ok_or(GovernanceError::GuildNotFound)?;
+
+    // Validate that payload type matches proposal type, and reject payloads
+    // that could never execute
     match (proposal_type, payload) {
-        (ProposalType::TreasurySpend, ExecutionPayload::TreasurySpend) => {}
-        (ProposalType::AddMember, ExecutionPayload::AddMember) => {}
-        (ProposalType::RemoveMember, ExecutionPayload::RemoveMember) => {}
-        (ProposalType::RuleChange, ExecutionPayload::RuleChange) => {}
+        (ProposalType::TreasurySpend, ExecutionPayload::TreasurySpend(data)) => {
+            if data.amount <= 0 {
+                return Err(GovernanceError::InvalidSpendAmount);
+            }
+            let treasury = treasury_storage::get_treasury(env, data.treasury_id)
+                .ok_or(GovernanceError::TreasuryNotInGuild)?;
+            if treasury.guild_id != guild_id {
+                return Err(GovernanceError::TreasuryNotInGuild);
+            }
+        }
+        (ProposalType::AddMember, ExecutionPayload::AddMember(_)) => {}
+        (ProposalType::RemoveMember, ExecutionPayload::RemoveMember(_)) => {}
+        (ProposalType::RuleChange, ExecutionPayload::RuleChange(data)) => {
+            let mut config = get_config(env, guild_id);
+            let mut rules = guild_rules::get_rules(env, guild_id);
+            apply_rule_change(env, &mut config, &mut rules, data)?;
+        }
         (ProposalType::GeneralDecision, ExecutionPayload::GeneralDecision) => {}
+        (ProposalType::DisputeResolution, ExecutionPayload::DisputeResolution(data)) => {
+            let disputed = bounty_storage::get_bounty(env, data.bounty_id)
+                .is_some_and(|b| b.guild_id == guild_id && b.status == BountyStatus::Disputed);
+            if !disputed || data.claimer_percent > 100 {
+                return Err(GovernanceError::InvalidDisputeResolution);
+            }
+        }
         _ => {
-            panic!("execution payload does not match proposal type");
+            return Err(GovernanceError::PayloadMismatch);
         }
     }
 
-    // Ensure guild exists
-    let _guild =
-        guild_storage::get_guild(env, guild_id).unwrap_or_else(|| panic!("guild not found"));
+    Ok(())
 }
 
 fn get_member(env: &Env, guild_id: u64, address: &Address) -> Option<Member> {
     guild_storage::get_member(env, guild_id, address)
 }
 
-fn ensure_guild_member(env: &Env, guild_id: u64, address: &Address) {
-    if get_member(env, guild_id, address).is_none() {
-        panic!("only guild members can perform this action");
-    }
-}
-
 pub fn create_proposal(
     env: &Env,
     guild_id: u64,
@@ -55,30 +77,28 @@ pub fn create_proposal(
     title: String,
     description: String,
     execution_payload: ExecutionPayload,
-) -> u64 {
+) -> Result<u64, GovernanceError> {
     proposer.require_auth();
 
     // must be guild member
-    let member = get_member(env, guild_id, &proposer)
-        .unwrap_or_else(|| panic!("proposer must be a guild member"));
+    let member = get_member(env, guild_id, &proposer).ok_or(GovernanceError::NotGuildMember)?;
 
     let cfg: GovernanceConfig = get_config(env, guild_id);
 
-    // proposer reputation based on role weight
-    let reputation = crate::governance::types::role_weight(&member.role) as u32;
-    if reputation < cfg.min_proposer_reputation {
-        panic!("insufficient reputation to create proposal");
+    let reputation = member_reputation(env, guild_id, &member);
+    if reputation < cfg.min_proposer_reputation as i128 {
+        return Err(GovernanceError::InsufficientReputation);
     }
 
-    if title.len() == 0 || title.len() > 200 {
-        panic!("proposal title length invalid");
+    if title.is_empty() || title.len() > 200 {
+        return Err(GovernanceError::InvalidTitle);
     }
 
     if description.len() > 2000 {
-        panic!("proposal description too long");
+        return Err(GovernanceError::DescriptionTooLong);
     }
 
-    validate_execution_payload(env, guild_id, &proposal_type, &execution_payload);
+    validate_execution_payload(env, guild_id, &proposal_type, &execution_payload)?;
 
     let id = get_next_proposal_id(env);
     let now = env.ledger().timestamp();
@@ -103,6 +123,7 @@ pub fn create_proposal(
     };
 
     store_proposal(env, &proposal);
+    take_snapshot(env, &proposal, &cfg);
 
     let event = ProposalCreatedEvent {
         proposal_id: id,
@@ -119,28 +140,31 @@ pub fn create_proposal(
         event,
     );
 
-    id
+    Ok(id)
 }
 
-pub fn cancel_proposal(env: &Env, proposal_id: u64, canceller: Address) -> bool {
+pub fn cancel_proposal(
+    env: &Env,
+    proposal_id: u64,
+    canceller: Address,
+) -> Result<bool, GovernanceError> {
     canceller.require_auth();
 
-    let mut proposal =
-        load_proposal(env, proposal_id).unwrap_or_else(|| panic!("proposal not found"));
+    let mut proposal = load_proposal(env, proposal_id).ok_or(GovernanceError::ProposalNotFound)?;
 
     if !matches!(
         proposal.status,
         ProposalStatus::Active | ProposalStatus::Draft
     ) {
-        panic!("only active or draft proposals can be cancelled");
+        return Err(GovernanceError::ProposalNotCancellable);
     }
 
     // allow proposer or guild owner to cancel
     let guild = guild_storage::get_guild(env, proposal.guild_id)
-        .unwrap_or_else(|| panic!("guild not found"));
+        .ok_or(GovernanceError::GuildNotFound)?;
 
     if canceller != proposal.proposer && canceller != guild.owner {
-        panic!("only proposer or guild owner can cancel");
+        return Err(GovernanceError::Unauthorized);
     }
 
     proposal.status = ProposalStatus::Cancelled;
@@ -155,11 +179,11 @@ pub fn cancel_proposal(env: &Env, proposal_id: u64, canceller: Address) -> bool
         event,
     );
 
-    true
+    Ok(true)
 }
 
-pub fn get_proposal(env: &Env, proposal_id: u64) -> Proposal {
-    load_proposal(env, proposal_id).unwrap_or_else(|| panic!("proposal not found"))
+pub fn get_proposal(env: &Env, proposal_id: u64) -> Result<Proposal, GovernanceError> {
+    load_proposal(env, proposal_id).ok_or(GovernanceError::ProposalNotFound)
 }
 
 pub fn get_active_proposals(env: &Env, guild_id: u64) -> Vec<Proposal> {
@@ -173,27 +197,31 @@ pub fn get_active_proposals(env: &Env, guild_id: u64) -> Vec<Proposal> {
     active
 }
 
+pub fn get_governance_config(env: &Env, guild_id: u64) -> GovernanceConfig {
+    get_config(env, guild_id)
+}
+
 pub fn update_governance_config(
     env: &Env,
     guild_id: u64,
     caller: Address,
     config: GovernanceConfig,
-) -> bool {
+) -> Result<bool, GovernanceError> {
     // only guild owner can update config
-    let guild =
-        guild_storage::get_guild(env, guild_id).unwrap_or_else(|| panic!("guild not found"));
+    let guild = guild_storage::get_guild(env, guild_id).ok_or(GovernanceError::GuildNotFound)?;
 
     if caller != guild.owner {
-        panic!("only guild owner can update governance config");
+        return Err(GovernanceError::Unauthorized);
     }
     caller.require_auth();
 
     if config.quorum_percentage == 0 || config.quorum_percentage > 100 {
-        panic!("invalid quorum percentage");
+        return Err(GovernanceError::InvalidQuorum);
     }
     if config.approval_threshold == 0 || config.approval_threshold > 100 {
-        panic!("invalid approval threshold");
+        return Err(GovernanceError::InvalidApprovalThreshold);
     }
+    ensure_supported_mode(env, &config.voting_mode)?;
 
     set_config(env, guild_id, &config);
 
@@ -206,5 +234,5 @@ pub fn update_governance_config(
         event,
     );
 
-    true
+    Ok(true)
 }
@@ -1,190 +1,462 @@
-use soroban_sdk::{symbol_short, Address, Env, Map, Symbol, Vec};
-
-use crate::governance::types::{GovernanceConfig, Proposal, Vote};
-
-const PROPOSALS_KEY: Symbol = symbol_short!("g_props");
-const PROPOSAL_COUNTER_KEY: Symbol = symbol_short!("g_pcnt");
-const GUILD_PROPOSALS_KEY: Symbol = symbol_short!("g_pidx");
-
-const VOTES_KEY: Symbol = symbol_short!("g_votes");
-
-const DELEGATIONS_KEY: Symbol = symbol_short!("g_deleg");
+use soroban_sdk::{contracttype, symbol_short, Address, Env, Map, Symbol, TryFromVal, Val, Vec};
+
+use crate::governance::types::{
+    DelegationCheckpoint, GovernanceConfig, GovernanceConfigV1, Proposal, ProposalSnapshot,
+    ProposalTally, ProposalV1, Vote,
+};
+use crate::guild::storage as guild_storage;
+use crate::paging;
+use crate::ttl;
+
+/// Storage keys for proposals, votes, delegations and configs
+#[contracttype]
+pub enum GovernanceStorageKey {
+    ProposalCounter,
+    Proposal(u64),
+    GuildProposalsLen(u64),
+    GuildProposalsPage(u64, u32), // Vec<proposal_id>
+    Vote(u64, Address),           // (proposal_id, voter)
+    Delegation(u64, Address),     // (guild_id, delegator) -> delegate
+    Config(u64),
+    Snapshot(u64),               // proposal_id -> ProposalSnapshot
+    DelegationHistory(u64, Address), // (guild_id, delegator) -> Vec<DelegationCheckpoint>
+    GuildVotesLen(u64),
+    GuildVotesPage(u64, u32),          // Vec<(proposal_id, voter)>
+    DelegatorSlot(u64, Address),       // (guild_id, delegator) -> position in the delegate's index
+    DelegatorsLen(u64, Address),       // (guild_id, delegate)
+    DelegatorsPage(u64, Address, u32), // Vec<Address> delegating to the delegate
+    Tally(u64),                        // proposal_id -> ProposalTally while being counted
+}
 
-const GOV_CONFIG_KEY: Symbol = symbol_short!("g_conf");
+// Keys used by releases that kept each collection in a single map
+const LEGACY_PROPOSALS_KEY: Symbol = symbol_short!("g_props");
+const LEGACY_PROPOSAL_COUNTER_KEY: Symbol = symbol_short!("g_pcnt");
+const LEGACY_GUILD_PROPOSALS_KEY: Symbol = symbol_short!("g_pidx");
+const LEGACY_VOTES_KEY: Symbol = symbol_short!("g_votes");
+const LEGACY_DELEGATIONS_KEY: Symbol = symbol_short!("g_deleg");
+const LEGACY_GOV_CONFIG_KEY: Symbol = symbol_short!("g_conf");
 
 pub fn get_next_proposal_id(env: &Env) -> u64 {
-    let current: u64 = env
-        .storage()
-        .persistent()
-        .get(&PROPOSAL_COUNTER_KEY)
-        .unwrap_or(0u64);
+    let current = ttl::get_counter(env, &GovernanceStorageKey::ProposalCounter, 0);
     let next = current + 1;
-    env.storage()
-        .persistent()
-        .set(&PROPOSAL_COUNTER_KEY, &next);
+    ttl::set_counter(env, &GovernanceStorageKey::ProposalCounter, next);
     next
 }
 
 pub fn store_proposal(env: &Env, proposal: &Proposal) {
-    let mut proposals: Map<u64, Proposal> = env
-        .storage()
-        .persistent()
-        .get(&PROPOSALS_KEY)
-        .unwrap_or_else(|| Map::new(env));
-
-    proposals.set(proposal.id, proposal.clone());
-    env.storage().persistent().set(&PROPOSALS_KEY, &proposals);
+    let key = GovernanceStorageKey::Proposal(proposal.id);
 
     // index by guild
-    let mut index: Map<u64, Vec<u64>> = env
-        .storage()
-        .persistent()
-        .get(&GUILD_PROPOSALS_KEY)
-        .unwrap_or_else(|| Map::new(env));
-
-    let mut list = index.get(proposal.guild_id).unwrap_or_else(|| Vec::new(env));
-    if !list.iter().any(|id| id == proposal.id) {
-        list.push_back(proposal.id);
-        index.set(proposal.guild_id, list);
-        env.storage()
-            .persistent()
-            .set(&GUILD_PROPOSALS_KEY, &index);
+    if !env.storage().persistent().has(&key) {
+        let guild_id = proposal.guild_id;
+        paging::push(
+            env,
+            &GovernanceStorageKey::GuildProposalsLen(guild_id),
+            |page| GovernanceStorageKey::GuildProposalsPage(guild_id, page),
+            proposal.id,
+        );
     }
+
+    ttl::set_persistent(env, &key, proposal);
 }
 
 pub fn get_proposal(env: &Env, proposal_id: u64) -> Option<Proposal> {
-    let proposals: Map<u64, Proposal> = env
-        .storage()
-        .persistent()
-        .get(&PROPOSALS_KEY)
-        .unwrap_or_else(|| Map::new(env));
+    ttl::get_persistent(env, &GovernanceStorageKey::Proposal(proposal_id))
+}
 
-    proposals.get(proposal_id)
+pub fn get_guild_proposal_ids(env: &Env, guild_id: u64) -> Vec<u64> {
+    paging::collect(
+        env,
+        &GovernanceStorageKey::GuildProposalsLen(guild_id),
+        |page| GovernanceStorageKey::GuildProposalsPage(guild_id, page),
+    )
 }
 
 pub fn get_guild_proposals(env: &Env, guild_id: u64) -> Vec<Proposal> {
-    let index: Map<u64, Vec<u64>> = env
-        .storage()
-        .persistent()
-        .get(&GUILD_PROPOSALS_KEY)
-        .unwrap_or_else(|| Map::new(env));
-
-    let ids = index.get(guild_id).unwrap_or_else(|| Vec::new(env));
+    proposals_for(env, &get_guild_proposal_ids(env, guild_id))
+}
 
-    let proposals: Map<u64, Proposal> = env
-        .storage()
-        .persistent()
-        .get(&PROPOSALS_KEY)
-        .unwrap_or_else(|| Map::new(env));
+/// Get one page (of `paging::PAGE_SIZE` entries) of a guild's proposals
+pub fn get_guild_proposals_page(env: &Env, guild_id: u64, page: u32) -> Vec<Proposal> {
+    let ids = paging::get_page(env, &GovernanceStorageKey::GuildProposalsPage(guild_id, page));
+    proposals_for(env, &ids)
+}
 
+fn proposals_for(env: &Env, ids: &Vec<u64>) -> Vec<Proposal> {
     let mut result = Vec::new(env);
     for id in ids.iter() {
-        if let Some(p) = proposals.get(id) {
+        if let Some(p) = get_proposal(env, id) {
             result.push_back(p);
         }
     }
     result
 }
 
-pub fn store_vote(env: &Env, vote: &Vote) {
-    let mut votes_map: Map<u64, Map<Address, Vote>> = env
-        .storage()
-        .persistent()
-        .get(&VOTES_KEY)
-        .unwrap_or_else(|| Map::new(env));
+/// Store a vote, adding it to the guild's vote index if new
+pub fn store_vote(env: &Env, guild_id: u64, vote: &Vote) {
+    let key = GovernanceStorageKey::Vote(vote.proposal_id, vote.voter.clone());
+
+    if !env.storage().persistent().has(&key) {
+        paging::push(
+            env,
+            &GovernanceStorageKey::GuildVotesLen(guild_id),
+            |page| GovernanceStorageKey::GuildVotesPage(guild_id, page),
+            (vote.proposal_id, vote.voter.clone()),
+        );
+    }
 
-    let mut proposal_votes = votes_map
-        .get(vote.proposal_id)
-        .unwrap_or_else(|| Map::new(env));
+    ttl::set_persistent(env, &key, vote);
+}
+
+pub fn get_vote(env: &Env, proposal_id: u64, voter: &Address) -> Option<Vote> {
+    ttl::get_persistent(env, &GovernanceStorageKey::Vote(proposal_id, voter.clone()))
+}
+
+pub fn set_proposal_snapshot(env: &Env, proposal_id: u64, snapshot: &ProposalSnapshot) {
+    ttl::set_persistent(env, &GovernanceStorageKey::Snapshot(proposal_id), snapshot);
+}
+
+/// What a proposal recorded when voting opened, or `None` for proposals
+/// created before snapshots were taken
+pub fn get_proposal_snapshot(env: &Env, proposal_id: u64) -> Option<ProposalSnapshot> {
+    ttl::get_persistent(env, &GovernanceStorageKey::Snapshot(proposal_id))
+}
+
+/// Votes counted so far for a proposal, `None` before counting starts
+pub fn get_tally(env: &Env, proposal_id: u64) -> Option<ProposalTally> {
+    ttl::get_persistent(env, &GovernanceStorageKey::Tally(proposal_id))
+}
 
-    proposal_votes.set(vote.voter.clone(), vote.clone());
-    votes_map.set(vote.proposal_id, proposal_votes);
+pub fn set_tally(env: &Env, proposal_id: u64, tally: &ProposalTally) {
+    ttl::set_persistent(env, &GovernanceStorageKey::Tally(proposal_id), tally);
+}
 
+pub fn remove_tally(env: &Env, proposal_id: u64) {
     env.storage()
         .persistent()
-        .set(&VOTES_KEY, &votes_map);
+        .remove(&GovernanceStorageKey::Tally(proposal_id));
 }
 
-pub fn get_vote(env: &Env, proposal_id: u64, voter: &Address) -> Option<Vote> {
-    let votes_map: Map<u64, Map<Address, Vote>> = env
-        .storage()
-        .persistent()
-        .get(&VOTES_KEY)
-        .unwrap_or_else(|| Map::new(env));
+/// Record a delegator's delegation before its first change in the guild's
+/// current snapshot epoch; nothing is recorded before any proposal has opened
+fn checkpoint_delegation(env: &Env, guild_id: u64, delegator: &Address) {
+    let epoch = guild_storage::get_snapshot_epoch(env, guild_id);
+    if epoch == 0 {
+        return;
+    }
 
-    let proposal_votes = votes_map.get(proposal_id)?;
-    proposal_votes.get(voter.clone())
+    let key = GovernanceStorageKey::DelegationHistory(guild_id, delegator.clone());
+    let mut history: Vec<DelegationCheckpoint> =
+        ttl::get_persistent(env, &key).unwrap_or_else(|| Vec::new(env));
+    if history.last().map(|checkpoint| checkpoint.epoch) == Some(epoch) {
+        return;
+    }
+    history.push_back(DelegationCheckpoint {
+        epoch,
+        delegate: get_delegate(env, guild_id, delegator),
+    });
+    ttl::set_persistent(env, &key, &history);
 }
 
-pub fn get_all_votes(env: &Env, proposal_id: u64) -> Map<Address, Vote> {
-    let votes_map: Map<u64, Map<Address, Vote>> = env
-        .storage()
-        .persistent()
-        .get(&VOTES_KEY)
-        .unwrap_or_else(|| Map::new(env));
+/// Add a delegator to the index of addresses delegating to `delegate`
+fn index_delegator(env: &Env, guild_id: u64, delegate: &Address, delegator: &Address) {
+    let slot = paging::push(
+        env,
+        &GovernanceStorageKey::DelegatorsLen(guild_id, delegate.clone()),
+        |page| GovernanceStorageKey::DelegatorsPage(guild_id, delegate.clone(), page),
+        delegator.clone(),
+    );
+    ttl::set_persistent(
+        env,
+        &GovernanceStorageKey::DelegatorSlot(guild_id, delegator.clone()),
+        &slot,
+    );
+}
 
-    votes_map
-        .get(proposal_id)
-        .unwrap_or_else(|| Map::new(env))
+/// Drop a delegator from the index of addresses delegating to `delegate`
+fn unindex_delegator(env: &Env, guild_id: u64, delegate: &Address, delegator: &Address) {
+    let slot_key = GovernanceStorageKey::DelegatorSlot(guild_id, delegator.clone());
+    if let Some(slot) = env.storage().persistent().get::<_, u32>(&slot_key) {
+        env.storage().persistent().remove(&slot_key);
+
+        let moved: Option<Address> = paging::swap_remove(
+            env,
+            &GovernanceStorageKey::DelegatorsLen(guild_id, delegate.clone()),
+            |page| GovernanceStorageKey::DelegatorsPage(guild_id, delegate.clone(), page),
+            slot,
+        );
+        if let Some(moved) = moved {
+            ttl::set_persistent(
+                env,
+                &GovernanceStorageKey::DelegatorSlot(guild_id, moved),
+                &slot,
+            );
+        }
+    }
 }
 
 pub fn set_delegation(env: &Env, guild_id: u64, delegator: &Address, delegate: &Address) {
-    let mut delegations: Map<(u64, Address), Address> = env
-        .storage()
-        .persistent()
-        .get(&DELEGATIONS_KEY)
-        .unwrap_or_else(|| Map::new(env));
-
-    delegations.set((guild_id, delegator.clone()), delegate.clone());
-    env.storage()
-        .persistent()
-        .set(&DELEGATIONS_KEY, &delegations);
+    checkpoint_delegation(env, guild_id, delegator);
+    if let Some(previous) = get_delegate(env, guild_id, delegator) {
+        unindex_delegator(env, guild_id, &previous, delegator);
+    }
+    index_delegator(env, guild_id, delegate, delegator);
+    ttl::set_persistent(
+        env,
+        &GovernanceStorageKey::Delegation(guild_id, delegator.clone()),
+        delegate,
+    );
 }
 
 pub fn remove_delegation(env: &Env, guild_id: u64, delegator: &Address) {
-    let mut delegations: Map<(u64, Address), Address> = env
-        .storage()
-        .persistent()
-        .get(&DELEGATIONS_KEY)
-        .unwrap_or_else(|| Map::new(env));
-
-    if delegations.contains_key((guild_id, delegator.clone())) {
-        delegations.remove((guild_id, delegator.clone()));
-        env.storage()
-            .persistent()
-            .set(&DELEGATIONS_KEY, &delegations);
+    checkpoint_delegation(env, guild_id, delegator);
+    if let Some(previous) = get_delegate(env, guild_id, delegator) {
+        unindex_delegator(env, guild_id, &previous, delegator);
     }
+    env.storage()
+        .persistent()
+        .remove(&GovernanceStorageKey::Delegation(guild_id, delegator.clone()));
 }
 
 pub fn get_delegate(env: &Env, guild_id: u64, delegator: &Address) -> Option<Address> {
-    let delegations: Map<(u64, Address), Address> = env
-        .storage()
-        .persistent()
-        .get(&DELEGATIONS_KEY)
-        .unwrap_or_else(|| Map::new(env));
+    ttl::get_persistent(env, &GovernanceStorageKey::Delegation(guild_id, delegator.clone()))
+}
+
+/// Addresses currently delegating their vote to `delegate`
+pub fn get_delegators(env: &Env, guild_id: u64, delegate: &Address) -> Vec<Address> {
+    paging::collect(
+        env,
+        &GovernanceStorageKey::DelegatorsLen(guild_id, delegate.clone()),
+        |page| GovernanceStorageKey::DelegatorsPage(guild_id, delegate.clone(), page),
+    )
+}
 
-    delegations.get((guild_id, delegator.clone()))
+/// Get a delegator's delegate as of a guild snapshot epoch
+pub fn get_delegate_at(
+    env: &Env,
+    guild_id: u64,
+    delegator: &Address,
+    epoch: u32,
+) -> Option<Address> {
+    let key = GovernanceStorageKey::DelegationHistory(guild_id, delegator.clone());
+    if let Some(history) = ttl::get_persistent::<_, Vec<DelegationCheckpoint>>(env, &key) {
+        for checkpoint in history.iter() {
+            if checkpoint.epoch > epoch {
+                return checkpoint.delegate;
+            }
+        }
+    }
+    get_delegate(env, guild_id, delegator)
 }
 
 pub fn get_config(env: &Env, guild_id: u64) -> GovernanceConfig {
-    let configs: Map<u64, GovernanceConfig> = env
-        .storage()
-        .persistent()
-        .get(&GOV_CONFIG_KEY)
-        .unwrap_or_else(|| Map::new(env));
+    ttl::get_persistent::<_, Val>(env, &GovernanceStorageKey::Config(guild_id))
+        .map(|raw| decode_config(env, raw))
+        .unwrap_or_else(GovernanceConfig::default)
+}
 
-    configs.get(guild_id).unwrap_or_else(GovernanceConfig::default)
+/// Read a stored config, upgrading the layout written before voting modes
+/// existed
+fn decode_config(env: &Env, raw: Val) -> GovernanceConfig {
+    // Structs are stored as maps keyed by field name; the host rejects
+    // decoding a map with missing fields, so check the layout first.
+    let fields = Map::<Symbol, Val>::try_from_val(env, &raw).unwrap_or_else(|_| Map::new(env));
+    let decoded = if fields.contains_key(Symbol::new(env, "voting_mode")) {
+        GovernanceConfig::try_from_val(env, &raw).ok()
+    } else {
+        GovernanceConfigV1::try_from_val(env, &raw)
+            .ok()
+            .map(GovernanceConfig::from)
+    };
+    decoded.unwrap_or_else(GovernanceConfig::default)
+}
+
+/// Read a legacy proposal, upgrading the layout written before execution
+/// payloads carried their data
+fn decode_proposal(env: &Env, raw: Val) -> Option<Proposal> {
+    // Enum values are stored as `[variant, data..]`; the old payloads were
+    // all unit variants, so a one-element payload marks the old layout.
+    let legacy = Map::<Symbol, Val>::try_from_val(env, &raw)
+        .ok()
+        .and_then(|fields| fields.get(Symbol::new(env, "execution_payload")))
+        .and_then(|payload| Vec::<Val>::try_from_val(env, &payload).ok())
+        .is_some_and(|payload| payload.len() == 1);
+    if legacy {
+        ProposalV1::try_from_val(env, &raw).ok().map(Proposal::from)
+    } else {
+        Proposal::try_from_val(env, &raw).ok()
+    }
 }
 
 pub fn set_config(env: &Env, guild_id: u64, config: &GovernanceConfig) {
-    let mut configs: Map<u64, GovernanceConfig> = env
-        .storage()
-        .persistent()
-        .get(&GOV_CONFIG_KEY)
+    ttl::set_persistent(env, &GovernanceStorageKey::Config(guild_id), config);
+}
+
+/// Extend the TTL of a guild's governance config, of the proposals and
+/// votes at positions `cursor..cursor + limit` of its indexes, and of the
+/// delegations made by and to the members and departed members at those
+/// positions of the guild's rosters
+///
+/// # Returns
+/// Whether the proposal or vote index holds entries past the batch
+pub fn bump_guild_governance(env: &Env, guild_id: u64, cursor: u32, limit: u32) -> bool {
+    ttl::extend_persistent_if_present(env, &GovernanceStorageKey::Config(guild_id));
+
+    let proposal_ids: Vec<u64> = paging::get_range(
+        env,
+        &GovernanceStorageKey::GuildProposalsLen(guild_id),
+        |page| GovernanceStorageKey::GuildProposalsPage(guild_id, page),
+        cursor,
+        limit,
+    );
+    for proposal_id in proposal_ids.iter() {
+        ttl::extend_persistent_if_present(env, &GovernanceStorageKey::Proposal(proposal_id));
+        ttl::extend_persistent_if_present(env, &GovernanceStorageKey::Snapshot(proposal_id));
+        ttl::extend_persistent_if_present(env, &GovernanceStorageKey::Tally(proposal_id));
+    }
+
+    let votes: Vec<(u64, Address)> = paging::get_range(
+        env,
+        &GovernanceStorageKey::GuildVotesLen(guild_id),
+        |page| GovernanceStorageKey::GuildVotesPage(guild_id, page),
+        cursor,
+        limit,
+    );
+    for (proposal_id, voter) in votes.iter() {
+        ttl::extend_persistent_if_present(env, &GovernanceStorageKey::Vote(proposal_id, voter));
+    }
+
+    let mut delegators = guild_storage::get_member_address_range(env, guild_id, cursor, limit);
+    delegators.append(&guild_storage::get_departed_range(env, guild_id, cursor, limit));
+    for address in delegators.iter() {
+        ttl::extend_persistent_if_present(
+            env,
+            &GovernanceStorageKey::Delegation(guild_id, address.clone()),
+        );
+        ttl::extend_persistent_if_present(
+            env,
+            &GovernanceStorageKey::DelegationHistory(guild_id, address.clone()),
+        );
+        ttl::extend_persistent_if_present(
+            env,
+            &GovernanceStorageKey::DelegatorSlot(guild_id, address.clone()),
+        );
+
+        let len_key = GovernanceStorageKey::DelegatorsLen(guild_id, address.clone());
+        ttl::extend_persistent_if_present(env, &len_key);
+        for page in 0..paging::len(env, &len_key).div_ceil(paging::PAGE_SIZE) {
+            ttl::extend_persistent_if_present(
+                env,
+                &GovernanceStorageKey::DelegatorsPage(guild_id, address.clone(), page),
+            );
+        }
+    }
+
+    let end = cursor.saturating_add(limit);
+    paging::len(env, &GovernanceStorageKey::GuildProposalsLen(guild_id)) > end
+        || paging::len(env, &GovernanceStorageKey::GuildVotesLen(guild_id)) > end
+}
+
+/// Move the proposal counter written by older releases into instance storage
+pub fn migrate_legacy_counters(env: &Env) {
+    ttl::promote_counter(
+        env,
+        &LEGACY_PROPOSAL_COUNTER_KEY,
+        &GovernanceStorageKey::ProposalCounter,
+    );
+}
+
+/// Move up to `limit` delegations, configs and proposals (with their votes)
+/// out of the legacy single-map layout into per-entity entries
+///
+/// Proposals that cannot be decoded are left in the legacy map rather than
+/// dropped, so no data is lost.
+///
+/// # Returns
+/// The number of delegations, configs and proposals migrated by this call
+pub fn migrate_legacy(env: &Env, limit: u32) -> u32 {
+    let storage = env.storage().persistent();
+    let mut migrated = 0u32;
+
+    if let Some(mut delegations) =
+        storage.get::<_, Map<(u64, Address), Address>>(&LEGACY_DELEGATIONS_KEY)
+    {
+        for (guild_id, delegator) in delegations.keys().iter() {
+            if migrated >= limit {
+                break;
+            }
+            if let Some(delegate) = delegations.get((guild_id, delegator.clone())) {
+                set_delegation(env, guild_id, &delegator, &delegate);
+            }
+            delegations.remove((guild_id, delegator));
+            migrated += 1;
+        }
+        if delegations.is_empty() {
+            storage.remove(&LEGACY_DELEGATIONS_KEY);
+        } else {
+            ttl::set_persistent(env, &LEGACY_DELEGATIONS_KEY, &delegations);
+        }
+    }
+
+    if let Some(mut configs) = storage.get::<_, Map<u64, Val>>(&LEGACY_GOV_CONFIG_KEY) {
+        for guild_id in configs.keys().iter() {
+            if migrated >= limit {
+                break;
+            }
+            if let Some(config) = configs.get(guild_id) {
+                set_config(env, guild_id, &decode_config(env, config));
+            }
+            configs.remove(guild_id);
+            migrated += 1;
+        }
+        if configs.is_empty() {
+            storage.remove(&LEGACY_GOV_CONFIG_KEY);
+        } else {
+            ttl::set_persistent(env, &LEGACY_GOV_CONFIG_KEY, &configs);
+        }
+    }
+
+    let mut proposals: Map<u64, Val> = match storage.get(&LEGACY_PROPOSALS_KEY) {
+        Some(proposals) => proposals,
+        None => return migrated,
+    };
+    let mut votes: Map<u64, Map<Address, Vote>> = storage
+        .get(&LEGACY_VOTES_KEY)
         .unwrap_or_else(|| Map::new(env));
 
-    configs.set(guild_id, config.clone());
-    env.storage()
-        .persistent()
-        .set(&GOV_CONFIG_KEY, &configs);
+    // Proposal ids ascend in creation order, so the rebuilt guild indexes
+    // keep their original ordering.
+    for proposal_id in proposals.keys().iter() {
+        if migrated >= limit {
+            break;
+        }
+        let proposal = match proposals
+            .get(proposal_id)
+            .and_then(|raw| decode_proposal(env, raw))
+        {
+            Some(proposal) => proposal,
+            None => continue,
+        };
+
+        store_proposal(env, &proposal);
+        if let Some(proposal_votes) = votes.get(proposal_id) {
+            for (_, vote) in proposal_votes.iter() {
+                store_vote(env, proposal.guild_id, &vote);
+            }
+        }
+        proposals.remove(proposal_id);
+        votes.remove(proposal_id);
+        migrated += 1;
+    }
+
+    if proposals.is_empty() {
+        storage.remove(&LEGACY_PROPOSALS_KEY);
+        storage.remove(&LEGACY_VOTES_KEY);
+        storage.remove(&LEGACY_GUILD_PROPOSALS_KEY);
+    } else {
+        ttl::set_persistent(env, &LEGACY_PROPOSALS_KEY, &proposals);
+        ttl::set_persistent(env, &LEGACY_VOTES_KEY, &votes);
+    }
+
+    migrated
 }
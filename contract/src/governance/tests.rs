@@ -1,209 +1,1564 @@
-#[cfg(test)]
-mod tests {
-    use crate::governance::types::{ProposalStatus, ProposalType, VoteDecision};
-    use crate::guild::types::Role;
-    use crate::StellarGuildsContract;
-    use crate::StellarGuildsContractClient;
-    use soroban_sdk::testutils::{Address as _, Ledger, LedgerInfo};
-    use soroban_sdk::{Address, Env, String};
-
-    fn setup_env() -> Env {
-        let env = Env::default();
-        env.budget().reset_unlimited();
-        env
-    }
+use crate::bounty::types::AssignmentMode;
+use crate::governance::storage::{get_delegate, get_delegators, GovernanceStorageKey};
+use crate::governance::voting::TALLY_BATCH;
+use crate::governance::types::{
+    AddMemberData, DisputeResolutionData, ExecutionPayload, GovernanceError, ProposalStatus, ProposalType, RemoveMemberData,
+    ExecutionPayloadV1, GovernanceConfig, GovernanceConfigV1, ProposalV1, RuleChangeData,
+    TreasurySpendData, VoteDecision, VotingMode,
+};
+use crate::guild::types::Role;
+use crate::reputation;
+use crate::StellarGuildsContract;
+use crate::StellarGuildsContractClient;
+use soroban_sdk::testutils::{Address as _, Ledger, LedgerInfo};
+use soroban_sdk::{token, Address, Env, String};
 
-    fn set_ledger_timestamp(env: &Env, timestamp: u64) {
-        env.ledger().set(LedgerInfo {
-            timestamp,
-            protocol_version: 20,
-            sequence_number: 0,
-            network_id: Default::default(),
-            base_reserve: 10,
-            min_temp_entry_ttl: 100,
-            min_persistent_entry_ttl: 100,
-            max_entry_ttl: 1000000,
-        });
-    }
+fn setup_env() -> Env {
+    let env = Env::default();
+    env.budget().reset_unlimited();
+    env
+}
 
-    fn register_and_init_contract(env: &Env) -> Address {
-        let contract_id = env.register_contract(None, StellarGuildsContract);
-        let client = StellarGuildsContractClient::new(env, &contract_id);
-        client.initialize();
-        contract_id
-    }
+fn set_ledger_timestamp(env: &Env, timestamp: u64) {
+    env.ledger().set(LedgerInfo {
+        timestamp,
+        protocol_version: 20,
+        sequence_number: 0,
+        network_id: Default::default(),
+        base_reserve: 10,
+        min_temp_entry_ttl: 100,
+        min_persistent_entry_ttl: 100,
+        max_entry_ttl: 1000000,
+    });
+}
 
-    fn setup_guild(client: &StellarGuildsContractClient<'_>, env: &Env, owner: &Address) -> u64 {
-        let name = String::from_str(env, "Gov Guild");
-        let desc = String::from_str(env, "Governance test guild");
-        client.create_guild(&name, &desc, owner)
-    }
+fn register_and_init_contract(env: &Env) -> Address {
+    let contract_id = env.register_contract(None, StellarGuildsContract);
+    let client = StellarGuildsContractClient::new(env, &contract_id);
+    client.mock_all_auths().initialize(&Address::generate(env));
+    contract_id
+}
+
+fn setup_guild(client: &StellarGuildsContractClient<'_>, env: &Env, owner: &Address) -> u64 {
+    let name = String::from_str(env, "Gov Guild");
+    let desc = String::from_str(env, "Governance test guild");
+    client.create_guild(&name, &desc, owner)
+}
+
+fn setup_guild_with_members(
+    env: &Env,
+    client: &StellarGuildsContractClient<'_>,
+    owner: &Address,
+) -> (u64, Address, Address, Address) {
+    let admin = Address::generate(env);
+    let member = Address::generate(env);
+    let contributor = Address::generate(env);
 
-    fn setup_guild_with_members(
-        env: &Env,
-        client: &StellarGuildsContractClient<'_>,
-        owner: &Address,
-    ) -> (u64, Address, Address, Address) {
-        let admin = Address::generate(env);
-        let member = Address::generate(env);
-        let contributor = Address::generate(env);
+    let guild_id = setup_guild(client, env, owner);
 
-        let guild_id = setup_guild(client, env, owner);
+    // add roles
+    client.add_member(&guild_id, &admin, &Role::Admin, owner);
+    client.add_member(&guild_id, &member, &Role::Member, owner);
+    client.add_member(&guild_id, &contributor, &Role::Contributor, owner);
 
-        // add roles
-        client.add_member(&guild_id, &admin, &Role::Admin, owner);
-        client.add_member(&guild_id, &member, &Role::Member, owner);
-        client.add_member(&guild_id, &contributor, &Role::Contributor, owner);
+    (guild_id, admin, member, contributor)
+}
+
+/// Governance token that keeps a balance history, as token-weighted voting
+/// requires
+mod voting_token {
+    use soroban_sdk::{contract, contractimpl, Address, Env, Vec};
+
+    #[contract]
+    pub struct VotingToken;
 
-        (guild_id, admin, member, contributor)
+    #[contractimpl]
+    impl VotingToken {
+        /// Set the balance of `id` from the current ledger on
+        pub fn set_balance(env: Env, id: Address, amount: i128) {
+            let mut history: Vec<(u32, i128)> =
+                env.storage().persistent().get(&id).unwrap_or(Vec::new(&env));
+            history.push_back((env.ledger().sequence(), amount));
+            env.storage().persistent().set(&id, &history);
+        }
+
+        pub fn balance_at(env: Env, id: Address, ledger: u32) -> i128 {
+            let history: Vec<(u32, i128)> =
+                env.storage().persistent().get(&id).unwrap_or(Vec::new(&env));
+            let mut balance = 0;
+            for (at, amount) in history.iter() {
+                if at <= ledger {
+                    balance = amount;
+                }
+            }
+            balance
+        }
     }
+}
 
-    #[test]
-    fn test_create_proposal_basic() {
-        let env = setup_env();
-        let owner = Address::generate(&env);
+fn set_ledger_sequence(env: &Env, sequence: u32) {
+    env.ledger().with_mut(|ledger| ledger.sequence_number = sequence);
+}
 
-        set_ledger_timestamp(&env, 1000);
-        env.mock_all_auths();
+#[test]
+fn test_create_proposal_basic() {
+    let env = setup_env();
+    let owner = Address::generate(&env);
 
-        let contract_id = register_and_init_contract(&env);
-        let client = StellarGuildsContractClient::new(&env, &contract_id);
+    set_ledger_timestamp(&env, 1000);
+    env.mock_all_auths();
 
-        let (guild_id, _admin, _member, _contributor) =
-            setup_guild_with_members(&env, &client, &owner);
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
 
-        // owner creates proposal
-        let proposal_id = client.create_proposal(
-            &guild_id,
-            &owner,
-            &ProposalType::GeneralDecision,
-            &String::from_str(&env, "Test Proposal"),
-            &String::from_str(&env, "Description"),
-        );
+    let (guild_id, _admin, _member, _contributor) =
+        setup_guild_with_members(&env, &client, &owner);
 
-        assert_eq!(proposal_id, 1);
+    // owner creates proposal
+    let proposal_id = client.create_proposal(
+        &guild_id,
+        &owner,
+        &ProposalType::GeneralDecision,
+        &String::from_str(&env, "Test Proposal"),
+        &String::from_str(&env, "Description"),
+        &ExecutionPayload::GeneralDecision,
+    );
 
-        let proposal = client.get_proposal(&proposal_id);
-        assert_eq!(proposal.guild_id, guild_id);
-        assert_eq!(proposal.proposer, owner);
-        assert_eq!(proposal.status, ProposalStatus::Active);
-    }
+    assert_eq!(proposal_id, 1);
+
+    let proposal = client.get_proposal(&proposal_id);
+    assert_eq!(proposal.guild_id, guild_id);
+    assert_eq!(proposal.proposer, owner);
+    assert_eq!(proposal.status, ProposalStatus::Active);
+}
+
+#[test]
+fn test_vote_and_weights() {
+    let env = setup_env();
+    let owner = Address::generate(&env);
+
+    set_ledger_timestamp(&env, 1000);
+    env.mock_all_auths();
+
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+    let (guild_id, admin, member, contributor) =
+        setup_guild_with_members(&env, &client, &owner);
+
+    // owner creates proposal
+    let proposal_id = client.create_proposal(
+        &guild_id,
+        &owner,
+        &ProposalType::GeneralDecision,
+        &String::from_str(&env, "Test Proposal"),
+        &String::from_str(&env, "Description"),
+        &ExecutionPayload::GeneralDecision,
+    );
+
+    // voting: owner FOR, admin FOR, member AGAINST, contributor ABSTAIN
+    client.vote(&proposal_id, &owner, &VoteDecision::For);
+    client.vote(&proposal_id, &admin, &VoteDecision::For);
+    client.vote(&proposal_id, &member, &VoteDecision::Against);
+    client.vote(&proposal_id, &contributor, &VoteDecision::Abstain);
+
+    // fast-forward time to after voting_end
+    let proposal = client.get_proposal(&proposal_id);
+    let end = proposal.voting_end;
+    set_ledger_timestamp(&env, end + 1);
+
+    let status = client.finalize_proposal(&proposal_id);
+    assert_eq!(status, ProposalStatus::Passed);
+
+    let proposal = client.get_proposal(&proposal_id);
+    // weights: owner 10 + admin 5 for FOR = 15; member AGAINST 2; contributor ABSTAIN 1
+    assert_eq!(proposal.votes_for, 15);
+    assert_eq!(proposal.votes_against, 2);
+    assert_eq!(proposal.votes_abstain, 1);
+}
+
+#[test]
+fn test_vote_delegation() {
+    let env = setup_env();
+    let owner = Address::generate(&env);
+
+    set_ledger_timestamp(&env, 1000);
+    env.mock_all_auths();
+
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+    let (guild_id, admin, member, contributor) =
+        setup_guild_with_members(&env, &client, &owner);
+
+    // member delegates to admin, contributor delegates to member; the
+    // delegation graph is snapshotted when the proposal opens
+    client.delegate_vote(&guild_id, &member, &admin);
+    client.delegate_vote(&guild_id, &contributor, &member);
+
+    let proposal_id = client.create_proposal(
+        &guild_id,
+        &owner,
+        &ProposalType::GeneralDecision,
+        &String::from_str(&env, "Delegation Proposal"),
+        &String::from_str(&env, "Delegation"),
+        &ExecutionPayload::GeneralDecision,
+    );
+
+    // only admin votes FOR
+    client.vote(&proposal_id, &admin, &VoteDecision::For);
+
+    let proposal = client.get_proposal(&proposal_id);
+    let end = proposal.voting_end;
+    set_ledger_timestamp(&env, end + 1);
+
+    let status = client.finalize_proposal(&proposal_id);
+    assert_eq!(status, ProposalStatus::Passed);
+
+    let proposal = client.get_proposal(&proposal_id);
+    // admin FOR (weight 5) + member delegated (2) + contributor delegated (1) = 8
+    assert_eq!(proposal.votes_for, 8);
+}
+
+#[test]
+fn test_quorum_rejection() {
+    let env = setup_env();
+    let owner = Address::generate(&env);
+
+    set_ledger_timestamp(&env, 1000);
+    env.mock_all_auths();
+
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+    let (guild_id, _admin, _member, contributor) =
+        setup_guild_with_members(&env, &client, &owner);
+
+    // only contributor (weight 1 of total 18) votes, below quorum 30%
+    let proposal_id = client.create_proposal(
+        &guild_id,
+        &owner,
+        &ProposalType::GeneralDecision,
+        &String::from_str(&env, "Low Quorum"),
+        &String::from_str(&env, "Low quorum"),
+        &ExecutionPayload::GeneralDecision,
+    );
+
+    client.vote(&proposal_id, &contributor, &VoteDecision::For);
+
+    let proposal = client.get_proposal(&proposal_id);
+    let end = proposal.voting_end;
+    set_ledger_timestamp(&env, end + 1);
+
+    let status = client.finalize_proposal(&proposal_id);
+    assert_eq!(status, ProposalStatus::Rejected);
+}
+
+// ============ Payload Execution Tests ============
+
+fn create_and_pass(
+    env: &Env,
+    client: &StellarGuildsContractClient<'_>,
+    guild_id: u64,
+    owner: &Address,
+    admin: &Address,
+    proposal_type: ProposalType,
+    payload: ExecutionPayload,
+) -> u64 {
+    let proposal_id = client.create_proposal(
+        &guild_id,
+        owner,
+        &proposal_type,
+        &String::from_str(env, "Executable"),
+        &String::from_str(env, "Runs on execution"),
+        &payload,
+    );
+
+    client.vote(&proposal_id, owner, &VoteDecision::For);
+    client.vote(&proposal_id, admin, &VoteDecision::For);
+
+    let proposal = client.get_proposal(&proposal_id);
+    set_ledger_timestamp(env, proposal.voting_end + 1);
+
+    proposal_id
+}
+
+#[test]
+fn test_execute_add_member_proposal() {
+    let env = setup_env();
+    let owner = Address::generate(&env);
+
+    set_ledger_timestamp(&env, 1000);
+    env.mock_all_auths();
+
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+    let (guild_id, admin, _member, _contributor) =
+        setup_guild_with_members(&env, &client, &owner);
+
+    let newcomer = Address::generate(&env);
+    let payload = ExecutionPayload::AddMember(AddMemberData {
+        address: newcomer.clone(),
+        role: Role::Member,
+    });
+    let proposal_id = create_and_pass(
+        &env,
+        &client,
+        guild_id,
+        &owner,
+        &admin,
+        ProposalType::AddMember,
+        payload.clone(),
+    );
+
+    // the payload is persisted with the proposal
+    assert_eq!(client.get_proposal(&proposal_id).execution_payload, payload);
+
+    assert!(client.execute_proposal(&proposal_id));
+    assert!(client.is_member(&guild_id, &newcomer));
+    assert_eq!(client.get_member(&guild_id, &newcomer).role, Role::Member);
+    assert_eq!(
+        client.get_proposal(&proposal_id).status,
+        ProposalStatus::Executed
+    );
+}
+
+#[test]
+fn test_add_member_proposal_settles_join_request() {
+    let env = setup_env();
+    let owner = Address::generate(&env);
+
+    set_ledger_timestamp(&env, 1000);
+    env.mock_all_auths();
+
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+    let (guild_id, admin, _member, _contributor) =
+        setup_guild_with_members(&env, &client, &owner);
+
+    let applicant = Address::generate(&env);
+    client.request_to_join(&guild_id, &applicant, &String::from_str(&env, "Let me in"));
+    assert_eq!(client.get_join_requests(&guild_id).len(), 1);
+
+    let proposal_id = create_and_pass(
+        &env,
+        &client,
+        guild_id,
+        &owner,
+        &admin,
+        ProposalType::AddMember,
+        ExecutionPayload::AddMember(AddMemberData {
+            address: applicant.clone(),
+            role: Role::Contributor,
+        }),
+    );
+
+    assert!(client.execute_proposal(&proposal_id));
+    assert_eq!(client.get_member(&guild_id, &applicant).role, Role::Contributor);
+    assert_eq!(client.get_join_requests(&guild_id).len(), 0);
+}
+
+#[test]
+fn test_execute_remove_member_proposal() {
+    let env = setup_env();
+    let owner = Address::generate(&env);
+
+    set_ledger_timestamp(&env, 1000);
+    env.mock_all_auths();
+
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+    let (guild_id, admin, member, _contributor) =
+        setup_guild_with_members(&env, &client, &owner);
+
+    let proposal_id = create_and_pass(
+        &env,
+        &client,
+        guild_id,
+        &owner,
+        &admin,
+        ProposalType::RemoveMember,
+        ExecutionPayload::RemoveMember(RemoveMemberData {
+            address: member.clone(),
+        }),
+    );
+
+    assert!(client.execute_proposal(&proposal_id));
+    assert!(!client.is_member(&guild_id, &member));
+}
+
+#[test]
+fn test_execute_treasury_spend_proposal() {
+    let env = setup_env();
+    let owner = Address::generate(&env);
+
+    set_ledger_timestamp(&env, 1000);
+    env.mock_all_auths();
+
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+    let (guild_id, admin, _member, _contributor) =
+        setup_guild_with_members(&env, &client, &owner);
+
+    let mut signers = soroban_sdk::Vec::new(&env);
+    signers.push_back(owner.clone());
+    signers.push_back(admin.clone());
+    let treasury_id = client.initialize_treasury(&guild_id, &signers, &2u32);
+    client.deposit_treasury(&treasury_id, &owner, &500i128, &None);
+
+    let recipient = Address::generate(&env);
+    let proposal_id = create_and_pass(
+        &env,
+        &client,
+        guild_id,
+        &owner,
+        &admin,
+        ProposalType::TreasurySpend,
+        ExecutionPayload::TreasurySpend(TreasurySpendData {
+            treasury_id,
+            amount: 200,
+            token: None,
+            recipient,
+            reason: String::from_str(&env, "grant"),
+        }),
+    );
+
+    assert!(client.execute_proposal(&proposal_id));
+    assert_eq!(client.get_treasury_balance(&treasury_id, &None), 300);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #315)")] // InsufficientBalance
+fn test_execute_treasury_spend_insufficient_balance_fails() {
+    let env = setup_env();
+    let owner = Address::generate(&env);
+
+    set_ledger_timestamp(&env, 1000);
+    env.mock_all_auths();
+
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+    let (guild_id, admin, _member, _contributor) =
+        setup_guild_with_members(&env, &client, &owner);
+
+    let mut signers = soroban_sdk::Vec::new(&env);
+    signers.push_back(owner.clone());
+    let treasury_id = client.initialize_treasury(&guild_id, &signers, &1u32);
+
+    let proposal_id = create_and_pass(
+        &env,
+        &client,
+        guild_id,
+        &owner,
+        &admin,
+        ProposalType::TreasurySpend,
+        ExecutionPayload::TreasurySpend(TreasurySpendData {
+            treasury_id,
+            amount: 200,
+            token: None,
+            recipient: Address::generate(&env),
+            reason: String::from_str(&env, "grant"),
+        }),
+    );
+
+    client.execute_proposal(&proposal_id);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #421)")] // TreasuryNotInGuild
+fn test_treasury_spend_from_other_guild_rejected() {
+    let env = setup_env();
+    let owner = Address::generate(&env);
+
+    set_ledger_timestamp(&env, 1000);
+    env.mock_all_auths();
+
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+    let guild_id = setup_guild(&client, &env, &owner);
+    let other_guild_id = setup_guild(&client, &env, &owner);
+
+    let mut signers = soroban_sdk::Vec::new(&env);
+    signers.push_back(owner.clone());
+    let treasury_id = client.initialize_treasury(&other_guild_id, &signers, &1u32);
+
+    client.create_proposal(
+        &guild_id,
+        &owner,
+        &ProposalType::TreasurySpend,
+        &String::from_str(&env, "Spend"),
+        &String::from_str(&env, "Not our treasury"),
+        &ExecutionPayload::TreasurySpend(TreasurySpendData {
+            treasury_id,
+            amount: 10,
+            token: None,
+            recipient: owner.clone(),
+            reason: String::from_str(&env, "grant"),
+        }),
+    );
+}
+
+#[test]
+fn test_execute_rule_change_proposal() {
+    let env = setup_env();
+    let owner = Address::generate(&env);
+
+    set_ledger_timestamp(&env, 1000);
+    env.mock_all_auths();
+
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+    let (guild_id, admin, _member, _contributor) =
+        setup_guild_with_members(&env, &client, &owner);
+
+    let proposal_id = create_and_pass(
+        &env,
+        &client,
+        guild_id,
+        &owner,
+        &admin,
+        ProposalType::RuleChange,
+        ExecutionPayload::RuleChange(RuleChangeData {
+            key: String::from_str(&env, "quorum_percentage"),
+            value: String::from_str(&env, "45"),
+        }),
+    );
+
+    assert!(client.execute_proposal(&proposal_id));
+    assert_eq!(client.get_governance_config(&guild_id).quorum_percentage, 45);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #418)")] // UnknownRule
+fn test_rule_change_unknown_key_rejected() {
+    let env = setup_env();
+    let owner = Address::generate(&env);
+
+    set_ledger_timestamp(&env, 1000);
+    env.mock_all_auths();
+
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+    let guild_id = setup_guild(&client, &env, &owner);
+
+    client.create_proposal(
+        &guild_id,
+        &owner,
+        &ProposalType::RuleChange,
+        &String::from_str(&env, "Rule"),
+        &String::from_str(&env, "Unknown rule"),
+        &ExecutionPayload::RuleChange(RuleChangeData {
+            key: String::from_str(&env, "no_such_rule"),
+            value: String::from_str(&env, "1"),
+        }),
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #419)")] // InvalidRuleValue
+fn test_rule_change_non_numeric_value_rejected() {
+    let env = setup_env();
+    let owner = Address::generate(&env);
+
+    set_ledger_timestamp(&env, 1000);
+    env.mock_all_auths();
+
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+    let guild_id = setup_guild(&client, &env, &owner);
+
+    client.create_proposal(
+        &guild_id,
+        &owner,
+        &ProposalType::RuleChange,
+        &String::from_str(&env, "Rule"),
+        &String::from_str(&env, "Bad value"),
+        &ExecutionPayload::RuleChange(RuleChangeData {
+            key: String::from_str(&env, "voting_period_days"),
+            value: String::from_str(&env, "ten"),
+        }),
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #406)")] // PayloadMismatch
+fn test_payload_mismatch_rejected() {
+    let env = setup_env();
+    let owner = Address::generate(&env);
+
+    set_ledger_timestamp(&env, 1000);
+    env.mock_all_auths();
+
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+    let guild_id = setup_guild(&client, &env, &owner);
+
+    client.create_proposal(
+        &guild_id,
+        &owner,
+        &ProposalType::AddMember,
+        &String::from_str(&env, "Mismatch"),
+        &String::from_str(&env, "Wrong payload"),
+        &ExecutionPayload::GeneralDecision,
+    );
+}
+
+#[test]
+fn test_rule_change_proposal_updates_guild_rules() {
+    let env = setup_env();
+    let owner = Address::generate(&env);
+
+    set_ledger_timestamp(&env, 1000);
+    env.mock_all_auths();
+
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+    let (guild_id, admin, _member, _contributor) =
+        setup_guild_with_members(&env, &client, &owner);
+
+    let max_members = create_and_pass(
+        &env,
+        &client,
+        guild_id,
+        &owner,
+        &admin,
+        ProposalType::RuleChange,
+        ExecutionPayload::RuleChange(RuleChangeData {
+            key: String::from_str(&env, "max_members"),
+            value: String::from_str(&env, "25"),
+        }),
+    );
+    assert!(client.execute_proposal(&max_members));
+
+    let creator_role = create_and_pass(
+        &env,
+        &client,
+        guild_id,
+        &owner,
+        &admin,
+        ProposalType::RuleChange,
+        ExecutionPayload::RuleChange(RuleChangeData {
+            key: String::from_str(&env, "bounty_creator_role"),
+            value: String::from_str(&env, "member"),
+        }),
+    );
+    assert!(client.execute_proposal(&creator_role));
+
+    let rules = client.get_guild_rules(&guild_id);
+    assert_eq!(rules.max_members, 25);
+    assert_eq!(rules.bounty_creator_role, Role::Member);
+}
+
+// ============ Voting Mode Tests ============
+
+#[test]
+fn test_one_member_one_vote_mode() {
+    let env = setup_env();
+    let owner = Address::generate(&env);
+
+    set_ledger_timestamp(&env, 1000);
+    env.mock_all_auths();
+
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+    let (guild_id, admin, member, contributor) =
+        setup_guild_with_members(&env, &client, &owner);
+
+    let mut config = client.get_governance_config(&guild_id);
+    config.voting_mode = VotingMode::OneMemberOneVote;
+    client.update_governance_config(&guild_id, &owner, &config);
+
+    let proposal_id = client.create_proposal(
+        &guild_id,
+        &owner,
+        &ProposalType::GeneralDecision,
+        &String::from_str(&env, "Equal votes"),
+        &String::from_str(&env, "One member, one vote"),
+        &ExecutionPayload::GeneralDecision,
+    );
+
+    // owner alone would win under role weighting (10 vs 8)
+    client.vote(&proposal_id, &owner, &VoteDecision::For);
+    client.vote(&proposal_id, &admin, &VoteDecision::Against);
+    client.vote(&proposal_id, &member, &VoteDecision::Against);
+    client.vote(&proposal_id, &contributor, &VoteDecision::Against);
+
+    let proposal = client.get_proposal(&proposal_id);
+    set_ledger_timestamp(&env, proposal.voting_end + 1);
+
+    assert_eq!(client.finalize_proposal(&proposal_id), ProposalStatus::Rejected);
+
+    let proposal = client.get_proposal(&proposal_id);
+    assert_eq!(proposal.votes_for, 1);
+    assert_eq!(proposal.votes_against, 3);
+}
+
+#[test]
+fn test_token_weighted_mode() {
+    let env = setup_env();
+    let owner = Address::generate(&env);
+
+    set_ledger_timestamp(&env, 1000);
+    env.mock_all_auths();
+
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+    let (guild_id, admin, member, _contributor) =
+        setup_guild_with_members(&env, &client, &owner);
+
+    let gov_token = env.register_contract(None, voting_token::VotingToken);
+    let token_client = voting_token::VotingTokenClient::new(&env, &gov_token);
+    set_ledger_sequence(&env, 10);
+    token_client.set_balance(&owner, &100);
+    token_client.set_balance(&admin, &100);
+    token_client.set_balance(&member, &800);
+
+    let mut config = client.get_governance_config(&guild_id);
+    config.voting_mode = VotingMode::TokenWeighted(gov_token);
+    client.update_governance_config(&guild_id, &owner, &config);
+
+    set_ledger_sequence(&env, 11);
+    let proposal_id = client.create_proposal(
+        &guild_id,
+        &owner,
+        &ProposalType::GeneralDecision,
+        &String::from_str(&env, "Token vote"),
+        &String::from_str(&env, "Weighted by balance"),
+        &ExecutionPayload::GeneralDecision,
+    );
+
+    client.vote(&proposal_id, &owner, &VoteDecision::For);
+    client.vote(&proposal_id, &admin, &VoteDecision::For);
+    client.vote(&proposal_id, &member, &VoteDecision::Against);
+
+    let proposal = client.get_proposal(&proposal_id);
+    set_ledger_timestamp(&env, proposal.voting_end + 1);
+
+    assert_eq!(client.finalize_proposal(&proposal_id), ProposalStatus::Rejected);
+
+    let proposal = client.get_proposal(&proposal_id);
+    assert_eq!(proposal.votes_for, 200);
+    assert_eq!(proposal.votes_against, 800);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #425)")] // UnsupportedVotingToken
+fn test_token_weighted_mode_refuses_token_without_history() {
+    let env = setup_env();
+    let owner = Address::generate(&env);
+
+    set_ledger_timestamp(&env, 1000);
+    env.mock_all_auths();
+
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+    let guild_id = setup_guild(&client, &env, &owner);
+
+    // a Stellar asset contract only knows current balances
+    let gov_token = env.register_stellar_asset_contract_v2(owner.clone()).address();
+
+    let mut config = client.get_governance_config(&guild_id);
+    config.voting_mode = VotingMode::TokenWeighted(gov_token);
+    client.update_governance_config(&guild_id, &owner, &config);
+}
+
+#[test]
+fn test_voting_mode_rule_change() {
+    let env = setup_env();
+    let owner = Address::generate(&env);
+
+    set_ledger_timestamp(&env, 1000);
+    env.mock_all_auths();
 
-    #[test]
-    fn test_vote_and_weights() {
-        let env = setup_env();
-        let owner = Address::generate(&env);
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
 
-        set_ledger_timestamp(&env, 1000);
-        env.mock_all_auths();
+    let (guild_id, admin, _member, _contributor) =
+        setup_guild_with_members(&env, &client, &owner);
 
-        let contract_id = register_and_init_contract(&env);
-        let client = StellarGuildsContractClient::new(&env, &contract_id);
+    let proposal_id = create_and_pass(
+        &env,
+        &client,
+        guild_id,
+        &owner,
+        &admin,
+        ProposalType::RuleChange,
+        ExecutionPayload::RuleChange(RuleChangeData {
+            key: String::from_str(&env, "voting_mode"),
+            value: String::from_str(&env, "one_member_one_vote"),
+        }),
+    );
 
-        let (guild_id, admin, member, contributor) =
-            setup_guild_with_members(&env, &client, &owner);
+    assert!(client.execute_proposal(&proposal_id));
+    assert_eq!(
+        client.get_governance_config(&guild_id).voting_mode,
+        VotingMode::OneMemberOneVote
+    );
+}
+
+#[test]
+fn test_config_written_before_voting_modes_is_readable() {
+    let env = setup_env();
+    let owner = Address::generate(&env);
+
+    set_ledger_timestamp(&env, 1000);
+    env.mock_all_auths();
+
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+    let guild_id = setup_guild(&client, &env, &owner);
 
-        // owner creates proposal
-        let proposal_id = client.create_proposal(
-            &guild_id,
-            &owner,
-            &ProposalType::GeneralDecision,
-            &String::from_str(&env, "Test Proposal"),
-            &String::from_str(&env, "Description"),
+    env.as_contract(&contract_id, || {
+        env.storage().persistent().set(
+            &GovernanceStorageKey::Config(guild_id),
+            &GovernanceConfigV1 {
+                quorum_percentage: 50,
+                approval_threshold: 70,
+                voting_period_days: 3,
+                min_proposer_reputation: 0,
+            },
         );
+    });
 
-        // voting: owner FOR, admin FOR, member AGAINST, contributor ABSTAIN
-        client.vote(&proposal_id, &owner, &VoteDecision::For);
-        client.vote(&proposal_id, &admin, &VoteDecision::For);
-        client.vote(&proposal_id, &member, &VoteDecision::Against);
-        client.vote(&proposal_id, &contributor, &VoteDecision::Abstain);
-
-        // fast-forward time to after voting_end
-        let proposal = client.get_proposal(&proposal_id);
-        let end = proposal.voting_end;
-        set_ledger_timestamp(&env, end + 1);
-
-        let status = client.finalize_proposal(&proposal_id);
-        assert_eq!(status, ProposalStatus::Passed);
-
-        let proposal = client.get_proposal(&proposal_id);
-        // weights: owner 10 + admin 5 for FOR = 15; member AGAINST 2; contributor ABSTAIN 1
-        assert_eq!(proposal.votes_for, 15);
-        assert_eq!(proposal.votes_against, 2);
-        assert_eq!(proposal.votes_abstain, 1);
-    }
+    let config = client.get_governance_config(&guild_id);
+    assert_eq!(
+        config,
+        GovernanceConfig {
+            quorum_percentage: 50,
+            approval_threshold: 70,
+            voting_period_days: 3,
+            min_proposer_reputation: 0,
+            voting_mode: VotingMode::RoleWeighted,
+        }
+    );
+}
+
+#[test]
+fn test_migrate_proposals_written_before_payload_data() {
+    use soroban_sdk::{symbol_short, Map};
 
-    #[test]
-    fn test_vote_delegation() {
-        let env = setup_env();
-        let owner = Address::generate(&env);
+    let env = setup_env();
+    let owner = Address::generate(&env);
 
-        set_ledger_timestamp(&env, 1000);
-        env.mock_all_auths();
+    set_ledger_timestamp(&env, 1000);
+    env.mock_all_auths();
 
-        let contract_id = register_and_init_contract(&env);
-        let client = StellarGuildsContractClient::new(&env, &contract_id);
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
 
-        let (guild_id, admin, member, contributor) =
-            setup_guild_with_members(&env, &client, &owner);
+    let guild_id = setup_guild(&client, &env, &owner);
 
-        let proposal_id = client.create_proposal(
-            &guild_id,
-            &owner,
-            &ProposalType::GeneralDecision,
-            &String::from_str(&env, "Delegation Proposal"),
-            &String::from_str(&env, "Delegation"),
+    // Lay out proposals the way earlier releases stored them
+    env.as_contract(&contract_id, || {
+        let legacy = |id: u64, proposal_type, execution_payload| ProposalV1 {
+            id,
+            guild_id,
+            proposer: owner.clone(),
+            proposal_type,
+            title: String::from_str(&env, "Legacy"),
+            description: String::from_str(&env, "Stored before payload data"),
+            voting_start: 0,
+            voting_end: 100,
+            status: ProposalStatus::Passed,
+            votes_for: 10,
+            votes_against: 0,
+            votes_abstain: 0,
+            execution_payload,
+            passed_at: Some(100),
+            executed_at: None,
+        };
+        let proposals = Map::from_array(
+            &env,
+            [
+                (
+                    1u64,
+                    legacy(1, ProposalType::AddMember, ExecutionPayloadV1::AddMember),
+                ),
+                (
+                    2u64,
+                    legacy(
+                        2,
+                        ProposalType::GeneralDecision,
+                        ExecutionPayloadV1::GeneralDecision,
+                    ),
+                ),
+            ],
         );
+        let storage = env.storage().persistent();
+        storage.set(&symbol_short!("g_props"), &proposals);
+        storage.set(&symbol_short!("g_pcnt"), &2u64);
+    });
 
-        // member delegates to admin, contributor delegates to member
-        client.delegate_vote(&guild_id, &member, &admin);
-        client.delegate_vote(&guild_id, &contributor, &member);
+    assert_eq!(client.migrate_storage(&10), 2);
+    assert_eq!(client.migrate_storage(&10), 0);
 
-        // only admin votes FOR
-        client.vote(&proposal_id, &admin, &VoteDecision::For);
+    let action = client.get_proposal(&1);
+    assert_eq!(action.proposal_type, ProposalType::AddMember);
+    assert_eq!(action.execution_payload, ExecutionPayload::GeneralDecision);
+    assert_eq!(action.status, ProposalStatus::Passed);
+    assert_eq!(action.votes_for, 10);
 
-        let proposal = client.get_proposal(&proposal_id);
-        let end = proposal.voting_end;
-        set_ledger_timestamp(&env, end + 1);
+    let decision = client.get_proposal(&2);
+    assert_eq!(decision.execution_payload, ExecutionPayload::GeneralDecision);
+    assert_eq!(client.get_proposals_page(&guild_id, &0).len(), 2);
 
-        let status = client.finalize_proposal(&proposal_id);
-        assert_eq!(status, ProposalStatus::Passed);
+    // The legacy action proposal has no data to act on
+    set_ledger_timestamp(&env, 200);
+    assert!(!client.execute_proposal(&1));
+    assert!(client.execute_proposal(&2));
+}
 
-        let proposal = client.get_proposal(&proposal_id);
-        // admin FOR (weight 5) + member delegated (2) + contributor delegated (1) = 8
-        assert_eq!(proposal.votes_for, 8);
-    }
+#[test]
+fn test_migrate_governance_maps_in_batches() {
+    use soroban_sdk::{symbol_short, IntoVal, Map, Val};
 
-    #[test]
-    fn test_quorum_rejection() {
-        let env = setup_env();
-        let owner = Address::generate(&env);
+    let env = setup_env();
+    let owner = Address::generate(&env);
+    let delegator = Address::generate(&env);
+    let delegate = Address::generate(&env);
 
-        set_ledger_timestamp(&env, 1000);
-        env.mock_all_auths();
+    set_ledger_timestamp(&env, 1000);
+    env.mock_all_auths();
 
-        let contract_id = register_and_init_contract(&env);
-        let client = StellarGuildsContractClient::new(&env, &contract_id);
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
 
-        let (guild_id, _admin, _member, contributor) =
-            setup_guild_with_members(&env, &client, &owner);
+    let guild_id = setup_guild(&client, &env, &owner);
 
-        // only contributor (weight 1 of total 18) votes, below quorum 30%
-        let proposal_id = client.create_proposal(
-            &guild_id,
-            &owner,
-            &ProposalType::GeneralDecision,
-            &String::from_str(&env, "Low Quorum"),
-            &String::from_str(&env, "Low quorum"),
+    // Lay out delegations, configs and proposals the way earlier releases
+    // stored them, with one proposal no layout can decode
+    env.as_contract(&contract_id, || {
+        let delegations = Map::from_array(
+            &env,
+            [
+                ((guild_id, delegator.clone()), delegate.clone()),
+                ((guild_id, owner.clone()), delegate.clone()),
+            ],
         );
+        let config = GovernanceConfigV1 {
+            quorum_percentage: 50,
+            approval_threshold: 70,
+            voting_period_days: 3,
+            min_proposer_reputation: 0,
+        };
+        let configs: Map<u64, Val> = Map::from_array(&env, [(guild_id, config.into_val(&env))]);
+        let proposal = ProposalV1 {
+            id: 1,
+            guild_id,
+            proposer: owner.clone(),
+            proposal_type: ProposalType::GeneralDecision,
+            title: String::from_str(&env, "Legacy"),
+            description: String::from_str(&env, "Stored before payload data"),
+            voting_start: 0,
+            voting_end: 100,
+            status: ProposalStatus::Passed,
+            votes_for: 10,
+            votes_against: 0,
+            votes_abstain: 0,
+            execution_payload: ExecutionPayloadV1::GeneralDecision,
+            passed_at: Some(100),
+            executed_at: None,
+        };
+        let proposals: Map<u64, Val> = Map::from_array(
+            &env,
+            [
+                (1u64, proposal.into_val(&env)),
+                (2u64, String::from_str(&env, "garbage").into_val(&env)),
+            ],
+        );
+
+        let storage = env.storage().persistent();
+        storage.set(&symbol_short!("g_deleg"), &delegations);
+        storage.set(&symbol_short!("g_conf"), &configs);
+        storage.set(&symbol_short!("g_props"), &proposals);
+    });
+
+    // Delegations and configs count against the limit like proposals do
+    assert_eq!(client.migrate_storage(&2), 2);
+    assert_eq!(client.migrate_storage(&2), 2);
+    assert_eq!(client.migrate_storage(&2), 0);
+
+    assert_eq!(client.get_governance_config(&guild_id).quorum_percentage, 50);
+    assert_eq!(client.get_proposal(&1).votes_for, 10);
+    env.as_contract(&contract_id, || {
+        assert_eq!(get_delegate(&env, guild_id, &delegator), Some(delegate.clone()));
+        assert_eq!(get_delegate(&env, guild_id, &owner), Some(delegate.clone()));
+
+        // The undecodable proposal stays where it was instead of being lost
+        let storage = env.storage().persistent();
+        assert!(!storage.has(&symbol_short!("g_deleg")));
+        assert!(!storage.has(&symbol_short!("g_conf")));
+        let remaining: Map<u64, Val> = storage.get(&symbol_short!("g_props")).unwrap();
+        assert_eq!(remaining.len(), 1);
+        assert!(remaining.contains_key(2));
+    });
+}
+
+// ============ Vote Snapshot Tests ============
+
+#[test]
+fn test_role_change_during_voting_does_not_change_weight() {
+    let env = setup_env();
+    let owner = Address::generate(&env);
+
+    set_ledger_timestamp(&env, 1000);
+    env.mock_all_auths();
+
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+    let (guild_id, admin, member, contributor) =
+        setup_guild_with_members(&env, &client, &owner);
+
+    let proposal_id = client.create_proposal(
+        &guild_id,
+        &owner,
+        &ProposalType::GeneralDecision,
+        &String::from_str(&env, "Snapshot"),
+        &String::from_str(&env, "Weights are frozen"),
+        &ExecutionPayload::GeneralDecision,
+    );
+    assert_eq!(client.get_vote_weight(&proposal_id, &contributor), 1);
+
+    // promoting the contributor after voting opened has no effect
+    client.update_role(&guild_id, &contributor, &Role::Owner, &owner);
+    assert_eq!(client.get_vote_weight(&proposal_id, &contributor), 1);
+
+    client.vote(&proposal_id, &owner, &VoteDecision::For);
+    client.vote(&proposal_id, &admin, &VoteDecision::Against);
+    client.vote(&proposal_id, &member, &VoteDecision::Against);
+    client.vote(&proposal_id, &contributor, &VoteDecision::Against);
+
+    let proposal = client.get_proposal(&proposal_id);
+    set_ledger_timestamp(&env, proposal.voting_end + 1);
+    client.finalize_proposal(&proposal_id);
+
+    let proposal = client.get_proposal(&proposal_id);
+    assert_eq!(proposal.votes_for, 10);
+    assert_eq!(proposal.votes_against, 8);
+}
+
+#[test]
+fn test_removed_member_keeps_snapshotted_vote() {
+    let env = setup_env();
+    let owner = Address::generate(&env);
+
+    set_ledger_timestamp(&env, 1000);
+    env.mock_all_auths();
+
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+    let (guild_id, admin, _member, _contributor) =
+        setup_guild_with_members(&env, &client, &owner);
+
+    let proposal_id = client.create_proposal(
+        &guild_id,
+        &owner,
+        &ProposalType::GeneralDecision,
+        &String::from_str(&env, "Snapshot"),
+        &String::from_str(&env, "Roster is frozen"),
+        &ExecutionPayload::GeneralDecision,
+    );
+
+    client.vote(&proposal_id, &admin, &VoteDecision::For);
+    client.remove_member(&guild_id, &admin, &owner);
+
+    let proposal = client.get_proposal(&proposal_id);
+    set_ledger_timestamp(&env, proposal.voting_end + 1);
+    client.finalize_proposal(&proposal_id);
+
+    assert_eq!(client.get_proposal(&proposal_id).votes_for, 5);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #422)")] // NotEligibleVoter
+fn test_member_added_after_activation_cannot_vote() {
+    let env = setup_env();
+    let owner = Address::generate(&env);
+
+    set_ledger_timestamp(&env, 1000);
+    env.mock_all_auths();
+
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+    let guild_id = setup_guild(&client, &env, &owner);
+
+    let proposal_id = client.create_proposal(
+        &guild_id,
+        &owner,
+        &ProposalType::GeneralDecision,
+        &String::from_str(&env, "Snapshot"),
+        &String::from_str(&env, "Roster is frozen"),
+        &ExecutionPayload::GeneralDecision,
+    );
+
+    let latecomer = Address::generate(&env);
+    client.add_member(&guild_id, &latecomer, &Role::Member, &owner);
+
+    client.vote(&proposal_id, &latecomer, &VoteDecision::For);
+}
+
+#[test]
+fn test_delegation_after_activation_is_ignored() {
+    let env = setup_env();
+    let owner = Address::generate(&env);
+
+    set_ledger_timestamp(&env, 1000);
+    env.mock_all_auths();
+
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+    let (guild_id, admin, member, _contributor) =
+        setup_guild_with_members(&env, &client, &owner);
+
+    let proposal_id = client.create_proposal(
+        &guild_id,
+        &owner,
+        &ProposalType::GeneralDecision,
+        &String::from_str(&env, "Snapshot"),
+        &String::from_str(&env, "Delegations are frozen"),
+        &ExecutionPayload::GeneralDecision,
+    );
+
+    client.delegate_vote(&guild_id, &member, &admin);
+    client.vote(&proposal_id, &admin, &VoteDecision::For);
+
+    let proposal = client.get_proposal(&proposal_id);
+    set_ledger_timestamp(&env, proposal.voting_end + 1);
+    client.finalize_proposal(&proposal_id);
+
+    // only the admin's own weight counts
+    assert_eq!(client.get_proposal(&proposal_id).votes_for, 5);
+}
+
+#[test]
+fn test_reputation_weight_read_when_voting_opened() {
+    let env = setup_env();
+    let owner = Address::generate(&env);
+
+    set_ledger_timestamp(&env, 1000);
+    env.mock_all_auths();
+
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+    let (guild_id, admin, member, _contributor) =
+        setup_guild_with_members(&env, &client, &owner);
+
+    let mut config = client.get_governance_config(&guild_id);
+    config.voting_mode = VotingMode::ReputationWeighted;
+    client.update_governance_config(&guild_id, &owner, &config);
+
+    env.as_contract(&contract_id, || {
+        reputation::record_bounty_completed(&env, guild_id, &admin);
+    });
+
+    let proposal_id = client.create_proposal(
+        &guild_id,
+        &owner,
+        &ProposalType::GeneralDecision,
+        &String::from_str(&env, "Reputation vote"),
+        &String::from_str(&env, "Weighted by reputation"),
+        &ExecutionPayload::GeneralDecision,
+    );
+
+    // points earned or lost once voting is open do not move weights
+    env.as_contract(&contract_id, || {
+        reputation::record_bounty_expired(&env, guild_id, &admin);
+        reputation::record_bounty_completed(&env, guild_id, &member);
+        reputation::record_bounty_completed(&env, guild_id, &member);
+    });
+    assert_eq!(client.get_reputation(&guild_id, &admin), 0);
+    assert_eq!(client.get_vote_weight(&proposal_id, &admin), 10);
+    assert_eq!(client.get_vote_weight(&proposal_id, &member), 0);
+
+    client.vote(&proposal_id, &admin, &VoteDecision::For);
+    client.vote(&proposal_id, &member, &VoteDecision::Against);
+
+    let proposal = client.get_proposal(&proposal_id);
+    set_ledger_timestamp(&env, proposal.voting_end + 1);
+    assert_eq!(client.finalize_proposal(&proposal_id), ProposalStatus::Passed);
+
+    let proposal = client.get_proposal(&proposal_id);
+    assert_eq!(proposal.votes_for, 10);
+    assert_eq!(proposal.votes_against, 0);
+}
+
+#[test]
+fn test_undelegation_after_activation_is_ignored() {
+    let env = setup_env();
+    let owner = Address::generate(&env);
+
+    set_ledger_timestamp(&env, 1000);
+    env.mock_all_auths();
+
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+    let (guild_id, admin, member, _contributor) =
+        setup_guild_with_members(&env, &client, &owner);
 
-        client.vote(&proposal_id, &contributor, &VoteDecision::For);
+    client.delegate_vote(&guild_id, &member, &admin);
 
-        let proposal = client.get_proposal(&proposal_id);
-        let end = proposal.voting_end;
-        set_ledger_timestamp(&env, end + 1);
+    let proposal_id = client.create_proposal(
+        &guild_id,
+        &owner,
+        &ProposalType::GeneralDecision,
+        &String::from_str(&env, "Snapshot"),
+        &String::from_str(&env, "Delegations are frozen"),
+        &ExecutionPayload::GeneralDecision,
+    );
 
-        let status = client.finalize_proposal(&proposal_id);
-        assert_eq!(status, ProposalStatus::Rejected);
+    client.undelegate_vote(&guild_id, &member);
+    client.vote(&proposal_id, &admin, &VoteDecision::For);
+
+    let proposal = client.get_proposal(&proposal_id);
+    set_ledger_timestamp(&env, proposal.voting_end + 1);
+    client.finalize_proposal(&proposal_id);
+
+    // the member's weight still follows the admin
+    assert_eq!(client.get_proposal(&proposal_id).votes_for, 7);
+}
+
+#[test]
+fn test_member_removed_after_activation_can_still_vote() {
+    let env = setup_env();
+    let owner = Address::generate(&env);
+
+    set_ledger_timestamp(&env, 1000);
+    env.mock_all_auths();
+
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+    let (guild_id, _admin, member, _contributor) =
+        setup_guild_with_members(&env, &client, &owner);
+
+    let proposal_id = client.create_proposal(
+        &guild_id,
+        &owner,
+        &ProposalType::GeneralDecision,
+        &String::from_str(&env, "Snapshot"),
+        &String::from_str(&env, "Roster is frozen"),
+        &ExecutionPayload::GeneralDecision,
+    );
+
+    client.remove_member(&guild_id, &member, &owner);
+    client.vote(&proposal_id, &member, &VoteDecision::Against);
+
+    let proposal = client.get_proposal(&proposal_id);
+    set_ledger_timestamp(&env, proposal.voting_end + 1);
+    client.finalize_proposal(&proposal_id);
+
+    assert_eq!(client.get_proposal(&proposal_id).votes_against, 2);
+}
+
+#[test]
+fn test_finalize_counts_large_roster_in_batches() {
+    let env = setup_env();
+    let owner = Address::generate(&env);
+
+    set_ledger_timestamp(&env, 1000);
+    env.mock_all_auths();
+
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+    let (guild_id, _admin, member, _contributor) =
+        setup_guild_with_members(&env, &client, &owner);
+    let mut last = owner.clone();
+    for _ in 0..TALLY_BATCH {
+        last = Address::generate(&env);
+        client.add_member(&guild_id, &last, &Role::Contributor, &owner);
     }
+
+    let proposal_id = client.create_proposal(
+        &guild_id,
+        &owner,
+        &ProposalType::GeneralDecision,
+        &String::from_str(&env, "Large roster"),
+        &String::from_str(&env, "Counted over several calls"),
+        &ExecutionPayload::GeneralDecision,
+    );
+    client.vote(&proposal_id, &owner, &VoteDecision::For);
+    client.vote(&proposal_id, &last, &VoteDecision::Against);
+
+    let proposal = client.get_proposal(&proposal_id);
+    set_ledger_timestamp(&env, proposal.voting_end);
+    assert_eq!(
+        client.try_execute_proposal(&proposal_id).err(),
+        Some(Ok(GovernanceError::TallyInProgress))
+    );
+    assert_eq!(client.finalize_proposal(&proposal_id), ProposalStatus::Active);
+    // counting has started, so voting is over
+    assert_eq!(
+        client.try_vote(&proposal_id, &member, &VoteDecision::For).err(),
+        Some(Ok(GovernanceError::VotingClosed))
+    );
+
+    // moves the last member into a position that was already counted
+    client.remove_member(&guild_id, &member, &owner);
+    client.add_member(&guild_id, &Address::generate(&env), &Role::Owner, &owner);
+
+    // owner 10 + admin 5 + member 2 + contributors 51: quorum not reached
+    assert_eq!(client.finalize_proposal(&proposal_id), ProposalStatus::Rejected);
+    let proposal = client.get_proposal(&proposal_id);
+    assert_eq!(proposal.votes_for, 10);
+    assert_eq!(proposal.votes_against, 1);
+}
+
+#[test]
+fn test_tokens_moved_after_activation_are_not_counted_twice() {
+    let env = setup_env();
+    let owner = Address::generate(&env);
+
+    set_ledger_timestamp(&env, 1000);
+    env.mock_all_auths();
+
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+    let (guild_id, admin, member, _contributor) =
+        setup_guild_with_members(&env, &client, &owner);
+
+    let gov_token = env.register_contract(None, voting_token::VotingToken);
+    let token_client = voting_token::VotingTokenClient::new(&env, &gov_token);
+    set_ledger_sequence(&env, 10);
+    token_client.set_balance(&admin, &100);
+
+    let mut config = client.get_governance_config(&guild_id);
+    config.voting_mode = VotingMode::TokenWeighted(gov_token.clone());
+    client.update_governance_config(&guild_id, &owner, &config);
+
+    set_ledger_sequence(&env, 11);
+    let proposal_id = client.create_proposal(
+        &guild_id,
+        &owner,
+        &ProposalType::GeneralDecision,
+        &String::from_str(&env, "Token vote"),
+        &String::from_str(&env, "Weighted by balance"),
+        &ExecutionPayload::GeneralDecision,
+    );
+
+    // the admin votes, then hands their tokens to the member, who votes too
+    client.vote(&proposal_id, &admin, &VoteDecision::For);
+    set_ledger_sequence(&env, 12);
+    token_client.set_balance(&admin, &0);
+    token_client.set_balance(&member, &100);
+    client.vote(&proposal_id, &member, &VoteDecision::For);
+
+    assert_eq!(client.get_vote_weight(&proposal_id, &admin), 100);
+    assert_eq!(client.get_vote_weight(&proposal_id, &member), 0);
+
+    let proposal = client.get_proposal(&proposal_id);
+    set_ledger_timestamp(&env, proposal.voting_end + 1);
+    client.finalize_proposal(&proposal_id);
+
+    assert_eq!(client.get_proposal(&proposal_id).votes_for, 100);
+}
+
+#[test]
+fn test_config_change_during_voting_does_not_apply() {
+    let env = setup_env();
+    let owner = Address::generate(&env);
+
+    set_ledger_timestamp(&env, 1000);
+    env.mock_all_auths();
+
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+    let (guild_id, _admin, _member, _contributor) =
+        setup_guild_with_members(&env, &client, &owner);
+
+    let proposal_id = client.create_proposal(
+        &guild_id,
+        &owner,
+        &ProposalType::GeneralDecision,
+        &String::from_str(&env, "Snapshot"),
+        &String::from_str(&env, "Config is frozen"),
+        &ExecutionPayload::GeneralDecision,
+    );
+
+    let mut config = client.get_governance_config(&guild_id);
+    config.voting_mode = VotingMode::OneMemberOneVote;
+    client.update_governance_config(&guild_id, &owner, &config);
+
+    client.vote(&proposal_id, &owner, &VoteDecision::For);
+
+    let proposal = client.get_proposal(&proposal_id);
+    set_ledger_timestamp(&env, proposal.voting_end + 1);
+    client.finalize_proposal(&proposal_id);
+
+    // the owner's role weight counts, not a single vote
+    assert_eq!(client.get_proposal(&proposal_id).votes_for, 10);
+}
+
+#[test]
+fn test_execute_dispute_resolution_proposal() {
+    let env = setup_env();
+    let owner = Address::generate(&env);
+
+    set_ledger_timestamp(&env, 1000);
+    env.mock_all_auths();
+
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+    let (guild_id, admin, _member, contributor) =
+        setup_guild_with_members(&env, &client, &owner);
+
+    let token = env.register_stellar_asset_contract_v2(owner.clone()).address();
+    token::StellarAssetClient::new(&env, &token).mint(&owner, &100);
+
+    let bounty_id = client.create_bounty(
+        &guild_id,
+        &owner,
+        &String::from_str(&env, "Task"),
+        &String::from_str(&env, "Description"),
+        &100i128,
+        &token,
+        &0u64,
+        &AssignmentMode::FirstCome,
+    );
+    client.fund_bounty(&bounty_id, &owner, &100i128);
+    client.claim_bounty(&bounty_id, &contributor);
+    client.submit_work(&bounty_id, &String::from_str(&env, "https://github.com/pr/1"));
+    client.open_bounty_dispute(&bounty_id, &contributor, &String::from_str(&env, "Ignored"));
+
+    let proposal_id = create_and_pass(
+        &env,
+        &client,
+        guild_id,
+        &owner,
+        &admin,
+        ProposalType::DisputeResolution,
+        ExecutionPayload::DisputeResolution(DisputeResolutionData {
+            bounty_id,
+            claimer_percent: 75,
+        }),
+    );
+
+    assert!(client.execute_proposal(&proposal_id));
+
+    let balances = token::TokenClient::new(&env, &token);
+    assert_eq!(balances.balance(&contributor), 75);
+    assert_eq!(balances.balance(&owner), 25);
+    assert_eq!(client.get_bounty_dispute(&bounty_id).claimer_percent, Some(75));
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #423)")] // InvalidDisputeResolution
+fn test_dispute_resolution_for_undisputed_bounty_rejected() {
+    let env = setup_env();
+    let owner = Address::generate(&env);
+
+    set_ledger_timestamp(&env, 1000);
+    env.mock_all_auths();
+
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+    let guild_id = setup_guild(&client, &env, &owner);
+
+    client.create_proposal(
+        &guild_id,
+        &owner,
+        &ProposalType::DisputeResolution,
+        &String::from_str(&env, "Settle"),
+        &String::from_str(&env, "No such dispute"),
+        &ExecutionPayload::DisputeResolution(DisputeResolutionData {
+            bounty_id: 1,
+            claimer_percent: 50,
+        }),
+    );
+}
+
+// ============ Leave & Ban Tests ============
+
+#[test]
+fn test_leaving_delegate_clears_delegation() {
+    let env = setup_env();
+    let owner = Address::generate(&env);
+
+    set_ledger_timestamp(&env, 1000);
+    env.mock_all_auths();
+
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+    let (guild_id, admin, member, _contributor) =
+        setup_guild_with_members(&env, &client, &owner);
+
+    client.delegate_vote(&guild_id, &member, &admin);
+    client.leave_guild(&guild_id, &admin);
+
+    env.as_contract(&contract_id, || {
+        assert!(get_delegate(&env, guild_id, &member).is_none());
+    });
+}
+
+#[test]
+fn test_leaving_delegate_keeps_redirected_delegations() {
+    let env = setup_env();
+    let owner = Address::generate(&env);
+
+    set_ledger_timestamp(&env, 1000);
+    env.mock_all_auths();
+
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+    let (guild_id, admin, member, contributor) =
+        setup_guild_with_members(&env, &client, &owner);
+
+    client.delegate_vote(&guild_id, &member, &admin);
+    client.delegate_vote(&guild_id, &contributor, &admin);
+    // the contributor moves their vote elsewhere before the admin leaves
+    client.delegate_vote(&guild_id, &contributor, &owner);
+    client.leave_guild(&guild_id, &admin);
+
+    env.as_contract(&contract_id, || {
+        assert!(get_delegate(&env, guild_id, &member).is_none());
+        assert_eq!(get_delegate(&env, guild_id, &contributor), Some(owner.clone()));
+        assert!(get_delegators(&env, guild_id, &admin).is_empty());
+        assert_eq!(get_delegators(&env, guild_id, &owner).len(), 1);
+    });
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #424)")] // AddressBanned
+fn test_cannot_delegate_to_banned_address() {
+    let env = setup_env();
+    let owner = Address::generate(&env);
+
+    set_ledger_timestamp(&env, 1000);
+    env.mock_all_auths();
+
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+    let (guild_id, _admin, member, contributor) =
+        setup_guild_with_members(&env, &client, &owner);
+
+    client.ban_member(&guild_id, &contributor, &owner);
+    client.delegate_vote(&guild_id, &member, &contributor);
 }
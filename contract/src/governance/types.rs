@@ -1,7 +1,44 @@
-use soroban_sdk::{contracttype, Address, String, Vec};
+use soroban_sdk::{contracterror, contracttype, Address, String};
 
 use crate::guild::types::Role;
 
+/// Error types for governance operations
+///
+/// Codes live in the 400-499 range so they never collide with the errors of
+/// other modules when surfaced to clients.
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum GovernanceError {
+    ProposalNotFound = 400,
+    GuildNotFound = 401,
+    NotGuildMember = 402,
+    InsufficientReputation = 403,
+    InvalidTitle = 404,
+    DescriptionTooLong = 405,
+    PayloadMismatch = 406,
+    ProposalNotCancellable = 407,
+    Unauthorized = 408,
+    InvalidQuorum = 409,
+    InvalidApprovalThreshold = 410,
+    ProposalNotActive = 411,
+    VotingClosed = 412,
+    SelfDelegation = 413,
+    DelegationCycle = 414,
+    VotingNotFinished = 415,
+    ProposalNotPassed = 416,
+    ExecutionWindowExpired = 417,
+    UnknownRule = 418,
+    InvalidRuleValue = 419,
+    InvalidSpendAmount = 420,
+    TreasuryNotInGuild = 421,
+    NotEligibleVoter = 422,
+    InvalidDisputeResolution = 423,
+    AddressBanned = 424,
+    UnsupportedVotingToken = 425,
+    TallyInProgress = 426,
+}
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum ProposalType {
@@ -10,6 +47,7 @@ pub enum ProposalType {
     RemoveMember,
     RuleChange,
     GeneralDecision,
+    DisputeResolution,
 }
 
 #[contracttype]
@@ -32,6 +70,22 @@ pub enum VoteDecision {
     Abstain,
 }
 
+/// How much a member's vote counts
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum VotingMode {
+    /// Weight by guild role (see `role_weight`)
+    RoleWeighted,
+    /// Every member's vote counts once
+    OneMemberOneVote,
+    /// Weight by balance of the given governance token as of the ledger
+    /// before voting opened; the token must report historical balances (see
+    /// `VotingTokenInterface`)
+    TokenWeighted(Address),
+    /// Weight by the member's reputation in the guild
+    ReputationWeighted,
+}
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct GovernanceConfig {
@@ -39,6 +93,7 @@ pub struct GovernanceConfig {
     pub approval_threshold: u32,
     pub voting_period_days: u32,
     pub min_proposer_reputation: u32,
+    pub voting_mode: VotingMode,
 }
 
 impl GovernanceConfig {
@@ -48,28 +103,54 @@ impl GovernanceConfig {
             approval_threshold: 60,
             voting_period_days: 7,
             min_proposer_reputation: 0,
+            voting_mode: VotingMode::RoleWeighted,
+        }
+    }
+}
+
+/// Governance config layout written before voting modes existed
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GovernanceConfigV1 {
+    pub quorum_percentage: u32,
+    pub approval_threshold: u32,
+    pub voting_period_days: u32,
+    pub min_proposer_reputation: u32,
+}
+
+impl From<GovernanceConfigV1> for GovernanceConfig {
+    fn from(config: GovernanceConfigV1) -> Self {
+        Self {
+            quorum_percentage: config.quorum_percentage,
+            approval_threshold: config.approval_threshold,
+            voting_period_days: config.voting_period_days,
+            min_proposer_reputation: config.min_proposer_reputation,
+            voting_mode: VotingMode::RoleWeighted,
         }
     }
 }
 
-/// Simple execution payload for Soroban compatibility.
-/// Complex payloads are stored as serialized strings or handled externally.
+/// Action carried out when a proposal is executed.
+/// The payload is persisted with the proposal, so voters approve the exact
+/// action that will run.
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum ExecutionPayload {
-    /// Treasury spend: (treasury_id, amount, recipient_str, reason)
-    TreasurySpend,
+    /// Move funds out of a treasury linked to the guild
+    TreasurySpend(TreasurySpendData),
     /// Add member to guild
-    AddMember,
+    AddMember(AddMemberData),
     /// Remove member from guild
-    RemoveMember,
+    RemoveMember(RemoveMemberData),
     /// Change a guild rule
-    RuleChange,
+    RuleChange(RuleChangeData),
     /// General decision (signalling only)
     GeneralDecision,
+    /// Settle a bounty dispute
+    DisputeResolution(DisputeResolutionData),
 }
 
-/// Detailed payload data stored separately for complex operations
+/// Payload data for each executable proposal type
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct TreasurySpendData {
@@ -100,6 +181,14 @@ pub struct RuleChangeData {
     pub value: String,
 }
 
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DisputeResolutionData {
+    pub bounty_id: u64,
+    /// Share of the escrow awarded to the claimer, in percent
+    pub claimer_percent: u32,
+}
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct GeneralDecisionData {
@@ -126,6 +215,64 @@ pub struct Proposal {
     pub executed_at: Option<u64>,
 }
 
+/// Execution payload layout written before payloads carried their data
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ExecutionPayloadV1 {
+    TreasurySpend,
+    AddMember,
+    RemoveMember,
+    RuleChange,
+    GeneralDecision,
+}
+
+/// Proposal layout written before payloads carried their data
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProposalV1 {
+    pub id: u64,
+    pub guild_id: u64,
+    pub proposer: Address,
+    pub proposal_type: ProposalType,
+    pub title: String,
+    pub description: String,
+    pub voting_start: u64,
+    pub voting_end: u64,
+    pub status: ProposalStatus,
+    pub votes_for: i128,
+    pub votes_against: i128,
+    pub votes_abstain: i128,
+    pub execution_payload: ExecutionPayloadV1,
+    pub passed_at: Option<u64>,
+    pub executed_at: Option<u64>,
+}
+
+impl From<ProposalV1> for Proposal {
+    /// Legacy payloads only signalled the action and carry nothing to act
+    /// on, so they become `GeneralDecision`. The proposal keeps its type, so
+    /// executing a migrated action proposal reports failure instead of
+    /// running with made-up data.
+    fn from(proposal: ProposalV1) -> Self {
+        Self {
+            id: proposal.id,
+            guild_id: proposal.guild_id,
+            proposer: proposal.proposer,
+            proposal_type: proposal.proposal_type,
+            title: proposal.title,
+            description: proposal.description,
+            voting_start: proposal.voting_start,
+            voting_end: proposal.voting_end,
+            status: proposal.status,
+            votes_for: proposal.votes_for,
+            votes_against: proposal.votes_against,
+            votes_abstain: proposal.votes_abstain,
+            execution_payload: ExecutionPayload::GeneralDecision,
+            passed_at: proposal.passed_at,
+            executed_at: proposal.executed_at,
+        }
+    }
+}
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct Vote {
@@ -136,6 +283,56 @@ pub struct Vote {
     pub timestamp: u64,
 }
 
+/// What a proposal records when it becomes Active; voters are resolved
+/// against it lazily
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProposalSnapshot {
+    /// Guild snapshot epoch the roster and delegations are read at
+    pub epoch: u32,
+    /// Ledger whose closing balances weigh token votes
+    pub ledger: u32,
+    /// Governance config in force when voting opened
+    pub config: GovernanceConfig,
+}
+
+/// Votes counted so far for a proposal whose voting has closed
+///
+/// Large rosters are counted over several calls to `finalize_proposal`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProposalTally {
+    /// Position in the proposal's roster of the next member to count
+    pub cursor: u32,
+    /// Weight of every member counted so far, whether they voted or not
+    pub total_weight: i128,
+    pub votes_for: i128,
+    pub votes_against: i128,
+    pub votes_abstain: i128,
+}
+
+/// A member's voting position on a proposal, as of when voting opened
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VoterSnapshot {
+    pub voter: Address,
+    /// Weight of the member's own vote under the proposal's voting mode
+    pub weight: i128,
+    /// Member whose vote counts for this one, after following delegations
+    pub representative: Address,
+}
+
+/// A delegation as it stood before the first change made to it in a guild
+/// snapshot epoch
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DelegationCheckpoint {
+    /// Epoch in which the delegation changed
+    pub epoch: u32,
+    /// Delegate before the change, `None` if there was no delegation
+    pub delegate: Option<Address>,
+}
+
 // Events
 
 #[contracttype]
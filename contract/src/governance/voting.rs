@@ -1,21 +1,33 @@
-use soroban_sdk::{Address, Env, String, Symbol};
-
-use crate::governance::storage::{get_all_votes, get_config, get_delegate, get_proposal as load_proposal, get_vote, set_delegation, store_proposal, store_vote, remove_delegation};
-use crate::governance::types::{Proposal, ProposalFinalizedEvent, ProposalStatus, Vote, VoteCastEvent, VoteDecision};
+use soroban_sdk::{contractclient, Address, Env, Symbol};
+
+use crate::governance::storage::{
+    get_config, get_delegate, get_delegate_at, get_delegators, get_proposal as load_proposal,
+    get_proposal_snapshot, get_tally, get_vote, remove_delegation, remove_tally, set_delegation,
+    set_proposal_snapshot, set_tally, store_proposal, store_vote,
+};
+use crate::governance::types::{
+    role_weight, GovernanceConfig, GovernanceError, Proposal, ProposalFinalizedEvent,
+    ProposalSnapshot, ProposalStatus, ProposalTally, Vote, VoteCastEvent, VoteDecision,
+    VoterSnapshot, VotingMode,
+};
 use crate::guild::storage as guild_storage;
-use crate::guild::types::Role;
-use crate::governance::types::role_weight;
+use crate::guild::types::Member;
+use crate::reputation;
 
 const EVENT_TOPIC_VOTE_CAST: &str = "vote_cast";
 const EVENT_TOPIC_VOTE_DELEGATED: &str = "vote_delegated";
 const EVENT_TOPIC_VOTE_UNDELEGATED: &str = "vote_undelegated";
 const EVENT_TOPIC_PROPOSAL_FINALIZED: &str = "proposal_finalized";
 
-fn resolve_delegate(env: &Env, guild_id: u64, addr: &Address) -> Address {
+/// Members counted per call to `finalize_proposal`
+pub(crate) const TALLY_BATCH: u32 = 50;
+
+/// Follow a member's delegations as they stood at a guild snapshot epoch
+fn resolve_delegate_at(env: &Env, guild_id: u64, addr: &Address, epoch: u32) -> Address {
     let mut current = addr.clone();
     // prevent infinite loops in case of unexpected cycles
     for _ in 0..16 {
-        if let Some(next) = get_delegate(env, guild_id, &current) {
+        if let Some(next) = get_delegate_at(env, guild_id, &current, epoch) {
             if next == current {
                 break;
             }
@@ -27,60 +39,153 @@ fn resolve_delegate(env: &Env, guild_id: u64, addr: &Address) -> Address {
     current
 }
 
-fn compute_total_weight_and_tallies(env: &Env, proposal: &Proposal) -> (i128, i128, i128, i128) {
-    // returns (total_votes_weight, for_weight, against_weight, abstain_weight)
-    let votes_map = get_all_votes(env, proposal.id);
-    let cfg = get_config(env, proposal.guild_id);
+/// Interface a governance token must implement for token-weighted voting
+///
+/// Votes are weighed by balances at a past ledger, so tokens moved once
+/// voting opens cannot be voted a second time. Stellar asset contracts keep
+/// no balance history and cannot be used.
+#[contractclient(name = "VotingTokenClient")]
+#[allow(dead_code)] // only the generated client is called
+pub trait VotingTokenInterface {
+    /// Balance held by `id` at the close of `ledger`
+    fn balance_at(env: Env, id: Address, ledger: u32) -> i128;
+}
 
-    let members = guild_storage::get_all_members(env, proposal.guild_id);
+/// Refuse token-weighted voting with a token that cannot report historical
+/// balances
+pub(crate) fn ensure_supported_mode(env: &Env, mode: &VotingMode) -> Result<(), GovernanceError> {
+    if let VotingMode::TokenWeighted(token) = mode {
+        let probe = VotingTokenClient::new(env, token)
+            .try_balance_at(&env.current_contract_address(), &env.ledger().sequence());
+        if !matches!(probe, Ok(Ok(_))) {
+            return Err(GovernanceError::UnsupportedVotingToken);
+        }
+    }
+    Ok(())
+}
 
-    let mut total_votes_weight: i128 = 0;
-    let mut for_weight: i128 = 0;
-    let mut against_weight: i128 = 0;
-    let mut abstain_weight: i128 = 0;
+/// Reputation of a member within their guild, from the reputation ledger
+pub(crate) fn member_reputation(env: &Env, guild_id: u64, member: &Member) -> i128 {
+    reputation::get_reputation(env, guild_id, &member.address)
+}
 
-    for member in members.iter() {
-        let rep = resolve_delegate(env, proposal.guild_id, &member.address);
-        let weight = role_weight(&member.role);
+/// Weight of a member's vote on a proposal, as of when voting opened
+///
+/// `member` is the membership at the proposal's epoch. Token weights are
+/// read at the ledger recorded when voting opened and reputation weights at
+/// the proposal's epoch, decayed up to the start of voting.
+fn snapshot_weight(
+    env: &Env,
+    proposal: &Proposal,
+    snapshot: &ProposalSnapshot,
+    member: &Member,
+) -> i128 {
+    match &snapshot.config.voting_mode {
+        VotingMode::TokenWeighted(token) => VotingTokenClient::new(env, token)
+            .balance_at(&member.address, &snapshot.ledger)
+            .max(0),
+        VotingMode::ReputationWeighted => reputation::get_reputation_at(
+            env,
+            proposal.guild_id,
+            &member.address,
+            snapshot.epoch,
+            proposal.voting_start,
+        )
+        .max(0),
+        VotingMode::RoleWeighted => role_weight(&member.role),
+        VotingMode::OneMemberOneVote => 1,
+    }
+}
 
-        let decision_opt = if rep == member.address {
-            votes_map.get(member.address.clone()).map(|v| v.decision)
-        } else {
-            votes_map.get(rep).map(|v| v.decision)
-        };
+/// Record what a proposal's votes are resolved against
+///
+/// Called when a proposal becomes Active. Only the guild's snapshot epoch and
+/// the governance config are stored; later role changes, membership changes
+/// and delegations are checkpointed against the epoch, so they do not affect
+/// the proposal's outcome.
+pub(crate) fn take_snapshot(
+    env: &Env,
+    proposal: &Proposal,
+    cfg: &GovernanceConfig,
+) -> ProposalSnapshot {
+    let snapshot = ProposalSnapshot {
+        epoch: guild_storage::open_snapshot_epoch(env, proposal.guild_id),
+        // the last ledger closed before voting opened, so balances cannot be
+        // moved into place within the opening ledger
+        ledger: env.ledger().sequence().saturating_sub(1),
+        config: cfg.clone(),
+    };
+    set_proposal_snapshot(env, proposal.id, &snapshot);
+    snapshot
+}
 
-        if let Some(decision) = decision_opt {
-            total_votes_weight += weight;
-            match decision {
-                VoteDecision::For => for_weight += weight,
-                VoteDecision::Against => against_weight += weight,
-                VoteDecision::Abstain => abstain_weight += weight,
-            }
-        }
-    }
+/// What a proposal's votes are resolved against
+///
+/// Proposals created before snapshots were taken record one on first use,
+/// against the guild's roster and config at that point.
+fn proposal_snapshot(env: &Env, proposal: &Proposal) -> ProposalSnapshot {
+    get_proposal_snapshot(env, proposal.id)
+        .unwrap_or_else(|| take_snapshot(env, proposal, &get_config(env, proposal.guild_id)))
+}
 
-    (total_votes_weight, for_weight, against_weight, abstain_weight)
+/// A member's voting position on a proposal
+///
+/// The position is resolved from the roster, delegations and balances as
+/// they stood when voting opened, so it is the same whenever it is read.
+/// `None` if the address was not a member when voting opened.
+fn resolve_voter(
+    env: &Env,
+    proposal: &Proposal,
+    snapshot: &ProposalSnapshot,
+    address: &Address,
+) -> Option<VoterSnapshot> {
+    let guild_id = proposal.guild_id;
+    let member = guild_storage::get_member_at(env, guild_id, address, snapshot.epoch)?;
+    Some(VoterSnapshot {
+        voter: address.clone(),
+        weight: snapshot_weight(env, proposal, snapshot, &member),
+        representative: resolve_delegate_at(env, guild_id, address, snapshot.epoch),
+    })
 }
 
-pub fn vote(env: &Env, proposal_id: u64, voter: Address, decision: VoteDecision) -> bool {
+/// Weight a member holds in a proposal's snapshot
+pub fn get_vote_weight(env: &Env, proposal_id: u64, voter: Address) -> Result<i128, GovernanceError> {
+    let proposal = load_proposal(env, proposal_id).ok_or(GovernanceError::ProposalNotFound)?;
+    get_proposal_snapshot(env, proposal_id)
+        .and_then(|snapshot| resolve_voter(env, &proposal, &snapshot, &voter))
+        .map(|snapshot| snapshot.weight)
+        .ok_or(GovernanceError::NotEligibleVoter)
+}
+
+pub fn vote(
+    env: &Env,
+    proposal_id: u64,
+    voter: Address,
+    decision: VoteDecision,
+) -> Result<bool, GovernanceError> {
     voter.require_auth();
 
-    let mut proposal = load_proposal(env, proposal_id).unwrap_or_else(|| panic!("proposal not found"));
+    let proposal = load_proposal(env, proposal_id).ok_or(GovernanceError::ProposalNotFound)?;
 
     if !matches!(proposal.status, ProposalStatus::Active) {
-        panic!("proposal not active");
+        return Err(GovernanceError::ProposalNotActive);
     }
 
     let now = env.ledger().timestamp();
     if now < proposal.voting_start || now > proposal.voting_end {
-        panic!("voting period closed");
+        return Err(GovernanceError::VotingClosed);
     }
 
-    // must be guild member
-    let member = guild_storage::get_member(env, proposal.guild_id, &voter)
-        .unwrap_or_else(|| panic!("voter must be guild member"));
+    // counting has started, so a late vote could land behind the cursor
+    if get_tally(env, proposal_id).is_some() {
+        return Err(GovernanceError::VotingClosed);
+    }
 
-    let weight = role_weight(&member.role);
+    // must have been a guild member when voting opened
+    let snapshot = proposal_snapshot(env, &proposal);
+    let weight = resolve_voter(env, &proposal, &snapshot, &voter)
+        .ok_or(GovernanceError::NotEligibleVoter)?
+        .weight;
 
     let vote = Vote {
         voter: voter.clone(),
@@ -90,7 +195,7 @@ pub fn vote(env: &Env, proposal_id: u64, voter: Address, decision: VoteDecision)
         timestamp: now,
     };
 
-    store_vote(env, &vote);
+    store_vote(env, proposal.guild_id, &vote);
 
     let event = VoteCastEvent {
         proposal_id,
@@ -102,27 +207,37 @@ pub fn vote(env: &Env, proposal_id: u64, voter: Address, decision: VoteDecision)
         event,
     );
 
-    true
+    Ok(true)
 }
 
-pub fn delegate_vote(env: &Env, guild_id: u64, delegator: Address, delegate: Address) -> bool {
+pub fn delegate_vote(
+    env: &Env,
+    guild_id: u64,
+    delegator: Address,
+    delegate: Address,
+) -> Result<bool, GovernanceError> {
     delegator.require_auth();
 
     if delegator == delegate {
-        panic!("cannot delegate to self");
+        return Err(GovernanceError::SelfDelegation);
+    }
+
+    // banned addresses can neither hand out nor receive voting power
+    if guild_storage::is_banned(env, guild_id, &delegator)
+        || guild_storage::is_banned(env, guild_id, &delegate)
+    {
+        return Err(GovernanceError::AddressBanned);
     }
 
     // both must be guild members
-    let _d1 = guild_storage::get_member(env, guild_id, &delegator)
-        .unwrap_or_else(|| panic!("delegator must be guild member"));
-    let _d2 = guild_storage::get_member(env, guild_id, &delegate)
-        .unwrap_or_else(|| panic!("delegate must be guild member"));
+    guild_storage::get_member(env, guild_id, &delegator).ok_or(GovernanceError::NotGuildMember)?;
+    guild_storage::get_member(env, guild_id, &delegate).ok_or(GovernanceError::NotGuildMember)?;
 
     // check for cycles: walk starting from delegate
     let mut current = delegate.clone();
     for _ in 0..16 {
         if current == delegator {
-            panic!("delegation cycle detected");
+            return Err(GovernanceError::DelegationCycle);
         }
         if let Some(next) = get_delegate(env, guild_id, &current) {
             if next == current {
@@ -146,10 +261,14 @@ pub fn delegate_vote(env: &Env, guild_id: u64, delegator: Address, delegate: Add
         event,
     );
 
-    true
+    Ok(true)
 }
 
-pub fn undelegate_vote(env: &Env, guild_id: u64, delegator: Address) -> bool {
+pub fn undelegate_vote(
+    env: &Env,
+    guild_id: u64,
+    delegator: Address,
+) -> Result<bool, GovernanceError> {
     delegator.require_auth();
 
     remove_delegation(env, guild_id, &delegator);
@@ -160,46 +279,102 @@ pub fn undelegate_vote(env: &Env, guild_id: u64, delegator: Address) -> bool {
         event,
     );
 
-    true
+    Ok(true)
 }
 
-pub fn finalize_proposal(env: &Env, proposal_id: u64) -> ProposalStatus {
-    let mut proposal = load_proposal(env, proposal_id).unwrap_or_else(|| panic!("proposal not found"));
+/// Drop every delegation made by or to an address leaving a guild
+///
+/// Members who had delegated to the address vote for themselves again.
+pub(crate) fn clear_member_delegations(env: &Env, guild_id: u64, address: &Address) {
+    let mut delegators = get_delegators(env, guild_id, address);
+    if get_delegate(env, guild_id, address).is_some() {
+        delegators.push_back(address.clone());
+    }
+
+    for delegator in delegators.iter() {
+        remove_delegation(env, guild_id, &delegator);
+
+        let event = crate::governance::types::VoteUndelegatedEvent { guild_id, delegator };
+        env.events().publish(
+            (Symbol::new(env, EVENT_TOPIC_VOTE_UNDELEGATED), Symbol::new(env, "v0")),
+            event,
+        );
+    }
+}
+
+/// Count a proposal's votes once voting has closed
+///
+/// Members are counted from the roster as it stood when voting opened,
+/// `TALLY_BATCH` of them per call; progress is kept between calls.
+///
+/// # Returns
+/// `Active` while members remain to be counted, the final status otherwise
+pub fn finalize_proposal(env: &Env, proposal_id: u64) -> Result<ProposalStatus, GovernanceError> {
+    let mut proposal = load_proposal(env, proposal_id).ok_or(GovernanceError::ProposalNotFound)?;
 
     if !matches!(proposal.status, ProposalStatus::Active) {
-        return proposal.status;
+        return Ok(proposal.status);
     }
 
     let now = env.ledger().timestamp();
     if now < proposal.voting_end {
-        panic!("voting period not finished");
+        return Err(GovernanceError::VotingNotFinished);
     }
 
-    let cfg = get_config(env, proposal.guild_id);
-
-    let members = guild_storage::get_all_members(env, proposal.guild_id);
-    let mut total_possible_weight: i128 = 0;
-    for member in members.iter() {
-        total_possible_weight += role_weight(&member.role);
+    let snapshot = proposal_snapshot(env, &proposal);
+    let mut tally = get_tally(env, proposal_id).unwrap_or(ProposalTally {
+        cursor: 0,
+        total_weight: 0,
+        votes_for: 0,
+        votes_against: 0,
+        votes_abstain: 0,
+    });
+
+    let addresses = guild_storage::get_member_address_range_at(
+        env,
+        proposal.guild_id,
+        snapshot.epoch,
+        tally.cursor,
+        TALLY_BATCH,
+    );
+    for address in addresses.iter() {
+        let voter = match resolve_voter(env, &proposal, &snapshot, &address) {
+            Some(voter) => voter,
+            None => continue,
+        };
+        tally.total_weight += voter.weight;
+        if let Some(vote) = get_vote(env, proposal_id, &voter.representative) {
+            match vote.decision {
+                VoteDecision::For => tally.votes_for += voter.weight,
+                VoteDecision::Against => tally.votes_against += voter.weight,
+                VoteDecision::Abstain => tally.votes_abstain += voter.weight,
+            }
+        }
     }
+    tally.cursor += addresses.len();
 
-    let quorum_threshold: i128 = (total_possible_weight * (cfg.quorum_percentage as i128)) / 100;
+    if addresses.len() == TALLY_BATCH {
+        set_tally(env, proposal_id, &tally);
+        return Ok(ProposalStatus::Active);
+    }
+    remove_tally(env, proposal_id);
 
-    let (total_votes_weight, for_weight, against_weight, abstain_weight) =
-        compute_total_weight_and_tallies(env, &proposal);
+    let cfg = snapshot.config;
+    let quorum_threshold: i128 = (tally.total_weight * (cfg.quorum_percentage as i128)) / 100;
+    let total_votes_weight = tally.votes_for + tally.votes_against + tally.votes_abstain;
 
-    proposal.votes_for = for_weight;
-    proposal.votes_against = against_weight;
-    proposal.votes_abstain = abstain_weight;
+    proposal.votes_for = tally.votes_for;
+    proposal.votes_against = tally.votes_against;
+    proposal.votes_abstain = tally.votes_abstain;
 
     if total_votes_weight < quorum_threshold {
         proposal.status = ProposalStatus::Rejected;
     } else {
-        let counted = for_weight + against_weight;
+        let counted = tally.votes_for + tally.votes_against;
         if counted == 0 {
             proposal.status = ProposalStatus::Rejected;
         } else {
-            let approval_pct = (for_weight * 100) / counted;
+            let approval_pct = (tally.votes_for * 100) / counted;
             if approval_pct >= (cfg.approval_threshold as i128) {
                 proposal.status = ProposalStatus::Passed;
                 if proposal.passed_at.is_none() {
@@ -225,5 +400,5 @@ pub fn finalize_proposal(env: &Env, proposal_id: u64) -> ProposalStatus {
         event,
     );
 
-    proposal.status
+    Ok(proposal.status)
 }
@@ -0,0 +1,114 @@
+use crate::guild::membership;
+use crate::guild::roles;
+use crate::guild::storage;
+use crate::guild::types::{BanUpdatedEvent, GuildError, Permission};
+use soroban_sdk::{Address, Env, Symbol, Vec};
+
+/// Ban an address from a guild
+///
+/// A banned address is removed from the guild if it is a member, loses any
+/// pending invitation or join request, and cannot rejoin, claim the guild's
+/// bounties or take part in its vote delegation until unbanned.
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `guild_id` - The ID of the guild
+/// * `address` - The address to ban
+/// * `caller` - The address making the request (must hold `ManageMembers`)
+///
+/// # Returns
+/// `Ok(true)` if successful
+///
+/// # Errors
+/// - `GuildError::GuildNotFound` if the guild doesn't exist
+/// - `GuildError::CallerNotMember` if the caller is not a member of the guild
+/// - `GuildError::InsufficientPermissions` if the caller lacks `ManageMembers`,
+///   bans themselves or the guild owner, or could not remove the member
+/// - `GuildError::MemberBanned` if the address is already banned
+/// - `GuildError::LastOwner` if the address is the guild's last owner
+pub fn ban_member(
+    env: &Env,
+    guild_id: u64,
+    address: Address,
+    caller: Address,
+) -> Result<bool, GuildError> {
+    let guild = storage::get_guild(env, guild_id).ok_or(GuildError::GuildNotFound)?;
+    roles::require_permission(env, guild_id, &caller, Permission::ManageMembers)?;
+    if address == caller || address == guild.owner {
+        return Err(GuildError::InsufficientPermissions);
+    }
+    if storage::is_banned(env, guild_id, &address) {
+        return Err(GuildError::MemberBanned);
+    }
+
+    // Removal applies the usual rules on who may remove whom
+    if storage::has_member(env, guild_id, &address) {
+        membership::remove_member(env, guild_id, address.clone(), caller.clone())?;
+    }
+    storage::remove_invitation(env, guild_id, &address);
+    storage::remove_join_request(env, guild_id, &address);
+    storage::add_ban(env, guild_id, &address);
+
+    env.events().publish(
+        (Symbol::new(env, "member_banned"), Symbol::new(env, "v0")),
+        BanUpdatedEvent {
+            guild_id,
+            address,
+            banned: true,
+            updated_by: caller,
+        },
+    );
+
+    Ok(true)
+}
+
+/// Lift the ban on an address
+///
+/// The address is not re-added; it may be invited or ask to join again.
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `guild_id` - The ID of the guild
+/// * `address` - The banned address
+/// * `caller` - The address making the request (must hold `ManageMembers`)
+///
+/// # Returns
+/// `Ok(true)` if successful
+///
+/// # Errors
+/// - `GuildError::CallerNotMember` if the caller is not a member of the guild
+/// - `GuildError::InsufficientPermissions` if the caller lacks `ManageMembers`
+/// - `GuildError::NotBanned` if the address is not banned
+pub fn unban_member(
+    env: &Env,
+    guild_id: u64,
+    address: Address,
+    caller: Address,
+) -> Result<bool, GuildError> {
+    roles::require_permission(env, guild_id, &caller, Permission::ManageMembers)?;
+    if !storage::remove_ban(env, guild_id, &address) {
+        return Err(GuildError::NotBanned);
+    }
+
+    env.events().publish(
+        (Symbol::new(env, "member_unbanned"), Symbol::new(env, "v0")),
+        BanUpdatedEvent {
+            guild_id,
+            address,
+            banned: false,
+            updated_by: caller,
+        },
+    );
+
+    Ok(true)
+}
+
+/// Check if an address is banned from a guild
+pub fn is_banned(env: &Env, guild_id: u64, address: Address) -> bool {
+    storage::is_banned(env, guild_id, &address)
+}
+
+/// Get the addresses banned from a guild
+pub fn get_banned(env: &Env, guild_id: u64) -> Vec<Address> {
+    storage::get_banned(env, guild_id)
+}
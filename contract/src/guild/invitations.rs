@@ -0,0 +1,386 @@
+use crate::guild::membership;
+use crate::guild::roles;
+use crate::guild::storage;
+use crate::guild::types::{
+    GuildError, Invitation, InvitationClosedEvent, InvitationCreatedEvent, JoinRequest,
+    JoinRequestClosedEvent, JoinRequestedEvent, Permission, Role,
+};
+use soroban_sdk::{Address, Env, String, Symbol, Vec};
+
+/// Maximum length of the note attached to a join request
+pub const MAX_JOIN_MESSAGE_LEN: u32 = 512;
+
+/// Invite an address to join a guild
+///
+/// Inviting an address that already has a pending invitation replaces it.
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `guild_id` - The ID of the guild
+/// * `invitee` - The address being invited
+/// * `role` - The role the invitee receives on accepting
+/// * `caller` - The address sending the invitation (must be able to assign `role`)
+/// * `expires_at` - Timestamp after which the invitation can no longer be accepted
+///
+/// # Returns
+/// `Ok(true)` if successful
+///
+/// # Errors
+/// - `GuildError::GuildNotFound` if the guild doesn't exist
+/// - `GuildError::MemberAlreadyExists` if the invitee is already a member
+/// - `GuildError::MemberBanned` if the invitee is banned from the guild
+/// - `GuildError::CallerNotMember` if the caller is not a member of the guild
+/// - `GuildError::InsufficientPermissions` if the caller cannot assign the role
+/// - `GuildError::InvalidExpiry` if `expires_at` is not in the future
+pub fn invite_member(
+    env: &Env,
+    guild_id: u64,
+    invitee: Address,
+    role: Role,
+    caller: Address,
+    expires_at: u64,
+) -> Result<bool, GuildError> {
+    storage::get_guild(env, guild_id).ok_or(GuildError::GuildNotFound)?;
+    if storage::has_member(env, guild_id, &invitee) {
+        return Err(GuildError::MemberAlreadyExists);
+    }
+    if storage::is_banned(env, guild_id, &invitee) {
+        return Err(GuildError::MemberBanned);
+    }
+    membership::require_can_add(env, guild_id, &caller, role)?;
+
+    let now = env.ledger().timestamp();
+    if expires_at <= now {
+        return Err(GuildError::InvalidExpiry);
+    }
+
+    storage::store_invitation(
+        env,
+        &Invitation {
+            guild_id,
+            invitee: invitee.clone(),
+            role,
+            invited_by: caller.clone(),
+            created_at: now,
+            expires_at,
+        },
+    );
+
+    env.events().publish(
+        (
+            Symbol::new(env, "invitation_created"),
+            Symbol::new(env, "v0"),
+        ),
+        InvitationCreatedEvent {
+            guild_id,
+            invitee,
+            role,
+            invited_by: caller,
+            expires_at,
+        },
+    );
+
+    Ok(true)
+}
+
+/// Accept a pending invitation, joining the guild with the offered role
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `guild_id` - The ID of the guild
+/// * `invitee` - The invited address
+///
+/// # Returns
+/// `Ok(true)` if successful
+///
+/// # Errors
+/// - `GuildError::GuildNotFound` if the guild doesn't exist
+/// - `GuildError::InvitationNotFound` if the address has no pending invitation
+/// - `GuildError::InvitationExpired` if the invitation has expired
+/// - `GuildError::MemberLimitReached` if the guild is at its `max_members` rule
+pub fn accept_invitation(env: &Env, guild_id: u64, invitee: Address) -> Result<bool, GuildError> {
+    let guild = storage::get_guild(env, guild_id).ok_or(GuildError::GuildNotFound)?;
+    let invitation =
+        storage::get_invitation(env, guild_id, &invitee).ok_or(GuildError::InvitationNotFound)?;
+    if env.ledger().timestamp() > invitation.expires_at {
+        return Err(GuildError::InvitationExpired);
+    }
+
+    // Dropping the invitation is part of becoming a member
+    membership::insert_member(env, guild, invitee.clone(), invitation.role)?;
+
+    env.events().publish(
+        (
+            Symbol::new(env, "invitation_accepted"),
+            Symbol::new(env, "v0"),
+        ),
+        InvitationClosedEvent { guild_id, invitee },
+    );
+
+    Ok(true)
+}
+
+/// Decline a pending invitation
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `guild_id` - The ID of the guild
+/// * `invitee` - The invited address
+///
+/// # Returns
+/// `Ok(true)` if successful
+///
+/// # Errors
+/// - `GuildError::InvitationNotFound` if the address has no pending invitation
+pub fn decline_invitation(env: &Env, guild_id: u64, invitee: Address) -> Result<bool, GuildError> {
+    if !storage::remove_invitation(env, guild_id, &invitee) {
+        return Err(GuildError::InvitationNotFound);
+    }
+
+    env.events().publish(
+        (
+            Symbol::new(env, "invitation_declined"),
+            Symbol::new(env, "v0"),
+        ),
+        InvitationClosedEvent { guild_id, invitee },
+    );
+
+    Ok(true)
+}
+
+/// Withdraw a pending invitation
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `guild_id` - The ID of the guild
+/// * `invitee` - The invited address
+/// * `caller` - The address revoking the invitation (must hold `ManageMembers`)
+///
+/// # Returns
+/// `Ok(true)` if successful
+///
+/// # Errors
+/// - `GuildError::CallerNotMember` if the caller is not a member of the guild
+/// - `GuildError::InsufficientPermissions` if the caller lacks `ManageMembers`
+/// - `GuildError::InvitationNotFound` if the address has no pending invitation
+pub fn revoke_invitation(
+    env: &Env,
+    guild_id: u64,
+    invitee: Address,
+    caller: Address,
+) -> Result<bool, GuildError> {
+    roles::require_permission(env, guild_id, &caller, Permission::ManageMembers)?;
+    if !storage::remove_invitation(env, guild_id, &invitee) {
+        return Err(GuildError::InvitationNotFound);
+    }
+
+    env.events().publish(
+        (
+            Symbol::new(env, "invitation_revoked"),
+            Symbol::new(env, "v0"),
+        ),
+        InvitationClosedEvent { guild_id, invitee },
+    );
+
+    Ok(true)
+}
+
+/// Ask to join a guild
+///
+/// Members holding `ManageMembers` answer with `approve_join_request` or
+/// `deny_join_request`; the guild may also admit the applicant through an
+/// `AddMember` proposal, which settles the request once executed.
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `guild_id` - The ID of the guild
+/// * `applicant` - The address asking to join
+/// * `message` - A note to the guild's admins
+///
+/// # Returns
+/// `Ok(true)` if successful
+///
+/// # Errors
+/// - `GuildError::GuildNotFound` if the guild doesn't exist
+/// - `GuildError::MemberAlreadyExists` if the applicant is already a member
+/// - `GuildError::MemberBanned` if the applicant is banned from the guild
+/// - `GuildError::JoinRequestExists` if the applicant already has a pending request
+/// - `GuildError::InvalidDescription` if the message is longer than 512 characters
+pub fn request_to_join(
+    env: &Env,
+    guild_id: u64,
+    applicant: Address,
+    message: String,
+) -> Result<bool, GuildError> {
+    storage::get_guild(env, guild_id).ok_or(GuildError::GuildNotFound)?;
+    if storage::has_member(env, guild_id, &applicant) {
+        return Err(GuildError::MemberAlreadyExists);
+    }
+    if storage::is_banned(env, guild_id, &applicant) {
+        return Err(GuildError::MemberBanned);
+    }
+    if storage::get_join_request(env, guild_id, &applicant).is_some() {
+        return Err(GuildError::JoinRequestExists);
+    }
+    if message.len() > MAX_JOIN_MESSAGE_LEN {
+        return Err(GuildError::InvalidDescription);
+    }
+
+    storage::store_join_request(
+        env,
+        &JoinRequest {
+            guild_id,
+            applicant: applicant.clone(),
+            message: message.clone(),
+            created_at: env.ledger().timestamp(),
+        },
+    );
+
+    env.events().publish(
+        (Symbol::new(env, "join_requested"), Symbol::new(env, "v0")),
+        JoinRequestedEvent {
+            guild_id,
+            applicant,
+            message,
+        },
+    );
+
+    Ok(true)
+}
+
+/// Approve a pending join request, admitting the applicant with `role`
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `guild_id` - The ID of the guild
+/// * `applicant` - The address that asked to join
+/// * `role` - The role to give the applicant
+/// * `caller` - The address approving (must be able to assign `role`)
+///
+/// # Returns
+/// `Ok(true)` if successful
+///
+/// # Errors
+/// - `GuildError::GuildNotFound` if the guild doesn't exist
+/// - `GuildError::JoinRequestNotFound` if the address has no pending request
+/// - `GuildError::CallerNotMember` if the caller is not a member of the guild
+/// - `GuildError::InsufficientPermissions` if the caller cannot assign the role
+/// - `GuildError::MemberLimitReached` if the guild is at its `max_members` rule
+pub fn approve_join_request(
+    env: &Env,
+    guild_id: u64,
+    applicant: Address,
+    role: Role,
+    caller: Address,
+) -> Result<bool, GuildError> {
+    let guild = storage::get_guild(env, guild_id).ok_or(GuildError::GuildNotFound)?;
+    if storage::get_join_request(env, guild_id, &applicant).is_none() {
+        return Err(GuildError::JoinRequestNotFound);
+    }
+    membership::require_can_add(env, guild_id, &caller, role)?;
+
+    // Dropping the request is part of becoming a member
+    membership::insert_member(env, guild, applicant.clone(), role)?;
+
+    env.events().publish(
+        (
+            Symbol::new(env, "join_request_approved"),
+            Symbol::new(env, "v0"),
+        ),
+        JoinRequestClosedEvent {
+            guild_id,
+            applicant,
+            closed_by: caller,
+        },
+    );
+
+    Ok(true)
+}
+
+/// Deny a pending join request
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `guild_id` - The ID of the guild
+/// * `applicant` - The address that asked to join
+/// * `caller` - The address denying (must hold `ManageMembers`)
+///
+/// # Returns
+/// `Ok(true)` if successful
+///
+/// # Errors
+/// - `GuildError::CallerNotMember` if the caller is not a member of the guild
+/// - `GuildError::InsufficientPermissions` if the caller lacks `ManageMembers`
+/// - `GuildError::JoinRequestNotFound` if the address has no pending request
+pub fn deny_join_request(
+    env: &Env,
+    guild_id: u64,
+    applicant: Address,
+    caller: Address,
+) -> Result<bool, GuildError> {
+    roles::require_permission(env, guild_id, &caller, Permission::ManageMembers)?;
+    if !storage::remove_join_request(env, guild_id, &applicant) {
+        return Err(GuildError::JoinRequestNotFound);
+    }
+
+    env.events().publish(
+        (
+            Symbol::new(env, "join_request_denied"),
+            Symbol::new(env, "v0"),
+        ),
+        JoinRequestClosedEvent {
+            guild_id,
+            applicant,
+            closed_by: caller,
+        },
+    );
+
+    Ok(true)
+}
+
+/// Withdraw one's own pending join request
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `guild_id` - The ID of the guild
+/// * `applicant` - The address that asked to join
+///
+/// # Returns
+/// `Ok(true)` if successful
+///
+/// # Errors
+/// - `GuildError::JoinRequestNotFound` if the address has no pending request
+pub fn withdraw_join_request(
+    env: &Env,
+    guild_id: u64,
+    applicant: Address,
+) -> Result<bool, GuildError> {
+    if !storage::remove_join_request(env, guild_id, &applicant) {
+        return Err(GuildError::JoinRequestNotFound);
+    }
+
+    env.events().publish(
+        (
+            Symbol::new(env, "join_request_withdrawn"),
+            Symbol::new(env, "v0"),
+        ),
+        JoinRequestClosedEvent {
+            guild_id,
+            applicant: applicant.clone(),
+            closed_by: applicant,
+        },
+    );
+
+    Ok(true)
+}
+
+/// Get the pending invitations of a guild, including expired ones that were
+/// never answered
+pub fn get_invitations(env: &Env, guild_id: u64) -> Vec<Invitation> {
+    storage::get_invitations(env, guild_id)
+}
+
+/// Get the pending join requests of a guild
+pub fn get_join_requests(env: &Env, guild_id: u64) -> Vec<JoinRequest> {
+    storage::get_join_requests(env, guild_id)
+}
@@ -1,6 +1,11 @@
+use crate::bounty::claims;
+use crate::governance::voting;
+use crate::guild::roles;
+use crate::guild::rules;
 use crate::guild::storage;
 use crate::guild::types::{
-    Guild, GuildCreatedEvent, Member, MemberAddedEvent, MemberRemovedEvent, Role, RoleUpdatedEvent,
+    Guild, GuildCreatedEvent, GuildError, Member, MemberAddedEvent, MemberRemovedEvent, Permission,
+    Role, RoleUpdatedEvent,
 };
 use soroban_sdk::{Address, Env, String, Symbol, Vec};
 
@@ -16,26 +21,20 @@ use soroban_sdk::{Address, Env, String, Symbol, Vec};
 /// The ID of the newly created guild
 ///
 /// # Errors
-/// Returns Result with error if:
-/// - Name or description is too long
+/// - `GuildError::InvalidName` if the name is empty or longer than 256 characters
+/// - `GuildError::InvalidDescription` if the description is longer than 512 characters
 pub fn create_guild(
     env: &Env,
     name: String,
     description: String,
     owner: Address,
-) -> Result<u64, String> {
+) -> Result<u64, GuildError> {
     // Validate inputs
-    if name.len() == 0 || name.len() > 256 {
-        return Err(String::from_str(
-            env,
-            "Guild name must be between 1 and 256 characters",
-        ));
+    if name.is_empty() || name.len() > 256 {
+        return Err(GuildError::InvalidName);
     }
     if description.len() > 512 {
-        return Err(String::from_str(
-            env,
-            "Guild description must be at most 512 characters",
-        ));
+        return Err(GuildError::InvalidDescription);
     }
 
     // Get next guild ID
@@ -89,67 +88,112 @@ pub fn create_guild(
 /// * `caller` - The address attempting to add the member (must have permission)
 ///
 /// # Returns
-/// Result with true if successful, error string if failed
+/// `Ok(true)` if successful
 ///
 /// # Errors
-/// Returns Result with error if:
-/// - Guild doesn't exist
-/// - Member already exists
-/// - Caller doesn't have permission to add members
-/// - Attempting to add an owner without proper permission
+/// - `GuildError::GuildNotFound` if the guild doesn't exist
+/// - `GuildError::MemberAlreadyExists` if the address is already a member
+/// - `GuildError::CallerNotMember` if the caller is not a member of the guild
+/// - `GuildError::InsufficientPermissions` if the caller cannot assign the role
+/// - `GuildError::MemberBanned` if the address is banned from the guild
+/// - `GuildError::MemberLimitReached` if the guild is at its `max_members` rule
 pub fn add_member(
     env: &Env,
     guild_id: u64,
     address: Address,
     role: Role,
     caller: Address,
-) -> Result<bool, String> {
+) -> Result<bool, GuildError> {
     // Get the guild
-    let guild =
-        storage::get_guild(env, guild_id).ok_or(String::from_str(env, "Guild not found"))?;
+    let guild = storage::get_guild(env, guild_id).ok_or(GuildError::GuildNotFound)?;
 
     // Check if member already exists
     if storage::has_member(env, guild_id, &address) {
-        return Err(String::from_str(env, "Member already exists in guild"));
+        return Err(GuildError::MemberAlreadyExists);
     }
 
+    require_can_add(env, guild_id, &caller, role)?;
+    insert_member(env, guild, address, role)?;
+
+    Ok(true)
+}
+
+/// Check that `caller` may bring a new member into a guild with `role`, or
+/// give an existing member that role
+///
+/// Only Owners assign `Owner` and only Owners and Admins assign `Admin`;
+/// lower roles need the `ManageMembers` permission.
+///
+/// # Errors
+/// - `GuildError::CallerNotMember` if the caller is not a member of the guild
+/// - `GuildError::InsufficientPermissions` if the caller cannot assign the role
+pub fn require_can_add(
+    env: &Env,
+    guild_id: u64,
+    caller: &Address,
+    role: Role,
+) -> Result<(), GuildError> {
     // Get caller's role
-    let caller_member = storage::get_member(env, guild_id, &caller)
-        .ok_or(String::from_str(env, "Caller is not a member of the guild"))?;
+    let caller_member =
+        storage::get_member(env, guild_id, caller).ok_or(GuildError::CallerNotMember)?;
 
     // Check permissions based on role being assigned
     match role {
         Role::Owner => {
             // Only current owner can add new owners
             if caller_member.role != Role::Owner {
-                return Err(String::from_str(env, "Only owner can add new owners"));
+                return Err(GuildError::InsufficientPermissions);
             }
         }
         Role::Admin => {
             // Owner and Admin can add admins
             if caller_member.role != Role::Owner && caller_member.role != Role::Admin {
-                return Err(String::from_str(env, "Only owner or admin can add admins"));
+                return Err(GuildError::InsufficientPermissions);
             }
         }
         Role::Member | Role::Contributor => {
-            // Owner and Admin can add members and contributors
-            if !caller_member.role.has_permission(&Role::Member) {
-                return Err(String::from_str(
-                    env,
-                    "Insufficient permissions to add members",
-                ));
-            }
+            // Members and contributors are added by holders of `ManageMembers`
+            roles::require_permission(env, guild_id, caller, Permission::ManageMembers)?;
         }
     }
 
+    Ok(())
+}
+
+/// Store a new member of a guild and bump its member count
+///
+/// Any pending invitation or join request of the address is settled by the
+/// membership, so it is dropped. Callers are responsible for authorization.
+///
+/// # Errors
+/// - `GuildError::MemberBanned` if the address is banned from the guild
+/// - `GuildError::MemberLimitReached` if the guild is at its `max_members` rule
+pub fn insert_member(
+    env: &Env,
+    guild: Guild,
+    address: Address,
+    role: Role,
+) -> Result<(), GuildError> {
+    let guild_id = guild.id;
+    if storage::is_banned(env, guild_id, &address) {
+        return Err(GuildError::MemberBanned);
+    }
+
+    let max_members = rules::get_rules(env, guild_id).max_members;
+    if max_members > 0 && guild.member_count >= max_members {
+        return Err(GuildError::MemberLimitReached);
+    }
+
     // Create and store the member
     let timestamp = env.ledger().timestamp();
     let member = Member {
         address: address.clone(),
-        role: role.clone(),
+        role,
         joined_at: timestamp,
     };
     storage::store_member(env, guild_id, &member);
+    storage::remove_invitation(env, guild_id, &address);
+    storage::remove_join_request(env, guild_id, &address);
 
     // Update guild member count
     let mut updated_guild = guild;
@@ -167,7 +211,7 @@ pub fn add_member(
         },
     );
 
-    Ok(true)
+    Ok(())
 }
 
 /// Remove a member from a guild
@@ -179,27 +223,29 @@ pub fn add_member(
 /// * `caller` - The address attempting to remove the member
 ///
 /// # Returns
-/// Result with true if successful, error string if failed
+/// `Ok(true)` if successful
 ///
 /// # Errors
-/// Returns Result with error if:
-/// - Guild doesn't exist
-/// - Member doesn't exist
-/// - Caller doesn't have permission
-/// - Attempting to remove the last owner
+/// - `GuildError::GuildNotFound` if the guild doesn't exist
+/// - `GuildError::MemberNotFound` if the address is not a member
+/// - `GuildError::CallerNotMember` if the caller is not a member of the guild
+/// - `GuildError::InsufficientPermissions` if the caller cannot remove the member,
+///   or someone else tries to remove `Guild.owner`
+/// - `GuildError::LastOwner` when attempting to remove the last owner
+/// - `GuildError::OwnerCannotLeave` if `Guild.owner` removes themselves, and
+///   must hand over with `transfer_ownership` first
 pub fn remove_member(
     env: &Env,
     guild_id: u64,
     address: Address,
     caller: Address,
-) -> Result<bool, String> {
+) -> Result<bool, GuildError> {
     // Get the guild
-    let guild =
-        storage::get_guild(env, guild_id).ok_or(String::from_str(env, "Guild not found"))?;
+    let guild = storage::get_guild(env, guild_id).ok_or(GuildError::GuildNotFound)?;
 
     // Check if member exists
-    let member = storage::get_member(env, guild_id, &address)
-        .ok_or(String::from_str(env, "Member not found"))?;
+    let member =
+        storage::get_member(env, guild_id, &address).ok_or(GuildError::MemberNotFound)?;
 
     // Check if caller is trying to remove themselves (self-removal is allowed)
     let is_self_removal = caller == address;
@@ -208,56 +254,51 @@ pub fn remove_member(
     if member.role == Role::Owner {
         let owner_count = storage::count_owners(env, guild_id);
         if owner_count <= 1 {
-            return Err(String::from_str(env, "Cannot remove the last owner"));
+            return Err(GuildError::LastOwner);
         }
     }
 
+    // `Guild.owner` stays a member until ownership has been handed over
+    if address == guild.owner {
+        return Err(if is_self_removal {
+            GuildError::OwnerCannotLeave
+        } else {
+            GuildError::InsufficientPermissions
+        });
+    }
+
     if !is_self_removal {
         // Get caller's role
-        let caller_member = storage::get_member(env, guild_id, &caller)
-            .ok_or(String::from_str(env, "Caller is not a member of the guild"))?;
+        let caller_member =
+            storage::get_member(env, guild_id, &caller).ok_or(GuildError::CallerNotMember)?;
 
         // Determine permission requirements based on member's role
         match member.role {
             Role::Owner => {
                 // Only owners can remove owners
                 if caller_member.role != Role::Owner {
-                    return Err(String::from_str(env, "Only owner can remove owners"));
+                    return Err(GuildError::InsufficientPermissions);
                 }
                 // Prevent removing last owner
                 let owner_count = storage::count_owners(env, guild_id);
                 if owner_count <= 1 {
-                    return Err(String::from_str(env, "Cannot remove the last owner"));
+                    return Err(GuildError::LastOwner);
                 }
             }
             Role::Admin => {
                 // Only owner and admin can remove admins
                 if caller_member.role != Role::Owner && caller_member.role != Role::Admin {
-                    return Err(String::from_str(
-                        env,
-                        "Only owner or admin can remove admins",
-                    ));
+                    return Err(GuildError::InsufficientPermissions);
                 }
             }
             Role::Member | Role::Contributor => {
-                // Owner and Admin can remove members and contributors
-                if !caller_member.role.has_permission(&Role::Member) {
-                    return Err(String::from_str(
-                        env,
-                        "Insufficient permissions to remove members",
-                    ));
-                }
+                // Holders of `ManageMembers` can remove members and contributors
+                roles::require_permission(env, guild_id, &caller, Permission::ManageMembers)?;
             }
         }
     }
 
-    // Remove the member
-    storage::remove_member(env, guild_id, &address);
-
-    // Update guild member count
-    let mut updated_guild = guild;
-    updated_guild.member_count = updated_guild.member_count.saturating_sub(1);
-    storage::update_guild(env, &updated_guild);
+    detach_member(env, guild, &address);
 
     // Emit event
     env.events().publish(
@@ -268,6 +309,62 @@ pub fn remove_member(
     Ok(true)
 }
 
+/// Leave a guild
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `guild_id` - The ID of the guild
+/// * `address` - The member leaving
+///
+/// # Returns
+/// `Ok(true)` if successful
+///
+/// # Errors
+/// - `GuildError::GuildNotFound` if the guild doesn't exist
+/// - `GuildError::MemberNotFound` if the address is not a member
+/// - `GuildError::LastOwner` if the member is the guild's last owner
+/// - `GuildError::OwnerCannotLeave` if the member is `Guild.owner`, who must
+///   hand over with `transfer_ownership` first
+pub fn leave_guild(env: &Env, guild_id: u64, address: Address) -> Result<bool, GuildError> {
+    let guild = storage::get_guild(env, guild_id).ok_or(GuildError::GuildNotFound)?;
+    let member =
+        storage::get_member(env, guild_id, &address).ok_or(GuildError::MemberNotFound)?;
+
+    if member.role == Role::Owner && storage::count_owners(env, guild_id) <= 1 {
+        return Err(GuildError::LastOwner);
+    }
+    if address == guild.owner {
+        return Err(GuildError::OwnerCannotLeave);
+    }
+
+    detach_member(env, guild, &address);
+
+    env.events().publish(
+        (Symbol::new(env, "member_left"), Symbol::new(env, "v0")),
+        MemberRemovedEvent { guild_id, address },
+    );
+
+    Ok(true)
+}
+
+/// Take an address off a guild's roster, dropping its custom role, the
+/// delegations made by or to it and the bounties it has claimed but not
+/// submitted
+fn detach_member(env: &Env, guild: Guild, address: &Address) {
+    let guild_id = guild.id;
+
+    // Remove the member
+    storage::remove_member(env, guild_id, address);
+    roles::release_member(env, guild_id, address);
+    voting::clear_member_delegations(env, guild_id, address);
+    claims::release_member_claims(env, guild_id, address);
+
+    // Update guild member count
+    let mut updated_guild = guild;
+    updated_guild.member_count = updated_guild.member_count.saturating_sub(1);
+    storage::update_guild(env, &updated_guild);
+}
+
 /// Update a member's role
 ///
 /// # Arguments
@@ -278,74 +375,77 @@ pub fn remove_member(
 /// * `caller` - The address attempting to update the role
 ///
 /// # Returns
-/// Result with true if successful, error string if failed
+/// `Ok(true)` if successful
 ///
 /// # Errors
-/// Returns Result with error if:
-/// - Guild doesn't exist
-/// - Member doesn't exist
-/// - Caller doesn't have permission
-/// - Attempting to change the last owner's role
+/// - `GuildError::GuildNotFound` if the guild doesn't exist
+/// - `GuildError::MemberNotFound` if the address is not a member
+/// - `GuildError::CallerNotMember` if the caller is not a member of the guild
+/// - `GuildError::InsufficientPermissions` if the caller cannot change the role,
+///   or the member is `Guild.owner`, who must hand over with
+///   `transfer_ownership` before being demoted
+/// - `GuildError::LastOwner` when attempting to demote the last owner
 pub fn update_role(
     env: &Env,
     guild_id: u64,
     address: Address,
     new_role: Role,
     caller: Address,
-) -> Result<bool, String> {
+) -> Result<bool, GuildError> {
     // Get the guild
-    let _guild =
-        storage::get_guild(env, guild_id).ok_or(String::from_str(env, "Guild not found"))?;
+    let guild = storage::get_guild(env, guild_id).ok_or(GuildError::GuildNotFound)?;
 
     // Get the member
-    let member = storage::get_member(env, guild_id, &address)
-        .ok_or(String::from_str(env, "Member not found"))?;
+    let member =
+        storage::get_member(env, guild_id, &address).ok_or(GuildError::MemberNotFound)?;
 
     // Get caller's role
-    let caller_member = storage::get_member(env, guild_id, &caller)
-        .ok_or(String::from_str(env, "Caller is not a member of the guild"))?;
+    let caller_member =
+        storage::get_member(env, guild_id, &caller).ok_or(GuildError::CallerNotMember)?;
 
     // Check permissions
     match member.role {
         Role::Owner => {
             // Only owners can change owner roles
             if caller_member.role != Role::Owner {
-                return Err(String::from_str(env, "Only owner can change owner role"));
+                return Err(GuildError::InsufficientPermissions);
             }
             // Prevent changing the last owner to another role
             if new_role != Role::Owner {
                 let owner_count = storage::count_owners(env, guild_id);
                 if owner_count <= 1 {
-                    return Err(String::from_str(env, "Cannot demote the last owner"));
+                    return Err(GuildError::LastOwner);
+                }
+                // `Guild.owner` keeps the Owner role until ownership moves
+                if address == guild.owner {
+                    return Err(GuildError::InsufficientPermissions);
                 }
             }
         }
         Role::Admin => {
             // Only owner and admin can change admin roles
             if caller_member.role != Role::Owner && caller_member.role != Role::Admin {
-                return Err(String::from_str(
-                    env,
-                    "Only owner or admin can change admin role",
-                ));
+                return Err(GuildError::InsufficientPermissions);
             }
         }
         Role::Member | Role::Contributor => {
-            // Only Owner and Admin can change member/contributor roles
-            if caller_member.role != Role::Owner && caller_member.role != Role::Admin {
-                return Err(String::from_str(
-                    env,
-                    "Insufficient permissions to change member role",
-                ));
-            }
+            // Holders of `ManageMembers` can change member/contributor roles
+            roles::require_permission(env, guild_id, &caller, Permission::ManageMembers)?;
         }
     }
 
-    let old_role = member.role.clone();
+    // The caller must also be allowed to hand out the new role
+    require_can_add(env, guild_id, &caller, new_role)?;
+
+    let old_role = member.role;
+    if new_role == Role::Owner {
+        roles::release_member(env, guild_id, &address);
+    }
 
     // Update the member's role
     let updated_member = Member {
         address: address.clone(),
-        role: new_role.clone(),
+        role: new_role,
         joined_at: member.joined_at,
     };
     storage::store_member(env, guild_id, &updated_member);
@@ -372,9 +472,9 @@ pub fn update_role(
 /// * `address` - The address of the member
 ///
 /// # Returns
-/// The Member if found, error string if not
-pub fn get_member(env: &Env, guild_id: u64, address: Address) -> Result<Member, String> {
-    storage::get_member(env, guild_id, &address).ok_or(String::from_str(env, "Member not found"))
+/// The Member if found, `GuildError::MemberNotFound` otherwise
+pub fn get_member(env: &Env, guild_id: u64, address: Address) -> Result<Member, GuildError> {
+    storage::get_member(env, guild_id, &address).ok_or(GuildError::MemberNotFound)
 }
 
 /// Get all members of a guild
@@ -1,13 +1,23 @@
-/// Guild management module
-/// 
-/// This module provides all guild creation, membership management, and role-based
-/// permission functionality for the Stellar Guilds platform.
-///
-/// # Overview
-/// - `types`: Defines all core data structures (Guild, Member, Role, Events)
-/// - `storage`: Manages persistent storage of guilds and members
-/// - `membership`: Core functions for guild and member management
+//! Guild management module
+//!
+//! This module provides all guild creation, membership management, and role-based
+//! permission functionality for the Stellar Guilds platform.
+//!
+//! # Overview
+//! - `types`: Defines all core data structures (Guild, Member, Role, Events)
+//! - `storage`: Manages persistent storage of guilds and members
+//! - `membership`: Core functions for guild and member management
+//! - `rules`: Per-guild settings registry read by the other modules
+//! - `profile`: Guild metadata updates and two-step ownership transfer
+//! - `invitations`: Invitations and join requests awaiting an answer
+//! - `roles`: Permission presets of the built-in roles and guild-defined custom roles
+//! - `bans`: Per-guild ban list
 
 pub mod types;
 pub mod storage;
 pub mod membership;
+pub mod rules;
+pub mod profile;
+pub mod invitations;
+pub mod roles;
+pub mod bans;
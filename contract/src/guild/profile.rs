@@ -0,0 +1,211 @@
+use crate::guild::membership;
+use crate::guild::roles;
+use crate::guild::storage;
+use crate::guild::types::{
+    GuildError, GuildMetadataUpdatedEvent, GuildProfile, OwnershipTransferStartedEvent,
+    OwnershipTransferredEvent, Role,
+};
+use soroban_sdk::{Address, Env, String, Symbol, Vec};
+
+/// Maximum length of a logo, website or social link URL
+pub const MAX_URL_LEN: u32 = 256;
+/// Maximum number of social links on a guild profile
+pub const MAX_SOCIAL_LINKS: u32 = 5;
+
+/// Get the profile of a guild, falling back to an empty one
+pub fn get_profile(env: &Env, guild_id: u64) -> GuildProfile {
+    storage::get_profile(env, guild_id).unwrap_or_else(|| GuildProfile {
+        logo_url: String::from_str(env, ""),
+        website: String::from_str(env, ""),
+        social_links: Vec::new(env),
+    })
+}
+
+/// Check that a profile fits the length limits
+///
+/// # Errors
+/// - `GuildError::InvalidMetadata` if a URL is longer than `MAX_URL_LEN` or
+///   there are more than `MAX_SOCIAL_LINKS` social links
+pub fn validate_profile(profile: &GuildProfile) -> Result<(), GuildError> {
+    if profile.logo_url.len() > MAX_URL_LEN
+        || profile.website.len() > MAX_URL_LEN
+        || profile.social_links.len() > MAX_SOCIAL_LINKS
+    {
+        return Err(GuildError::InvalidMetadata);
+    }
+    for link in profile.social_links.iter() {
+        if link.is_empty() || link.len() > MAX_URL_LEN {
+            return Err(GuildError::InvalidMetadata);
+        }
+    }
+    Ok(())
+}
+
+/// Replace the name, description and profile of a guild
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `guild_id` - The ID of the guild
+/// * `caller` - The address making the change (must be the guild owner)
+/// * `name` - The new name
+/// * `description` - The new description
+/// * `profile` - The new logo URL, website and social links
+///
+/// # Returns
+/// `Ok(true)` if successful
+///
+/// # Errors
+/// - `GuildError::GuildNotFound` if the guild doesn't exist
+/// - `GuildError::InsufficientPermissions` if the caller is not the owner
+/// - `GuildError::InvalidName` if the name is empty or longer than 256 characters
+/// - `GuildError::InvalidDescription` if the description is longer than 512 characters
+/// - `GuildError::InvalidMetadata` if the profile exceeds its limits
+pub fn update_guild_metadata(
+    env: &Env,
+    guild_id: u64,
+    caller: Address,
+    name: String,
+    description: String,
+    profile: GuildProfile,
+) -> Result<bool, GuildError> {
+    let mut guild = storage::get_guild(env, guild_id).ok_or(GuildError::GuildNotFound)?;
+    if caller != guild.owner {
+        return Err(GuildError::InsufficientPermissions);
+    }
+    caller.require_auth();
+
+    if name.is_empty() || name.len() > 256 {
+        return Err(GuildError::InvalidName);
+    }
+    if description.len() > 512 {
+        return Err(GuildError::InvalidDescription);
+    }
+    validate_profile(&profile)?;
+
+    guild.name = name.clone();
+    guild.description = description.clone();
+    storage::update_guild(env, &guild);
+    storage::store_profile(env, guild_id, &profile);
+
+    env.events().publish(
+        (Symbol::new(env, "guild_metadata_updated"), Symbol::new(env, "v0")),
+        GuildMetadataUpdatedEvent {
+            guild_id,
+            name,
+            description,
+            profile,
+        },
+    );
+
+    Ok(true)
+}
+
+/// Nominate a new owner for a guild
+///
+/// The nominee takes over only once they call `accept_ownership`; until
+/// then the current owner keeps full control and may nominate someone else.
+/// Nominating the current owner withdraws a pending nomination.
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `guild_id` - The ID of the guild
+/// * `caller` - The address making the request (must be the guild owner)
+/// * `new_owner` - The address that must accept ownership
+///
+/// # Returns
+/// `Ok(true)` if successful
+///
+/// # Errors
+/// - `GuildError::GuildNotFound` if the guild doesn't exist
+/// - `GuildError::InsufficientPermissions` if the caller is not the owner
+pub fn transfer_ownership(
+    env: &Env,
+    guild_id: u64,
+    caller: Address,
+    new_owner: Address,
+) -> Result<bool, GuildError> {
+    let guild = storage::get_guild(env, guild_id).ok_or(GuildError::GuildNotFound)?;
+    if caller != guild.owner {
+        return Err(GuildError::InsufficientPermissions);
+    }
+    caller.require_auth();
+
+    if new_owner == guild.owner {
+        storage::clear_pending_owner(env, guild_id);
+    } else {
+        storage::set_pending_owner(env, guild_id, &new_owner);
+    }
+
+    env.events().publish(
+        (Symbol::new(env, "ownership_transfer_started"), Symbol::new(env, "v0")),
+        OwnershipTransferStartedEvent {
+            guild_id,
+            owner: guild.owner,
+            pending_owner: new_owner,
+        },
+    );
+
+    Ok(true)
+}
+
+/// Accept a pending ownership nomination
+///
+/// The nominee becomes `Guild.owner` and an `Owner` member of the guild
+/// (joining it if needed); the previous owner stays on as an `Admin`.
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `guild_id` - The ID of the guild
+/// * `new_owner` - The nominee (must match the pending nomination)
+///
+/// # Returns
+/// `Ok(true)` if successful
+///
+/// # Errors
+/// - `GuildError::GuildNotFound` if the guild doesn't exist
+/// - `GuildError::NoPendingTransfer` if `new_owner` was not nominated
+/// - `GuildError::MemberLimitReached` if the nominee would have to join a
+///   guild that is at its `max_members` rule
+pub fn accept_ownership(env: &Env, guild_id: u64, new_owner: Address) -> Result<bool, GuildError> {
+    let mut guild = storage::get_guild(env, guild_id).ok_or(GuildError::GuildNotFound)?;
+    if storage::get_pending_owner(env, guild_id) != Some(new_owner.clone()) {
+        return Err(GuildError::NoPendingTransfer);
+    }
+    new_owner.require_auth();
+
+    match storage::get_member(env, guild_id, &new_owner) {
+        Some(mut member) => {
+            member.role = Role::Owner;
+            storage::store_member(env, guild_id, &member);
+            roles::release_member(env, guild_id, &new_owner);
+        }
+        None => {
+            membership::insert_member(env, guild, new_owner.clone(), Role::Owner)?;
+            guild = storage::get_guild(env, guild_id).ok_or(GuildError::GuildNotFound)?;
+        }
+    }
+
+    // The previous owner may have left or been demoted already
+    let previous_owner = guild.owner.clone();
+    if let Some(mut member) = storage::get_member(env, guild_id, &previous_owner) {
+        if member.role == Role::Owner {
+            member.role = Role::Admin;
+            storage::store_member(env, guild_id, &member);
+        }
+    }
+
+    guild.owner = new_owner.clone();
+    storage::update_guild(env, &guild);
+    storage::clear_pending_owner(env, guild_id);
+
+    env.events().publish(
+        (Symbol::new(env, "ownership_transferred"), Symbol::new(env, "v0")),
+        OwnershipTransferredEvent {
+            guild_id,
+            previous_owner,
+            new_owner,
+        },
+    );
+
+    Ok(true)
+}
@@ -0,0 +1,296 @@
+use crate::guild::rules;
+use crate::guild::storage;
+use crate::guild::types::{
+    CustomRole, CustomRoleAssignedEvent, CustomRoleUpdatedEvent, GuildError, Permission, Role,
+};
+use soroban_sdk::{Address, Env, Symbol, Vec};
+
+/// Every permission bit currently defined
+pub const ALL_PERMISSIONS: u32 = (1 << 8) - 1;
+/// Maximum number of custom roles a guild may define
+pub const MAX_CUSTOM_ROLES: u32 = 32;
+
+/// Get the permissions a built-in role grants in a guild
+///
+/// Owners hold every permission, Admins every permission but
+/// `ManageGuild`, and Members and Contributors may only sign. Bounty
+/// creation follows the guild's `bounty_creator_role` rule.
+pub fn preset_permissions(env: &Env, guild_id: u64, role: Role) -> u32 {
+    let mut permissions = match role {
+        Role::Owner => ALL_PERMISSIONS,
+        Role::Admin => ALL_PERMISSIONS & !Permission::ManageGuild.mask(),
+        Role::Member | Role::Contributor => Permission::SignTransactions.mask(),
+    };
+
+    let creator_role = rules::get_rules(env, guild_id).bounty_creator_role;
+    if role.has_permission(&creator_role) {
+        permissions |= Permission::CreateBounty.mask();
+    } else {
+        permissions &= !Permission::CreateBounty.mask();
+    }
+
+    permissions
+}
+
+/// Get the permissions held by an address in a guild (0 for non-members)
+///
+/// A member holding a custom role gets exactly that role's permissions;
+/// everyone else gets the preset of their built-in role.
+pub fn get_member_permissions(env: &Env, guild_id: u64, address: &Address) -> u32 {
+    let member = match storage::get_member(env, guild_id, address) {
+        Some(member) => member,
+        None => return 0,
+    };
+
+    match storage::get_member_custom_role(env, guild_id, address) {
+        Some(name) => storage::get_custom_roles(env, guild_id)
+            .get(name)
+            .map(|role| role.permissions)
+            .unwrap_or(0),
+        None => preset_permissions(env, guild_id, member.role),
+    }
+}
+
+/// Check if an address holds a permission in a guild
+pub fn has_guild_permission(
+    env: &Env,
+    guild_id: u64,
+    address: Address,
+    permission: Permission,
+) -> bool {
+    get_member_permissions(env, guild_id, &address) & permission.mask() != 0
+}
+
+/// Check that `caller` is a member of the guild holding `permission`
+///
+/// # Errors
+/// - `GuildError::CallerNotMember` if the caller is not a member of the guild
+/// - `GuildError::InsufficientPermissions` if the caller lacks the permission
+pub fn require_permission(
+    env: &Env,
+    guild_id: u64,
+    caller: &Address,
+    permission: Permission,
+) -> Result<(), GuildError> {
+    if !storage::has_member(env, guild_id, caller) {
+        return Err(GuildError::CallerNotMember);
+    }
+    if !has_guild_permission(env, guild_id, caller.clone(), permission) {
+        return Err(GuildError::InsufficientPermissions);
+    }
+    Ok(())
+}
+
+/// Check that `caller` is an Owner of the guild; only Owners shape roles
+fn require_owner(env: &Env, guild_id: u64, caller: &Address) -> Result<(), GuildError> {
+    storage::get_guild(env, guild_id).ok_or(GuildError::GuildNotFound)?;
+    let member = storage::get_member(env, guild_id, caller).ok_or(GuildError::CallerNotMember)?;
+    if member.role != Role::Owner {
+        return Err(GuildError::InsufficientPermissions);
+    }
+    Ok(())
+}
+
+/// Create a custom role, or replace the permissions of an existing one
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `guild_id` - The ID of the guild
+/// * `caller` - The address making the change (must be an Owner)
+/// * `name` - The name of the role
+/// * `permissions` - Bitset of `Permission::mask` values
+///
+/// # Returns
+/// `Ok(true)` if successful
+///
+/// # Errors
+/// - `GuildError::GuildNotFound` if the guild doesn't exist
+/// - `GuildError::CallerNotMember` if the caller is not a member of the guild
+/// - `GuildError::InsufficientPermissions` if the caller is not an Owner
+/// - `GuildError::InvalidPermissions` if the bitset has undefined bits set
+/// - `GuildError::RoleLimitReached` if the guild already has `MAX_CUSTOM_ROLES`
+pub fn define_role(
+    env: &Env,
+    guild_id: u64,
+    caller: Address,
+    name: Symbol,
+    permissions: u32,
+) -> Result<bool, GuildError> {
+    require_owner(env, guild_id, &caller)?;
+    if permissions & !ALL_PERMISSIONS != 0 {
+        return Err(GuildError::InvalidPermissions);
+    }
+
+    let mut roles = storage::get_custom_roles(env, guild_id);
+    let holders = match roles.get(name.clone()) {
+        Some(role) => role.holders,
+        None => {
+            if roles.len() >= MAX_CUSTOM_ROLES {
+                return Err(GuildError::RoleLimitReached);
+            }
+            0
+        }
+    };
+    roles.set(
+        name.clone(),
+        CustomRole {
+            name: name.clone(),
+            permissions,
+            holders,
+        },
+    );
+    storage::store_custom_roles(env, guild_id, &roles);
+
+    env.events().publish(
+        (
+            Symbol::new(env, "custom_role_updated"),
+            Symbol::new(env, "v0"),
+        ),
+        CustomRoleUpdatedEvent {
+            guild_id,
+            name,
+            permissions: Some(permissions),
+        },
+    );
+
+    Ok(true)
+}
+
+/// Delete a custom role nobody holds
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `guild_id` - The ID of the guild
+/// * `caller` - The address making the change (must be an Owner)
+/// * `name` - The name of the role
+///
+/// # Returns
+/// `Ok(true)` if successful
+///
+/// # Errors
+/// - `GuildError::GuildNotFound` if the guild doesn't exist
+/// - `GuildError::CallerNotMember` if the caller is not a member of the guild
+/// - `GuildError::InsufficientPermissions` if the caller is not an Owner
+/// - `GuildError::RoleNotFound` if the guild has no such role
+/// - `GuildError::RoleInUse` if a member still holds the role
+pub fn delete_role(
+    env: &Env,
+    guild_id: u64,
+    caller: Address,
+    name: Symbol,
+) -> Result<bool, GuildError> {
+    require_owner(env, guild_id, &caller)?;
+
+    let mut roles = storage::get_custom_roles(env, guild_id);
+    let role = roles.get(name.clone()).ok_or(GuildError::RoleNotFound)?;
+    if role.holders > 0 {
+        return Err(GuildError::RoleInUse);
+    }
+    roles.remove(name.clone());
+    storage::store_custom_roles(env, guild_id, &roles);
+
+    env.events().publish(
+        (
+            Symbol::new(env, "custom_role_updated"),
+            Symbol::new(env, "v0"),
+        ),
+        CustomRoleUpdatedEvent {
+            guild_id,
+            name,
+            permissions: None,
+        },
+    );
+
+    Ok(true)
+}
+
+/// Give a member a custom role, or return them to their built-in role's
+/// preset when `name` is `None`
+///
+/// Owners always hold every permission and cannot be given a custom role.
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `guild_id` - The ID of the guild
+/// * `caller` - The address making the change (must be an Owner)
+/// * `address` - The member
+/// * `name` - The custom role to give, or `None` to clear it
+///
+/// # Returns
+/// `Ok(true)` if successful
+///
+/// # Errors
+/// - `GuildError::GuildNotFound` if the guild doesn't exist
+/// - `GuildError::CallerNotMember` if the caller is not a member of the guild
+/// - `GuildError::InsufficientPermissions` if the caller is not an Owner, or
+///   the member is an Owner
+/// - `GuildError::MemberNotFound` if the address is not a member
+/// - `GuildError::RoleNotFound` if the guild has no such role
+pub fn assign_role(
+    env: &Env,
+    guild_id: u64,
+    caller: Address,
+    address: Address,
+    name: Option<Symbol>,
+) -> Result<bool, GuildError> {
+    require_owner(env, guild_id, &caller)?;
+    let member = storage::get_member(env, guild_id, &address).ok_or(GuildError::MemberNotFound)?;
+    if member.role == Role::Owner {
+        return Err(GuildError::InsufficientPermissions);
+    }
+
+    let mut roles = storage::get_custom_roles(env, guild_id);
+    if let Some(name) = &name {
+        let mut role = roles.get(name.clone()).ok_or(GuildError::RoleNotFound)?;
+        role.holders += 1;
+        roles.set(name.clone(), role);
+    }
+    if let Some(previous) = storage::get_member_custom_role(env, guild_id, &address) {
+        if let Some(mut role) = roles.get(previous.clone()) {
+            role.holders = role.holders.saturating_sub(1);
+            roles.set(previous, role);
+        }
+    }
+    storage::store_custom_roles(env, guild_id, &roles);
+
+    match &name {
+        Some(name) => storage::set_member_custom_role(env, guild_id, &address, name),
+        None => storage::clear_member_custom_role(env, guild_id, &address),
+    }
+
+    env.events().publish(
+        (
+            Symbol::new(env, "custom_role_assigned"),
+            Symbol::new(env, "v0"),
+        ),
+        CustomRoleAssignedEvent {
+            guild_id,
+            address,
+            role: name,
+        },
+    );
+
+    Ok(true)
+}
+
+/// Drop the custom role of an address that left the guild or became an
+/// Owner; callers are responsible for authorization
+pub fn release_member(env: &Env, guild_id: u64, address: &Address) {
+    let name = match storage::get_member_custom_role(env, guild_id, address) {
+        Some(name) => name,
+        None => return,
+    };
+    storage::clear_member_custom_role(env, guild_id, address);
+
+    let mut roles = storage::get_custom_roles(env, guild_id);
+    if let Some(mut role) = roles.get(name.clone()) {
+        role.holders = role.holders.saturating_sub(1);
+        roles.set(name, role);
+        storage::store_custom_roles(env, guild_id, &roles);
+    }
+}
+
+/// Get the custom roles defined by a guild
+pub fn get_custom_roles(env: &Env, guild_id: u64) -> Vec<CustomRole> {
+    storage::get_custom_roles(env, guild_id).values()
+}
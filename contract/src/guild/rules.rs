@@ -0,0 +1,93 @@
+use crate::guild::storage;
+use crate::guild::types::{GuildError, GuildRules, GuildRulesUpdatedEvent, Role};
+use soroban_sdk::{Address, Env, Symbol};
+
+/// Default lifetime of a bounty created without an explicit expiry (30 days)
+pub const DEFAULT_BOUNTY_EXPIRY_SECONDS: u64 = 60 * 60 * 24 * 30;
+/// Default maximum length of a bounty description
+pub const DEFAULT_MAX_BOUNTY_DESCRIPTION_LEN: u32 = 2048;
+/// Default time a treasury transaction stays approvable (7 days)
+pub const DEFAULT_TREASURY_TX_EXPIRY_SECONDS: u64 = 60 * 60 * 24 * 7;
+
+impl Default for GuildRules {
+    fn default() -> Self {
+        Self {
+            max_members: 0,
+            default_bounty_expiry: DEFAULT_BOUNTY_EXPIRY_SECONDS,
+            bounty_creator_role: Role::Admin,
+            max_bounty_description_len: DEFAULT_MAX_BOUNTY_DESCRIPTION_LEN,
+            min_bounty_reward: 0,
+            treasury_tx_expiry: DEFAULT_TREASURY_TX_EXPIRY_SECONDS,
+        }
+    }
+}
+
+/// Get the rules of a guild, falling back to the defaults
+pub fn get_rules(env: &Env, guild_id: u64) -> GuildRules {
+    storage::get_rules(env, guild_id).unwrap_or_default()
+}
+
+/// Check that a set of rules is usable
+///
+/// # Errors
+/// - `GuildError::InvalidRule` if an expiry or length limit is zero, or the
+///   minimum reward is negative
+pub fn validate_rules(rules: &GuildRules) -> Result<(), GuildError> {
+    if rules.default_bounty_expiry == 0
+        || rules.treasury_tx_expiry == 0
+        || rules.max_bounty_description_len == 0
+        || rules.min_bounty_reward < 0
+    {
+        return Err(GuildError::InvalidRule);
+    }
+    Ok(())
+}
+
+/// Validate and store the rules of a guild
+///
+/// Callers are responsible for authorization: the owner through
+/// `update_rules`, or governance through an executed `RuleChange` proposal.
+pub fn set_rules(env: &Env, guild_id: u64, rules: GuildRules) -> Result<(), GuildError> {
+    storage::get_guild(env, guild_id).ok_or(GuildError::GuildNotFound)?;
+    validate_rules(&rules)?;
+
+    storage::store_rules(env, guild_id, &rules);
+
+    env.events().publish(
+        (Symbol::new(env, "guild_rules_updated"), Symbol::new(env, "v0")),
+        GuildRulesUpdatedEvent { guild_id, rules },
+    );
+
+    Ok(())
+}
+
+/// Replace the rules of a guild
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `guild_id` - The ID of the guild
+/// * `caller` - The address making the change (must be the guild owner)
+/// * `rules` - The new rules
+///
+/// # Returns
+/// `Ok(true)` if successful
+///
+/// # Errors
+/// - `GuildError::GuildNotFound` if the guild doesn't exist
+/// - `GuildError::InsufficientPermissions` if the caller is not the owner
+/// - `GuildError::InvalidRule` if the rules are not usable
+pub fn update_rules(
+    env: &Env,
+    guild_id: u64,
+    caller: Address,
+    rules: GuildRules,
+) -> Result<bool, GuildError> {
+    let guild = storage::get_guild(env, guild_id).ok_or(GuildError::GuildNotFound)?;
+    if caller != guild.owner {
+        return Err(GuildError::InsufficientPermissions);
+    }
+    caller.require_auth();
+
+    set_rules(env, guild_id, rules)?;
+    Ok(true)
+}
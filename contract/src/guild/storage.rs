@@ -1,172 +1,809 @@
-use crate::guild::types::{Guild, Member, Role};
-use soroban_sdk::{symbol_short, Address, Env, Map, Symbol, Vec};
+use crate::guild::types::{
+    CustomRole, Guild, GuildProfile, GuildRules, Invitation, JoinRequest, Member,
+    MemberCheckpoint, MemberRecord, Role,
+};
+use crate::paging;
+use crate::ttl;
+use soroban_sdk::{contracttype, symbol_short, Address, Env, Map, Symbol, Vec};
 
-// Storage keys as symbols for efficient lookup
-const GUILDS_KEY: Symbol = symbol_short!("guilds");
-const MEMBERS_KEY: Symbol = symbol_short!("members");
-const GUILD_COUNTER_KEY: Symbol = symbol_short!("guild_cnt");
+/// Storage keys for guilds and their rosters
+///
+/// Every guild and every membership lives in its own entry; the roster of a
+/// guild is enumerated through a paginated index of member addresses.
+#[contracttype]
+pub enum GuildStorageKey {
+    GuildCounter,
+    Guild(u64),
+    Member(u64, Address),
+    MemberSlot(u64, Address),   // position of the member in the roster index
+    MemberIndexLen(u64),        // number of addresses in the roster index
+    MemberIndexPage(u64, u32),  // Vec<Address>
+    Rules(u64),
+    Profile(u64),
+    PendingOwner(u64),
+    Invitation(u64, Address),
+    InvitationSlot(u64, Address),
+    InvitationIndexLen(u64),
+    InvitationIndexPage(u64, u32), // Vec<Address> of invitees
+    JoinRequest(u64, Address),
+    JoinRequestSlot(u64, Address),
+    JoinRequestIndexLen(u64),
+    JoinRequestIndexPage(u64, u32), // Vec<Address> of applicants
+    CustomRoles(u64),               // Map<Symbol, CustomRole>
+    MemberCustomRole(u64, Address), // Symbol
+    BannedSlot(u64, Address),       // position of the address in the ban index
+    BannedIndexLen(u64),
+    BannedIndexPage(u64, u32),      // Vec<Address> of banned addresses
+    SnapshotEpoch(u64),             // u32, advanced each time a proposal opens
+    MemberHistory(u64, Address),    // Vec<MemberCheckpoint>
+    DepartedSlot(u64, Address),
+    DepartedIndexLen(u64),
+    DepartedIndexPage(u64, u32),    // Vec<Address> that left after a proposal opened
+    OwnerCount(u64),                // u32, members holding the Owner role
+    MemberIndexEpochs(u64, u32),    // Vec<u32>, epochs in which a roster page changed
+    MemberIndexPageAt(u64, u32, u32), // Vec<Address>, roster page before a change in an epoch
+}
+
+// Keys used by releases that kept every guild in a single map
+const LEGACY_GUILDS_KEY: Symbol = symbol_short!("guilds");
+const LEGACY_MEMBERS_KEY: Symbol = symbol_short!("members");
+const LEGACY_GUILD_COUNTER_KEY: Symbol = symbol_short!("guild_cnt");
 
 /// Initialize storage for guilds and members
 /// This should be called during contract initialization
 pub fn initialize(env: &Env) {
-    // Create an empty map for guilds
-    let guilds: Map<u64, Guild> = Map::new(env);
-    env.storage().persistent().set(&GUILDS_KEY, &guilds);
-
-    // Create an empty nested map for members
-    // Structure: guild_id -> (address -> member)
-    let members: Map<u64, Map<Address, Member>> = Map::new(env);
-    env.storage().persistent().set(&MEMBERS_KEY, &members);
-
-    // Initialize guild counter to 0
-    env.storage().persistent().set(&GUILD_COUNTER_KEY, &0u64);
+    if !env.storage().instance().has(&GuildStorageKey::GuildCounter) {
+        ttl::set_counter(env, &GuildStorageKey::GuildCounter, 0);
+    }
 }
 
 /// Get the next guild ID and increment the counter
 pub fn get_next_guild_id(env: &Env) -> u64 {
-    let counter: u64 = env
-        .storage()
-        .persistent()
-        .get(&GUILD_COUNTER_KEY)
-        .unwrap_or(0u64);
+    let counter = ttl::get_counter(env, &GuildStorageKey::GuildCounter, 0);
 
     let next_id = counter + 1;
-    env.storage()
-        .persistent()
-        .set(&GUILD_COUNTER_KEY, &next_id);
+    ttl::set_counter(env, &GuildStorageKey::GuildCounter, next_id);
 
     next_id
 }
 
 /// Store a guild
 pub fn store_guild(env: &Env, guild: &Guild) {
-    let mut guilds: Map<u64, Guild> = env
-        .storage()
-        .persistent()
-        .get(&GUILDS_KEY)
-        .unwrap_or_else(|| Map::new(env));
-
-    guilds.set(guild.id, guild.clone());
-    env.storage().persistent().set(&GUILDS_KEY, &guilds);
+    ttl::set_persistent(env, &GuildStorageKey::Guild(guild.id), guild);
 }
 
 /// Get a guild by ID
 pub fn get_guild(env: &Env, guild_id: u64) -> Option<Guild> {
-    let guilds: Map<u64, Guild> = env
-        .storage()
-        .persistent()
-        .get(&GUILDS_KEY)
-        .unwrap_or_else(|| Map::new(env));
-
-    guilds.get(guild_id)
+    ttl::get_persistent(env, &GuildStorageKey::Guild(guild_id))
 }
 
-/// Store a member in a guild
+/// Store a member in a guild, adding them to the roster index if new
 pub fn store_member(env: &Env, guild_id: u64, member: &Member) {
-    let mut members_map: Map<u64, Map<Address, Member>> = env
-        .storage()
-        .persistent()
-        .get(&MEMBERS_KEY)
-        .unwrap_or_else(|| Map::new(env));
+    let key = GuildStorageKey::Member(guild_id, member.address.clone());
+    checkpoint_member(env, guild_id, &member.address);
 
-    let mut guild_members = members_map
-        .get(guild_id)
-        .unwrap_or_else(|| Map::new(env));
+    let previous: Option<Member> = ttl::get_persistent(env, &key);
+    let was_owner = previous.as_ref().is_some_and(|m| m.role == Role::Owner);
+    if was_owner != (member.role == Role::Owner) {
+        let owners = count_owners(env, guild_id);
+        let owners = if was_owner {
+            owners.saturating_sub(1)
+        } else {
+            owners + 1
+        };
+        set_owner_count(env, guild_id, owners);
+    }
 
-    guild_members.set(member.address.clone(), member.clone());
-    members_map.set(guild_id, guild_members);
+    if previous.is_none() {
+        let len = paging::len(env, &GuildStorageKey::MemberIndexLen(guild_id));
+        checkpoint_roster_page(env, guild_id, len / paging::PAGE_SIZE);
+        let slot = paging::push(
+            env,
+            &GuildStorageKey::MemberIndexLen(guild_id),
+            |page| GuildStorageKey::MemberIndexPage(guild_id, page),
+            member.address.clone(),
+        );
+        ttl::set_persistent(
+            env,
+            &GuildStorageKey::MemberSlot(guild_id, member.address.clone()),
+            &slot,
+        );
+    }
 
-    env.storage()
-        .persistent()
-        .set(&MEMBERS_KEY, &members_map);
+    ttl::set_persistent(env, &key, member);
 }
 
 /// Get a member from a guild
 pub fn get_member(env: &Env, guild_id: u64, address: &Address) -> Option<Member> {
-    let members_map: Map<u64, Map<Address, Member>> = env
-        .storage()
-        .persistent()
-        .get(&MEMBERS_KEY)
-        .unwrap_or_else(|| Map::new(env));
-
-    let guild_members = members_map.get(guild_id)?;
-    guild_members.get(address.clone())
+    ttl::get_persistent(env, &GuildStorageKey::Member(guild_id, address.clone()))
 }
 
 /// Remove a member from a guild
 pub fn remove_member(env: &Env, guild_id: u64, address: &Address) -> bool {
-    let mut members_map: Map<u64, Map<Address, Member>> = env
-        .storage()
-        .persistent()
-        .get(&MEMBERS_KEY)
-        .unwrap_or_else(|| Map::new(env));
+    let key = GuildStorageKey::Member(guild_id, address.clone());
+    let member: Member = match ttl::get_persistent(env, &key) {
+        Some(member) => member,
+        None => return false,
+    };
+    checkpoint_member(env, guild_id, address);
+    if member.role == Role::Owner {
+        set_owner_count(env, guild_id, count_owners(env, guild_id).saturating_sub(1));
+    }
+    env.storage().persistent().remove(&key);
+
+    // Proposals opened before the departure still read the address's
+    // history, so `bump_guild` must keep reaching it
+    let departed_slot = GuildStorageKey::DepartedSlot(guild_id, address.clone());
+    if get_snapshot_epoch(env, guild_id) > 0 && !env.storage().persistent().has(&departed_slot) {
+        index_address(
+            env,
+            &GuildStorageKey::DepartedIndexLen(guild_id),
+            |page| GuildStorageKey::DepartedIndexPage(guild_id, page),
+            &departed_slot,
+            address,
+        );
+    }
+
+    let slot_key = GuildStorageKey::MemberSlot(guild_id, address.clone());
+    if let Some(slot) = env.storage().persistent().get::<_, u32>(&slot_key) {
+        env.storage().persistent().remove(&slot_key);
 
-    if let Some(mut guild_members) = members_map.get(guild_id) {
-        // Remove the member
-        let had_member = guild_members.contains_key(address.clone());
-        if had_member {
-            guild_members.remove(address.clone());
-            members_map.set(guild_id, guild_members);
-            env.storage()
-                .persistent()
-                .set(&MEMBERS_KEY, &members_map);
+        let len = paging::len(env, &GuildStorageKey::MemberIndexLen(guild_id));
+        checkpoint_roster_page(env, guild_id, slot / paging::PAGE_SIZE);
+        checkpoint_roster_page(env, guild_id, len.saturating_sub(1) / paging::PAGE_SIZE);
+        let moved: Option<Address> = paging::swap_remove(
+            env,
+            &GuildStorageKey::MemberIndexLen(guild_id),
+            |page| GuildStorageKey::MemberIndexPage(guild_id, page),
+            slot,
+        );
+        if let Some(moved) = moved {
+            ttl::set_persistent(env, &GuildStorageKey::MemberSlot(guild_id, moved), &slot);
         }
-        had_member
-    } else {
-        false
     }
+
+    true
+}
+
+/// Current snapshot epoch of a guild
+pub fn get_snapshot_epoch(env: &Env, guild_id: u64) -> u32 {
+    ttl::get_persistent(env, &GuildStorageKey::SnapshotEpoch(guild_id)).unwrap_or(0)
+}
+
+/// Start a new snapshot epoch for a proposal that is opening
+///
+/// Roster changes made from now on are checkpointed, so the proposal keeps
+/// reading the roster as it stands at the returned epoch.
+pub fn open_snapshot_epoch(env: &Env, guild_id: u64) -> u32 {
+    let epoch = get_snapshot_epoch(env, guild_id);
+    ttl::set_persistent(env, &GuildStorageKey::SnapshotEpoch(guild_id), &(epoch + 1));
+    epoch
+}
+
+/// Record an address's membership before its first change in the current
+/// epoch; nothing is recorded before any proposal has opened
+fn checkpoint_member(env: &Env, guild_id: u64, address: &Address) {
+    let epoch = get_snapshot_epoch(env, guild_id);
+    if epoch == 0 {
+        return;
+    }
+
+    let key = GuildStorageKey::MemberHistory(guild_id, address.clone());
+    let mut history: Vec<MemberCheckpoint> =
+        ttl::get_persistent(env, &key).unwrap_or_else(|| Vec::new(env));
+    if history.last().map(|checkpoint| checkpoint.epoch) == Some(epoch) {
+        return;
+    }
+    let record = match get_member(env, guild_id, address) {
+        Some(member) => MemberRecord::Present(member),
+        None => MemberRecord::Absent,
+    };
+    history.push_back(MemberCheckpoint { epoch, record });
+    ttl::set_persistent(env, &key, &history);
+}
+
+/// Get a member of a guild as of a snapshot epoch
+pub fn get_member_at(env: &Env, guild_id: u64, address: &Address, epoch: u32) -> Option<Member> {
+    let history: Option<Vec<MemberCheckpoint>> =
+        ttl::get_persistent(env, &GuildStorageKey::MemberHistory(guild_id, address.clone()));
+    if let Some(history) = history {
+        for checkpoint in history.iter() {
+            if checkpoint.epoch > epoch {
+                return match checkpoint.record {
+                    MemberRecord::Present(member) => Some(member),
+                    MemberRecord::Absent => None,
+                };
+            }
+        }
+    }
+    get_member(env, guild_id, address)
+}
+
+/// Record a page of the roster index before its first change in the current
+/// epoch; nothing is recorded before any proposal has opened
+fn checkpoint_roster_page(env: &Env, guild_id: u64, page: u32) {
+    let epoch = get_snapshot_epoch(env, guild_id);
+    if epoch == 0 {
+        return;
+    }
+
+    let key = GuildStorageKey::MemberIndexEpochs(guild_id, page);
+    let mut epochs: Vec<u32> = ttl::get_persistent(env, &key).unwrap_or_else(|| Vec::new(env));
+    if epochs.last() == Some(epoch) {
+        return;
+    }
+    let addresses: Vec<Address> =
+        paging::get_page(env, &GuildStorageKey::MemberIndexPage(guild_id, page));
+    ttl::set_persistent(
+        env,
+        &GuildStorageKey::MemberIndexPageAt(guild_id, page, epoch),
+        &addresses,
+    );
+    epochs.push_back(epoch);
+    ttl::set_persistent(env, &key, &epochs);
+}
+
+/// Read a page of the roster index as of a snapshot epoch
+fn get_roster_page_at(env: &Env, guild_id: u64, page: u32, epoch: u32) -> Vec<Address> {
+    let epochs: Option<Vec<u32>> =
+        ttl::get_persistent(env, &GuildStorageKey::MemberIndexEpochs(guild_id, page));
+    if let Some(epochs) = epochs {
+        for changed in epochs.iter() {
+            if changed > epoch {
+                let key = GuildStorageKey::MemberIndexPageAt(guild_id, page, changed);
+                return paging::get_page(env, &key);
+            }
+        }
+    }
+    paging::get_page(env, &GuildStorageKey::MemberIndexPage(guild_id, page))
+}
+
+/// Get up to `limit` addresses of a guild's roster as it stood at a snapshot
+/// epoch, starting at position `cursor`
+///
+/// Positions are those of the roster index at the epoch, so walking the
+/// roster in batches neither skips nor repeats a member however the roster
+/// changes in between.
+pub fn get_member_address_range_at(
+    env: &Env,
+    guild_id: u64,
+    epoch: u32,
+    cursor: u32,
+    limit: u32,
+) -> Vec<Address> {
+    let mut addresses = Vec::new(env);
+    let end = cursor.saturating_add(limit);
+
+    let mut position = cursor;
+    while position < end {
+        let page = get_roster_page_at(env, guild_id, position / paging::PAGE_SIZE, epoch);
+        let from = position % paging::PAGE_SIZE;
+        let to = (from + end - position).min(page.len());
+        if from >= to {
+            break;
+        }
+        addresses.append(&page.slice(from..to));
+        position += to - from;
+    }
+    addresses
+}
+
+/// Get up to `limit` addresses that left a guild after a proposal opened,
+/// starting at position `cursor`
+pub fn get_departed_range(env: &Env, guild_id: u64, cursor: u32, limit: u32) -> Vec<Address> {
+    paging::get_range(
+        env,
+        &GuildStorageKey::DepartedIndexLen(guild_id),
+        |page| GuildStorageKey::DepartedIndexPage(guild_id, page),
+        cursor,
+        limit,
+    )
+}
+
+/// Get the addresses of all members of a guild
+pub fn get_member_addresses(env: &Env, guild_id: u64) -> Vec<Address> {
+    paging::collect(
+        env,
+        &GuildStorageKey::MemberIndexLen(guild_id),
+        |page| GuildStorageKey::MemberIndexPage(guild_id, page),
+    )
+}
+
+/// Get up to `limit` member addresses of a guild, starting at position
+/// `cursor` of the roster index
+pub fn get_member_address_range(env: &Env, guild_id: u64, cursor: u32, limit: u32) -> Vec<Address> {
+    paging::get_range(
+        env,
+        &GuildStorageKey::MemberIndexLen(guild_id),
+        |page| GuildStorageKey::MemberIndexPage(guild_id, page),
+        cursor,
+        limit,
+    )
 }
 
 /// Get all members of a guild
 pub fn get_all_members(env: &Env, guild_id: u64) -> Vec<Member> {
-    let members_map: Map<u64, Map<Address, Member>> = env
-        .storage()
-        .persistent()
-        .get(&MEMBERS_KEY)
-        .unwrap_or_else(|| Map::new(env));
+    members_for(env, guild_id, &get_member_addresses(env, guild_id))
+}
 
-    if let Some(guild_members) = members_map.get(guild_id) {
-        let mut result = Vec::new(env);
-        
-        // Iterate through all members in the guild using iter()
-        for (_, member) in guild_members.iter() {
+/// Get one page (of `paging::PAGE_SIZE` entries) of a guild's members
+pub fn get_members_page(env: &Env, guild_id: u64, page: u32) -> Vec<Member> {
+    let addresses = paging::get_page(env, &GuildStorageKey::MemberIndexPage(guild_id, page));
+    members_for(env, guild_id, &addresses)
+}
+
+fn members_for(env: &Env, guild_id: u64, addresses: &Vec<Address>) -> Vec<Member> {
+    let mut result = Vec::new(env);
+    for address in addresses.iter() {
+        if let Some(member) = get_member(env, guild_id, &address) {
             result.push_back(member);
         }
-        
-        result
-    } else {
-        Vec::new(env)
     }
+    result
 }
 
 /// Check if a member exists in a guild
 pub fn has_member(env: &Env, guild_id: u64, address: &Address) -> bool {
-    get_member(env, guild_id, address).is_some()
+    env.storage()
+        .persistent()
+        .has(&GuildStorageKey::Member(guild_id, address.clone()))
 }
 
 /// Update a guild's metadata
 pub fn update_guild(env: &Env, guild: &Guild) {
-    let mut guilds: Map<u64, Guild> = env
-        .storage()
+    store_guild(env, guild);
+}
+
+/// Get the rules of a guild, if any were set
+pub fn get_rules(env: &Env, guild_id: u64) -> Option<GuildRules> {
+    ttl::get_persistent(env, &GuildStorageKey::Rules(guild_id))
+}
+
+/// Store the rules of a guild
+pub fn store_rules(env: &Env, guild_id: u64, rules: &GuildRules) {
+    ttl::set_persistent(env, &GuildStorageKey::Rules(guild_id), rules);
+}
+
+/// Get the profile of a guild, if one was set
+pub fn get_profile(env: &Env, guild_id: u64) -> Option<GuildProfile> {
+    ttl::get_persistent(env, &GuildStorageKey::Profile(guild_id))
+}
+
+/// Store the profile of a guild
+pub fn store_profile(env: &Env, guild_id: u64, profile: &GuildProfile) {
+    ttl::set_persistent(env, &GuildStorageKey::Profile(guild_id), profile);
+}
+
+/// Get the address nominated to take over a guild, if any
+pub fn get_pending_owner(env: &Env, guild_id: u64) -> Option<Address> {
+    ttl::get_persistent(env, &GuildStorageKey::PendingOwner(guild_id))
+}
+
+/// Record the address nominated to take over a guild
+pub fn set_pending_owner(env: &Env, guild_id: u64, pending_owner: &Address) {
+    ttl::set_persistent(env, &GuildStorageKey::PendingOwner(guild_id), pending_owner);
+}
+
+/// Clear a guild's pending ownership nomination
+pub fn clear_pending_owner(env: &Env, guild_id: u64) {
+    env.storage()
         .persistent()
-        .get(&GUILDS_KEY)
-        .unwrap_or_else(|| Map::new(env));
+        .remove(&GuildStorageKey::PendingOwner(guild_id));
+}
+
+/// Get the custom roles defined by a guild
+pub fn get_custom_roles(env: &Env, guild_id: u64) -> Map<Symbol, CustomRole> {
+    ttl::get_persistent(env, &GuildStorageKey::CustomRoles(guild_id))
+        .unwrap_or_else(|| Map::new(env))
+}
+
+/// Store the custom roles defined by a guild
+pub fn store_custom_roles(env: &Env, guild_id: u64, roles: &Map<Symbol, CustomRole>) {
+    ttl::set_persistent(env, &GuildStorageKey::CustomRoles(guild_id), roles);
+}
 
-    guilds.set(guild.id, guild.clone());
-    env.storage().persistent().set(&GUILDS_KEY, &guilds);
+/// Get the custom role held by a member, if any
+pub fn get_member_custom_role(env: &Env, guild_id: u64, address: &Address) -> Option<Symbol> {
+    ttl::get_persistent(env, &GuildStorageKey::MemberCustomRole(guild_id, address.clone()))
+}
+
+/// Record the custom role held by a member
+pub fn set_member_custom_role(env: &Env, guild_id: u64, address: &Address, role: &Symbol) {
+    ttl::set_persistent(
+        env,
+        &GuildStorageKey::MemberCustomRole(guild_id, address.clone()),
+        role,
+    );
+}
+
+/// Clear the custom role held by a member
+pub fn clear_member_custom_role(env: &Env, guild_id: u64, address: &Address) {
+    env.storage()
+        .persistent()
+        .remove(&GuildStorageKey::MemberCustomRole(guild_id, address.clone()));
+}
+
+/// Append an address to one of a guild's address indexes, remembering its
+/// position under `slot_key`
+fn index_address<F>(
+    env: &Env,
+    len_key: &GuildStorageKey,
+    page_key: F,
+    slot_key: &GuildStorageKey,
+    address: &Address,
+) where
+    F: Fn(u32) -> GuildStorageKey,
+{
+    let slot = paging::push(env, len_key, page_key, address.clone());
+    ttl::set_persistent(env, slot_key, &slot);
+}
+
+/// Drop an address from one of a guild's address indexes
+fn unindex_address<F, S>(
+    env: &Env,
+    len_key: &GuildStorageKey,
+    page_key: F,
+    slot_key: S,
+    address: &Address,
+) where
+    F: Fn(u32) -> GuildStorageKey,
+    S: Fn(Address) -> GuildStorageKey,
+{
+    let key = slot_key(address.clone());
+    if let Some(slot) = env.storage().persistent().get::<_, u32>(&key) {
+        env.storage().persistent().remove(&key);
+
+        let moved: Option<Address> = paging::swap_remove(env, len_key, page_key, slot);
+        if let Some(moved) = moved {
+            ttl::set_persistent(env, &slot_key(moved), &slot);
+        }
+    }
+}
+
+/// Store an invitation, adding the invitee to the guild's invitation index
+/// if new
+pub fn store_invitation(env: &Env, invitation: &Invitation) {
+    let guild_id = invitation.guild_id;
+    let key = GuildStorageKey::Invitation(guild_id, invitation.invitee.clone());
+
+    if !env.storage().persistent().has(&key) {
+        index_address(
+            env,
+            &GuildStorageKey::InvitationIndexLen(guild_id),
+            |page| GuildStorageKey::InvitationIndexPage(guild_id, page),
+            &GuildStorageKey::InvitationSlot(guild_id, invitation.invitee.clone()),
+            &invitation.invitee,
+        );
+    }
+
+    ttl::set_persistent(env, &key, invitation);
+}
+
+/// Get the pending invitation of an address to a guild
+pub fn get_invitation(env: &Env, guild_id: u64, invitee: &Address) -> Option<Invitation> {
+    ttl::get_persistent(env, &GuildStorageKey::Invitation(guild_id, invitee.clone()))
+}
+
+/// Remove an invitation
+///
+/// # Returns
+/// true if there was an invitation to remove
+pub fn remove_invitation(env: &Env, guild_id: u64, invitee: &Address) -> bool {
+    let key = GuildStorageKey::Invitation(guild_id, invitee.clone());
+    if !env.storage().persistent().has(&key) {
+        return false;
+    }
+    env.storage().persistent().remove(&key);
+
+    unindex_address(
+        env,
+        &GuildStorageKey::InvitationIndexLen(guild_id),
+        |page| GuildStorageKey::InvitationIndexPage(guild_id, page),
+        |address| GuildStorageKey::InvitationSlot(guild_id, address),
+        invitee,
+    );
+    true
+}
+
+/// Get all pending invitations of a guild
+pub fn get_invitations(env: &Env, guild_id: u64) -> Vec<Invitation> {
+    let invitees: Vec<Address> = paging::collect(
+        env,
+        &GuildStorageKey::InvitationIndexLen(guild_id),
+        |page| GuildStorageKey::InvitationIndexPage(guild_id, page),
+    );
+
+    let mut result = Vec::new(env);
+    for invitee in invitees.iter() {
+        if let Some(invitation) = get_invitation(env, guild_id, &invitee) {
+            result.push_back(invitation);
+        }
+    }
+    result
+}
+
+/// Store a join request, adding the applicant to the guild's join request
+/// index if new
+pub fn store_join_request(env: &Env, request: &JoinRequest) {
+    let guild_id = request.guild_id;
+    let key = GuildStorageKey::JoinRequest(guild_id, request.applicant.clone());
+
+    if !env.storage().persistent().has(&key) {
+        index_address(
+            env,
+            &GuildStorageKey::JoinRequestIndexLen(guild_id),
+            |page| GuildStorageKey::JoinRequestIndexPage(guild_id, page),
+            &GuildStorageKey::JoinRequestSlot(guild_id, request.applicant.clone()),
+            &request.applicant,
+        );
+    }
+
+    ttl::set_persistent(env, &key, request);
+}
+
+/// Get the pending join request of an address to a guild
+pub fn get_join_request(env: &Env, guild_id: u64, applicant: &Address) -> Option<JoinRequest> {
+    ttl::get_persistent(env, &GuildStorageKey::JoinRequest(guild_id, applicant.clone()))
+}
+
+/// Remove a join request
+///
+/// # Returns
+/// true if there was a join request to remove
+pub fn remove_join_request(env: &Env, guild_id: u64, applicant: &Address) -> bool {
+    let key = GuildStorageKey::JoinRequest(guild_id, applicant.clone());
+    if !env.storage().persistent().has(&key) {
+        return false;
+    }
+    env.storage().persistent().remove(&key);
+
+    unindex_address(
+        env,
+        &GuildStorageKey::JoinRequestIndexLen(guild_id),
+        |page| GuildStorageKey::JoinRequestIndexPage(guild_id, page),
+        |address| GuildStorageKey::JoinRequestSlot(guild_id, address),
+        applicant,
+    );
+    true
+}
+
+/// Get all pending join requests of a guild
+pub fn get_join_requests(env: &Env, guild_id: u64) -> Vec<JoinRequest> {
+    let applicants: Vec<Address> = paging::collect(
+        env,
+        &GuildStorageKey::JoinRequestIndexLen(guild_id),
+        |page| GuildStorageKey::JoinRequestIndexPage(guild_id, page),
+    );
+
+    let mut result = Vec::new(env);
+    for applicant in applicants.iter() {
+        if let Some(request) = get_join_request(env, guild_id, &applicant) {
+            result.push_back(request);
+        }
+    }
+    result
+}
+
+/// Check if an address is banned from a guild
+pub fn is_banned(env: &Env, guild_id: u64, address: &Address) -> bool {
+    env.storage()
+        .persistent()
+        .has(&GuildStorageKey::BannedSlot(guild_id, address.clone()))
+}
+
+/// Add an address to a guild's ban list
+pub fn add_ban(env: &Env, guild_id: u64, address: &Address) {
+    if is_banned(env, guild_id, address) {
+        return;
+    }
+    index_address(
+        env,
+        &GuildStorageKey::BannedIndexLen(guild_id),
+        |page| GuildStorageKey::BannedIndexPage(guild_id, page),
+        &GuildStorageKey::BannedSlot(guild_id, address.clone()),
+        address,
+    );
+}
+
+/// Remove an address from a guild's ban list
+///
+/// # Returns
+/// true if the address was banned
+pub fn remove_ban(env: &Env, guild_id: u64, address: &Address) -> bool {
+    if !is_banned(env, guild_id, address) {
+        return false;
+    }
+    unindex_address(
+        env,
+        &GuildStorageKey::BannedIndexLen(guild_id),
+        |page| GuildStorageKey::BannedIndexPage(guild_id, page),
+        |address| GuildStorageKey::BannedSlot(guild_id, address),
+        address,
+    );
+    true
+}
+
+/// Get the addresses banned from a guild
+pub fn get_banned(env: &Env, guild_id: u64) -> Vec<Address> {
+    paging::collect(
+        env,
+        &GuildStorageKey::BannedIndexLen(guild_id),
+        |page| GuildStorageKey::BannedIndexPage(guild_id, page),
+    )
+}
+
+/// Extend the TTL of a guild's record, rules, profile and custom roles, and
+/// of the members, departed members, invitations, join requests and bans at
+/// positions `cursor..cursor + limit` of their indexes
+///
+/// # Returns
+/// Whether any of those indexes holds entries past the batch
+pub fn bump_guild(env: &Env, guild_id: u64, cursor: u32, limit: u32) -> bool {
+    ttl::extend_persistent_if_present(env, &GuildStorageKey::Guild(guild_id));
+    ttl::extend_persistent_if_present(env, &GuildStorageKey::Rules(guild_id));
+    ttl::extend_persistent_if_present(env, &GuildStorageKey::Profile(guild_id));
+    ttl::extend_persistent_if_present(env, &GuildStorageKey::PendingOwner(guild_id));
+    ttl::extend_persistent_if_present(env, &GuildStorageKey::CustomRoles(guild_id));
+    ttl::extend_persistent_if_present(env, &GuildStorageKey::SnapshotEpoch(guild_id));
+    ttl::extend_persistent_if_present(env, &GuildStorageKey::OwnerCount(guild_id));
+
+    for address in get_member_address_range(env, guild_id, cursor, limit).iter() {
+        ttl::extend_persistent_if_present(env, &GuildStorageKey::Member(guild_id, address.clone()));
+        ttl::extend_persistent_if_present(
+            env,
+            &GuildStorageKey::MemberCustomRole(guild_id, address.clone()),
+        );
+        ttl::extend_persistent_if_present(
+            env,
+            &GuildStorageKey::MemberHistory(guild_id, address.clone()),
+        );
+        ttl::extend_persistent_if_present(env, &GuildStorageKey::MemberSlot(guild_id, address));
+    }
+
+    let first_page = cursor / paging::PAGE_SIZE;
+    let last_page = cursor.saturating_add(limit).div_ceil(paging::PAGE_SIZE);
+    for page in first_page..last_page {
+        let key = GuildStorageKey::MemberIndexEpochs(guild_id, page);
+        let epochs: Option<Vec<u32>> = ttl::get_persistent(env, &key);
+        if let Some(epochs) = epochs {
+            for epoch in epochs.iter() {
+                ttl::extend_persistent_if_present(
+                    env,
+                    &GuildStorageKey::MemberIndexPageAt(guild_id, page, epoch),
+                );
+            }
+        }
+    }
+
+    for address in get_departed_range(env, guild_id, cursor, limit).iter() {
+        ttl::extend_persistent_if_present(
+            env,
+            &GuildStorageKey::MemberHistory(guild_id, address.clone()),
+        );
+        ttl::extend_persistent_if_present(env, &GuildStorageKey::DepartedSlot(guild_id, address));
+    }
+
+    let invitees: Vec<Address> = paging::get_range(
+        env,
+        &GuildStorageKey::InvitationIndexLen(guild_id),
+        |page| GuildStorageKey::InvitationIndexPage(guild_id, page),
+        cursor,
+        limit,
+    );
+    for invitee in invitees.iter() {
+        let key = GuildStorageKey::Invitation(guild_id, invitee.clone());
+        ttl::extend_persistent_if_present(env, &key);
+        ttl::extend_persistent_if_present(env, &GuildStorageKey::InvitationSlot(guild_id, invitee));
+    }
+
+    let applicants: Vec<Address> = paging::get_range(
+        env,
+        &GuildStorageKey::JoinRequestIndexLen(guild_id),
+        |page| GuildStorageKey::JoinRequestIndexPage(guild_id, page),
+        cursor,
+        limit,
+    );
+    for applicant in applicants.iter() {
+        let key = GuildStorageKey::JoinRequest(guild_id, applicant.clone());
+        ttl::extend_persistent_if_present(env, &key);
+        let slot_key = GuildStorageKey::JoinRequestSlot(guild_id, applicant);
+        ttl::extend_persistent_if_present(env, &slot_key);
+    }
+
+    let banned: Vec<Address> = paging::get_range(
+        env,
+        &GuildStorageKey::BannedIndexLen(guild_id),
+        |page| GuildStorageKey::BannedIndexPage(guild_id, page),
+        cursor,
+        limit,
+    );
+    for address in banned.iter() {
+        ttl::extend_persistent_if_present(env, &GuildStorageKey::BannedSlot(guild_id, address));
+    }
+
+    let end = cursor.saturating_add(limit);
+    [
+        GuildStorageKey::MemberIndexLen(guild_id),
+        GuildStorageKey::DepartedIndexLen(guild_id),
+        GuildStorageKey::InvitationIndexLen(guild_id),
+        GuildStorageKey::JoinRequestIndexLen(guild_id),
+        GuildStorageKey::BannedIndexLen(guild_id),
+    ]
+    .iter()
+    .any(|len_key| paging::len(env, len_key) > end)
 }
 
 /// Count owners in a guild
+///
+/// Guilds whose roster predates the owner counter are counted by walking
+/// the roster until an owner is first added or removed.
 pub fn count_owners(env: &Env, guild_id: u64) -> u32 {
+    if let Some(count) = ttl::get_persistent(env, &GuildStorageKey::OwnerCount(guild_id)) {
+        return count;
+    }
+
     let members = get_all_members(env, guild_id);
     let mut count = 0u32;
-    
+
     for i in 0..members.len() {
         let member = members.get_unchecked(i);
         if member.role == Role::Owner {
             count += 1;
         }
     }
-    
+
     count
 }
+
+fn set_owner_count(env: &Env, guild_id: u64, count: u32) {
+    ttl::set_persistent(env, &GuildStorageKey::OwnerCount(guild_id), &count);
+}
+
+/// Move the guild counter written by older releases into instance storage
+pub fn migrate_legacy_counters(env: &Env) {
+    ttl::promote_counter(env, &LEGACY_GUILD_COUNTER_KEY, &GuildStorageKey::GuildCounter);
+}
+
+/// Move up to `limit` guilds (with their rosters) out of the legacy
+/// single-map layout into per-entity entries
+///
+/// # Returns
+/// The number of guilds migrated by this call
+pub fn migrate_legacy(env: &Env, limit: u32) -> u32 {
+    let storage = env.storage().persistent();
+    let mut guilds: Map<u64, Guild> = match storage.get(&LEGACY_GUILDS_KEY) {
+        Some(guilds) => guilds,
+        None => return 0,
+    };
+    let mut members: Map<u64, Map<Address, Member>> = storage
+        .get(&LEGACY_MEMBERS_KEY)
+        .unwrap_or_else(|| Map::new(env));
+
+    let mut migrated = 0u32;
+    for guild_id in guilds.keys().iter() {
+        if migrated >= limit {
+            break;
+        }
+        if let Some(guild) = guilds.get(guild_id) {
+            store_guild(env, &guild);
+        }
+        if let Some(roster) = members.get(guild_id) {
+            for (_, member) in roster.iter() {
+                store_member(env, guild_id, &member);
+            }
+        }
+        guilds.remove(guild_id);
+        members.remove(guild_id);
+        migrated += 1;
+    }
+
+    if guilds.is_empty() {
+        storage.remove(&LEGACY_GUILDS_KEY);
+        storage.remove(&LEGACY_MEMBERS_KEY);
+    } else {
+        ttl::set_persistent(env, &LEGACY_GUILDS_KEY, &guilds);
+        ttl::set_persistent(env, &LEGACY_MEMBERS_KEY, &members);
+    }
+
+    migrated
+}
@@ -1,4 +1,38 @@
-use soroban_sdk::{contracttype, Address};
+use soroban_sdk::{contracterror, contracttype, Address, String, Symbol, Vec};
+
+/// Error types for guild and membership operations
+///
+/// Codes live in the 100-199 range so they never collide with the errors of
+/// other modules when surfaced to clients.
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum GuildError {
+    GuildNotFound = 100,
+    InvalidName = 101,
+    InvalidDescription = 102,
+    MemberAlreadyExists = 103,
+    MemberNotFound = 104,
+    CallerNotMember = 105,
+    InsufficientPermissions = 106,
+    LastOwner = 107,
+    MemberLimitReached = 108,
+    InvalidRule = 109,
+    NoPendingTransfer = 110,
+    InvalidMetadata = 111,
+    InvitationNotFound = 112,
+    InvitationExpired = 113,
+    JoinRequestNotFound = 114,
+    JoinRequestExists = 115,
+    InvalidExpiry = 116,
+    InvalidPermissions = 117,
+    RoleNotFound = 118,
+    RoleInUse = 119,
+    RoleLimitReached = 120,
+    MemberBanned = 121,
+    NotBanned = 122,
+    OwnerCannotLeave = 123,
+}
 
 /// Role enum for guild members
 /// - Owner: Full control over the guild
@@ -18,16 +52,69 @@ impl Role {
     /// Check if a role has permission for a required role level
     /// Hierarchy: Owner > Admin > Member > Contributor
     pub fn has_permission(&self, required_role: &Role) -> bool {
-        match (self, required_role) {
-            (Role::Owner, _) => true,
-            (Role::Admin, Role::Admin) | (Role::Admin, Role::Member) | (Role::Admin, Role::Contributor) => true,
-            (Role::Member, Role::Member) | (Role::Member, Role::Contributor) => true,
-            (Role::Contributor, Role::Contributor) => true,
-            _ => false,
-        }
+        matches!(
+            (self, required_role),
+            (Role::Owner, _)
+                | (Role::Admin, Role::Admin)
+                | (Role::Admin, Role::Member)
+                | (Role::Admin, Role::Contributor)
+                | (Role::Member, Role::Member)
+                | (Role::Member, Role::Contributor)
+                | (Role::Contributor, Role::Contributor)
+        )
+    }
+}
+
+/// A single capability checked by the guild, bounty, milestone and treasury
+/// modules
+///
+/// The discriminant is the bit the permission occupies in a permission set
+/// (see `Permission::mask`).
+#[contracttype]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Permission {
+    /// Create bounties for the guild
+    CreateBounty = 0,
+    /// Assign, cancel and configure bounties created by others
+    ManageBounties = 1,
+    /// Review submissions, judge contests and approve milestones
+    ApproveWork = 2,
+    /// Add, remove and re-role members below Admin; answer invitations and
+    /// join requests
+    ManageMembers = 3,
+    /// Propose treasury spending, such as bounty funding
+    ProposeTreasurySpend = 4,
+    /// Approve treasury transactions as a signer
+    SignTransactions = 5,
+    /// Add milestones to projects, extend deadlines and cancel projects
+    ManageProjects = 6,
+    /// Change guild-wide settings such as fees and the arbiter panel
+    ManageGuild = 7,
+}
+
+impl Permission {
+    /// The bit this permission occupies in a permission set
+    pub fn mask(self) -> u32 {
+        1 << (self as u32)
     }
 }
 
+/// A guild-defined role granting an arbitrary set of permissions
+///
+/// Members holding a custom role are checked against its permissions
+/// instead of those of their built-in role, which still decides who may
+/// manage them.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CustomRole {
+    /// Name of the role, unique within the guild
+    pub name: Symbol,
+    /// Bitset of `Permission::mask` values
+    pub permissions: u32,
+    /// Number of members currently holding the role
+    pub holders: u32,
+}
+
 /// Guild struct containing guild metadata
 #[contracttype]
 #[derive(Clone, Debug)]
@@ -46,6 +133,18 @@ pub struct Guild {
     pub member_count: u32,
 }
 
+/// Optional public profile of a guild, kept alongside the `Guild` record
+#[contracttype]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GuildProfile {
+    /// URL of the guild's logo (empty if unset)
+    pub logo_url: String,
+    /// URL of the guild's website (empty if unset)
+    pub website: String,
+    /// Links to the guild's social accounts
+    pub social_links: Vec<String>,
+}
+
 /// Member struct representing a guild member
 #[contracttype]
 #[derive(Clone, Debug)]
@@ -58,6 +157,76 @@ pub struct Member {
     pub joined_at: u64,
 }
 
+/// A member's record as it stood before the first change made to it in a
+/// snapshot epoch
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct MemberCheckpoint {
+    /// Epoch in which the record changed
+    pub epoch: u32,
+    /// Record before the change
+    pub record: MemberRecord,
+}
+
+/// Membership of an address at some point in time
+#[contracttype]
+#[derive(Clone, Debug)]
+pub enum MemberRecord {
+    Absent,
+    Present(Member),
+}
+
+/// An offer of membership awaiting the invitee's answer
+#[contracttype]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Invitation {
+    pub guild_id: u64,
+    /// Address being invited
+    pub invitee: Address,
+    /// Role the invitee receives on accepting
+    pub role: Role,
+    /// Member who sent the invitation
+    pub invited_by: Address,
+    /// Timestamp when the invitation was sent (in seconds)
+    pub created_at: u64,
+    /// Timestamp after which the invitation can no longer be accepted
+    pub expires_at: u64,
+}
+
+/// A request from an outsider to join a guild, awaiting an admin's decision
+#[contracttype]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct JoinRequest {
+    pub guild_id: u64,
+    /// Address asking to join
+    pub applicant: Address,
+    /// Free-form note to the guild's admins
+    pub message: String,
+    /// Timestamp when the request was made (in seconds)
+    pub created_at: u64,
+}
+
+/// Per-guild settings consulted by the membership, bounty and treasury
+/// modules
+///
+/// Rules are changed by the guild owner or by a passed `RuleChange` proposal.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GuildRules {
+    /// Maximum number of members (0 = unlimited)
+    pub max_members: u32,
+    /// Lifetime of a bounty created without an explicit expiry (in seconds)
+    pub default_bounty_expiry: u64,
+    /// Lowest role allowed to create bounties
+    pub bounty_creator_role: Role,
+    /// Maximum length of a bounty description
+    pub max_bounty_description_len: u32,
+    /// Smallest reward a bounty may offer
+    pub min_bounty_reward: i128,
+    /// Time a treasury transaction stays approvable (in seconds)
+    pub treasury_tx_expiry: u64,
+}
+
 /// Event emitted when a guild is created
 #[contracttype]
 #[derive(Clone, Debug)]
@@ -95,3 +264,109 @@ pub struct RoleUpdatedEvent {
     pub old_role: Role,
     pub new_role: Role,
 }
+
+/// Event emitted when a guild's rules are changed
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct GuildRulesUpdatedEvent {
+    pub guild_id: u64,
+    pub rules: GuildRules,
+}
+
+/// Event emitted when a guild's name, description or profile is changed
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct GuildMetadataUpdatedEvent {
+    pub guild_id: u64,
+    pub name: String,
+    pub description: String,
+    pub profile: GuildProfile,
+}
+
+/// Event emitted when the owner nominates a successor (or withdraws the
+/// nomination, in which case `pending_owner` is the owner itself)
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct OwnershipTransferStartedEvent {
+    pub guild_id: u64,
+    pub owner: Address,
+    pub pending_owner: Address,
+}
+
+/// Event emitted when the nominee accepts ownership of a guild
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct OwnershipTransferredEvent {
+    pub guild_id: u64,
+    pub previous_owner: Address,
+    pub new_owner: Address,
+}
+
+/// Event emitted when a member invites an address to a guild
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct InvitationCreatedEvent {
+    pub guild_id: u64,
+    pub invitee: Address,
+    pub role: Role,
+    pub invited_by: Address,
+    pub expires_at: u64,
+}
+
+/// Event emitted when an invitation is accepted, declined or revoked
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct InvitationClosedEvent {
+    pub guild_id: u64,
+    pub invitee: Address,
+}
+
+/// Event emitted when an address asks to join a guild
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct JoinRequestedEvent {
+    pub guild_id: u64,
+    pub applicant: Address,
+    pub message: String,
+}
+
+/// Event emitted when a join request is approved, denied or withdrawn
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct JoinRequestClosedEvent {
+    pub guild_id: u64,
+    pub applicant: Address,
+    /// The member who decided, or the applicant on withdrawal
+    pub closed_by: Address,
+}
+
+/// Event emitted when a custom role is created, changed or deleted
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct CustomRoleUpdatedEvent {
+    pub guild_id: u64,
+    pub name: Symbol,
+    /// The new permissions, or `None` if the role was deleted
+    pub permissions: Option<u32>,
+}
+
+/// Event emitted when a member is given or stripped of a custom role
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct CustomRoleAssignedEvent {
+    pub guild_id: u64,
+    pub address: Address,
+    /// The role now held, or `None` if it was cleared
+    pub role: Option<Symbol>,
+}
+
+/// Event emitted when an address is banned from or readmitted to a guild
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct BanUpdatedEvent {
+    pub guild_id: u64,
+    pub address: Address,
+    pub banned: bool,
+    /// The member who changed the ban
+    pub updated_by: Address,
+}
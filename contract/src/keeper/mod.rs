@@ -0,0 +1,196 @@
+//! Expiry Keeper Module
+//!
+//! Bounties, milestones and treasury transactions lapse at a deadline, but
+//! each module only applies expiry when the item is next touched. This
+//! module keeps every such deadline in a time-bucketed index so that anyone
+//! can act as a keeper and expire a bounded batch of lapsed items in one
+//! call: refunding the escrow of expired bounties, marking milestones
+//! expired and expiring pending treasury transactions.
+//!
+//! # Features
+//! - Deadline index grouped into hourly buckets, oldest first
+//! - Permissionless, bounded sweeps
+//! - Items whose deadline was extended are moved to their new bucket
+//! - Optional per-item keeper reward paid from a funded pool
+//!
+//! A bucket is swept once its whole hour has passed, so items are expired
+//! by the sweeper at most an hour after their deadline. The per-module
+//! lazy checks still apply in the meantime.
+
+pub mod storage;
+pub mod types;
+
+use soroban_sdk::{panic_with_error, token::Client as TokenClient, Address, Env, Symbol};
+
+use crate::admin::require_admin;
+use crate::bounty::expire_due_bounty;
+use crate::keeper::storage::{get_reward, pop_due, schedule, set_reward};
+use crate::keeper::types::{
+    DueItem, DueOutcome, KeeperPoolFundedEvent, KeeperReward, KeeperRewardUpdatedEvent,
+    SweepCompletedEvent, SweepResult,
+};
+use crate::milestone::tracker::expire_due_milestone;
+use crate::treasury::management::expire_due_transaction;
+
+pub use types::KeeperError;
+
+/// Maximum items one sweep takes off the deadline index
+pub const MAX_SWEEP_BATCH: u32 = 50;
+
+/// Expire up to `limit` lapsed bounties, milestones and treasury transactions
+///
+/// Anyone may call this. If a keeper reward is configured, the keeper is
+/// paid for every item expired, as far as the reward pool allows.
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `keeper` - Address of the caller, which receives the reward
+/// * `limit` - Maximum items to process, at most `MAX_SWEEP_BATCH`
+///
+/// # Errors
+/// - `KeeperError::InvalidBatchSize` if `limit` is 0 or above `MAX_SWEEP_BATCH`
+pub fn sweep_expired(env: &Env, keeper: Address, limit: u32) -> Result<SweepResult, KeeperError> {
+    keeper.require_auth();
+
+    if limit == 0 || limit > MAX_SWEEP_BATCH {
+        return Err(KeeperError::InvalidBatchSize);
+    }
+
+    let now = env.ledger().timestamp();
+    let mut result = SweepResult::default();
+
+    while result.processed < limit {
+        let Some(item) = pop_due(env, now) else {
+            break;
+        };
+        result.processed += 1;
+
+        let outcome = match &item {
+            DueItem::Bounty(id) => expire_due_bounty(env, *id),
+            DueItem::Milestone(id) => expire_due_milestone(env, *id),
+            DueItem::Transaction(id) => expire_due_transaction(env, *id),
+        };
+        match outcome {
+            DueOutcome::Expired => result.expired += 1,
+            DueOutcome::Pending(deadline) => schedule(env, item, deadline),
+            DueOutcome::Settled => {}
+        }
+    }
+
+    if result.expired > 0 {
+        if let Some(mut reward) = get_reward(env) {
+            let owed = reward
+                .amount_per_item
+                .saturating_mul(result.expired as i128)
+                .min(reward.pool);
+            if owed > 0 {
+                let client = TokenClient::new(env, &reward.token);
+                client.transfer(&env.current_contract_address(), &keeper, &owed);
+                reward.pool -= owed;
+                set_reward(env, &reward);
+                result.reward = owed;
+            }
+        }
+    }
+
+    env.events().publish(
+        (Symbol::new(env, "keeper"), Symbol::new(env, "swept")),
+        SweepCompletedEvent {
+            keeper,
+            processed: result.processed,
+            expired: result.expired,
+            reward: result.reward,
+        },
+    );
+
+    Ok(result)
+}
+
+/// Set the reward paid to keepers for every item they expire (must be
+/// called by the admin)
+///
+/// A reward of zero turns keeper rewards off. The reward token can only be
+/// changed once the pool is empty.
+///
+/// # Errors
+/// - `AdminError::NotInitialized` if no admin has been recorded
+/// - `KeeperError::InvalidReward` if the reward is negative
+/// - `KeeperError::RewardPoolNotEmpty` if the token changes while the pool
+///   still holds tokens
+pub fn set_keeper_reward(
+    env: &Env,
+    token: Address,
+    amount_per_item: i128,
+) -> Result<bool, KeeperError> {
+    if let Err(e) = require_admin(env) {
+        panic_with_error!(env, e);
+    }
+
+    if amount_per_item < 0 {
+        return Err(KeeperError::InvalidReward);
+    }
+
+    let pool = match get_reward(env) {
+        Some(current) if current.token != token && current.pool > 0 => {
+            return Err(KeeperError::RewardPoolNotEmpty);
+        }
+        Some(current) if current.token == token => current.pool,
+        _ => 0,
+    };
+
+    set_reward(
+        env,
+        &KeeperReward {
+            token: token.clone(),
+            amount_per_item,
+            pool,
+        },
+    );
+
+    env.events().publish(
+        (Symbol::new(env, "keeper"), Symbol::new(env, "reward_set")),
+        KeeperRewardUpdatedEvent {
+            token,
+            amount_per_item,
+        },
+    );
+
+    Ok(true)
+}
+
+/// Add tokens to the keeper reward pool
+///
+/// # Returns
+/// The new pool balance
+///
+/// # Errors
+/// - `KeeperError::RewardNotConfigured` if no keeper reward is set
+/// - `KeeperError::InvalidAmount` if the amount is not positive
+pub fn fund_keeper_pool(env: &Env, funder: Address, amount: i128) -> Result<i128, KeeperError> {
+    funder.require_auth();
+
+    let mut reward = get_reward(env).ok_or(KeeperError::RewardNotConfigured)?;
+    if amount <= 0 {
+        return Err(KeeperError::InvalidAmount);
+    }
+
+    let client = TokenClient::new(env, &reward.token);
+    client.transfer(&funder, &env.current_contract_address(), &amount);
+
+    reward.pool += amount;
+    set_reward(env, &reward);
+
+    env.events().publish(
+        (Symbol::new(env, "keeper"), Symbol::new(env, "pool_funded")),
+        KeeperPoolFundedEvent {
+            funder,
+            amount,
+            pool: reward.pool,
+        },
+    );
+
+    Ok(reward.pool)
+}
+
+#[cfg(test)]
+mod tests;
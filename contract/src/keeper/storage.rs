@@ -0,0 +1,91 @@
+use soroban_sdk::{contracttype, Env, Vec};
+
+use crate::keeper::types::{DueItem, KeeperReward};
+use crate::paging;
+use crate::ttl;
+
+/// Length of the time window grouped into one bucket of the deadline index
+pub const BUCKET_SECONDS: u64 = 3_600;
+
+/// Storage keys for the deadline index and the keeper reward
+#[contracttype]
+pub enum KeeperStorageKey {
+    DueBuckets,           // Vec<u64>, bucket numbers holding items, ascending
+    BucketLen(u64),       // number of items in a bucket
+    BucketPage(u64, u32), // Vec<DueItem>
+    Reward,               // KeeperReward, in instance storage
+}
+
+/// Add an item to the deadline index
+pub fn schedule(env: &Env, item: DueItem, deadline: u64) {
+    let bucket = deadline / BUCKET_SECONDS;
+
+    let position = paging::push(
+        env,
+        &KeeperStorageKey::BucketLen(bucket),
+        |page| KeeperStorageKey::BucketPage(bucket, page),
+        item,
+    );
+    if position > 0 {
+        return;
+    }
+
+    // First item of the bucket: record the bucket, keeping the list sorted
+    let mut buckets = get_due_buckets(env);
+    let mut index = buckets.len();
+    while index > 0 && buckets.get_unchecked(index - 1) > bucket {
+        index -= 1;
+    }
+    buckets.insert(index, bucket);
+    ttl::set_persistent(env, &KeeperStorageKey::DueBuckets, &buckets);
+}
+
+/// Take the next item from a bucket whose whole window is before `now`
+pub fn pop_due(env: &Env, now: u64) -> Option<DueItem> {
+    let mut buckets = get_due_buckets(env);
+
+    while let Some(bucket) = buckets.first() {
+        if (bucket + 1) * BUCKET_SECONDS > now {
+            return None;
+        }
+
+        let item = paging::pop(env, &KeeperStorageKey::BucketLen(bucket), |page| {
+            KeeperStorageKey::BucketPage(bucket, page)
+        });
+        if paging::len(env, &KeeperStorageKey::BucketLen(bucket)) == 0 {
+            buckets.pop_front();
+            ttl::set_persistent(env, &KeeperStorageKey::DueBuckets, &buckets);
+        }
+        if item.is_some() {
+            return item;
+        }
+    }
+    None
+}
+
+/// Number of items in the deadline index that are due by `now`
+pub fn count_due(env: &Env, now: u64) -> u32 {
+    let mut count = 0;
+    for bucket in get_due_buckets(env).iter() {
+        if (bucket + 1) * BUCKET_SECONDS > now {
+            break;
+        }
+        count += paging::len(env, &KeeperStorageKey::BucketLen(bucket));
+    }
+    count
+}
+
+fn get_due_buckets(env: &Env) -> Vec<u64> {
+    ttl::get_persistent(env, &KeeperStorageKey::DueBuckets).unwrap_or_else(|| Vec::new(env))
+}
+
+pub fn get_reward(env: &Env) -> Option<KeeperReward> {
+    env.storage().instance().get(&KeeperStorageKey::Reward)
+}
+
+pub fn set_reward(env: &Env, reward: &KeeperReward) {
+    env.storage()
+        .instance()
+        .set(&KeeperStorageKey::Reward, reward);
+    ttl::extend_instance(env);
+}
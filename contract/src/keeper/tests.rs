@@ -0,0 +1,319 @@
+use crate::bounty::types::{AssignmentMode, BountyStatus};
+use crate::keeper::storage::BUCKET_SECONDS;
+use crate::milestone::types::{MilestoneInput, MilestoneStatus};
+use crate::treasury::types::TransactionStatus;
+use crate::StellarGuildsContract;
+use crate::StellarGuildsContractClient;
+use soroban_sdk::testutils::{Address as _, Ledger, LedgerInfo};
+use soroban_sdk::{token, Address, Env, String, Vec};
+
+const WEEK: u64 = 7 * 24 * 60 * 60;
+
+fn setup_env() -> Env {
+    let env = Env::default();
+    env.budget().reset_unlimited();
+    env
+}
+
+fn set_ledger_timestamp(env: &Env, timestamp: u64) {
+    env.ledger().set(LedgerInfo {
+        timestamp,
+        protocol_version: 20,
+        sequence_number: 0,
+        network_id: Default::default(),
+        base_reserve: 10,
+        min_temp_entry_ttl: 100,
+        min_persistent_entry_ttl: 100,
+        max_entry_ttl: 1000000,
+    });
+}
+
+fn register_and_init_contract(env: &Env, admin: &Address) -> Address {
+    let contract_id = env.register_contract(None, StellarGuildsContract);
+    let client = StellarGuildsContractClient::new(env, &contract_id);
+    client.mock_all_auths().initialize(admin);
+    contract_id
+}
+
+fn setup_guild(client: &StellarGuildsContractClient<'_>, env: &Env, owner: &Address) -> u64 {
+    let name = String::from_str(env, "Test Guild");
+    let description = String::from_str(env, "A test guild");
+    client.create_guild(&name, &description, owner)
+}
+
+fn create_token(env: &Env, admin: &Address) -> Address {
+    env.register_stellar_asset_contract_v2(admin.clone())
+        .address()
+}
+
+fn mint(env: &Env, token: &Address, to: &Address, amount: i128) {
+    token::StellarAssetClient::new(env, token).mint(to, &amount);
+}
+
+fn balance(env: &Env, token: &Address, addr: &Address) -> i128 {
+    token::Client::new(env, token).balance(addr)
+}
+
+fn create_bounty(
+    client: &StellarGuildsContractClient<'_>,
+    env: &Env,
+    guild_id: u64,
+    owner: &Address,
+    token: &Address,
+    reward: i128,
+) -> u64 {
+    client.create_bounty(
+        &guild_id,
+        owner,
+        &String::from_str(env, "Task"),
+        &String::from_str(env, "Description"),
+        &reward,
+        token,
+        &(1000 + WEEK),
+        &AssignmentMode::FirstCome,
+    )
+}
+
+/// A timestamp by which the bucket holding `deadline` has been swept
+fn after_bucket(deadline: u64) -> u64 {
+    (deadline / BUCKET_SECONDS + 1) * BUCKET_SECONDS
+}
+
+#[test]
+fn test_sweep_expires_bounty_and_refunds_funder() {
+    let env = setup_env();
+    let owner = Address::generate(&env);
+    let funder = Address::generate(&env);
+    let keeper = Address::generate(&env);
+
+    set_ledger_timestamp(&env, 1000);
+    env.mock_all_auths();
+
+    let contract_id = register_and_init_contract(&env, &owner);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+    let guild_id = setup_guild(&client, &env, &owner);
+    let token = create_token(&env, &owner);
+    mint(&env, &token, &funder, 500);
+
+    let bounty_id = create_bounty(&client, &env, guild_id, &owner, &token, 500);
+    client.fund_bounty(&bounty_id, &funder, &500i128);
+
+    // Past the deadline but inside its bucket: not swept yet
+    set_ledger_timestamp(&env, 1000 + WEEK + 1);
+    assert_eq!(client.get_due_expiry_count(), 0);
+    assert_eq!(client.sweep_expired(&keeper, &10).processed, 0);
+
+    set_ledger_timestamp(&env, after_bucket(1000 + WEEK));
+    assert_eq!(client.get_due_expiry_count(), 1);
+
+    let result = client.sweep_expired(&keeper, &10);
+    assert_eq!(result.processed, 1);
+    assert_eq!(result.expired, 1);
+    assert_eq!(result.reward, 0);
+
+    assert_eq!(client.get_bounty(&bounty_id).status, BountyStatus::Expired);
+    assert_eq!(balance(&env, &token, &funder), 500);
+    assert_eq!(client.get_due_expiry_count(), 0);
+}
+
+#[test]
+fn test_sweep_respects_batch_limit() {
+    let env = setup_env();
+    let owner = Address::generate(&env);
+    let keeper = Address::generate(&env);
+
+    set_ledger_timestamp(&env, 1000);
+    env.mock_all_auths();
+
+    let contract_id = register_and_init_contract(&env, &owner);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+    let guild_id = setup_guild(&client, &env, &owner);
+    let token = create_token(&env, &owner);
+
+    for _ in 0..3 {
+        create_bounty(&client, &env, guild_id, &owner, &token, 0);
+    }
+
+    set_ledger_timestamp(&env, after_bucket(1000 + WEEK));
+    assert_eq!(client.sweep_expired(&keeper, &2).expired, 2);
+    assert_eq!(client.sweep_expired(&keeper, &2).expired, 1);
+    assert_eq!(client.sweep_expired(&keeper, &2).processed, 0);
+}
+
+#[test]
+fn test_sweep_skips_settled_bounty() {
+    let env = setup_env();
+    let owner = Address::generate(&env);
+    let keeper = Address::generate(&env);
+
+    set_ledger_timestamp(&env, 1000);
+    env.mock_all_auths();
+
+    let contract_id = register_and_init_contract(&env, &owner);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+    let guild_id = setup_guild(&client, &env, &owner);
+    let token = create_token(&env, &owner);
+
+    let bounty_id = create_bounty(&client, &env, guild_id, &owner, &token, 0);
+    client.cancel_bounty(&bounty_id, &owner);
+
+    set_ledger_timestamp(&env, after_bucket(1000 + WEEK));
+    let result = client.sweep_expired(&keeper, &10);
+    assert_eq!(result.processed, 1);
+    assert_eq!(result.expired, 0);
+    assert_eq!(
+        client.get_bounty(&bounty_id).status,
+        BountyStatus::Cancelled
+    );
+}
+
+#[test]
+fn test_sweep_moves_extended_milestone_to_new_deadline() {
+    let env = setup_env();
+    let owner = Address::generate(&env);
+    let contributor = Address::generate(&env);
+    let keeper = Address::generate(&env);
+
+    set_ledger_timestamp(&env, 1000);
+    env.mock_all_auths();
+
+    let contract_id = register_and_init_contract(&env, &owner);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+    let guild_id = setup_guild(&client, &env, &owner);
+
+    let mut milestones: Vec<MilestoneInput> = Vec::new(&env);
+    milestones.push_back(MilestoneInput {
+        title: String::from_str(&env, "Phase 1"),
+        description: String::from_str(&env, "Initial development"),
+        payment_amount: 100,
+        deadline: 1000 + WEEK,
+    });
+    client.create_project(
+        &guild_id,
+        &contributor,
+        &milestones,
+        &100i128,
+        &1u64,
+        &None,
+        &true,
+    );
+    let milestone_id = 1u64;
+
+    client.extend_milestone_deadline(&milestone_id, &(1000 + 2 * WEEK), &owner);
+
+    set_ledger_timestamp(&env, after_bucket(1000 + WEEK));
+    let result = client.sweep_expired(&keeper, &10);
+    assert_eq!(result.processed, 1);
+    assert_eq!(result.expired, 0);
+    assert_eq!(
+        client.get_milestone(&milestone_id).status,
+        MilestoneStatus::Pending
+    );
+
+    set_ledger_timestamp(&env, after_bucket(1000 + 2 * WEEK));
+    let result = client.sweep_expired(&keeper, &10);
+    assert_eq!(result.expired, 1);
+    assert_eq!(
+        client.get_milestone(&milestone_id).status,
+        MilestoneStatus::Expired
+    );
+}
+
+#[test]
+fn test_sweep_expires_pending_withdrawal() {
+    let env = setup_env();
+    let owner = Address::generate(&env);
+    let signer = Address::generate(&env);
+    let keeper = Address::generate(&env);
+
+    set_ledger_timestamp(&env, 1000);
+    env.mock_all_auths();
+
+    let contract_id = register_and_init_contract(&env, &owner);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+    let guild_id = setup_guild(&client, &env, &owner);
+
+    let mut signers = Vec::new(&env);
+    signers.push_back(owner.clone());
+    signers.push_back(signer.clone());
+    let treasury_id = client.initialize_treasury(&guild_id, &signers, &2u32);
+    client.deposit_treasury(&treasury_id, &owner, &1000i128, &None);
+
+    let tx_id = client.propose_withdrawal(
+        &treasury_id,
+        &owner,
+        &Address::generate(&env),
+        &100i128,
+        &None,
+        &String::from_str(&env, "expense"),
+    );
+
+    set_ledger_timestamp(&env, after_bucket(1000 + WEEK));
+    assert_eq!(client.sweep_expired(&keeper, &10).expired, 1);
+
+    let history = client.get_transaction_history(&treasury_id, &10);
+    let tx = history.iter().find(|t| t.id == tx_id).unwrap();
+    assert_eq!(tx.status, TransactionStatus::Expired);
+}
+
+#[test]
+fn test_keeper_reward_is_paid_until_pool_runs_out() {
+    let env = setup_env();
+    let owner = Address::generate(&env);
+    let keeper = Address::generate(&env);
+
+    set_ledger_timestamp(&env, 1000);
+    env.mock_all_auths();
+
+    let contract_id = register_and_init_contract(&env, &owner);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+    let guild_id = setup_guild(&client, &env, &owner);
+    let token = create_token(&env, &owner);
+    mint(&env, &token, &owner, 15);
+
+    client.set_keeper_reward(&token, &10i128);
+    assert_eq!(client.fund_keeper_pool(&owner, &15i128), 15);
+
+    for _ in 0..3 {
+        create_bounty(&client, &env, guild_id, &owner, &token, 0);
+    }
+
+    set_ledger_timestamp(&env, after_bucket(1000 + WEEK));
+    assert_eq!(client.sweep_expired(&keeper, &1).reward, 10);
+    assert_eq!(client.sweep_expired(&keeper, &2).reward, 5);
+    assert_eq!(balance(&env, &token, &keeper), 15);
+    assert_eq!(client.get_keeper_reward().unwrap().pool, 0);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #702)")] // RewardPoolNotEmpty
+fn test_keeper_reward_token_locked_while_pool_funded() {
+    let env = setup_env();
+    let owner = Address::generate(&env);
+
+    set_ledger_timestamp(&env, 1000);
+    env.mock_all_auths();
+
+    let contract_id = register_and_init_contract(&env, &owner);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+    let token = create_token(&env, &owner);
+    mint(&env, &token, &owner, 100);
+
+    client.set_keeper_reward(&token, &10i128);
+    client.fund_keeper_pool(&owner, &100i128);
+    client.set_keeper_reward(&create_token(&env, &owner), &10i128);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #700)")] // InvalidBatchSize
+fn test_sweep_zero_limit_fails() {
+    let env = setup_env();
+    let owner = Address::generate(&env);
+
+    set_ledger_timestamp(&env, 1000);
+    env.mock_all_auths();
+
+    let contract_id = register_and_init_contract(&env, &owner);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+    client.sweep_expired(&Address::generate(&env), &0);
+}
@@ -0,0 +1,86 @@
+use soroban_sdk::{contracterror, contracttype, Address};
+
+/// Error types for the expiry keeper
+///
+/// Codes live in the 700-799 range so they never collide with the errors of
+/// other modules when surfaced to clients.
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum KeeperError {
+    InvalidBatchSize = 700,
+    InvalidReward = 701,
+    RewardPoolNotEmpty = 702,
+    RewardNotConfigured = 703,
+    InvalidAmount = 704,
+}
+
+/// Something that lapses at a deadline and is expired by the sweeper
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum DueItem {
+    Bounty(u64),
+    Milestone(u64),
+    Transaction(u64),
+}
+
+/// What a module found when asked to expire one of its items
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum DueOutcome {
+    /// The item lapsed and was expired now
+    Expired,
+    /// The item was moved to a later deadline and is not due yet
+    Pending(u64),
+    /// The item no longer needs expiring: it finished, was cancelled, was
+    /// already expired or no longer exists
+    Settled,
+}
+
+/// Reward paid to keepers for every item they expire, out of a funded pool
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct KeeperReward {
+    pub token: Address,
+    pub amount_per_item: i128,
+    /// Tokens left in the pool
+    pub pool: i128,
+}
+
+/// Outcome of one sweep
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct SweepResult {
+    /// Items taken off the deadline index
+    pub processed: u32,
+    /// Items that were expired
+    pub expired: u32,
+    /// Reward paid to the keeper
+    pub reward: i128,
+}
+
+/// Event emitted when a keeper sweeps expired items
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SweepCompletedEvent {
+    pub keeper: Address,
+    pub processed: u32,
+    pub expired: u32,
+    pub reward: i128,
+}
+
+/// Event emitted when the admin changes the keeper reward
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct KeeperRewardUpdatedEvent {
+    pub token: Address,
+    pub amount_per_item: i128,
+}
+
+/// Event emitted when tokens are added to the keeper reward pool
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct KeeperPoolFundedEvent {
+    pub funder: Address,
+    pub amount: i128,
+    pub pool: i128,
+}
@@ -17,7 +17,7 @@ pub use tracker::{
     get_milestone_view, get_project_progress, reject_milestone, release_milestone_payment,
     start_milestone, submit_milestone,
 };
-pub use types::{Milestone, MilestoneInput, MilestoneStatus, Project, ProjectStatus};
+pub use types::{Milestone, MilestoneError, MilestoneInput};
 
 // Tests are disabled pending treasury integration
 #[cfg(test)]
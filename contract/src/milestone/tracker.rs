@@ -1,38 +1,87 @@
-use soroban_sdk::{Address, Env, String, Vec};
+use soroban_sdk::{panic_with_error, Address, Env, String, Vec};
 
-use crate::guild::membership::has_permission;
-use crate::guild::types::Role;
+use crate::guild::roles::has_guild_permission;
+use crate::guild::types::Permission;
+use crate::keeper::storage::schedule;
+use crate::keeper::types::{DueItem, DueOutcome};
 use crate::milestone::storage::{
     append_milestone_to_project, get_milestone, get_next_milestone_id, get_next_project_id,
     get_project, get_project_milestone_ids, store_milestone, store_project,
 };
 use crate::milestone::types::{
-    Milestone, MilestoneAddedEvent, MilestoneInput, MilestonePaymentReleasedEvent,
+    Milestone, MilestoneAddedEvent, MilestoneError, MilestoneInput, MilestonePaymentReleasedEvent,
     MilestoneRejectedEvent, MilestoneStatus, MilestoneStatusChangedEvent, MilestoneSubmittedEvent,
     Project, ProjectCreatedEvent, ProjectStatus, ProjectStatusChangedEvent,
 };
+use crate::reputation;
 use crate::treasury::execute_milestone_payment;
 
-fn assert_project_active(project: &Project, env: &Env) {
+fn assert_project_active(project: &Project) -> Result<(), MilestoneError> {
     if project.status != ProjectStatus::Active {
-        panic!("project is not active");
+        return Err(MilestoneError::ProjectNotActive);
     }
 
     // Basic safety: budget sanity
     if project.allocated_amount > project.total_amount {
-        panic!("project over-allocated");
+        return Err(MilestoneError::ProjectOverAllocated);
     }
+    Ok(())
 }
 
-fn ensure_not_expired(env: &Env, milestone: &mut Milestone) {
+fn ensure_not_expired(env: &Env, milestone: &mut Milestone) -> Result<(), MilestoneError> {
     let now = env.ledger().timestamp();
     if now > milestone.deadline && milestone.status != MilestoneStatus::Approved {
         milestone.status = MilestoneStatus::Expired;
         store_milestone(env, milestone);
-        panic!("milestone expired");
+        return Err(MilestoneError::MilestoneExpired);
     }
+    Ok(())
 }
 
+/// Expire a milestone taken off the keeper's deadline index
+pub(crate) fn expire_due_milestone(env: &Env, milestone_id: u64) -> DueOutcome {
+    let Some(mut milestone) = get_milestone(env, milestone_id) else {
+        return DueOutcome::Settled;
+    };
+    if matches!(
+        milestone.status,
+        MilestoneStatus::Approved | MilestoneStatus::Expired
+    ) {
+        return DueOutcome::Settled;
+    }
+    match get_project(env, milestone.project_id) {
+        Some(project) if project.status == ProjectStatus::Active => {}
+        _ => return DueOutcome::Settled,
+    }
+    if env.ledger().timestamp() <= milestone.deadline {
+        return DueOutcome::Pending(milestone.deadline);
+    }
+
+    let old_status = milestone.status.clone();
+    milestone.status = MilestoneStatus::Expired;
+    milestone.last_updated_at = env.ledger().timestamp();
+    store_milestone(env, &milestone);
+
+    let event = MilestoneStatusChangedEvent {
+        project_id: milestone.project_id,
+        milestone_id,
+        old_status,
+        new_status: MilestoneStatus::Expired,
+    };
+    env.events().publish(("MilestoneStatusChanged",), event);
+
+    DueOutcome::Expired
+}
+
+fn load_project(env: &Env, project_id: u64) -> Result<Project, MilestoneError> {
+    get_project(env, project_id).ok_or(MilestoneError::ProjectNotFound)
+}
+
+fn load_milestone(env: &Env, milestone_id: u64) -> Result<Milestone, MilestoneError> {
+    get_milestone(env, milestone_id).ok_or(MilestoneError::MilestoneNotFound)
+}
+
+#[allow(clippy::too_many_arguments)]
 pub fn create_project(
     env: &Env,
     guild_id: u64,
@@ -42,14 +91,14 @@ pub fn create_project(
     treasury_id: u64,
     token: Option<Address>,
     is_sequential: bool,
-) -> u64 {
+) -> Result<u64, MilestoneError> {
     contributor.require_auth();
 
     if total_amount <= 0 {
-        panic!("total_amount must be positive");
+        return Err(MilestoneError::InvalidAmount);
     }
     if milestones.is_empty() {
-        panic!("at least one milestone required");
+        return Err(MilestoneError::NoMilestones);
     }
 
     let now = env.ledger().timestamp();
@@ -58,24 +107,24 @@ pub fn create_project(
     let mut allocated: i128 = 0;
     for input in milestones.iter() {
         if input.payment_amount <= 0 {
-            panic!("milestone payment must be positive");
+            return Err(MilestoneError::InvalidAmount);
         }
-        if input.title.len() == 0 || input.title.len() > 256 {
-            panic!("milestone title length invalid");
+        if input.title.is_empty() || input.title.len() > 256 {
+            return Err(MilestoneError::InvalidTitle);
         }
         if input.description.len() > 1024 {
-            panic!("milestone description too long");
+            return Err(MilestoneError::DescriptionTooLong);
         }
         if input.deadline <= now {
-            panic!("milestone deadline must be in the future");
+            return Err(MilestoneError::InvalidDeadline);
         }
         allocated = allocated
             .checked_add(input.payment_amount)
-            .expect("overflow");
+            .ok_or(MilestoneError::ArithmeticOverflow)?;
     }
 
     if allocated > total_amount {
-        panic!("allocated milestone budget exceeds project total");
+        return Err(MilestoneError::BudgetExceeded);
     }
 
     let project_id = get_next_project_id(env);
@@ -97,13 +146,12 @@ pub fn create_project(
     store_project(env, &project);
 
     // Create milestones
-    let mut order: u32 = 1;
-    for input in milestones.iter() {
+    for (index, input) in milestones.iter().enumerate() {
         let milestone_id = get_next_milestone_id(env);
         let milestone = Milestone {
             id: milestone_id,
             project_id,
-            order,
+            order: index as u32 + 1,
             title: input.title.clone(),
             description: input.description.clone(),
             payment_amount: input.payment_amount,
@@ -118,6 +166,7 @@ pub fn create_project(
         };
         store_milestone(env, &milestone);
         append_milestone_to_project(env, project_id, milestone_id);
+        schedule(env, DueItem::Milestone(milestone_id), milestone.deadline);
 
         let event = MilestoneAddedEvent {
             project_id,
@@ -127,8 +176,6 @@ pub fn create_project(
             deadline: milestone.deadline,
         };
         env.events().publish(("MilestoneAdded",), event);
-
-        order += 1;
     }
 
     let project_event = ProjectCreatedEvent {
@@ -142,7 +189,7 @@ pub fn create_project(
     };
     env.events().publish(("ProjectCreated",), project_event);
 
-    project_id
+    Ok(project_id)
 }
 
 pub fn add_milestone(
@@ -153,43 +200,43 @@ pub fn add_milestone(
     amount: i128,
     deadline: u64,
     caller: Address,
-) -> u64 {
+) -> Result<u64, MilestoneError> {
     caller.require_auth();
 
-    let mut project = get_project(env, project_id).expect("project not found");
-    assert_project_active(&project, env);
+    let mut project = load_project(env, project_id)?;
+    assert_project_active(&project)?;
 
-    // Only guild admins can add milestones
-    if !has_permission(env, project.guild_id, caller, Role::Admin) {
-        panic!("caller must be guild admin");
+    // Only holders of `ManageProjects` can add milestones
+    if !has_guild_permission(env, project.guild_id, caller, Permission::ManageProjects) {
+        return Err(MilestoneError::Unauthorized);
     }
 
     let now = env.ledger().timestamp();
     if amount <= 0 {
-        panic!("amount must be positive");
+        return Err(MilestoneError::InvalidAmount);
     }
     if deadline <= now {
-        panic!("deadline must be in the future");
+        return Err(MilestoneError::InvalidDeadline);
     }
-    if title.len() == 0 || title.len() > 256 {
-        panic!("milestone title length invalid");
+    if title.is_empty() || title.len() > 256 {
+        return Err(MilestoneError::InvalidTitle);
     }
     if description.len() > 1024 {
-        panic!("milestone description too long");
+        return Err(MilestoneError::DescriptionTooLong);
     }
 
     let new_allocated = project
         .allocated_amount
         .checked_add(amount)
-        .expect("overflow");
+        .ok_or(MilestoneError::ArithmeticOverflow)?;
     if new_allocated > project.total_amount {
-        panic!("allocated milestone budget exceeds project total");
+        return Err(MilestoneError::BudgetExceeded);
     }
     project.allocated_amount = new_allocated;
     store_project(env, &project);
 
     let milestone_id = get_next_milestone_id(env);
-    let order = get_project_milestone_ids(env, project_id).len() as u32 + 1;
+    let order = get_project_milestone_ids(env, project_id).len() + 1;
 
     let milestone = Milestone {
         id: milestone_id,
@@ -210,6 +257,7 @@ pub fn add_milestone(
 
     store_milestone(env, &milestone);
     append_milestone_to_project(env, project_id, milestone_id);
+    schedule(env, DueItem::Milestone(milestone_id), deadline);
 
     let event = MilestoneAddedEvent {
         project_id,
@@ -220,36 +268,39 @@ pub fn add_milestone(
     };
     env.events().publish(("MilestoneAdded",), event);
 
-    milestone_id
+    Ok(milestone_id)
 }
 
-pub fn start_milestone(env: &Env, milestone_id: u64, contributor: Address) -> bool {
+pub fn start_milestone(
+    env: &Env,
+    milestone_id: u64,
+    contributor: Address,
+) -> Result<bool, MilestoneError> {
     contributor.require_auth();
 
-    let mut milestone = get_milestone(env, milestone_id).expect("milestone not found");
-    let project = get_project(env, milestone.project_id).expect("project not found");
+    let mut milestone = load_milestone(env, milestone_id)?;
+    let project = load_project(env, milestone.project_id)?;
 
-    assert_project_active(&project, env);
-    ensure_not_expired(env, &mut milestone);
+    assert_project_active(&project)?;
+    ensure_not_expired(env, &mut milestone)?;
 
     if contributor != project.contributor {
-        panic!("only project contributor can start milestone");
+        return Err(MilestoneError::Unauthorized);
     }
 
     if milestone.status != MilestoneStatus::Pending {
-        panic!("milestone not pending");
+        return Err(MilestoneError::NotPending);
     }
 
     if project.is_sequential {
         let ids = get_project_milestone_ids(env, project.id);
         for id in ids.iter() {
-            let other = get_milestone(env, id).expect("milestone missing");
-            if other.order + 1 == milestone.order {
-                if other.status != MilestoneStatus::Approved
-                    && other.status != MilestoneStatus::Expired
-                {
-                    panic!("previous milestone not completed");
-                }
+            let other = load_milestone(env, id)?;
+            if other.order + 1 == milestone.order
+                && other.status != MilestoneStatus::Approved
+                && other.status != MilestoneStatus::Expired
+            {
+                return Err(MilestoneError::PreviousMilestoneIncomplete);
             }
         }
     }
@@ -267,24 +318,28 @@ pub fn start_milestone(env: &Env, milestone_id: u64, contributor: Address) -> bo
     };
     env.events().publish(("MilestoneStatusChanged",), event);
 
-    true
+    Ok(true)
 }
 
-pub fn submit_milestone(env: &Env, milestone_id: u64, proof_url: String) -> bool {
-    let mut milestone = get_milestone(env, milestone_id).expect("milestone not found");
-    let project = get_project(env, milestone.project_id).expect("project not found");
+pub fn submit_milestone(
+    env: &Env,
+    milestone_id: u64,
+    proof_url: String,
+) -> Result<bool, MilestoneError> {
+    let mut milestone = load_milestone(env, milestone_id)?;
+    let project = load_project(env, milestone.project_id)?;
 
-    assert_project_active(&project, env);
-    ensure_not_expired(env, &mut milestone);
+    assert_project_active(&project)?;
+    ensure_not_expired(env, &mut milestone)?;
 
-    if proof_url.len() == 0 || proof_url.len() > 1024 {
-        panic!("invalid proof url");
+    if proof_url.is_empty() || proof_url.len() > 1024 {
+        return Err(MilestoneError::InvalidProofUrl);
     }
 
     if milestone.status != MilestoneStatus::InProgress
         && milestone.status != MilestoneStatus::Rejected
     {
-        panic!("milestone not in progress or previously rejected");
+        return Err(MilestoneError::NotInProgress);
     }
 
     let now = env.ledger().timestamp();
@@ -313,24 +368,28 @@ pub fn submit_milestone(env: &Env, milestone_id: u64, proof_url: String) -> bool
     env.events()
         .publish(("MilestoneStatusChanged",), status_event);
 
-    true
+    Ok(true)
 }
 
-pub fn approve_milestone(env: &Env, milestone_id: u64, approver: Address) -> bool {
+pub fn approve_milestone(
+    env: &Env,
+    milestone_id: u64,
+    approver: Address,
+) -> Result<bool, MilestoneError> {
     approver.require_auth();
 
-    let mut milestone = get_milestone(env, milestone_id).expect("milestone not found");
-    let mut project = get_project(env, milestone.project_id).expect("project not found");
+    let mut milestone = load_milestone(env, milestone_id)?;
+    let mut project = load_project(env, milestone.project_id)?;
 
-    assert_project_active(&project, env);
-    ensure_not_expired(env, &mut milestone);
+    assert_project_active(&project)?;
+    ensure_not_expired(env, &mut milestone)?;
 
-    if !has_permission(env, project.guild_id, approver, Role::Admin) {
-        panic!("approver must be guild admin");
+    if !has_guild_permission(env, project.guild_id, approver, Permission::ApproveWork) {
+        return Err(MilestoneError::Unauthorized);
     }
 
     if milestone.status != MilestoneStatus::Submitted {
-        panic!("milestone not submitted");
+        return Err(MilestoneError::NotSubmitted);
     }
 
     let old_status = milestone.status.clone();
@@ -347,31 +406,38 @@ pub fn approve_milestone(env: &Env, milestone_id: u64, approver: Address) -> boo
     env.events()
         .publish(("MilestoneStatusChanged",), status_event);
 
+    reputation::record_milestone_approved(env, project.guild_id, &project.contributor);
+
     // Automatic payment release (Option B via treasury)
-    let _ = release_milestone_payment_internal(env, &mut project, &mut milestone);
+    release_milestone_payment_internal(env, &mut project, &mut milestone)?;
 
-    true
+    Ok(true)
 }
 
-pub fn reject_milestone(env: &Env, milestone_id: u64, approver: Address, reason: String) -> bool {
+pub fn reject_milestone(
+    env: &Env,
+    milestone_id: u64,
+    approver: Address,
+    reason: String,
+) -> Result<bool, MilestoneError> {
     approver.require_auth();
 
-    let mut milestone = get_milestone(env, milestone_id).expect("milestone not found");
-    let project = get_project(env, milestone.project_id).expect("project not found");
+    let mut milestone = load_milestone(env, milestone_id)?;
+    let project = load_project(env, milestone.project_id)?;
 
-    assert_project_active(&project, env);
-    ensure_not_expired(env, &mut milestone);
+    assert_project_active(&project)?;
+    ensure_not_expired(env, &mut milestone)?;
 
-    if !has_permission(env, project.guild_id, approver, Role::Admin) {
-        panic!("approver must be guild admin");
+    if !has_guild_permission(env, project.guild_id, approver, Permission::ApproveWork) {
+        return Err(MilestoneError::Unauthorized);
     }
 
     if milestone.status != MilestoneStatus::Submitted {
-        panic!("milestone not submitted");
+        return Err(MilestoneError::NotSubmitted);
     }
 
-    if reason.len() == 0 || reason.len() > 512 {
-        panic!("invalid rejection reason");
+    if reason.is_empty() || reason.len() > 512 {
+        return Err(MilestoneError::InvalidReason);
     }
 
     let old_status = milestone.status.clone();
@@ -386,6 +452,8 @@ pub fn reject_milestone(env: &Env, milestone_id: u64, approver: Address, reason:
     };
     env.events().publish(("MilestoneRejected",), reject_event);
 
+    reputation::record_milestone_rejected(env, project.guild_id, &project.contributor);
+
     let status_event = MilestoneStatusChangedEvent {
         project_id: project.id,
         milestone_id,
@@ -395,12 +463,12 @@ pub fn reject_milestone(env: &Env, milestone_id: u64, approver: Address, reason:
     env.events()
         .publish(("MilestoneStatusChanged",), status_event);
 
-    true
+    Ok(true)
 }
 
 pub fn get_project_progress(env: &Env, project_id: u64) -> (u32, u32, u32) {
     let ids = get_project_milestone_ids(env, project_id);
-    let total = ids.len() as u32;
+    let total = ids.len();
     if total == 0 {
         return (0, 0, 0);
     }
@@ -418,15 +486,15 @@ pub fn get_project_progress(env: &Env, project_id: u64) -> (u32, u32, u32) {
     (completed, total, percentage)
 }
 
-pub fn get_milestone_view(env: &Env, milestone_id: u64) -> Milestone {
-    get_milestone(env, milestone_id).expect("milestone not found")
+pub fn get_milestone_view(env: &Env, milestone_id: u64) -> Result<Milestone, MilestoneError> {
+    load_milestone(env, milestone_id)
 }
 
-pub fn release_milestone_payment(env: &Env, milestone_id: u64) -> bool {
-    let mut milestone = get_milestone(env, milestone_id).expect("milestone not found");
-    let mut project = get_project(env, milestone.project_id).expect("project not found");
+pub fn release_milestone_payment(env: &Env, milestone_id: u64) -> Result<bool, MilestoneError> {
+    let mut milestone = load_milestone(env, milestone_id)?;
+    let mut project = load_project(env, milestone.project_id)?;
 
-    assert_project_active(&project, env);
+    assert_project_active(&project)?;
 
     release_milestone_payment_internal(env, &mut project, &mut milestone)
 }
@@ -435,33 +503,37 @@ fn release_milestone_payment_internal(
     env: &Env,
     project: &mut Project,
     milestone: &mut Milestone,
-) -> bool {
+) -> Result<bool, MilestoneError> {
     if milestone.status != MilestoneStatus::Approved {
-        panic!("milestone not approved");
+        return Err(MilestoneError::NotApproved);
     }
     if milestone.is_payment_released {
-        panic!("milestone payment already released");
+        return Err(MilestoneError::PaymentAlreadyReleased);
     }
 
     let new_released = project
         .released_amount
         .checked_add(milestone.payment_amount)
-        .expect("overflow");
+        .ok_or(MilestoneError::ArithmeticOverflow)?;
     if new_released > project.total_amount {
-        panic!("project budget exceeded");
+        return Err(MilestoneError::BudgetExceeded);
     }
 
-    // Execute payment via treasury helper (Option B)
+    // Execute payment via treasury helper (Option B). Treasury failures keep
+    // their own `TreasuryError` code so clients can tell them apart.
     let token = project.token.clone();
     let amount = milestone.payment_amount;
 
-    execute_milestone_payment(
+    let fees = match execute_milestone_payment(
         env,
         project.treasury_id,
         token.clone(),
         project.contributor.clone(),
         amount,
-    );
+    ) {
+        Ok(fees) => fees,
+        Err(e) => panic_with_error!(env, e),
+    };
 
     project.released_amount = new_released;
     milestone.is_payment_released = true;
@@ -474,9 +546,11 @@ fn release_milestone_payment_internal(
         project_id: project.id,
         milestone_id: milestone.id,
         treasury_id: project.treasury_id,
-        amount,
+        amount: amount - fees.total(),
         token,
         recipient: project.contributor.clone(),
+        guild_fee: fees.guild_fee,
+        platform_fee: fees.platform_fee,
     };
     env.events().publish(("MilestonePaymentReleased",), event);
 
@@ -505,7 +579,7 @@ fn release_milestone_payment_internal(
         env.events().publish(("ProjectStatusChanged",), pe);
     }
 
-    true
+    Ok(true)
 }
 
 pub fn extend_milestone_deadline(
@@ -513,45 +587,46 @@ pub fn extend_milestone_deadline(
     milestone_id: u64,
     new_deadline: u64,
     caller: Address,
-) -> bool {
+) -> Result<bool, MilestoneError> {
     caller.require_auth();
 
-    let mut milestone = get_milestone(env, milestone_id).expect("milestone not found");
-    let mut project = get_project(env, milestone.project_id).expect("project not found");
+    let mut milestone = load_milestone(env, milestone_id)?;
+    let project = load_project(env, milestone.project_id)?;
 
-    if !has_permission(env, project.guild_id, caller, Role::Admin) {
-        panic!("caller must be guild admin");
+    if !has_guild_permission(env, project.guild_id, caller, Permission::ManageProjects) {
+        return Err(MilestoneError::Unauthorized);
     }
 
     let now = env.ledger().timestamp();
     if new_deadline <= now || new_deadline <= milestone.deadline {
-        panic!("new deadline must be in the future and after current deadline");
+        return Err(MilestoneError::InvalidDeadline);
     }
 
     milestone.deadline = new_deadline;
     milestone.last_updated_at = now;
     if milestone.status == MilestoneStatus::Expired {
         milestone.status = MilestoneStatus::Pending;
+        // An expired milestone may already be off the keeper's index
+        schedule(env, DueItem::Milestone(milestone_id), new_deadline);
     }
     store_milestone(env, &milestone);
 
     // project status unchanged; event could be added if needed
-    let _ = &mut project; // silence unused for now
 
-    true
+    Ok(true)
 }
 
-pub fn cancel_project(env: &Env, project_id: u64, caller: Address) -> bool {
+pub fn cancel_project(env: &Env, project_id: u64, caller: Address) -> Result<bool, MilestoneError> {
     caller.require_auth();
 
-    let mut project = get_project(env, project_id).expect("project not found");
+    let mut project = load_project(env, project_id)?;
 
-    if !has_permission(env, project.guild_id, caller, Role::Admin) {
-        panic!("caller must be guild admin");
+    if !has_guild_permission(env, project.guild_id, caller, Permission::ManageProjects) {
+        return Err(MilestoneError::Unauthorized);
     }
 
     if project.status == ProjectStatus::Cancelled {
-        return true;
+        return Ok(true);
     }
 
     let old_status = project.status.clone();
@@ -565,5 +640,5 @@ pub fn cancel_project(env: &Env, project_id: u64, caller: Address) -> bool {
     };
     env.events().publish(("ProjectStatusChanged",), event);
 
-    true
+    Ok(true)
 }
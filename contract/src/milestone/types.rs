@@ -1,4 +1,35 @@
-use soroban_sdk::{contracttype, Address, String};
+use soroban_sdk::{contracterror, contracttype, Address, String};
+
+/// Error types for project and milestone operations
+///
+/// Codes live in the 500-599 range so they never collide with the errors of
+/// other modules when surfaced to clients.
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum MilestoneError {
+    ProjectNotFound = 500,
+    MilestoneNotFound = 501,
+    ProjectNotActive = 502,
+    ProjectOverAllocated = 503,
+    MilestoneExpired = 504,
+    InvalidAmount = 505,
+    NoMilestones = 506,
+    InvalidTitle = 507,
+    DescriptionTooLong = 508,
+    InvalidDeadline = 509,
+    BudgetExceeded = 510,
+    Unauthorized = 511,
+    NotPending = 512,
+    PreviousMilestoneIncomplete = 513,
+    InvalidProofUrl = 514,
+    NotInProgress = 515,
+    NotSubmitted = 516,
+    InvalidReason = 517,
+    NotApproved = 518,
+    PaymentAlreadyReleased = 519,
+    ArithmeticOverflow = 520,
+}
 
 /// Overall status of a project
 #[contracttype]
@@ -132,7 +163,10 @@ pub struct MilestonePaymentReleasedEvent {
     pub project_id: u64,
     pub milestone_id: u64,
     pub treasury_id: u64,
+    /// Amount received by the contributor, after fees
     pub amount: i128,
     pub token: Option<Address>,
     pub recipient: Address,
+    pub guild_fee: i128,
+    pub platform_fee: i128,
 }
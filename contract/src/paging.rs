@@ -0,0 +1,168 @@
+//! Paginated Index Entries
+//!
+//! Enumerable collections (guild rosters, proposal lists, ...) are stored as
+//! a length entry plus fixed-size pages of items, each page under its own
+//! storage key. Appending or removing an item rewrites at most two pages, so
+//! the cost of a write no longer grows with the size of the collection.
+
+use soroban_sdk::{Env, IntoVal, TryFromVal, Val, Vec};
+
+use crate::ttl;
+
+/// Maximum number of items stored in a single page
+pub const PAGE_SIZE: u32 = 50;
+
+/// Number of items in an index
+pub fn len<K>(env: &Env, len_key: &K) -> u32
+where
+    K: IntoVal<Env, Val>,
+{
+    ttl::get_persistent(env, len_key).unwrap_or(0)
+}
+
+/// Read a single page of an index
+pub fn get_page<K, V>(env: &Env, page_key: &K) -> Vec<V>
+where
+    K: IntoVal<Env, Val>,
+    V: IntoVal<Env, Val> + TryFromVal<Env, Val>,
+{
+    ttl::get_persistent(env, page_key).unwrap_or_else(|| Vec::new(env))
+}
+
+/// Append an item and return its position
+pub fn push<K, V, F>(env: &Env, len_key: &K, page_key: F, value: V) -> u32
+where
+    K: IntoVal<Env, Val>,
+    V: IntoVal<Env, Val> + TryFromVal<Env, Val>,
+    F: Fn(u32) -> K,
+{
+    let position = len(env, len_key);
+    let key = page_key(position / PAGE_SIZE);
+
+    let mut page: Vec<V> = get_page(env, &key);
+    page.push_back(value);
+    ttl::set_persistent(env, &key, &page);
+    ttl::set_persistent(env, len_key, &(position + 1));
+
+    position
+}
+
+/// Remove the item at `position` by moving the last item into its place
+///
+/// # Returns
+/// The item that was moved into `position`, if any, so callers can update
+/// whatever position they recorded for it.
+pub fn swap_remove<K, V, F>(env: &Env, len_key: &K, page_key: F, position: u32) -> Option<V>
+where
+    K: IntoVal<Env, Val>,
+    V: IntoVal<Env, Val> + TryFromVal<Env, Val>,
+    F: Fn(u32) -> K,
+{
+    let count = len(env, len_key);
+    if position >= count {
+        return None;
+    }
+
+    let last_position = count - 1;
+    let last_key = page_key(last_position / PAGE_SIZE);
+    let mut last_page: Vec<V> = get_page(env, &last_key);
+    let last = last_page.pop_back()?;
+
+    if last_page.is_empty() {
+        env.storage().persistent().remove(&last_key);
+    } else {
+        ttl::set_persistent(env, &last_key, &last_page);
+    }
+    ttl::set_persistent(env, len_key, &last_position);
+
+    if position == last_position {
+        return None;
+    }
+
+    let key = page_key(position / PAGE_SIZE);
+    let mut page: Vec<V> = get_page(env, &key);
+    page.set(position % PAGE_SIZE, last);
+    ttl::set_persistent(env, &key, &page);
+
+    page.get(position % PAGE_SIZE)
+}
+
+/// Remove and return the last item of an index
+pub fn pop<K, V, F>(env: &Env, len_key: &K, page_key: F) -> Option<V>
+where
+    K: IntoVal<Env, Val>,
+    V: IntoVal<Env, Val> + TryFromVal<Env, Val>,
+    F: Fn(u32) -> K,
+{
+    let count = len(env, len_key);
+    if count == 0 {
+        return None;
+    }
+
+    let last_position = count - 1;
+    let key = page_key(last_position / PAGE_SIZE);
+    let mut page: Vec<V> = get_page(env, &key);
+    let last = page.pop_back()?;
+
+    if page.is_empty() {
+        env.storage().persistent().remove(&key);
+    } else {
+        ttl::set_persistent(env, &key, &page);
+    }
+    if last_position == 0 {
+        env.storage().persistent().remove(len_key);
+    } else {
+        ttl::set_persistent(env, len_key, &last_position);
+    }
+
+    Some(last)
+}
+
+/// Read every item of an index, in order
+pub fn collect<K, V, F>(env: &Env, len_key: &K, page_key: F) -> Vec<V>
+where
+    K: IntoVal<Env, Val>,
+    V: IntoVal<Env, Val> + TryFromVal<Env, Val>,
+    F: Fn(u32) -> K,
+{
+    let mut result = Vec::new(env);
+    for page in 0..page_count(env, len_key) {
+        let items: Vec<V> = get_page(env, &page_key(page));
+        result.append(&items);
+    }
+    result
+}
+
+/// Read up to `limit` items of an index, starting at position `start`
+///
+/// Like every read, this extends the TTL of the length entry and of the
+/// pages read, so walking an index in batches keeps it alive.
+pub fn get_range<K, V, F>(env: &Env, len_key: &K, page_key: F, start: u32, limit: u32) -> Vec<V>
+where
+    K: IntoVal<Env, Val>,
+    V: IntoVal<Env, Val> + TryFromVal<Env, Val>,
+    F: Fn(u32) -> K,
+{
+    let mut items = Vec::new(env);
+    let end = start.saturating_add(limit).min(len(env, len_key));
+
+    let mut position = start;
+    while position < end {
+        let page: Vec<V> = get_page(env, &page_key(position / PAGE_SIZE));
+        let from = position % PAGE_SIZE;
+        let to = (from + end - position).min(page.len());
+        if from >= to {
+            break;
+        }
+        items.append(&page.slice(from..to));
+        position += to - from;
+    }
+    items
+}
+
+fn page_count<K>(env: &Env, len_key: &K) -> u32
+where
+    K: IntoVal<Env, Val>,
+{
+    len(env, len_key).div_ceil(PAGE_SIZE)
+}
@@ -1,4 +1,4 @@
-use soroban_sdk::{contracttype, Address, Env, String, Vec, contracterror, panic_with_error};
+use soroban_sdk::{Address, Env, String, Vec, contracterror};
 use crate::payment::types::{
     PaymentPool, Recipient, DistributionRule, DistributionStatus,
     PaymentPoolCreatedEvent, RecipientAddedEvent, DistributionExecutedEvent,
@@ -7,10 +7,11 @@ use crate::payment::types::{
 use crate::payment::storage::{
     get_payment_pool, store_payment_pool, get_pool_recipients, add_recipient_to_pool,
     recipient_exists_in_pool, update_pool_status, clear_pool_recipients, get_next_pool_id,
-    pool_exists,
 };
 
 /// Error types for payment distribution operations
+///
+/// Codes live in the 1-99 range; every other module uses its own hundred.
 #[contracterror]
 #[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
 #[repr(u32)]
@@ -163,12 +164,7 @@ pub fn validate_distribution(env: &Env, pool_id: u64) -> Result<bool, PaymentErr
     }
 
     match pool.rule {
-        DistributionRule::Percentage => {
-            let total_percentage: u32 = recipients.iter().map(|r| r.share).sum();
-            if total_percentage != 100 {
-                return Err(PaymentError::SharesNot100Percent);
-            }
-        }
+        DistributionRule::Percentage => validate_percentage_shares(&recipients)?,
         DistributionRule::EqualSplit | DistributionRule::Weighted => {
             // For equal split and weighted, we just need at least one recipient
             // Validation happens during execution for amount calculations
@@ -178,6 +174,40 @@ pub fn validate_distribution(env: &Env, pool_id: u64) -> Result<bool, PaymentErr
     Ok(true)
 }
 
+/// Validate a percentage split: every share between 1 and 100, each address
+/// listed once, and the shares summing to exactly 100
+pub(crate) fn validate_percentage_shares(recipients: &Vec<Recipient>) -> Result<(), PaymentError> {
+    if recipients.is_empty() {
+        return Err(PaymentError::NoRecipients);
+    }
+
+    for (i, recipient) in recipients.iter().enumerate() {
+        if recipient.share == 0 || recipient.share > 100 {
+            return Err(PaymentError::InvalidShare);
+        }
+        if recipients.iter().position(|r| r.address == recipient.address) != Some(i) {
+            return Err(PaymentError::DuplicateRecipient);
+        }
+    }
+
+    let total_percentage: u32 = recipients.iter().map(|r| r.share).sum();
+    if total_percentage != 100 {
+        return Err(PaymentError::SharesNot100Percent);
+    }
+
+    Ok(())
+}
+
+/// Calculate a percentage share of an amount, rounding down
+pub(crate) fn percentage_amount(total_amount: i128, share: u32) -> Result<i128, PaymentError> {
+    // amount = total_amount * share / 100
+    total_amount
+        .checked_mul(share as i128)
+        .ok_or(PaymentError::ArithmeticOverflow)?
+        .checked_div(100)
+        .ok_or(PaymentError::ArithmeticOverflow)
+}
+
 /// Calculate the amount a recipient should receive
 ///
 /// # Arguments
@@ -195,15 +225,7 @@ fn calculate_recipient_amount(
     total_weight: Option<u32>,
 ) -> Result<i128, PaymentError> {
     match pool.rule {
-        DistributionRule::Percentage => {
-            // amount = total_amount * share / 100
-            let amount = (pool.total_amount as i128)
-                .checked_mul(recipient.share as i128)
-                .ok_or(PaymentError::ArithmeticOverflow)?
-                .checked_div(100)
-                .ok_or(PaymentError::ArithmeticOverflow)?;
-            Ok(amount)
-        }
+        DistributionRule::Percentage => percentage_amount(pool.total_amount, recipient.share),
         DistributionRule::EqualSplit => {
             // amount = total_amount / total_recipients
             let amount = pool.total_amount
@@ -214,7 +236,7 @@ fn calculate_recipient_amount(
         DistributionRule::Weighted => {
             if let Some(total_w) = total_weight {
                 // amount = total_amount * recipient_weight / total_weight
-                let amount = (pool.total_amount as i128)
+                let amount = pool.total_amount
                     .checked_mul(recipient.share as i128)
                     .ok_or(PaymentError::ArithmeticOverflow)?
                     .checked_div(total_w as i128)
@@ -253,7 +275,7 @@ pub fn execute_distribution(env: &Env, pool_id: u64, caller: Address) -> Result<
     validate_distribution(env, pool_id)?;
 
     let recipients = get_pool_recipients(env, pool_id);
-    let total_recipients = recipients.len() as u32;
+    let total_recipients = recipients.len();
 
     // Calculate total weight for weighted distribution
     let total_weight = if pool.rule == DistributionRule::Weighted {
@@ -314,7 +336,7 @@ pub fn execute_distribution(env: &Env, pool_id: u64, caller: Address) -> Result<
     // Emit success event
     let event = DistributionExecutedEvent {
         pool_id,
-        total_recipients: total_recipients as u32,
+        total_recipients,
         total_distributed,
     };
     env.events().publish(("DistributionExecuted",), event);
@@ -338,7 +360,7 @@ pub fn get_recipient_amount(env: &Env, pool_id: u64, address: Address) -> Result
     // Find the recipient
     let recipient = recipients.iter().find(|r| r.address == address).ok_or(PaymentError::PoolNotFound)?;
 
-    let total_recipients = recipients.len() as u32;
+    let total_recipients = recipients.len();
     let total_weight = if pool.rule == DistributionRule::Weighted {
         Some(recipients.iter().map(|r| r.share).sum())
     } else {
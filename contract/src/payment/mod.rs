@@ -27,9 +27,10 @@ pub mod types;
 pub use distribution::{
     add_recipient, batch_distribute, cancel_distribution, create_payment_pool,
     execute_distribution, get_pool_status, get_recipient_amount, validate_distribution,
+    PaymentError,
 };
 // pub use storage::initialize_payment_storage;
-pub use types::{DistributionRule, DistributionStatus};
+pub use types::{DistributionRule, DistributionStatus, Recipient};
 
 #[cfg(test)]
 mod tests;
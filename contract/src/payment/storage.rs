@@ -1,5 +1,6 @@
-use soroban_sdk::{contracttype, Address, Env, Map, Vec};
+use soroban_sdk::{contracttype, Address, Env, Vec};
 use crate::payment::types::{PaymentPool, Recipient, DistributionStatus};
+use crate::ttl;
 
 /// Storage key for the next pool ID counter
 #[contracttype]
@@ -11,31 +12,30 @@ pub enum PaymentStorageKey {
 
 /// Initialize payment distribution storage
 pub fn initialize_payment_storage(env: &Env) {
-    if !env.storage().persistent().has(&PaymentStorageKey::NextPoolId) {
-        env.storage().persistent().set(&PaymentStorageKey::NextPoolId, &1u64);
+    if !env.storage().instance().has(&PaymentStorageKey::NextPoolId) {
+        ttl::set_counter(env, &PaymentStorageKey::NextPoolId, 1);
     }
 }
 
 /// Get the next available pool ID and increment the counter
 pub fn get_next_pool_id(env: &Env) -> u64 {
-    let mut next_id: u64 = env.storage().persistent().get(&PaymentStorageKey::NextPoolId).unwrap_or(1);
-    let current_id = next_id;
-    next_id += 1;
-    env.storage().persistent().set(&PaymentStorageKey::NextPoolId, &next_id);
+    let current_id = ttl::get_counter(env, &PaymentStorageKey::NextPoolId, 1);
+    ttl::set_counter(env, &PaymentStorageKey::NextPoolId, current_id + 1);
     current_id
 }
 
 /// Store a payment pool
 pub fn store_payment_pool(env: &Env, pool: &PaymentPool) {
-    env.storage().persistent().set(&PaymentStorageKey::Pool(pool.id), pool);
+    ttl::set_persistent(env, &PaymentStorageKey::Pool(pool.id), pool);
 }
 
 /// Get a payment pool by ID
 pub fn get_payment_pool(env: &Env, pool_id: u64) -> Option<PaymentPool> {
-    env.storage().persistent().get(&PaymentStorageKey::Pool(pool_id))
+    ttl::get_persistent(env, &PaymentStorageKey::Pool(pool_id))
 }
 
 /// Check if a pool exists
+#[allow(dead_code)]
 pub fn pool_exists(env: &Env, pool_id: u64) -> bool {
     env.storage().persistent().has(&PaymentStorageKey::Pool(pool_id))
 }
@@ -51,15 +51,15 @@ pub fn update_pool_status(env: &Env, pool_id: u64, status: DistributionStatus) {
 /// Add a recipient to a pool
 pub fn add_recipient_to_pool(env: &Env, pool_id: u64, recipient: &Recipient) {
     let key = PaymentStorageKey::Recipients(pool_id);
-    let mut recipients: Vec<Recipient> = env.storage().persistent().get(&key).unwrap_or(Vec::new(env));
+    let mut recipients: Vec<Recipient> = ttl::get_persistent(env, &key).unwrap_or(Vec::new(env));
     recipients.push_back(recipient.clone());
-    env.storage().persistent().set(&key, &recipients);
+    ttl::set_persistent(env, &key, &recipients);
 }
 
 /// Get all recipients for a pool
 pub fn get_pool_recipients(env: &Env, pool_id: u64) -> Vec<Recipient> {
     let key = PaymentStorageKey::Recipients(pool_id);
-    env.storage().persistent().get(&key).unwrap_or(Vec::new(env))
+    ttl::get_persistent(env, &key).unwrap_or(Vec::new(env))
 }
 
 /// Check if a recipient already exists in a pool
@@ -80,7 +80,13 @@ pub fn clear_pool_recipients(env: &Env, pool_id: u64) {
 }
 
 /// Get total number of pools created
+#[allow(dead_code)]
 pub fn get_total_pools(env: &Env) -> u64 {
-    let next_id: u64 = env.storage().persistent().get(&PaymentStorageKey::NextPoolId).unwrap_or(1);
+    let next_id = ttl::get_counter(env, &PaymentStorageKey::NextPoolId, 1);
     next_id.saturating_sub(1)
-}
\ No newline at end of file
+}
+
+/// Move the pool counter written by older releases into instance storage
+pub fn migrate_legacy_counters(env: &Env) {
+    ttl::promote_counter(env, &PaymentStorageKey::NextPoolId, &PaymentStorageKey::NextPoolId);
+}
@@ -0,0 +1,204 @@
+//! Reputation Module
+//!
+//! Keeps a per-guild reputation ledger. Members earn points when their
+//! bounties are completed or milestones approved, lose points when their work
+//! is rejected or expires, and every balance decays over time so reputation
+//! reflects recent contributions.
+//!
+//! Governance reads the ledger for proposer eligibility and for
+//! reputation-weighted voting.
+
+pub mod storage;
+pub mod types;
+
+use soroban_sdk::{Address, Env, Symbol, Vec};
+
+use crate::reputation::storage::{get_guild_addresses, get_record, get_standing_at, store_record};
+use crate::reputation::types::ReputationChangedEvent;
+
+pub use types::{ReputationEntry, ReputationRecord};
+
+/// Points earned when a bounty the member claimed is completed
+pub const BOUNTY_COMPLETION_POINTS: i128 = 10;
+/// Points earned when a milestone the member delivered is approved
+pub const MILESTONE_APPROVAL_POINTS: i128 = 5;
+/// Points lost when a milestone the member delivered is rejected
+pub const MILESTONE_REJECTION_PENALTY: i128 = 5;
+/// Points lost when a bounty the member claimed expires unfinished
+pub const BOUNTY_EXPIRY_PENALTY: i128 = 10;
+/// Points lost when a member's claim is reclaimed after their work window
+pub const BOUNTY_ABANDON_PENALTY: i128 = 5;
+
+/// Length of one decay period (30 days)
+pub const DECAY_PERIOD_SECONDS: u64 = 60 * 60 * 24 * 30;
+/// Share of a balance lost each decay period
+pub const DECAY_PERCENT: i128 = 10;
+/// Decay periods after which any balance is treated as fully decayed
+const MAX_DECAY_PERIODS: u64 = 128;
+
+/// Apply the decay accrued since `record.last_updated`
+///
+/// `last_updated` only advances by whole periods, so partial periods keep
+/// counting towards the next decay step.
+fn apply_decay(env: &Env, record: &mut ReputationRecord) {
+    let (points, last_updated) =
+        decay(record.points, record.last_updated, env.ledger().timestamp());
+    record.points = points;
+    record.last_updated = last_updated;
+}
+
+/// Points left at `now` from `points` held as of `last_updated`, with the
+/// `last_updated` they are held as of
+fn decay(points: i128, last_updated: u64, now: u64) -> (i128, u64) {
+    let periods = now.saturating_sub(last_updated) / DECAY_PERIOD_SECONDS;
+    if periods == 0 {
+        return (points, last_updated);
+    }
+
+    let mut points = points;
+    if periods >= MAX_DECAY_PERIODS {
+        points = 0;
+    } else {
+        for _ in 0..periods {
+            if points == 0 {
+                break;
+            }
+            points = points * (100 - DECAY_PERCENT) / 100;
+        }
+    }
+    (points, last_updated + periods * DECAY_PERIOD_SECONDS)
+}
+
+fn load_record(env: &Env, guild_id: u64, address: &Address) -> ReputationRecord {
+    let mut record = get_record(env, guild_id, address).unwrap_or(ReputationRecord {
+        guild_id,
+        address: address.clone(),
+        points: 0,
+        last_updated: env.ledger().timestamp(),
+        bounties_completed: 0,
+        milestones_approved: 0,
+        penalties: 0,
+    });
+    apply_decay(env, &mut record);
+    record
+}
+
+fn change_points(env: &Env, record: &mut ReputationRecord, delta: i128, reason: &str) {
+    let before = record.points;
+    record.points = (record.points + delta).max(0);
+    store_record(env, record);
+
+    env.events().publish(
+        (Symbol::new(env, "reputation"), Symbol::new(env, "changed")),
+        ReputationChangedEvent {
+            guild_id: record.guild_id,
+            address: record.address.clone(),
+            delta: record.points - before,
+            points: record.points,
+            reason: Symbol::new(env, reason),
+        },
+    );
+}
+
+/// Credit a member for a completed bounty
+pub fn record_bounty_completed(env: &Env, guild_id: u64, address: &Address) {
+    let mut record = load_record(env, guild_id, address);
+    record.bounties_completed += 1;
+    change_points(env, &mut record, BOUNTY_COMPLETION_POINTS, "bounty_completed");
+}
+
+/// Penalize a member whose claimed bounty expired unfinished
+pub fn record_bounty_expired(env: &Env, guild_id: u64, address: &Address) {
+    let mut record = load_record(env, guild_id, address);
+    record.penalties += 1;
+    change_points(env, &mut record, -BOUNTY_EXPIRY_PENALTY, "bounty_expired");
+}
+
+/// Penalise a member whose stalled claim was reclaimed
+pub fn record_bounty_abandoned(env: &Env, guild_id: u64, address: &Address) {
+    let mut record = load_record(env, guild_id, address);
+    record.penalties += 1;
+    change_points(env, &mut record, -BOUNTY_ABANDON_PENALTY, "bounty_abandoned");
+}
+
+/// Credit a member for an approved milestone
+pub fn record_milestone_approved(env: &Env, guild_id: u64, address: &Address) {
+    let mut record = load_record(env, guild_id, address);
+    record.milestones_approved += 1;
+    change_points(env, &mut record, MILESTONE_APPROVAL_POINTS, "milestone_approved");
+}
+
+/// Penalize a member whose milestone was rejected
+pub fn record_milestone_rejected(env: &Env, guild_id: u64, address: &Address) {
+    let mut record = load_record(env, guild_id, address);
+    record.penalties += 1;
+    change_points(env, &mut record, -MILESTONE_REJECTION_PENALTY, "milestone_rejected");
+}
+
+/// Current (decayed) reputation of an address in a guild
+pub fn get_reputation(env: &Env, guild_id: u64, address: &Address) -> i128 {
+    match get_record(env, guild_id, address) {
+        Some(mut record) => {
+            apply_decay(env, &mut record);
+            record.points
+        }
+        None => 0,
+    }
+}
+
+/// Reputation of an address in a guild as it stood at a guild snapshot
+/// epoch, decayed up to `timestamp`
+///
+/// Governance reads this with the epoch and time a proposal opened, so
+/// points earned or lost while voting is under way do not change weights.
+pub fn get_reputation_at(
+    env: &Env,
+    guild_id: u64,
+    address: &Address,
+    epoch: u32,
+    timestamp: u64,
+) -> i128 {
+    let (points, last_updated) = get_standing_at(env, guild_id, address, epoch);
+    decay(points, last_updated, timestamp).0
+}
+
+/// Full ledger record of an address in a guild, with decay applied
+pub fn get_reputation_record(env: &Env, guild_id: u64, address: &Address) -> Option<ReputationRecord> {
+    get_record(env, guild_id, address).map(|mut record| {
+        apply_decay(env, &mut record);
+        record
+    })
+}
+
+/// The `limit` highest (decayed) reputations in a guild, highest first
+pub fn get_leaderboard(env: &Env, guild_id: u64, limit: u32) -> Vec<ReputationEntry> {
+    let mut board: Vec<ReputationEntry> = Vec::new(env);
+    if limit == 0 {
+        return board;
+    }
+
+    for address in get_guild_addresses(env, guild_id).iter() {
+        let points = get_reputation(env, guild_id, &address);
+        if points == 0 {
+            continue;
+        }
+
+        // insertion into the sorted board, keeping at most `limit` entries
+        let mut position = board.len();
+        while position > 0 && board.get_unchecked(position - 1).points < points {
+            position -= 1;
+        }
+        if position >= limit {
+            continue;
+        }
+        board.insert(position, ReputationEntry { address, points });
+        if board.len() > limit {
+            board.pop_back();
+        }
+    }
+
+    board
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,114 @@
+use soroban_sdk::{contracttype, Address, Env, Vec};
+
+use crate::guild::storage as guild_storage;
+use crate::paging;
+use crate::reputation::types::{ReputationCheckpoint, ReputationRecord};
+use crate::ttl;
+
+/// Storage keys for the per-guild reputation ledgers
+#[contracttype]
+pub enum ReputationStorageKey {
+    Record(u64, Address),     // (guild_id, address)
+    GuildIndexLen(u64),       // guild_id
+    GuildIndexPage(u64, u32), // Vec<Address>
+    History(u64, Address),    // Vec<ReputationCheckpoint>
+}
+
+pub fn get_record(env: &Env, guild_id: u64, address: &Address) -> Option<ReputationRecord> {
+    ttl::get_persistent(env, &ReputationStorageKey::Record(guild_id, address.clone()))
+}
+
+/// Store a record, adding the address to the guild's ledger index if new
+pub fn store_record(env: &Env, record: &ReputationRecord) {
+    let key = ReputationStorageKey::Record(record.guild_id, record.address.clone());
+    checkpoint_record(env, record.guild_id, &record.address);
+
+    if !env.storage().persistent().has(&key) {
+        let guild_id = record.guild_id;
+        paging::push(
+            env,
+            &ReputationStorageKey::GuildIndexLen(guild_id),
+            |page| ReputationStorageKey::GuildIndexPage(guild_id, page),
+            record.address.clone(),
+        );
+    }
+
+    ttl::set_persistent(env, &key, record);
+}
+
+/// Record an address's standing before its first change in the guild's
+/// current snapshot epoch; nothing is recorded before any proposal has
+/// opened
+fn checkpoint_record(env: &Env, guild_id: u64, address: &Address) {
+    let epoch = guild_storage::get_snapshot_epoch(env, guild_id);
+    if epoch == 0 {
+        return;
+    }
+
+    let key = ReputationStorageKey::History(guild_id, address.clone());
+    let mut history: Vec<ReputationCheckpoint> =
+        ttl::get_persistent(env, &key).unwrap_or_else(|| Vec::new(env));
+    if history.last().map(|checkpoint| checkpoint.epoch) == Some(epoch) {
+        return;
+    }
+    let (points, last_updated) = match get_record(env, guild_id, address) {
+        Some(record) => (record.points, record.last_updated),
+        None => (0, 0),
+    };
+    history.push_back(ReputationCheckpoint {
+        epoch,
+        points,
+        last_updated,
+    });
+    ttl::set_persistent(env, &key, &history);
+}
+
+/// An address's points and their `last_updated` as of a guild snapshot
+/// epoch, before decay
+pub fn get_standing_at(env: &Env, guild_id: u64, address: &Address, epoch: u32) -> (i128, u64) {
+    let key = ReputationStorageKey::History(guild_id, address.clone());
+    if let Some(history) = ttl::get_persistent::<_, Vec<ReputationCheckpoint>>(env, &key) {
+        for checkpoint in history.iter() {
+            if checkpoint.epoch > epoch {
+                return (checkpoint.points, checkpoint.last_updated);
+            }
+        }
+    }
+    match get_record(env, guild_id, address) {
+        Some(record) => (record.points, record.last_updated),
+        None => (0, 0),
+    }
+}
+
+/// Addresses holding a record in a guild's ledger
+pub fn get_guild_addresses(env: &Env, guild_id: u64) -> Vec<Address> {
+    paging::collect(
+        env,
+        &ReputationStorageKey::GuildIndexLen(guild_id),
+        |page| ReputationStorageKey::GuildIndexPage(guild_id, page),
+    )
+}
+
+/// Extend the TTL of the records (and their history) at positions
+/// `cursor..cursor + limit` of a guild's reputation ledger
+///
+/// # Returns
+/// Whether the ledger holds records past the batch
+pub fn bump_guild_reputation(env: &Env, guild_id: u64, cursor: u32, limit: u32) -> bool {
+    let addresses: Vec<Address> = paging::get_range(
+        env,
+        &ReputationStorageKey::GuildIndexLen(guild_id),
+        |page| ReputationStorageKey::GuildIndexPage(guild_id, page),
+        cursor,
+        limit,
+    );
+    for address in addresses.iter() {
+        ttl::extend_persistent_if_present(
+            env,
+            &ReputationStorageKey::Record(guild_id, address.clone()),
+        );
+        ttl::extend_persistent_if_present(env, &ReputationStorageKey::History(guild_id, address));
+    }
+
+    paging::len(env, &ReputationStorageKey::GuildIndexLen(guild_id)) > cursor.saturating_add(limit)
+}
@@ -0,0 +1,347 @@
+//! Reputation Ledger Tests
+//!
+//! Coverage for points earned and lost through bounties and milestones,
+//! decay, the leaderboard, and governance use of reputation.
+
+use crate::bounty::types::AssignmentMode;
+use crate::governance::types::{ExecutionPayload, ProposalType};
+use crate::guild::types::Role;
+use crate::milestone::types::MilestoneInput;
+use crate::reputation::{
+    BOUNTY_COMPLETION_POINTS, BOUNTY_EXPIRY_PENALTY, DECAY_PERIOD_SECONDS,
+    MILESTONE_APPROVAL_POINTS,
+};
+use crate::StellarGuildsContract;
+use crate::StellarGuildsContractClient;
+use soroban_sdk::testutils::{Address as _, Ledger, LedgerInfo};
+use soroban_sdk::{token, Address, Env, String, Vec};
+
+// ============ Test Helpers ============
+
+fn setup_env() -> Env {
+    let env = Env::default();
+    env.budget().reset_unlimited();
+    env
+}
+
+fn set_ledger_timestamp(env: &Env, timestamp: u64) {
+    env.ledger().set(LedgerInfo {
+        timestamp,
+        protocol_version: 20,
+        sequence_number: 0,
+        network_id: Default::default(),
+        base_reserve: 10,
+        min_temp_entry_ttl: 100,
+        min_persistent_entry_ttl: 100,
+        max_entry_ttl: 10_000_000,
+    });
+}
+
+fn register_and_init_contract(env: &Env) -> Address {
+    let contract_id = env.register_contract(None, StellarGuildsContract);
+    let client = StellarGuildsContractClient::new(env, &contract_id);
+    client.mock_all_auths().initialize(&Address::generate(env));
+    contract_id
+}
+
+fn setup_guild(client: &StellarGuildsContractClient<'_>, env: &Env, owner: &Address) -> u64 {
+    let name = String::from_str(env, "Reputation Guild");
+    let description = String::from_str(env, "Guild with a reputation ledger");
+    client.create_guild(&name, &description, owner)
+}
+
+/// Create a funded bounty and take it through to `UnderReview` by `claimer`
+fn claimed_bounty(
+    env: &Env,
+    client: &StellarGuildsContractClient<'_>,
+    guild_id: u64,
+    owner: &Address,
+    claimer: &Address,
+    expiry: u64,
+) -> u64 {
+    let token = env.register_stellar_asset_contract_v2(owner.clone()).address();
+    token::StellarAssetClient::new(env, &token).mint(owner, &100);
+
+    let bounty_id = client.create_bounty(
+        &guild_id,
+        owner,
+        &String::from_str(env, "Task"),
+        &String::from_str(env, "Description"),
+        &100i128,
+        &token,
+        &expiry,
+        &AssignmentMode::FirstCome,
+    );
+    client.fund_bounty(&bounty_id, owner, &100i128);
+    client.claim_bounty(&bounty_id, claimer);
+    client.submit_work(&bounty_id, &String::from_str(env, "https://example.com/pr/1"));
+    bounty_id
+}
+
+fn complete_bounty(
+    env: &Env,
+    client: &StellarGuildsContractClient<'_>,
+    guild_id: u64,
+    owner: &Address,
+    claimer: &Address,
+) {
+    let bounty_id = claimed_bounty(env, client, guild_id, owner, claimer, 100_000);
+    client.approve_completion(&bounty_id, owner);
+}
+
+// ============ Earning and Slashing Tests ============
+
+#[test]
+fn test_completed_bounty_earns_reputation() {
+    let env = setup_env();
+    let owner = Address::generate(&env);
+    let claimer = Address::generate(&env);
+
+    set_ledger_timestamp(&env, 1000);
+    env.mock_all_auths();
+
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+    let guild_id = setup_guild(&client, &env, &owner);
+
+    assert_eq!(client.get_reputation(&guild_id, &claimer), 0);
+    assert_eq!(client.get_reputation_record(&guild_id, &claimer), None);
+
+    complete_bounty(&env, &client, guild_id, &owner, &claimer);
+
+    assert_eq!(
+        client.get_reputation(&guild_id, &claimer),
+        BOUNTY_COMPLETION_POINTS
+    );
+    let record = client.get_reputation_record(&guild_id, &claimer).unwrap();
+    assert_eq!(record.bounties_completed, 1);
+}
+
+#[test]
+fn test_expired_claimed_bounty_slashes_claimer() {
+    let env = setup_env();
+    let owner = Address::generate(&env);
+    let claimer = Address::generate(&env);
+
+    set_ledger_timestamp(&env, 1000);
+    env.mock_all_auths();
+
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+    let guild_id = setup_guild(&client, &env, &owner);
+
+    complete_bounty(&env, &client, guild_id, &owner, &claimer);
+    complete_bounty(&env, &client, guild_id, &owner, &claimer);
+
+    let bounty_id = claimed_bounty(&env, &client, guild_id, &owner, &claimer, 5000);
+    set_ledger_timestamp(&env, 6000);
+    assert!(client.expire_bounty(&bounty_id));
+
+    assert_eq!(
+        client.get_reputation(&guild_id, &claimer),
+        2 * BOUNTY_COMPLETION_POINTS - BOUNTY_EXPIRY_PENALTY
+    );
+    let record = client.get_reputation_record(&guild_id, &claimer).unwrap();
+    assert_eq!(record.penalties, 1);
+}
+
+#[test]
+fn test_reputation_never_goes_negative() {
+    let env = setup_env();
+    let owner = Address::generate(&env);
+    let claimer = Address::generate(&env);
+
+    set_ledger_timestamp(&env, 1000);
+    env.mock_all_auths();
+
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+    let guild_id = setup_guild(&client, &env, &owner);
+
+    let bounty_id = claimed_bounty(&env, &client, guild_id, &owner, &claimer, 5000);
+    set_ledger_timestamp(&env, 6000);
+    client.expire_bounty(&bounty_id);
+
+    assert_eq!(client.get_reputation(&guild_id, &claimer), 0);
+}
+
+#[test]
+fn test_milestone_approval_and_rejection() {
+    let env = setup_env();
+    let owner = Address::generate(&env);
+    let contributor = Address::generate(&env);
+
+    set_ledger_timestamp(&env, 1000);
+    env.mock_all_auths();
+
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+    let guild_id = setup_guild(&client, &env, &owner);
+
+    let mut signers = Vec::new(&env);
+    signers.push_back(owner.clone());
+    let treasury_id = client.initialize_treasury(&guild_id, &signers, &1u32);
+    client.deposit_treasury(&treasury_id, &owner, &2000i128, &None);
+
+    let mut milestones: Vec<MilestoneInput> = Vec::new(&env);
+    for title in ["M1", "M2"] {
+        milestones.push_back(MilestoneInput {
+            title: String::from_str(&env, title),
+            description: String::from_str(&env, ""),
+            payment_amount: 1000,
+            deadline: 1000 + 86400,
+        });
+    }
+    client.create_project(
+        &guild_id,
+        &contributor,
+        &milestones,
+        &2000i128,
+        &treasury_id,
+        &None,
+        &false,
+    );
+
+    let proof = String::from_str(&env, "https://example.com/proof");
+
+    client.start_milestone(&1u64, &contributor);
+    client.submit_milestone(&1u64, &proof);
+    client.approve_milestone(&1u64, &owner);
+    assert_eq!(
+        client.get_reputation(&guild_id, &contributor),
+        MILESTONE_APPROVAL_POINTS
+    );
+
+    client.start_milestone(&2u64, &contributor);
+    client.submit_milestone(&2u64, &proof);
+    client.reject_milestone(&2u64, &owner, &String::from_str(&env, "Incomplete"));
+    assert_eq!(client.get_reputation(&guild_id, &contributor), 0);
+
+    let record = client.get_reputation_record(&guild_id, &contributor).unwrap();
+    assert_eq!(record.milestones_approved, 1);
+    assert_eq!(record.penalties, 1);
+}
+
+// ============ Decay Tests ============
+
+#[test]
+fn test_reputation_decays_over_time() {
+    let env = setup_env();
+    let owner = Address::generate(&env);
+    let claimer = Address::generate(&env);
+
+    set_ledger_timestamp(&env, 1000);
+    env.mock_all_auths();
+
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+    let guild_id = setup_guild(&client, &env, &owner);
+
+    for _ in 0..10 {
+        complete_bounty(&env, &client, guild_id, &owner, &claimer);
+    }
+    assert_eq!(client.get_reputation(&guild_id, &claimer), 100);
+
+    // part of a period: no decay yet
+    set_ledger_timestamp(&env, 1000 + DECAY_PERIOD_SECONDS - 1);
+    assert_eq!(client.get_reputation(&guild_id, &claimer), 100);
+
+    // two full periods at 10% each
+    set_ledger_timestamp(&env, 1000 + 2 * DECAY_PERIOD_SECONDS);
+    assert_eq!(client.get_reputation(&guild_id, &claimer), 81);
+}
+
+// ============ Leaderboard Tests ============
+
+#[test]
+fn test_leaderboard_orders_by_points() {
+    let env = setup_env();
+    let owner = Address::generate(&env);
+    let first = Address::generate(&env);
+    let second = Address::generate(&env);
+    let third = Address::generate(&env);
+
+    set_ledger_timestamp(&env, 1000);
+    env.mock_all_auths();
+
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+    let guild_id = setup_guild(&client, &env, &owner);
+
+    complete_bounty(&env, &client, guild_id, &owner, &third);
+    for _ in 0..3 {
+        complete_bounty(&env, &client, guild_id, &owner, &first);
+    }
+    for _ in 0..2 {
+        complete_bounty(&env, &client, guild_id, &owner, &second);
+    }
+
+    let board = client.get_reputation_leaderboard(&guild_id, &2u32);
+    assert_eq!(board.len(), 2);
+    assert_eq!(board.get(0).unwrap().address, first);
+    assert_eq!(board.get(0).unwrap().points, 30);
+    assert_eq!(board.get(1).unwrap().address, second);
+
+    assert_eq!(client.get_reputation_leaderboard(&guild_id, &10u32).len(), 3);
+}
+
+// ============ Governance Tests ============
+
+#[test]
+#[should_panic(expected = "Error(Contract, #403)")] // InsufficientReputation
+fn test_proposer_needs_ledger_reputation() {
+    let env = setup_env();
+    let owner = Address::generate(&env);
+
+    set_ledger_timestamp(&env, 1000);
+    env.mock_all_auths();
+
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+    let guild_id = setup_guild(&client, &env, &owner);
+
+    let mut config = client.get_governance_config(&guild_id);
+    config.min_proposer_reputation = 5;
+    client.update_governance_config(&guild_id, &owner, &config);
+
+    // the owner role alone no longer counts as reputation
+    client.create_proposal(
+        &guild_id,
+        &owner,
+        &ProposalType::GeneralDecision,
+        &String::from_str(&env, "Proposal"),
+        &String::from_str(&env, "Description"),
+        &ExecutionPayload::GeneralDecision,
+    );
+}
+
+#[test]
+fn test_reputable_member_can_propose() {
+    let env = setup_env();
+    let owner = Address::generate(&env);
+    let member = Address::generate(&env);
+
+    set_ledger_timestamp(&env, 1000);
+    env.mock_all_auths();
+
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+    let guild_id = setup_guild(&client, &env, &owner);
+    client.add_member(&guild_id, &member, &Role::Member, &owner);
+
+    let mut config = client.get_governance_config(&guild_id);
+    config.min_proposer_reputation = 5;
+    client.update_governance_config(&guild_id, &owner, &config);
+
+    complete_bounty(&env, &client, guild_id, &owner, &member);
+
+    let proposal_id = client.create_proposal(
+        &guild_id,
+        &member,
+        &ProposalType::GeneralDecision,
+        &String::from_str(&env, "Proposal"),
+        &String::from_str(&env, "Description"),
+        &ExecutionPayload::GeneralDecision,
+    );
+    assert_eq!(client.get_proposal(&proposal_id).proposer, member);
+}
@@ -0,0 +1,48 @@
+use soroban_sdk::{contracttype, Address, Symbol};
+
+/// A member's standing in a guild's reputation ledger
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ReputationRecord {
+    pub guild_id: u64,
+    pub address: Address,
+    /// Points as of `last_updated`, before any decay since then
+    pub points: i128,
+    pub last_updated: u64,
+    pub bounties_completed: u32,
+    pub milestones_approved: u32,
+    pub penalties: u32,
+}
+
+/// A ledger record's standing before the first change made to it in a guild
+/// snapshot epoch
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ReputationCheckpoint {
+    /// Epoch in which the record changed
+    pub epoch: u32,
+    /// Points as of `last_updated`, 0 if there was no record yet
+    pub points: i128,
+    pub last_updated: u64,
+}
+
+/// A row of a guild's reputation leaderboard
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ReputationEntry {
+    pub address: Address,
+    pub points: i128,
+}
+
+// ============ Events ============
+
+/// Event emitted when a member earns or loses reputation
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ReputationChangedEvent {
+    pub guild_id: u64,
+    pub address: Address,
+    pub delta: i128,
+    pub points: i128,
+    pub reason: Symbol,
+}
@@ -0,0 +1,132 @@
+//! Payout fees
+//!
+//! A guild can take a cut of every bounty and milestone payout into one of
+//! its treasuries, and the contract admin can set a platform-wide fee on top
+//! (see `admin::set_platform_fee`). Both are expressed in basis points of
+//! the gross payout and withheld before the recipient is paid.
+
+use soroban_sdk::{token::Client as TokenClient, Address, Env, Symbol};
+
+use crate::admin::storage::get_platform_fee;
+use crate::guild::roles::has_guild_permission;
+use crate::guild::types::Permission;
+use crate::treasury::management::credit_held_funds;
+use crate::treasury::storage::{get_guild_fee, get_treasury, store_guild_fee};
+use crate::treasury::types::{GuildFee, GuildFeeUpdatedEvent, PayoutFees, TreasuryError};
+
+/// Highest fee a guild can take, in basis points (20%)
+pub const MAX_GUILD_FEE_BPS: u32 = 2_000;
+
+const BPS_DENOMINATOR: i128 = 10_000;
+
+/// Set the fee a guild takes from its bounty and milestone payouts
+///
+/// A fee of zero turns the guild fee off.
+///
+/// # Arguments
+/// * `guild_id` - The guild taking the fee
+/// * `caller` - A guild member holding `ManageGuild`
+/// * `treasury_id` - Treasury of the guild the fee is deposited into
+/// * `fee_bps` - Fee in basis points of each payout
+///
+/// # Errors
+/// - `TreasuryError::OnlyOwner` if caller lacks `ManageGuild`
+/// - `TreasuryError::TreasuryNotFound` if the treasury does not exist
+/// - `TreasuryError::GuildMismatch` if the treasury belongs to another guild
+/// - `TreasuryError::InvalidFee` if the fee exceeds `MAX_GUILD_FEE_BPS`
+pub fn set_guild_fee(
+    env: &Env,
+    guild_id: u64,
+    caller: Address,
+    treasury_id: u64,
+    fee_bps: u32,
+) -> Result<bool, TreasuryError> {
+    caller.require_auth();
+
+    if !has_guild_permission(env, guild_id, caller, Permission::ManageGuild) {
+        return Err(TreasuryError::OnlyOwner);
+    }
+
+    let treasury = get_treasury(env, treasury_id).ok_or(TreasuryError::TreasuryNotFound)?;
+    if treasury.guild_id != guild_id {
+        return Err(TreasuryError::GuildMismatch);
+    }
+    if fee_bps > MAX_GUILD_FEE_BPS {
+        return Err(TreasuryError::InvalidFee);
+    }
+
+    store_guild_fee(
+        env,
+        guild_id,
+        &GuildFee {
+            treasury_id,
+            fee_bps,
+        },
+    );
+
+    let event = GuildFeeUpdatedEvent {
+        guild_id,
+        treasury_id,
+        fee_bps,
+    };
+    env.events().publish(
+        (Symbol::new(env, "treasury"), Symbol::new(env, "guild_fee")),
+        event,
+    );
+
+    Ok(true)
+}
+
+/// Get the fee a guild takes from its payouts, if it set one
+pub fn get_guild_fee_config(env: &Env, guild_id: u64) -> Option<GuildFee> {
+    get_guild_fee(env, guild_id)
+}
+
+/// Work out the fees withheld from a payout made by a guild
+pub(crate) fn quote_fees(env: &Env, guild_id: u64, amount: i128) -> PayoutFees {
+    let guild_bps = get_guild_fee(env, guild_id).map_or(0, |f| f.fee_bps);
+    let platform_bps = get_platform_fee(env).map_or(0, |f| f.fee_bps);
+
+    PayoutFees {
+        guild_fee: amount * guild_bps as i128 / BPS_DENOMINATOR,
+        platform_fee: amount * platform_bps as i128 / BPS_DENOMINATOR,
+    }
+}
+
+/// Hand over fees withheld from a payout
+///
+/// The tokens must already be held by the contract. The guild fee is
+/// credited to the guild's fee treasury and the platform fee is transferred
+/// to the platform recipient; native payouts must not quote a platform fee,
+/// as there is no token to transfer it in.
+pub(crate) fn collect_fees(
+    env: &Env,
+    guild_id: u64,
+    token: &Option<Address>,
+    fees: &PayoutFees,
+) -> Result<(), TreasuryError> {
+    if fees.guild_fee > 0 {
+        if let Some(config) = get_guild_fee(env, guild_id) {
+            credit_held_funds(
+                env,
+                config.treasury_id,
+                token.clone(),
+                fees.guild_fee,
+                "guild_fee",
+            )?;
+        }
+    }
+
+    if fees.platform_fee > 0 {
+        if let (Some(config), Some(token_addr)) = (get_platform_fee(env), token) {
+            let client = TokenClient::new(env, token_addr);
+            client.transfer(
+                &env.current_contract_address(),
+                &config.recipient,
+                &fees.platform_fee,
+            );
+        }
+    }
+
+    Ok(())
+}
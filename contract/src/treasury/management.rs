@@ -1,17 +1,26 @@
 use soroban_sdk::{token::Client as TokenClient, Address, Env, String, Symbol, Vec};
 
+use crate::bounty;
+use crate::bounty::types::{BountyError, BountyStatus};
+use crate::guild::roles::has_guild_permission;
+use crate::guild::rules::get_rules;
+use crate::guild::types::Permission;
+use crate::keeper::storage::schedule;
+use crate::keeper::types::{DueItem, DueOutcome};
+use crate::treasury::fees::{collect_fees, quote_fees};
 use crate::treasury::multisig::{
-    add_approval, assert_signer, ensure_is_signer, expire_if_needed, required_approvals_for_tx,
-    validate_threshold, TX_EXPIRY_SECONDS,
+    add_approval, assert_signer, ensure_can_sign, ensure_is_signer, expire_if_needed, is_expired,
+    required_approvals_for_tx, validate_threshold,
 };
 use crate::treasury::storage::{
-    get_allowance, get_budget, get_next_treasury_id, get_next_tx_id, get_treasury,
-    get_treasury_transactions, store_allowance, store_budget, store_transaction, store_treasury,
+    get_allowance, get_bounty_funding, get_budget, get_next_treasury_id, get_next_tx_id,
+    get_treasury, get_treasury_transactions, store_allowance, store_bounty_funding, store_budget,
+    store_transaction, store_treasury,
 };
 use crate::treasury::types::{
-    Allowance, Budget, DepositEvent, EmergencyPauseEvent, Transaction, TransactionApprovedEvent,
-    TransactionExecutedEvent, TransactionStatus, TransactionType, Treasury,
-    TreasuryInitializedEvent, TreasuryError, WithdrawalProposedEvent,
+    Allowance, BountyFundingProposedEvent, Budget, DepositEvent, EmergencyPauseEvent, PayoutFees,
+    Transaction, TransactionApprovedEvent, TransactionExecutedEvent, TransactionStatus,
+    TransactionType, Treasury, TreasuryError, TreasuryInitializedEvent, WithdrawalProposedEvent,
 };
 
 pub fn initialize_treasury(
@@ -19,9 +28,9 @@ pub fn initialize_treasury(
     guild_id: u64,
     signers: Vec<Address>,
     approval_threshold: u32,
-) -> u64 {
+) -> Result<u64, TreasuryError> {
     // First signer is the owner
-    let owner = signers.get(0).expect("at least one signer required");
+    let owner = signers.get(0).ok_or(TreasuryError::NoSigners)?;
     owner.require_auth();
 
     let mut unique_signers = Vec::new(env);
@@ -31,8 +40,8 @@ pub fn initialize_treasury(
         }
     }
 
-    let signers_len = unique_signers.len() as u32;
-    validate_threshold(signers_len, approval_threshold);
+    let signers_len = unique_signers.len();
+    validate_threshold(signers_len, approval_threshold)?;
 
     let id = get_next_treasury_id(env);
 
@@ -65,7 +74,7 @@ pub fn initialize_treasury(
         event,
     );
 
-    id
+    Ok(id)
 }
 
 pub fn deposit(
@@ -74,15 +83,15 @@ pub fn deposit(
     depositor: Address,
     amount: i128,
     token: Option<Address>,
-) -> bool {
+) -> Result<bool, TreasuryError> {
     depositor.require_auth();
     if amount <= 0 {
-        panic!("amount must be positive");
+        return Err(TreasuryError::InvalidAmount);
     }
 
-    let mut treasury = get_treasury(env, treasury_id).expect("treasury not found");
+    let mut treasury = get_treasury(env, treasury_id).ok_or(TreasuryError::TreasuryNotFound)?;
     if treasury.paused {
-        panic!("treasury is paused");
+        return Err(TreasuryError::TreasuryPaused);
     }
 
     match token {
@@ -133,7 +142,7 @@ pub fn deposit(
         event,
     );
 
-    true
+    Ok(true)
 }
 
 pub fn propose_withdrawal(
@@ -144,17 +153,20 @@ pub fn propose_withdrawal(
     amount: i128,
     token: Option<Address>,
     reason: String,
-) -> u64 {
+) -> Result<u64, TreasuryError> {
     if amount <= 0 {
-        panic!("amount must be positive");
+        return Err(TreasuryError::InvalidAmount);
     }
 
-    let mut treasury = get_treasury(env, treasury_id).expect("treasury not found");
+    let treasury = get_treasury(env, treasury_id).ok_or(TreasuryError::TreasuryNotFound)?;
     if treasury.paused {
-        panic!("treasury is paused");
+        return Err(TreasuryError::TreasuryPaused);
     }
 
-    assert_signer(env, &treasury, &proposer);
+    assert_signer(env, &treasury, &proposer)?;
+
+    // Withdrawals stay approvable for the guild's `treasury_tx_expiry` rule
+    let tx_expiry = get_rules(env, treasury.guild_id).treasury_tx_expiry;
 
     let tx_id = get_next_tx_id(env);
     let now = env.ledger().timestamp();
@@ -172,10 +184,11 @@ pub fn propose_withdrawal(
         approvals,
         status: TransactionStatus::Pending,
         created_at: now,
-        expires_at: now + TX_EXPIRY_SECONDS,
+        expires_at: now + tx_expiry,
         reason,
     };
     store_transaction(env, &tx);
+    schedule(env, DueItem::Transaction(tx_id), tx.expires_at);
 
     let event = WithdrawalProposedEvent {
         treasury_id,
@@ -190,14 +203,187 @@ pub fn propose_withdrawal(
         event,
     );
 
-    tx_id
+    Ok(tx_id)
+}
+
+/// Propose moving treasury funds into a bounty's escrow
+///
+/// Once approved and executed like any other transaction, the tokens stay in
+/// the contract and are credited to the bounty as a treasury contribution, so
+/// refunds on cancellation or expiry return to this treasury's balance.
+///
+/// # Arguments
+/// * `treasury_id` - The ID of the treasury paying
+/// * `proposer` - A treasury signer holding `ProposeTreasurySpend` in the guild
+/// * `bounty_id` - The bounty to fund, in the treasury's guild
+/// * `amount` - Amount of the bounty's token to move
+/// * `reason` - Reason for the funding
+///
+/// # Returns
+/// The ID of the proposed transaction
+pub fn propose_bounty_funding(
+    env: &Env,
+    treasury_id: u64,
+    proposer: Address,
+    bounty_id: u64,
+    amount: i128,
+    reason: String,
+) -> Result<u64, TreasuryError> {
+    if amount <= 0 {
+        return Err(TreasuryError::InvalidAmount);
+    }
+
+    let treasury = get_treasury(env, treasury_id).ok_or(TreasuryError::TreasuryNotFound)?;
+    if treasury.paused {
+        return Err(TreasuryError::TreasuryPaused);
+    }
+
+    assert_signer(env, &treasury, &proposer)?;
+    if !has_guild_permission(
+        env,
+        treasury.guild_id,
+        proposer.clone(),
+        Permission::ProposeTreasurySpend,
+    ) {
+        return Err(TreasuryError::NotGuildAdmin);
+    }
+
+    let bounty =
+        bounty::storage::get_bounty(env, bounty_id).ok_or(TreasuryError::BountyNotFound)?;
+    if bounty.guild_id != treasury.guild_id {
+        return Err(TreasuryError::GuildMismatch);
+    }
+    if !matches!(
+        bounty.status,
+        BountyStatus::AwaitingFunds | BountyStatus::Open
+    ) {
+        return Err(TreasuryError::BountyNotFundable);
+    }
+
+    let tx_expiry = get_rules(env, treasury.guild_id).treasury_tx_expiry;
+
+    let tx_id = get_next_tx_id(env);
+    let now = env.ledger().timestamp();
+    let mut approvals = Vec::new(env);
+    approvals.push_back(proposer.clone());
+
+    let tx = Transaction {
+        id: tx_id,
+        treasury_id,
+        tx_type: TransactionType::BountyFunding,
+        amount,
+        token: Some(bounty.token),
+        recipient: Some(env.current_contract_address()),
+        proposer: proposer.clone(),
+        approvals,
+        status: TransactionStatus::Pending,
+        created_at: now,
+        expires_at: now + tx_expiry,
+        reason,
+    };
+    store_transaction(env, &tx);
+    schedule(env, DueItem::Transaction(tx_id), tx.expires_at);
+    store_bounty_funding(env, tx_id, bounty_id);
+
+    let event = BountyFundingProposedEvent {
+        treasury_id,
+        tx_id,
+        proposer,
+        bounty_id,
+        amount,
+    };
+    env.events().publish(
+        (
+            Symbol::new(env, "treasury"),
+            Symbol::new(env, "fund_bounty"),
+        ),
+        event,
+    );
+
+    Ok(tx_id)
 }
 
-pub fn approve_transaction(env: &Env, tx_id: u64, approver: Address) -> bool {
+/// Credit a refunded share of bounty escrow back to the treasury that
+/// funded it
+///
+/// The tokens are already held by the contract, so only the balance moves.
+/// Recorded as an executed deposit.
+pub fn credit_bounty_refund(
+    env: &Env,
+    treasury_id: u64,
+    token: &Address,
+    amount: i128,
+) -> Result<(), TreasuryError> {
+    credit_held_funds(
+        env,
+        treasury_id,
+        Some(token.clone()),
+        amount,
+        "bounty_refund",
+    )
+}
+
+/// Credit tokens the contract already holds to a treasury's balance and
+/// record them as an executed deposit
+pub(crate) fn credit_held_funds(
+    env: &Env,
+    treasury_id: u64,
+    token: Option<Address>,
+    amount: i128,
+    reason: &str,
+) -> Result<(), TreasuryError> {
+    let mut treasury = get_treasury(env, treasury_id).ok_or(TreasuryError::TreasuryNotFound)?;
+
+    match token {
+        Some(ref token_addr) => {
+            let mut balances = treasury.token_balances.clone();
+            let current = balances.get(token_addr.clone()).unwrap_or(0i128);
+            balances.set(token_addr.clone(), current + amount);
+            treasury.token_balances = balances;
+        }
+        None => treasury.balance_xlm += amount,
+    }
+    treasury.total_deposits += amount;
+    store_treasury(env, &treasury);
+
+    let tx_id = get_next_tx_id(env);
+    let now = env.ledger().timestamp();
+    let tx = Transaction {
+        id: tx_id,
+        treasury_id,
+        tx_type: TransactionType::Deposit,
+        amount,
+        token: token.clone(),
+        recipient: Some(env.current_contract_address()),
+        proposer: env.current_contract_address(),
+        approvals: Vec::new(env),
+        status: TransactionStatus::Executed,
+        created_at: now,
+        expires_at: now,
+        reason: String::from_str(env, reason),
+    };
+    store_transaction(env, &tx);
+
+    let event = DepositEvent {
+        treasury_id,
+        from: env.current_contract_address(),
+        amount,
+        token,
+    };
+    env.events().publish(
+        (Symbol::new(env, "treasury"), Symbol::new(env, "deposit")),
+        event,
+    );
+
+    Ok(())
+}
+
+pub fn approve_transaction(env: &Env, tx_id: u64, approver: Address) -> Result<bool, TreasuryError> {
     approver.require_auth();
 
-    let mut tx = crate::treasury::storage::get_transaction(env, tx_id).expect("tx not found");
-    let mut treasury = get_treasury(env, tx.treasury_id).expect("treasury not found");
+    let mut tx = crate::treasury::storage::get_transaction(env, tx_id)
+        .ok_or(TreasuryError::TransactionNotFound)?;
+    let treasury = get_treasury(env, tx.treasury_id).ok_or(TreasuryError::TreasuryNotFound)?;
 
     let now = env.ledger().timestamp();
     expire_if_needed(&mut tx, now);
@@ -205,14 +391,14 @@ pub fn approve_transaction(env: &Env, tx_id: u64, approver: Address) -> bool {
         tx.status,
         TransactionStatus::Rejected | TransactionStatus::Executed | TransactionStatus::Expired
     ) {
-        panic!("transaction not approvable");
+        return Err(TreasuryError::TransactionNotApprovable);
     }
 
-    ensure_is_signer(&treasury, &approver);
-    add_approval(&mut tx, &approver);
+    ensure_can_sign(env, &treasury, &approver)?;
+    add_approval(&mut tx, &approver)?;
 
     let required = required_approvals_for_tx(&treasury, &tx);
-    if (tx.approvals.len() as u32) >= required {
+    if tx.approvals.len() >= required {
         tx.status = TransactionStatus::Approved;
     }
 
@@ -228,7 +414,27 @@ pub fn approve_transaction(env: &Env, tx_id: u64, approver: Address) -> bool {
         event,
     );
 
-    true
+    Ok(true)
+}
+
+/// Expire a transaction taken off the keeper's deadline index
+pub(crate) fn expire_due_transaction(env: &Env, tx_id: u64) -> DueOutcome {
+    let Some(mut tx) = crate::treasury::storage::get_transaction(env, tx_id) else {
+        return DueOutcome::Settled;
+    };
+    if !matches!(
+        tx.status,
+        TransactionStatus::Pending | TransactionStatus::Approved
+    ) {
+        return DueOutcome::Settled;
+    }
+    if !is_expired(&tx, env.ledger().timestamp()) {
+        return DueOutcome::Pending(tx.expires_at);
+    }
+
+    tx.status = TransactionStatus::Expired;
+    store_transaction(env, &tx);
+    DueOutcome::Expired
 }
 
 fn enforce_budget(env: &Env, treasury_id: u64, category: &String, amount: i128) -> Result<(), TreasuryError> {
@@ -282,11 +488,12 @@ fn enforce_allowance(
     Ok(())
 }
 
-pub fn execute_transaction(env: &Env, tx_id: u64, executor: Address) -> bool {
+pub fn execute_transaction(env: &Env, tx_id: u64, executor: Address) -> Result<bool, TreasuryError> {
     executor.require_auth();
 
-    let mut tx = crate::treasury::storage::get_transaction(env, tx_id).expect("tx not found");
-    let mut treasury = get_treasury(env, tx.treasury_id).expect("treasury not found");
+    let mut tx = crate::treasury::storage::get_transaction(env, tx_id)
+        .ok_or(TreasuryError::TransactionNotFound)?;
+    let mut treasury = get_treasury(env, tx.treasury_id).ok_or(TreasuryError::TreasuryNotFound)?;
 
     let now = env.ledger().timestamp();
     expire_if_needed(&mut tx, now);
@@ -294,25 +501,41 @@ pub fn execute_transaction(env: &Env, tx_id: u64, executor: Address) -> bool {
         tx.status,
         TransactionStatus::Rejected | TransactionStatus::Executed | TransactionStatus::Expired
     ) {
-        panic!("transaction not executable");
+        return Err(TreasuryError::TransactionNotExecutable);
     }
 
     // when paused, only already-approved transactions may be executed
     if treasury.paused && !matches!(tx.status, TransactionStatus::Approved) {
-        panic!("treasury is paused");
+        return Err(TreasuryError::TreasuryPaused);
     }
 
-    ensure_is_signer(&treasury, &executor);
+    ensure_is_signer(&treasury, &executor)?;
 
     if !matches!(tx.status, TransactionStatus::Approved) {
-        panic!("transaction must be approved");
+        return Err(TreasuryError::TransactionNotApproved);
     }
 
     match tx.tx_type {
         TransactionType::Withdrawal
         | TransactionType::BountyFunding
         | TransactionType::MilestonePayment => {
-            let recipient = tx.recipient.clone().expect("recipient required");
+            let recipient = tx.recipient.clone().ok_or(TreasuryError::RecipientRequired)?;
+
+            // Bounty funding moves into escrow, which only takes what the
+            // reward still needs
+            let funded_bounty = match tx.tx_type {
+                TransactionType::BountyFunding => get_bounty_funding(env, tx_id),
+                _ => None,
+            };
+            if let Some(bounty_id) = funded_bounty {
+                let funded = bounty::fund_from_treasury(env, bounty_id, tx.treasury_id, tx.amount);
+                tx.amount = match funded {
+                    Ok(accepted) => accepted,
+                    Err(BountyError::BountyNotFound) => return Err(TreasuryError::BountyNotFound),
+                    // expired, closed or already fully funded since the proposal
+                    Err(_) => return Err(TreasuryError::BountyNotFundable),
+                };
+            }
 
             // budget category name from tx_type
             let category = match tx.tx_type {
@@ -322,19 +545,8 @@ pub fn execute_transaction(env: &Env, tx_id: u64, executor: Address) -> bool {
                 _ => String::from_str(env, "other"),
             };
 
-            // Convert Result to panic with expected error message
-            // This creates a proper contract error (all panics in Soroban become contract errors)
-            // while maintaining the expected error message for test compatibility
-            enforce_budget(env, tx.treasury_id, &category, tx.amount)
-                .unwrap_or_else(|e| match e {
-                    TreasuryError::BudgetExceeded => panic!("budget exceeded"),
-                    TreasuryError::AllowanceExceeded => panic!("allowance exceeded"),
-                });
-            enforce_allowance(env, tx.treasury_id, &executor, &tx.token, tx.amount)
-                .unwrap_or_else(|e| match e {
-                    TreasuryError::BudgetExceeded => panic!("budget exceeded"),
-                    TreasuryError::AllowanceExceeded => panic!("allowance exceeded"),
-                });
+            enforce_budget(env, tx.treasury_id, &category, tx.amount)?;
+            enforce_allowance(env, tx.treasury_id, &executor, &tx.token, tx.amount)?;
 
             match tx.token {
                 Some(ref token_addr) => {
@@ -343,16 +555,19 @@ pub fn execute_transaction(env: &Env, tx_id: u64, executor: Address) -> bool {
                     let mut balances = treasury.token_balances.clone();
                     let current = balances.get(token_addr.clone()).unwrap_or(0i128);
                     if current < tx.amount {
-                        panic!("insufficient treasury balance");
+                        return Err(TreasuryError::InsufficientBalance);
                     }
                     balances.set(token_addr.clone(), current - tx.amount);
                     treasury.token_balances = balances;
 
-                    client.transfer(&env.current_contract_address(), &recipient, &tx.amount);
+                    // Escrowed tokens stay in the contract
+                    if funded_bounty.is_none() {
+                        client.transfer(&env.current_contract_address(), &recipient, &tx.amount);
+                    }
                 }
                 None => {
                     if treasury.balance_xlm < tx.amount {
-                        panic!("insufficient XLM balance");
+                        return Err(TreasuryError::InsufficientBalance);
                     }
                     treasury.balance_xlm -= tx.amount;
                 }
@@ -362,7 +577,7 @@ pub fn execute_transaction(env: &Env, tx_id: u64, executor: Address) -> bool {
             store_treasury(env, &treasury);
         }
         TransactionType::Deposit => {
-            panic!("cannot execute deposit transaction");
+            return Err(TreasuryError::CannotExecuteDeposit);
         }
         TransactionType::AllowanceGrant => {
             // state-only; execution path not used in this simplified version
@@ -384,41 +599,49 @@ pub fn execute_transaction(env: &Env, tx_id: u64, executor: Address) -> bool {
         event,
     );
 
-    true
+    Ok(true)
 }
 
+/// Pay a milestone out of a treasury
+///
+/// Guild and platform fees are withheld from `amount` and the recipient is
+/// paid the rest. Native (XLM) payments carry no platform fee.
+///
+/// # Returns
+/// The fees withheld
 pub fn execute_milestone_payment(
     env: &Env,
     treasury_id: u64,
     token: Option<Address>,
     recipient: Address,
     amount: i128,
-) -> bool {
+) -> Result<PayoutFees, TreasuryError> {
     if amount <= 0 {
-        panic!("amount must be positive");
+        return Err(TreasuryError::InvalidAmount);
     }
 
-    let mut treasury = get_treasury(env, treasury_id).expect("treasury not found");
+    let mut treasury = get_treasury(env, treasury_id).ok_or(TreasuryError::TreasuryNotFound)?;
     if treasury.paused {
-        panic!("treasury is paused");
+        return Err(TreasuryError::TreasuryPaused);
     }
 
     // Budget enforcement under the "milestone" category
     let category = String::from_str(env, "milestone");
-    enforce_budget(env, treasury_id, &category, amount)
-        .unwrap_or_else(|e| match e {
-            TreasuryError::BudgetExceeded => panic!("budget exceeded"),
-            TreasuryError::AllowanceExceeded => panic!("allowance exceeded"),
-        });
+    enforce_budget(env, treasury_id, &category, amount)?;
 
     // Allowance enforcement (if any) keyed by current contract address;
     // if no allowance exists this is a no-op.
     let executor = env.current_contract_address();
-    enforce_allowance(env, treasury_id, &executor, &token, amount)
-        .unwrap_or_else(|e| match e {
-            TreasuryError::BudgetExceeded => panic!("budget exceeded"),
-            TreasuryError::AllowanceExceeded => panic!("allowance exceeded"),
-        });
+    enforce_allowance(env, treasury_id, &executor, &token, amount)?;
+
+    // Fees come out of the payment; the recipient is paid the rest. The
+    // platform fee is paid out in tokens, so native payments only carry the
+    // guild fee.
+    let mut fees = quote_fees(env, treasury.guild_id, amount);
+    if token.is_none() {
+        fees.platform_fee = 0;
+    }
+    let net_amount = amount - fees.total();
 
     // Move funds from treasury to recipient
     match token {
@@ -428,16 +651,16 @@ pub fn execute_milestone_payment(
             let mut balances = treasury.token_balances.clone();
             let current = balances.get(token_addr.clone()).unwrap_or(0i128);
             if current < amount {
-                panic!("insufficient treasury balance");
+                return Err(TreasuryError::InsufficientBalance);
             }
             balances.set(token_addr.clone(), current - amount);
             treasury.token_balances = balances;
 
-            client.transfer(&env.current_contract_address(), &recipient, &amount);
+            client.transfer(&env.current_contract_address(), &recipient, &net_amount);
         }
         None => {
             if treasury.balance_xlm < amount {
-                panic!("insufficient XLM balance");
+                return Err(TreasuryError::InsufficientBalance);
             }
             treasury.balance_xlm -= amount;
         }
@@ -474,7 +697,92 @@ pub fn execute_milestone_payment(
         event,
     );
 
-    true
+    collect_fees(env, treasury.guild_id, &tx.token, &fees)?;
+
+    Ok(fees)
+}
+
+/// Pay out a treasury spend approved by a governance proposal
+///
+/// The vote of the guild stands in for the multisig approvals, so the
+/// transaction is recorded as already executed.
+pub fn execute_governance_spend(
+    env: &Env,
+    treasury_id: u64,
+    guild_id: u64,
+    token: Option<Address>,
+    recipient: Address,
+    amount: i128,
+    reason: String,
+) -> Result<u64, TreasuryError> {
+    if amount <= 0 {
+        return Err(TreasuryError::InvalidAmount);
+    }
+
+    let mut treasury = get_treasury(env, treasury_id).ok_or(TreasuryError::TreasuryNotFound)?;
+    if treasury.guild_id != guild_id {
+        return Err(TreasuryError::GuildMismatch);
+    }
+    if treasury.paused {
+        return Err(TreasuryError::TreasuryPaused);
+    }
+
+    let category = String::from_str(env, "withdrawal");
+    enforce_budget(env, treasury_id, &category, amount)?;
+
+    match token {
+        Some(ref token_addr) => {
+            let client = TokenClient::new(env, token_addr);
+
+            let mut balances = treasury.token_balances.clone();
+            let current = balances.get(token_addr.clone()).unwrap_or(0i128);
+            if current < amount {
+                return Err(TreasuryError::InsufficientBalance);
+            }
+            balances.set(token_addr.clone(), current - amount);
+            treasury.token_balances = balances;
+
+            client.transfer(&env.current_contract_address(), &recipient, &amount);
+        }
+        None => {
+            if treasury.balance_xlm < amount {
+                return Err(TreasuryError::InsufficientBalance);
+            }
+            treasury.balance_xlm -= amount;
+        }
+    }
+
+    treasury.total_withdrawals += amount;
+    store_treasury(env, &treasury);
+
+    let tx_id = get_next_tx_id(env);
+    let now = env.ledger().timestamp();
+    let tx = Transaction {
+        id: tx_id,
+        treasury_id,
+        tx_type: TransactionType::Withdrawal,
+        amount,
+        token,
+        recipient: Some(recipient),
+        proposer: env.current_contract_address(),
+        approvals: Vec::new(env),
+        status: TransactionStatus::Executed,
+        created_at: now,
+        expires_at: now,
+        reason,
+    };
+    store_transaction(env, &tx);
+
+    let event = TransactionExecutedEvent { treasury_id, tx_id };
+    env.events().publish(
+        (
+            Symbol::new(env, "treasury"),
+            Symbol::new(env, "tx_executed"),
+        ),
+        event,
+    );
+
+    Ok(tx_id)
 }
 
 pub fn set_budget(
@@ -484,9 +792,9 @@ pub fn set_budget(
     category: String,
     amount: i128,
     period_seconds: u64,
-) -> bool {
-    let treasury = get_treasury(env, treasury_id).expect("treasury not found");
-    assert_signer(env, &treasury, &caller);
+) -> Result<bool, TreasuryError> {
+    let treasury = get_treasury(env, treasury_id).ok_or(TreasuryError::TreasuryNotFound)?;
+    assert_signer(env, &treasury, &caller)?;
 
     let now = env.ledger().timestamp();
     let mut budget = get_budget(env, treasury_id, &category).unwrap_or(Budget {
@@ -521,18 +829,22 @@ pub fn set_budget(
         event,
     );
 
-    true
+    Ok(true)
 }
 
-pub fn get_balance(env: &Env, treasury_id: u64, token: Option<Address>) -> i128 {
-    let treasury = get_treasury(env, treasury_id).expect("treasury not found");
-    match token {
+pub fn get_balance(
+    env: &Env,
+    treasury_id: u64,
+    token: Option<Address>,
+) -> Result<i128, TreasuryError> {
+    let treasury = get_treasury(env, treasury_id).ok_or(TreasuryError::TreasuryNotFound)?;
+    Ok(match token {
         Some(token_addr) => treasury
             .token_balances
             .get(token_addr.clone())
             .unwrap_or(0i128),
         None => treasury.balance_xlm,
-    }
+    })
 }
 
 pub fn get_transaction_history(env: &Env, treasury_id: u64, limit: u32) -> Vec<Transaction> {
@@ -543,7 +855,7 @@ pub fn get_transaction_history(env: &Env, treasury_id: u64, limit: u32) -> Vec<T
         return all;
     }
 
-    let start = len.checked_sub(limit).unwrap_or(0);
+    let start = len.saturating_sub(limit);
     let mut result = Vec::new(env);
     for (idx, tx) in all.iter().enumerate() {
         if (idx as u32) >= start {
@@ -561,16 +873,16 @@ pub fn grant_allowance(
     amount: i128,
     token: Option<Address>,
     period_seconds: u64,
-) -> bool {
-    let treasury = get_treasury(env, treasury_id).expect("treasury not found");
+) -> Result<bool, TreasuryError> {
+    let treasury = get_treasury(env, treasury_id).ok_or(TreasuryError::TreasuryNotFound)?;
 
     if treasury.owner != owner {
-        panic!("only owner can grant allowance");
+        return Err(TreasuryError::OnlyOwner);
     }
     owner.require_auth();
 
     if !treasury.is_signer(&admin) {
-        panic!("admin must be signer");
+        return Err(TreasuryError::AllowanceAdminNotSigner);
     }
 
     let now = env.ledger().timestamp();
@@ -603,12 +915,17 @@ pub fn grant_allowance(
         event,
     );
 
-    true
+    Ok(true)
 }
 
-pub fn emergency_pause(env: &Env, treasury_id: u64, signer: Address, paused: bool) -> bool {
-    let mut treasury = get_treasury(env, treasury_id).expect("treasury not found");
-    assert_signer(env, &treasury, &signer);
+pub fn emergency_pause(
+    env: &Env,
+    treasury_id: u64,
+    signer: Address,
+    paused: bool,
+) -> Result<bool, TreasuryError> {
+    let mut treasury = get_treasury(env, treasury_id).ok_or(TreasuryError::TreasuryNotFound)?;
+    assert_signer(env, &treasury, &signer)?;
 
     treasury.paused = paused;
     store_treasury(env, &treasury);
@@ -622,5 +939,5 @@ pub fn emergency_pause(env: &Env, treasury_id: u64, signer: Address, paused: boo
         event,
     );
 
-    true
+    Ok(true)
 }
@@ -2,31 +2,30 @@ pub mod types;
 pub mod storage;
 pub mod management;
 pub mod multisig;
+pub mod fees;
 
 pub use management::{
     approve_transaction,
+    credit_bounty_refund,
     deposit,
     emergency_pause,
+    execute_governance_spend,
+    execute_milestone_payment,
     execute_transaction,
     get_balance,
     get_transaction_history,
     grant_allowance,
     initialize_treasury,
+    propose_bounty_funding,
     propose_withdrawal,
     set_budget,
-        execute_milestone_payment, 
 };
 
+pub use fees::{get_guild_fee_config, set_guild_fee};
+
 pub use storage::initialize_treasury_storage;
 
-pub use types::{
-    Allowance,
-    Budget,
-    Transaction,
-    TransactionStatus,
-    TransactionType,
-    Treasury,
-};
-// Tests disabled pending fixes
+pub use types::{Transaction, TreasuryError};
+
 #[cfg(test)]
-mod tests;
\ No newline at end of file
+mod tests;
@@ -1,46 +1,70 @@
 use soroban_sdk::{Address, Env};
 
-use crate::treasury::types::{Transaction, TransactionStatus, TransactionType, Treasury};
+use crate::guild::roles::has_guild_permission;
+use crate::guild::storage as guild_storage;
+use crate::guild::types::Permission;
 
-pub const TX_EXPIRY_SECONDS: u64 = 60 * 60 * 24 * 7; // 7 days
+use crate::treasury::types::{
+    Transaction, TransactionStatus, TransactionType, Treasury, TreasuryError,
+};
 
-pub fn validate_threshold(signers_len: u32, threshold: u32) {
+pub fn validate_threshold(signers_len: u32, threshold: u32) -> Result<(), TreasuryError> {
     if signers_len == 0 {
-        panic!("at least one signer required");
+        return Err(TreasuryError::NoSigners);
     }
     if threshold == 0 || threshold > signers_len {
-        panic!("invalid threshold");
+        return Err(TreasuryError::InvalidThreshold);
     }
-    let half = (signers_len + 1) / 2; // ceil(0.5 * n)
+    let half = signers_len.div_ceil(2); // ceil(0.5 * n)
     if threshold < half {
-        panic!("threshold must be at least 50% of signers");
+        return Err(TreasuryError::ThresholdTooLow);
     }
+    Ok(())
 }
 
 /// Authorize and verify the address is a treasury signer.
 /// Call this ONLY when the address has not been authorized yet in this invocation.
-pub fn assert_signer(env: &Env, treasury: &Treasury, addr: &Address) {
+pub fn assert_signer(_env: &Env, treasury: &Treasury, addr: &Address) -> Result<(), TreasuryError> {
     addr.require_auth();
-    ensure_is_signer(treasury, addr);
+    ensure_is_signer(treasury, addr)
 }
 
 /// Verify the address is a treasury signer (no auth).
 /// Use when the address was already authorized at the entrypoint (e.g. approve_transaction, execute_transaction).
-pub fn ensure_is_signer(treasury: &Treasury, addr: &Address) {
+pub fn ensure_is_signer(treasury: &Treasury, addr: &Address) -> Result<(), TreasuryError> {
     if !treasury.is_signer(addr) {
-        panic!("caller is not a signer");
+        return Err(TreasuryError::NotSigner);
     }
+    Ok(())
+}
+
+/// Verify the address may approve transactions of the treasury (no auth).
+/// Signers who are members of the guild must also hold `SignTransactions`; outside co-signers
+/// are governed by the signer list alone.
+pub fn ensure_can_sign(
+    env: &Env,
+    treasury: &Treasury,
+    addr: &Address,
+) -> Result<(), TreasuryError> {
+    ensure_is_signer(treasury, addr)?;
+    if guild_storage::has_member(env, treasury.guild_id, addr)
+        && !has_guild_permission(env, treasury.guild_id, addr.clone(), Permission::SignTransactions)
+    {
+        return Err(TreasuryError::NotSigner);
+    }
+    Ok(())
 }
 
 pub fn has_approved(tx: &Transaction, addr: &Address) -> bool {
     tx.approvals.iter().any(|a| a == addr.clone())
 }
 
-pub fn add_approval(tx: &mut Transaction, addr: &Address) {
+pub fn add_approval(tx: &mut Transaction, addr: &Address) -> Result<(), TreasuryError> {
     if has_approved(tx, addr) {
-        panic!("duplicate approval");
+        return Err(TreasuryError::DuplicateApproval);
     }
     tx.approvals.push_back(addr.clone());
+    Ok(())
 }
 
 pub fn required_approvals_for_tx(treasury: &Treasury, tx: &Transaction) -> u32 {
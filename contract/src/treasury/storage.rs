@@ -1,6 +1,7 @@
-use soroban_sdk::{symbol_short, Address, Env, Map, String, Symbol, Vec};
+use soroban_sdk::{contracttype, symbol_short, Address, Env, Map, String, Symbol, Vec};
 
-use crate::treasury::types::{Allowance, Budget, Transaction, Treasury};
+use crate::treasury::types::{Allowance, Budget, GuildFee, Transaction, Treasury};
+use crate::ttl;
 
 const TREASURY_CNT_KEY: Symbol = symbol_short!("t_cnt");
 const TREASURIES_KEY: Symbol = symbol_short!("trsries");
@@ -12,48 +13,50 @@ const TREASURY_TX_INDEX_KEY: Symbol = symbol_short!("t_tx_idx");
 const BUDGETS_KEY: Symbol = symbol_short!("budgets");
 const ALLOWANCES_KEY: Symbol = symbol_short!("allows");
 
+/// Keys for treasury data stored in its own ledger entry
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum TreasuryStorageKey {
+    /// Bounty that a `BountyFunding` transaction pays into
+    BountyFunding(u64),
+    /// Fee a guild takes from its payouts
+    GuildFee(u64),
+}
+
 pub fn initialize_treasury_storage(env: &Env) {
-    let storage = env.storage().persistent();
+    let storage = env.storage().instance();
     if !storage.has(&TREASURY_CNT_KEY) {
-        storage.set(&TREASURY_CNT_KEY, &0u64);
+        ttl::set_counter(env, &TREASURY_CNT_KEY, 0);
     }
     if !storage.has(&TX_CNT_KEY) {
-        storage.set(&TX_CNT_KEY, &0u64);
+        ttl::set_counter(env, &TX_CNT_KEY, 0);
     }
 }
 
 pub fn get_next_treasury_id(env: &Env) -> u64 {
-    let storage = env.storage().persistent();
-    let current: u64 = storage.get(&TREASURY_CNT_KEY).unwrap_or(0u64);
+    let current = ttl::get_counter(env, &TREASURY_CNT_KEY, 0);
     let next = current + 1;
-    storage.set(&TREASURY_CNT_KEY, &next);
+    ttl::set_counter(env, &TREASURY_CNT_KEY, next);
     next
 }
 
 pub fn get_next_tx_id(env: &Env) -> u64 {
-    let storage = env.storage().persistent();
-    let current: u64 = storage.get(&TX_CNT_KEY).unwrap_or(0u64);
+    let current = ttl::get_counter(env, &TX_CNT_KEY, 0);
     let next = current + 1;
-    storage.set(&TX_CNT_KEY, &next);
+    ttl::set_counter(env, &TX_CNT_KEY, next);
     next
 }
 
 pub fn store_treasury(env: &Env, treasury: &Treasury) {
-    let mut treasuries: Map<u64, Treasury> = env
-        .storage()
-        .persistent()
-        .get(&TREASURIES_KEY)
+    let mut treasuries: Map<u64, Treasury> = ttl::get_persistent(env, &TREASURIES_KEY)
         .unwrap_or_else(|| Map::new(env));
 
     treasuries.set(treasury.id, treasury.clone());
-    env.storage().persistent().set(&TREASURIES_KEY, &treasuries);
+    ttl::set_persistent(env, &TREASURIES_KEY, &treasuries);
 }
 
 pub fn get_treasury(env: &Env, id: u64) -> Option<Treasury> {
-    let treasuries: Map<u64, Treasury> = env
-        .storage()
-        .persistent()
-        .get(&TREASURIES_KEY)
+    let treasuries: Map<u64, Treasury> = ttl::get_persistent(env, &TREASURIES_KEY)
         .unwrap_or_else(|| Map::new(env));
 
     treasuries.get(id)
@@ -61,20 +64,14 @@ pub fn get_treasury(env: &Env, id: u64) -> Option<Treasury> {
 
 pub fn store_transaction(env: &Env, tx: &Transaction) {
     // Store main tx map
-    let mut txs: Map<u64, Transaction> = env
-        .storage()
-        .persistent()
-        .get(&TRANSACTIONS_KEY)
+    let mut txs: Map<u64, Transaction> = ttl::get_persistent(env, &TRANSACTIONS_KEY)
         .unwrap_or_else(|| Map::new(env));
 
     txs.set(tx.id, tx.clone());
-    env.storage().persistent().set(&TRANSACTIONS_KEY, &txs);
+    ttl::set_persistent(env, &TRANSACTIONS_KEY, &txs);
 
     // Update treasury index - only add if this is a new transaction (updates don't append)
-    let mut index: Map<u64, Vec<u64>> = env
-        .storage()
-        .persistent()
-        .get(&TREASURY_TX_INDEX_KEY)
+    let mut index: Map<u64, Vec<u64>> = ttl::get_persistent(env, &TREASURY_TX_INDEX_KEY)
         .unwrap_or_else(|| Map::new(env));
 
     let mut list = index.get(tx.treasury_id).unwrap_or_else(|| Vec::new(env));
@@ -83,34 +80,23 @@ pub fn store_transaction(env: &Env, tx: &Transaction) {
         list.push_back(tx.id);
         index.set(tx.treasury_id, list);
     }
-    env.storage()
-        .persistent()
-        .set(&TREASURY_TX_INDEX_KEY, &index);
+    ttl::set_persistent(env, &TREASURY_TX_INDEX_KEY, &index);
 }
 
 pub fn get_transaction(env: &Env, tx_id: u64) -> Option<Transaction> {
-    let txs: Map<u64, Transaction> = env
-        .storage()
-        .persistent()
-        .get(&TRANSACTIONS_KEY)
+    let txs: Map<u64, Transaction> = ttl::get_persistent(env, &TRANSACTIONS_KEY)
         .unwrap_or_else(|| Map::new(env));
 
     txs.get(tx_id)
 }
 
 pub fn get_treasury_transactions(env: &Env, treasury_id: u64) -> Vec<Transaction> {
-    let index: Map<u64, Vec<u64>> = env
-        .storage()
-        .persistent()
-        .get(&TREASURY_TX_INDEX_KEY)
+    let index: Map<u64, Vec<u64>> = ttl::get_persistent(env, &TREASURY_TX_INDEX_KEY)
         .unwrap_or_else(|| Map::new(env));
 
     let ids = index.get(treasury_id).unwrap_or_else(|| Vec::new(env));
 
-    let txs: Map<u64, Transaction> = env
-        .storage()
-        .persistent()
-        .get(&TRANSACTIONS_KEY)
+    let txs: Map<u64, Transaction> = ttl::get_persistent(env, &TRANSACTIONS_KEY)
         .unwrap_or_else(|| Map::new(env));
 
     let mut result = Vec::new(env);
@@ -122,28 +108,39 @@ pub fn get_treasury_transactions(env: &Env, treasury_id: u64) -> Vec<Transaction
     result
 }
 
+pub fn store_bounty_funding(env: &Env, tx_id: u64, bounty_id: u64) {
+    ttl::set_persistent(env, &TreasuryStorageKey::BountyFunding(tx_id), &bounty_id);
+}
+
+/// Get the bounty a transaction funds, if it was proposed as bounty funding
+pub fn get_bounty_funding(env: &Env, tx_id: u64) -> Option<u64> {
+    ttl::get_persistent(env, &TreasuryStorageKey::BountyFunding(tx_id))
+}
+
+pub fn store_guild_fee(env: &Env, guild_id: u64, fee: &GuildFee) {
+    ttl::set_persistent(env, &TreasuryStorageKey::GuildFee(guild_id), fee);
+}
+
+pub fn get_guild_fee(env: &Env, guild_id: u64) -> Option<GuildFee> {
+    ttl::get_persistent(env, &TreasuryStorageKey::GuildFee(guild_id))
+}
+
 pub fn get_budget(env: &Env, treasury_id: u64, category: &String) -> Option<Budget> {
-    let budgets: Map<(u64, String), Budget> = env
-        .storage()
-        .persistent()
-        .get(&BUDGETS_KEY)
+    let budgets: Map<(u64, String), Budget> = ttl::get_persistent(env, &BUDGETS_KEY)
         .unwrap_or_else(|| Map::new(env));
 
     budgets.get((treasury_id, category.clone()))
 }
 
 pub fn store_budget(env: &Env, budget: &Budget) {
-    let mut budgets: Map<(u64, String), Budget> = env
-        .storage()
-        .persistent()
-        .get(&BUDGETS_KEY)
+    let mut budgets: Map<(u64, String), Budget> = ttl::get_persistent(env, &BUDGETS_KEY)
         .unwrap_or_else(|| Map::new(env));
 
     budgets.set(
         (budget.treasury_id, budget.category.clone()),
         budget.clone(),
     );
-    env.storage().persistent().set(&BUDGETS_KEY, &budgets);
+    ttl::set_persistent(env, &BUDGETS_KEY, &budgets);
 }
 
 pub fn get_allowance(
@@ -152,20 +149,14 @@ pub fn get_allowance(
     admin: &Address,
     token: &Option<Address>,
 ) -> Option<Allowance> {
-    let allowances: Map<(u64, Address, Option<Address>), Allowance> = env
-        .storage()
-        .persistent()
-        .get(&ALLOWANCES_KEY)
+    let allowances: Map<(u64, Address, Option<Address>), Allowance> = ttl::get_persistent(env, &ALLOWANCES_KEY)
         .unwrap_or_else(|| Map::new(env));
 
     allowances.get((treasury_id, admin.clone(), token.clone()))
 }
 
 pub fn store_allowance(env: &Env, allowance: &Allowance) {
-    let mut allowances: Map<(u64, Address, Option<Address>), Allowance> = env
-        .storage()
-        .persistent()
-        .get(&ALLOWANCES_KEY)
+    let mut allowances: Map<(u64, Address, Option<Address>), Allowance> = ttl::get_persistent(env, &ALLOWANCES_KEY)
         .unwrap_or_else(|| Map::new(env));
 
     allowances.set(
@@ -176,14 +167,29 @@ pub fn store_allowance(env: &Env, allowance: &Allowance) {
         ),
         allowance.clone(),
     );
-    env.storage().persistent().set(&ALLOWANCES_KEY, &allowances);
+    ttl::set_persistent(env, &ALLOWANCES_KEY, &allowances);
+}
+
+/// Extend the TTL of the entries holding treasuries, their transactions,
+/// budgets and allowances, and the guild's fee
+pub fn bump_guild_treasuries(env: &Env, guild_id: u64) {
+    ttl::extend_persistent_if_present(env, &TREASURIES_KEY);
+    ttl::extend_persistent_if_present(env, &TRANSACTIONS_KEY);
+    ttl::extend_persistent_if_present(env, &TREASURY_TX_INDEX_KEY);
+    ttl::extend_persistent_if_present(env, &BUDGETS_KEY);
+    ttl::extend_persistent_if_present(env, &ALLOWANCES_KEY);
+    ttl::extend_persistent_if_present(env, &TreasuryStorageKey::GuildFee(guild_id));
+}
+
+/// Move the counters written by older releases into instance storage
+pub fn migrate_legacy_counters(env: &Env) {
+    ttl::promote_counter(env, &TREASURY_CNT_KEY, &TREASURY_CNT_KEY);
+    ttl::promote_counter(env, &TX_CNT_KEY, &TX_CNT_KEY);
 }
 
+#[allow(dead_code)]
 pub fn list_budgets_for_treasury(env: &Env, treasury_id: u64) -> Vec<Budget> {
-    let budgets: Map<(u64, String), Budget> = env
-        .storage()
-        .persistent()
-        .get(&BUDGETS_KEY)
+    let budgets: Map<(u64, String), Budget> = ttl::get_persistent(env, &BUDGETS_KEY)
         .unwrap_or_else(|| Map::new(env));
 
     let mut result = Vec::new(env);
@@ -1,213 +1,757 @@
-#[cfg(test)]
-mod tests {
-    use crate::guild::types::Role;
-    use crate::treasury::types::{TransactionStatus, TransactionType};
-    use crate::StellarGuildsContract;
-    use crate::StellarGuildsContractClient;
-    use soroban_sdk::testutils::{Address as _, Ledger, LedgerInfo};
-    use soroban_sdk::{Address, Env, String, Vec};
-
-    fn setup_env() -> Env {
-        let env = Env::default();
-        env.budget().reset_unlimited();
-        env
-    }
-
-    fn set_ledger_timestamp(env: &Env, timestamp: u64) {
-        env.ledger().set(LedgerInfo {
-            timestamp,
-            protocol_version: 20,
-            sequence_number: 0,
-            network_id: Default::default(),
-            base_reserve: 10,
-            min_temp_entry_ttl: 100,
-            min_persistent_entry_ttl: 100,
-            max_entry_ttl: 1000000,
-        });
-    }
-
-    fn register_and_init_contract(env: &Env) -> Address {
-        let contract_id = env.register_contract(None, StellarGuildsContract);
-        let client = StellarGuildsContractClient::new(env, &contract_id);
-        client.initialize();
-        contract_id
-    }
-
-    fn setup_guild(client: &StellarGuildsContractClient<'_>, env: &Env, owner: &Address) -> u64 {
-        let name = String::from_str(env, "Test Guild");
-        let description = String::from_str(env, "A test guild");
-        client.create_guild(&name, &description, owner)
-    }
-
-    fn create_treasury(
-        env: &Env,
-        client: &StellarGuildsContractClient<'_>,
-        guild_id: u64,
-    ) -> (u64, Address, Address, Address) {
-        let owner = Address::generate(env);
-        let signer1 = Address::generate(env);
-        let signer2 = Address::generate(env);
-
-        env.mock_all_auths();
-
-        let mut signers = Vec::new(env);
-        signers.push_back(owner.clone());
-        signers.push_back(signer1.clone());
-        signers.push_back(signer2.clone());
-
-        let treasury_id = client.initialize_treasury(&guild_id, &signers, &2u32);
-
-        (treasury_id, owner, signer1, signer2)
-    }
-
-    #[test]
-    fn test_treasury_initialize_and_deposit_accounting() {
-        let env = setup_env();
-        let owner = Address::generate(&env);
-
-        set_ledger_timestamp(&env, 1000);
-        env.mock_all_auths();
-
-        let contract_id = register_and_init_contract(&env);
-        let client = StellarGuildsContractClient::new(&env, &contract_id);
-
-        let guild_id = setup_guild(&client, &env, &owner);
-        let (treasury_id, owner, _s1, _s2) = create_treasury(&env, &client, guild_id);
-
-        let depositor = owner.clone();
-        let amount: i128 = 500;
-
-        let ok = client.deposit_treasury(&treasury_id, &depositor, &amount, &None);
-        assert!(ok);
-
-        let bal = client.get_treasury_balance(&treasury_id, &None);
-        assert_eq!(bal, amount);
-
-        let history = client.get_transaction_history(&treasury_id, &10u32);
-        assert_eq!(history.len(), 1);
-        let tx = history.get(0).unwrap();
-        assert_eq!(tx.tx_type, TransactionType::Deposit);
-        assert_eq!(tx.amount, amount);
-        assert_eq!(tx.status, TransactionStatus::Executed);
-    }
-
-    #[test]
-    fn test_multisig_withdrawal_flow() {
-        let env = setup_env();
-        let owner = Address::generate(&env);
-
-        set_ledger_timestamp(&env, 1000);
-        env.mock_all_auths();
-
-        let contract_id = register_and_init_contract(&env);
-        let client = StellarGuildsContractClient::new(&env, &contract_id);
-
-        let guild_id = setup_guild(&client, &env, &owner);
-        let (treasury_id, owner, signer1, signer2) = create_treasury(&env, &client, guild_id);
-
-        // deposit some XLM accounting
-        let amount: i128 = 2000;
-        client.deposit_treasury(&treasury_id, &owner, &amount, &None);
-
-        let recipient = Address::generate(&env);
-
-        // create withdrawal proposal
-        let reason = String::from_str(&env, "payout");
-        let tx_id = client.propose_withdrawal(
-            &treasury_id,
-            &signer1,
-            &recipient,
-            &1500i128,
-            &None,
-            &reason,
-        );
-
-        // second signer approves
-        client.approve_transaction(&tx_id, &signer2);
-
-        // executor (owner) executes
-        client.execute_transaction(&tx_id, &owner);
-
-        let bal = client.get_treasury_balance(&treasury_id, &None);
-        assert_eq!(bal, 500);
-
-        let history = client.get_transaction_history(&treasury_id, &10u32);
-        assert_eq!(history.len(), 2); // 1 deposit + 1 withdrawal (proposed then executed)
-    }
-
-    #[test]
-    #[should_panic(expected = "budget exceeded")]
-    fn test_budget_enforcement() {
-        let env = setup_env();
-        let owner = Address::generate(&env);
+use crate::bounty::types::{AssignmentMode, BountyStatus, ContestTerms};
+use crate::guild::types::Role;
+use crate::milestone::types::{MilestoneInput, MilestonePaymentReleasedEvent};
+use crate::treasury::types::{TransactionStatus, TransactionType};
+use crate::StellarGuildsContract;
+use crate::StellarGuildsContractClient;
+use soroban_sdk::testutils::{Address as _, Events, Ledger, LedgerInfo};
+use soroban_sdk::{token, Address, Env, IntoVal, String, Symbol, TryFromVal, Val, Vec};
+
+fn setup_env() -> Env {
+    let env = Env::default();
+    env.budget().reset_unlimited();
+    env
+}
+
+fn set_ledger_timestamp(env: &Env, timestamp: u64) {
+    env.ledger().set(LedgerInfo {
+        timestamp,
+        protocol_version: 20,
+        sequence_number: 0,
+        network_id: Default::default(),
+        base_reserve: 10,
+        min_temp_entry_ttl: 100,
+        min_persistent_entry_ttl: 100,
+        max_entry_ttl: 1000000,
+    });
+}
+
+fn register_and_init_contract(env: &Env) -> Address {
+    let contract_id = env.register_contract(None, StellarGuildsContract);
+    let client = StellarGuildsContractClient::new(env, &contract_id);
+    client.mock_all_auths().initialize(&Address::generate(env));
+    contract_id
+}
+
+fn setup_guild(client: &StellarGuildsContractClient<'_>, env: &Env, owner: &Address) -> u64 {
+    let name = String::from_str(env, "Test Guild");
+    let description = String::from_str(env, "A test guild");
+    client.create_guild(&name, &description, owner)
+}
+
+fn create_treasury(
+    env: &Env,
+    client: &StellarGuildsContractClient<'_>,
+    guild_id: u64,
+) -> (u64, Address, Address, Address) {
+    let owner = Address::generate(env);
+    let signer1 = Address::generate(env);
+    let signer2 = Address::generate(env);
+
+    env.mock_all_auths();
+
+    let mut signers = Vec::new(env);
+    signers.push_back(owner.clone());
+    signers.push_back(signer1.clone());
+    signers.push_back(signer2.clone());
+
+    let treasury_id = client.initialize_treasury(&guild_id, &signers, &2u32);
+
+    (treasury_id, owner, signer1, signer2)
+}
+
+#[test]
+fn test_treasury_initialize_and_deposit_accounting() {
+    let env = setup_env();
+    let owner = Address::generate(&env);
+
+    set_ledger_timestamp(&env, 1000);
+    env.mock_all_auths();
+
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+    let guild_id = setup_guild(&client, &env, &owner);
+    let (treasury_id, owner, _s1, _s2) = create_treasury(&env, &client, guild_id);
+
+    let depositor = owner.clone();
+    let amount: i128 = 500;
+
+    let ok = client.deposit_treasury(&treasury_id, &depositor, &amount, &None);
+    assert!(ok);
+
+    let bal = client.get_treasury_balance(&treasury_id, &None);
+    assert_eq!(bal, amount);
+
+    let history = client.get_transaction_history(&treasury_id, &10u32);
+    assert_eq!(history.len(), 1);
+    let tx = history.get(0).unwrap();
+    assert_eq!(tx.tx_type, TransactionType::Deposit);
+    assert_eq!(tx.amount, amount);
+    assert_eq!(tx.status, TransactionStatus::Executed);
+}
+
+#[test]
+fn test_multisig_withdrawal_flow() {
+    let env = setup_env();
+    let owner = Address::generate(&env);
+
+    set_ledger_timestamp(&env, 1000);
+    env.mock_all_auths();
+
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+    let guild_id = setup_guild(&client, &env, &owner);
+    let (treasury_id, owner, signer1, signer2) = create_treasury(&env, &client, guild_id);
+
+    // deposit some XLM accounting
+    let amount: i128 = 2000;
+    client.deposit_treasury(&treasury_id, &owner, &amount, &None);
+
+    let recipient = Address::generate(&env);
+
+    // create withdrawal proposal
+    let reason = String::from_str(&env, "payout");
+    let tx_id = client.propose_withdrawal(
+        &treasury_id,
+        &signer1,
+        &recipient,
+        &1500i128,
+        &None,
+        &reason,
+    );
 
-        set_ledger_timestamp(&env, 1000);
-        env.mock_all_auths();
+    // second signer approves
+    client.approve_transaction(&tx_id, &signer2);
 
-        let contract_id = register_and_init_contract(&env);
-        let client = StellarGuildsContractClient::new(&env, &contract_id);
+    // executor (owner) executes
+    client.execute_transaction(&tx_id, &owner);
 
-        let guild_id = setup_guild(&client, &env, &owner);
-        let (treasury_id, owner, signer1, signer2) = create_treasury(&env, &client, guild_id);
+    let bal = client.get_treasury_balance(&treasury_id, &None);
+    assert_eq!(bal, 500);
 
-        client.deposit_treasury(&treasury_id, &owner, &5000i128, &None);
+    let history = client.get_transaction_history(&treasury_id, &10u32);
+    assert_eq!(history.len(), 2); // 1 deposit + 1 withdrawal (proposed then executed)
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #309)")] // NotSigner
+fn test_member_signer_needs_sign_permission() {
+    let env = setup_env();
+    let guild_owner = Address::generate(&env);
+
+    set_ledger_timestamp(&env, 1000);
+    env.mock_all_auths();
+
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+    let guild_id = setup_guild(&client, &env, &guild_owner);
+    let (treasury_id, owner, signer1, signer2) = create_treasury(&env, &client, guild_id);
+    client.deposit_treasury(&treasury_id, &owner, &2000i128, &None);
+
+    // signer2 joins the guild under a role that may not sign
+    let role = Symbol::new(&env, "observer");
+    client.add_member(&guild_id, &signer2, &Role::Member, &guild_owner);
+    client.define_custom_role(&guild_id, &guild_owner, &role, &0);
+    client.assign_custom_role(&guild_id, &guild_owner, &signer2, &Some(role));
+
+    let tx_id = client.propose_withdrawal(
+        &treasury_id,
+        &signer1,
+        &Address::generate(&env),
+        &1500i128,
+        &None,
+        &String::from_str(&env, "payout"),
+    );
+    client.approve_transaction(&tx_id, &signer2);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #300)")] // BudgetExceeded
+fn test_budget_enforcement() {
+    let env = setup_env();
+    let owner = Address::generate(&env);
+
+    set_ledger_timestamp(&env, 1000);
+    env.mock_all_auths();
+
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+    let guild_id = setup_guild(&client, &env, &owner);
+    let (treasury_id, owner, signer1, signer2) = create_treasury(&env, &client, guild_id);
+
+    client.deposit_treasury(&treasury_id, &owner, &5000i128, &None);
+
+    // set a small budget for withdrawals
+    let category = String::from_str(&env, "withdrawal");
+    client.set_budget(&treasury_id, &category, &1000i128, &3600u64, &owner);
+
+    let recipient = Address::generate(&env);
+
+    // first withdrawal within budget
+    let tx1 = client.propose_withdrawal(
+        &treasury_id,
+        &signer1,
+        &recipient,
+        &800i128,
+        &None,
+        &String::from_str(&env, "first"),
+    );
+    client.approve_transaction(&tx1, &signer2);
+    client.execute_transaction(&tx1, &owner);
+
+    // second withdrawal exceeding remaining budget should panic
+    let tx2 = client.propose_withdrawal(
+        &treasury_id,
+        &signer1,
+        &recipient,
+        &500i128,
+        &None,
+        &String::from_str(&env, "second"),
+    );
+
+    client.approve_transaction(&tx2, &signer2);
+
+    client.execute_transaction(&tx2, &owner);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #305)")] // TreasuryPaused
+fn test_emergency_pause_blocks_new_ops() {
+    let env = setup_env();
+    let owner = Address::generate(&env);
 
-        // set a small budget for withdrawals
-        let category = String::from_str(&env, "withdrawal");
-        client.set_budget(&treasury_id, &category, &1000i128, &3600u64, &owner);
+    set_ledger_timestamp(&env, 1000);
+    env.mock_all_auths();
 
-        let recipient = Address::generate(&env);
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
 
-        // first withdrawal within budget
-        let tx1 = client.propose_withdrawal(
-            &treasury_id,
-            &signer1,
-            &recipient,
-            &800i128,
-            &None,
-            &String::from_str(&env, "first"),
-        );
-        client.approve_transaction(&tx1, &signer2);
-        client.execute_transaction(&tx1, &owner);
+    let guild_id = setup_guild(&client, &env, &owner);
+    let (treasury_id, owner, signer1, _signer2) = create_treasury(&env, &client, guild_id);
 
-        // second withdrawal exceeding remaining budget should panic
-        let tx2 = client.propose_withdrawal(
-            &treasury_id,
-            &signer1,
-            &recipient,
-            &500i128,
-            &None,
-            &String::from_str(&env, "second"),
-        );
+    client.deposit_treasury(&treasury_id, &owner, &1000i128, &None);
 
-        client.approve_transaction(&tx2, &signer2);
-
-        client.execute_transaction(&tx2, &owner);
-    }
-
-    #[test]
-    #[should_panic(expected = "treasury is paused")]
-    fn test_emergency_pause_blocks_new_ops() {
-        let env = setup_env();
-        let owner = Address::generate(&env);
+    // pause
+    client.emergency_pause(&treasury_id, &signer1, &true);
 
-        set_ledger_timestamp(&env, 1000);
-        env.mock_all_auths();
+    let recipient = Address::generate(&env);
+
+    let reason = String::from_str(&env, "after pause");
+    client.propose_withdrawal(&treasury_id, &signer1, &recipient, &100i128, &None, &reason);
+}
 
-        let contract_id = register_and_init_contract(&env);
-        let client = StellarGuildsContractClient::new(&env, &contract_id);
+#[test]
+fn test_withdrawal_expiry_follows_guild_rule() {
+    let env = setup_env();
+    let guild_owner = Address::generate(&env);
+
+    set_ledger_timestamp(&env, 1000);
+    env.mock_all_auths();
+
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+    let guild_id = setup_guild(&client, &env, &guild_owner);
+    let (treasury_id, owner, signer1, _s2) = create_treasury(&env, &client, guild_id);
+
+    let mut rules = client.get_guild_rules(&guild_id);
+    rules.treasury_tx_expiry = 3600;
+    client.set_guild_rules(&guild_id, &guild_owner, &rules);
+
+    client.deposit_treasury(&treasury_id, &owner, &1000i128, &None);
+    client.propose_withdrawal(
+        &treasury_id,
+        &signer1,
+        &Address::generate(&env),
+        &100i128,
+        &None,
+        &String::from_str(&env, "payout"),
+    );
+
+    let history = client.get_transaction_history(&treasury_id, &10u32);
+    let tx = history.get(1).unwrap();
+    assert_eq!(tx.tx_type, TransactionType::Withdrawal);
+    assert_eq!(tx.expires_at, 1000 + 3600);
+}
+
+fn setup_bounty_funding(
+    env: &Env,
+    client: &StellarGuildsContractClient<'_>,
+    guild_owner: &Address,
+) -> (u64, u64, Address, Address) {
+    let guild_id = setup_guild(client, env, guild_owner);
+    let signer = Address::generate(env);
+
+    let mut signers = Vec::new(env);
+    signers.push_back(guild_owner.clone());
+    signers.push_back(signer.clone());
+    let treasury_id = client.initialize_treasury(&guild_id, &signers, &1u32);
+
+    let token = env
+        .register_stellar_asset_contract_v2(guild_owner.clone())
+        .address();
+    token::StellarAssetClient::new(env, &token).mint(guild_owner, &1000);
+    client.deposit_treasury(&treasury_id, guild_owner, &1000i128, &Some(token.clone()));
+
+    (guild_id, treasury_id, token, signer)
+}
+
+#[test]
+fn test_bounty_funding_moves_treasury_funds_into_escrow() {
+    let env = setup_env();
+    let guild_owner = Address::generate(&env);
+
+    set_ledger_timestamp(&env, 1000);
+    env.mock_all_auths();
+
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+    let (guild_id, treasury_id, token, signer) = setup_bounty_funding(&env, &client, &guild_owner);
+
+    let bounty_id = client.create_bounty(
+        &guild_id,
+        &guild_owner,
+        &String::from_str(&env, "Task"),
+        &String::from_str(&env, "Description"),
+        &600i128,
+        &token,
+        &(1000 + 30 * 24 * 60 * 60),
+        &AssignmentMode::FirstCome,
+    );
+
+    // Only what the reward still needs leaves the treasury
+    let tx_id = client.propose_bounty_funding(
+        &treasury_id,
+        &guild_owner,
+        &bounty_id,
+        &800i128,
+        &String::from_str(&env, "fund bounty"),
+    );
+    client.approve_transaction(&tx_id, &signer);
+    client.execute_transaction(&tx_id, &guild_owner);
+
+    let bounty = client.get_bounty(&bounty_id);
+    assert_eq!(bounty.funded_amount, 600);
+    assert_eq!(bounty.status, BountyStatus::Open);
+    assert_eq!(
+        client.get_treasury_balance(&treasury_id, &Some(token.clone())),
+        400
+    );
+    assert_eq!(
+        token::TokenClient::new(&env, &token).balance(&contract_id),
+        1000
+    );
+
+    // Cancelling returns the escrow to the treasury, not the creator
+    client.cancel_bounty(&bounty_id, &guild_owner);
+    assert_eq!(
+        client.get_treasury_balance(&treasury_id, &Some(token.clone())),
+        1000
+    );
+    assert_eq!(
+        token::TokenClient::new(&env, &token).balance(&guild_owner),
+        0
+    );
+
+    let history = client.get_transaction_history(&treasury_id, &10u32);
+    let refund = history.get(history.len() - 1).unwrap();
+    assert_eq!(refund.tx_type, TransactionType::Deposit);
+    assert_eq!(refund.amount, 600);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #321)")] // BountyNotFundable
+fn test_bounty_funding_fails_once_bounty_is_fully_funded() {
+    let env = setup_env();
+    let guild_owner = Address::generate(&env);
+
+    set_ledger_timestamp(&env, 1000);
+    env.mock_all_auths();
+
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+    let (guild_id, treasury_id, token, signer) = setup_bounty_funding(&env, &client, &guild_owner);
+
+    let bounty_id = client.create_bounty(
+        &guild_id,
+        &guild_owner,
+        &String::from_str(&env, "Task"),
+        &String::from_str(&env, "Description"),
+        &600i128,
+        &token,
+        &(1000 + 30 * 24 * 60 * 60),
+        &AssignmentMode::FirstCome,
+    );
+    let tx_id = client.propose_bounty_funding(
+        &treasury_id,
+        &guild_owner,
+        &bounty_id,
+        &600i128,
+        &String::from_str(&env, "fund bounty"),
+    );
+    client.approve_transaction(&tx_id, &signer);
+
+    // someone else covers the reward before the transaction runs
+    let funder = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &token).mint(&funder, &600);
+    client.fund_bounty(&bounty_id, &funder, &600i128);
+
+    client.execute_transaction(&tx_id, &guild_owner);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #319)")] // GuildMismatch
+fn test_bounty_funding_rejects_other_guild_bounty() {
+    let env = setup_env();
+    let guild_owner = Address::generate(&env);
+
+    set_ledger_timestamp(&env, 1000);
+    env.mock_all_auths();
+
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+    let (_guild_id, treasury_id, token, _signer) =
+        setup_bounty_funding(&env, &client, &guild_owner);
+
+    let other_guild = setup_guild(&client, &env, &guild_owner);
+    let bounty_id = client.create_bounty(
+        &other_guild,
+        &guild_owner,
+        &String::from_str(&env, "Task"),
+        &String::from_str(&env, "Description"),
+        &600i128,
+        &token,
+        &(1000 + 30 * 24 * 60 * 60),
+        &AssignmentMode::FirstCome,
+    );
+
+    client.propose_bounty_funding(
+        &treasury_id,
+        &guild_owner,
+        &bounty_id,
+        &600i128,
+        &String::from_str(&env, "fund bounty"),
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #322)")] // NotGuildAdmin
+fn test_bounty_funding_requires_guild_admin() {
+    let env = setup_env();
+    let guild_owner = Address::generate(&env);
+
+    set_ledger_timestamp(&env, 1000);
+    env.mock_all_auths();
+
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+    let (guild_id, treasury_id, token, signer) = setup_bounty_funding(&env, &client, &guild_owner);
+
+    let bounty_id = client.create_bounty(
+        &guild_id,
+        &guild_owner,
+        &String::from_str(&env, "Task"),
+        &String::from_str(&env, "Description"),
+        &600i128,
+        &token,
+        &(1000 + 30 * 24 * 60 * 60),
+        &AssignmentMode::FirstCome,
+    );
+
+    // A signer without an admin role in the guild cannot direct funds
+    client.propose_bounty_funding(
+        &treasury_id,
+        &signer,
+        &bounty_id,
+        &600i128,
+        &String::from_str(&env, "fund bounty"),
+    );
+}
+
+#[test]
+fn test_fees_withheld_from_bounty_release() {
+    let env = setup_env();
+    let guild_owner = Address::generate(&env);
+    let claimer = Address::generate(&env);
+    let platform = Address::generate(&env);
+
+    set_ledger_timestamp(&env, 1000);
+    env.mock_all_auths();
+
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+    let (guild_id, treasury_id, token, _signer) = setup_bounty_funding(&env, &client, &guild_owner);
+
+    client.set_guild_fee(&guild_id, &guild_owner, &treasury_id, &500u32);
+    client.set_platform_fee(&100u32, &platform);
+
+    let bounty_id = client.create_bounty(
+        &guild_id,
+        &guild_owner,
+        &String::from_str(&env, "Task"),
+        &String::from_str(&env, "Description"),
+        &1000i128,
+        &token,
+        &(1000 + 30 * 24 * 60 * 60),
+        &AssignmentMode::FirstCome,
+    );
+    token::StellarAssetClient::new(&env, &token).mint(&guild_owner, &1000);
+    client.fund_bounty(&bounty_id, &guild_owner, &1000i128);
+    client.claim_bounty(&bounty_id, &claimer);
+    client.submit_work(
+        &bounty_id,
+        &String::from_str(&env, "https://example.com/pr/1"),
+    );
+    client.approve_completion(&bounty_id, &guild_owner);
+    client.release_escrow(&bounty_id);
+
+    // 5% to the guild treasury, 1% to the platform, the rest to the claimer
+    let token_client = token::TokenClient::new(&env, &token);
+    assert_eq!(token_client.balance(&claimer), 940);
+    assert_eq!(token_client.balance(&platform), 10);
+    assert_eq!(
+        client.get_treasury_balance(&treasury_id, &Some(token.clone())),
+        1050
+    );
+}
+
+#[test]
+fn test_fees_withheld_from_contest_prizes() {
+    let env = setup_env();
+    let guild_owner = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let platform = Address::generate(&env);
+
+    set_ledger_timestamp(&env, 1000);
+    env.mock_all_auths();
+
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+    let (guild_id, treasury_id, token, _signer) = setup_bounty_funding(&env, &client, &guild_owner);
+
+    client.set_guild_fee(&guild_id, &guild_owner, &treasury_id, &500u32);
+    client.set_platform_fee(&100u32, &platform);
+
+    let terms = ContestTerms {
+        submission_deadline: 2000,
+        prize_percentages: soroban_sdk::vec![&env, 50u32, 30u32, 20u32],
+    };
+    let bounty_id = client.create_bounty(
+        &guild_id,
+        &guild_owner,
+        &String::from_str(&env, "Hackathon"),
+        &String::from_str(&env, "Description"),
+        &1000i128,
+        &token,
+        &5000u64,
+        &AssignmentMode::Contest(terms),
+    );
+    token::StellarAssetClient::new(&env, &token).mint(&guild_owner, &1000);
+    client.fund_bounty(&bounty_id, &guild_owner, &1000i128);
+
+    let url = String::from_str(&env, "https://example.com/entry");
+    client.submit_contest_entry(&bounty_id, &alice, &url);
+    client.submit_contest_entry(&bounty_id, &bob, &url);
+
+    set_ledger_timestamp(&env, 2001);
+    client.judge_contest(
+        &bounty_id,
+        &guild_owner,
+        &soroban_sdk::vec![&env, bob.clone(), alice.clone()],
+    );
+
+    // Each prize pays 5% to the guild treasury and 1% to the platform; the
+    // unawarded third tier is refunded in full
+    let token_client = token::TokenClient::new(&env, &token);
+    assert_eq!(token_client.balance(&bob), 470);
+    assert_eq!(token_client.balance(&alice), 282);
+    assert_eq!(token_client.balance(&guild_owner), 200);
+    assert_eq!(token_client.balance(&platform), 8);
+    assert_eq!(
+        client.get_treasury_balance(&treasury_id, &Some(token.clone())),
+        1040
+    );
+}
+
+#[test]
+fn test_fees_withheld_from_dispute_award() {
+    let env = setup_env();
+    let guild_owner = Address::generate(&env);
+    let claimer = Address::generate(&env);
+    let arbiter = Address::generate(&env);
+    let platform = Address::generate(&env);
+
+    set_ledger_timestamp(&env, 1000);
+    env.mock_all_auths();
+
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+    let (guild_id, treasury_id, token, _signer) = setup_bounty_funding(&env, &client, &guild_owner);
+
+    client.set_guild_fee(&guild_id, &guild_owner, &treasury_id, &500u32);
+    client.set_platform_fee(&100u32, &platform);
+
+    let bounty_id = client.create_bounty(
+        &guild_id,
+        &guild_owner,
+        &String::from_str(&env, "Task"),
+        &String::from_str(&env, "Description"),
+        &1000i128,
+        &token,
+        &(1000 + 30 * 24 * 60 * 60),
+        &AssignmentMode::FirstCome,
+    );
+    token::StellarAssetClient::new(&env, &token).mint(&guild_owner, &1000);
+    client.fund_bounty(&bounty_id, &guild_owner, &1000i128);
+    client.claim_bounty(&bounty_id, &claimer);
+    client.submit_work(
+        &bounty_id,
+        &String::from_str(&env, "https://example.com/pr/1"),
+    );
+
+    client.set_bounty_arbiters(
+        &guild_id,
+        &guild_owner,
+        &soroban_sdk::vec![&env, arbiter.clone()],
+    );
+    client.open_bounty_dispute(
+        &bounty_id,
+        &claimer,
+        &String::from_str(&env, "Delivered as specified"),
+    );
+    client.rule_bounty_dispute(&bounty_id, &arbiter, &60u32);
+
+    // Fees come out of the claimer's 600; the funder's 400 is refunded in full
+    let token_client = token::TokenClient::new(&env, &token);
+    assert_eq!(token_client.balance(&claimer), 564);
+    assert_eq!(token_client.balance(&guild_owner), 400);
+    assert_eq!(token_client.balance(&platform), 6);
+    assert_eq!(
+        client.get_treasury_balance(&treasury_id, &Some(token.clone())),
+        1030
+    );
+}
+
+#[test]
+fn test_guild_fee_withheld_from_milestone_payment() {
+    let env = setup_env();
+    let guild_owner = Address::generate(&env);
+    let contributor = Address::generate(&env);
+
+    set_ledger_timestamp(&env, 1000);
+    env.mock_all_auths();
+
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+    let (guild_id, treasury_id, token, _signer) = setup_bounty_funding(&env, &client, &guild_owner);
+
+    client.set_guild_fee(&guild_id, &guild_owner, &treasury_id, &1_000u32);
+
+    let mut milestones: Vec<MilestoneInput> = Vec::new(&env);
+    milestones.push_back(MilestoneInput {
+        title: String::from_str(&env, "M1"),
+        description: String::from_str(&env, ""),
+        payment_amount: 600,
+        deadline: 1000 + 86400,
+    });
+    client.create_project(
+        &guild_id,
+        &contributor,
+        &milestones,
+        &600i128,
+        &treasury_id,
+        &Some(token.clone()),
+        &false,
+    );
+
+    client.start_milestone(&1u64, &contributor);
+    client.submit_milestone(&1u64, &String::from_str(&env, "https://example.com/pr/2"));
+    client.approve_milestone(&1u64, &guild_owner);
+
+    // The 10% guild fee never leaves the treasury
+    assert_eq!(
+        token::TokenClient::new(&env, &token).balance(&contributor),
+        540
+    );
+    assert_eq!(
+        client.get_treasury_balance(&treasury_id, &Some(token.clone())),
+        460
+    );
+}
+
+#[test]
+fn test_native_milestone_payment_carries_no_platform_fee() {
+    let env = setup_env();
+    let guild_owner = Address::generate(&env);
+    let contributor = Address::generate(&env);
+    let platform = Address::generate(&env);
+
+    set_ledger_timestamp(&env, 1000);
+    env.mock_all_auths();
+
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+    let (guild_id, treasury_id, _token, _signer) =
+        setup_bounty_funding(&env, &client, &guild_owner);
+    client.deposit_treasury(&treasury_id, &guild_owner, &1000i128, &None);
+
+    client.set_guild_fee(&guild_id, &guild_owner, &treasury_id, &1_000u32);
+    client.set_platform_fee(&100u32, &platform);
+
+    let mut milestones: Vec<MilestoneInput> = Vec::new(&env);
+    milestones.push_back(MilestoneInput {
+        title: String::from_str(&env, "M1"),
+        description: String::from_str(&env, ""),
+        payment_amount: 600,
+        deadline: 1000 + 86400,
+    });
+    client.create_project(
+        &guild_id,
+        &contributor,
+        &milestones,
+        &600i128,
+        &treasury_id,
+        &None,
+        &false,
+    );
+
+    client.start_milestone(&1u64, &contributor);
+    client.submit_milestone(&1u64, &String::from_str(&env, "https://example.com/pr/2"));
+    client.approve_milestone(&1u64, &guild_owner);
+
+    // Only the guild fee is withheld; it stays in the treasury
+    let topics: Vec<Val> = (String::from_str(&env, "MilestonePaymentReleased"),).into_val(&env);
+    let (_, _, data) = env
+        .events()
+        .all()
+        .iter()
+        .find(|(_, event_topics, _)| *event_topics == topics)
+        .unwrap();
+    let released = MilestonePaymentReleasedEvent::try_from_val(&env, &data).unwrap();
+    assert_eq!(released.guild_fee, 60);
+    assert_eq!(released.platform_fee, 0);
+    assert_eq!(released.amount, 540);
+    assert_eq!(client.get_treasury_balance(&treasury_id, &None), 460);
+}
 
-        let guild_id = setup_guild(&client, &env, &owner);
-        let (treasury_id, owner, signer1, _signer2) = create_treasury(&env, &client, guild_id);
+#[test]
+#[should_panic(expected = "Error(Contract, #323)")] // InvalidFee
+fn test_guild_fee_above_max_fails() {
+    let env = setup_env();
+    let guild_owner = Address::generate(&env);
 
-        client.deposit_treasury(&treasury_id, &owner, &1000i128, &None);
+    set_ledger_timestamp(&env, 1000);
+    env.mock_all_auths();
 
-        // pause
-        client.emergency_pause(&treasury_id, &signer1, &true);
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+    let (guild_id, treasury_id, _token, _signer) =
+        setup_bounty_funding(&env, &client, &guild_owner);
 
-        let recipient = Address::generate(&env);
-
-        let reason = String::from_str(&env, "after pause");
-        client.propose_withdrawal(&treasury_id, &signer1, &recipient, &100i128, &None, &reason);
-    }
+    client.set_guild_fee(&guild_id, &guild_owner, &treasury_id, &2_001u32);
 }
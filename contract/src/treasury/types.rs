@@ -1,12 +1,37 @@
 use soroban_sdk::{contracttype, contracterror, Address, Env, Map, String, Vec};
 
 /// Error types for treasury operations
+///
+/// Codes live in the 300-399 range so they never collide with the errors of
+/// other modules when surfaced to clients.
 #[contracterror]
 #[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
 #[repr(u32)]
 pub enum TreasuryError {
-    BudgetExceeded = 1,
-    AllowanceExceeded = 2,
+    BudgetExceeded = 300,
+    AllowanceExceeded = 301,
+    TreasuryNotFound = 302,
+    TransactionNotFound = 303,
+    InvalidAmount = 304,
+    TreasuryPaused = 305,
+    NoSigners = 306,
+    InvalidThreshold = 307,
+    ThresholdTooLow = 308,
+    NotSigner = 309,
+    DuplicateApproval = 310,
+    TransactionNotApprovable = 311,
+    TransactionNotExecutable = 312,
+    TransactionNotApproved = 313,
+    RecipientRequired = 314,
+    InsufficientBalance = 315,
+    CannotExecuteDeposit = 316,
+    OnlyOwner = 317,
+    AllowanceAdminNotSigner = 318,
+    GuildMismatch = 319,
+    BountyNotFound = 320,
+    BountyNotFundable = 321,
+    NotGuildAdmin = 322,
+    InvalidFee = 323,
 }
 
 #[contracttype]
@@ -87,6 +112,29 @@ pub struct Allowance {
 
 // Events
 
+/// Fee a guild takes from its bounty and milestone payouts
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GuildFee {
+    /// Treasury the fee is deposited into
+    pub treasury_id: u64,
+    /// Fee in basis points of each payout
+    pub fee_bps: u32,
+}
+
+/// Fees withheld from a single payout
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct PayoutFees {
+    pub guild_fee: i128,
+    pub platform_fee: i128,
+}
+
+impl PayoutFees {
+    pub fn total(&self) -> i128 {
+        self.guild_fee + self.platform_fee
+    }
+}
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct TreasuryInitializedEvent {
@@ -115,6 +163,24 @@ pub struct WithdrawalProposedEvent {
     pub token: Option<Address>,
 }
 
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BountyFundingProposedEvent {
+    pub treasury_id: u64,
+    pub tx_id: u64,
+    pub proposer: Address,
+    pub bounty_id: u64,
+    pub amount: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GuildFeeUpdatedEvent {
+    pub guild_id: u64,
+    pub treasury_id: u64,
+    pub fee_bps: u32,
+}
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct TransactionApprovedEvent {
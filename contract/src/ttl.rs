@@ -0,0 +1,156 @@
+//! Storage TTL Policy
+//!
+//! Persistent entries on Soroban are archived once their time-to-live runs
+//! out, and an archived guild, escrow or treasury is unreachable until someone
+//! restores it. Every storage module routes its persistent reads and writes
+//! through the helpers below so that each touched entry is extended according
+//! to a single, configurable policy. Counters live in instance storage, which
+//! is extended together with the contract instance itself.
+
+use soroban_sdk::{contracttype, Env, IntoVal, TryFromVal, Val};
+
+/// Approximate number of ledgers closed per day (one every ~5 seconds)
+pub const DAY_IN_LEDGERS: u32 = 17_280;
+
+/// Default TTL below which a persistent entry is extended on access
+pub const DEFAULT_PERSISTENT_THRESHOLD: u32 = 30 * DAY_IN_LEDGERS;
+/// Default TTL a persistent entry is extended to
+pub const DEFAULT_PERSISTENT_EXTEND_TO: u32 = 60 * DAY_IN_LEDGERS;
+/// Default TTL below which the contract instance is extended
+pub const DEFAULT_INSTANCE_THRESHOLD: u32 = 7 * DAY_IN_LEDGERS;
+/// Default TTL the contract instance is extended to
+pub const DEFAULT_INSTANCE_EXTEND_TO: u32 = 30 * DAY_IN_LEDGERS;
+
+/// Maximum positions of each index a single `bump_guild` call extends
+pub const MAX_BUMP_BATCH: u32 = 50;
+
+/// Thresholds (in ledgers) used when extending storage entries
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TtlConfig {
+    pub persistent_threshold: u32,
+    pub persistent_extend_to: u32,
+    pub instance_threshold: u32,
+    pub instance_extend_to: u32,
+}
+
+impl Default for TtlConfig {
+    fn default() -> Self {
+        TtlConfig {
+            persistent_threshold: DEFAULT_PERSISTENT_THRESHOLD,
+            persistent_extend_to: DEFAULT_PERSISTENT_EXTEND_TO,
+            instance_threshold: DEFAULT_INSTANCE_THRESHOLD,
+            instance_extend_to: DEFAULT_INSTANCE_EXTEND_TO,
+        }
+    }
+}
+
+#[contracttype]
+pub enum TtlStorageKey {
+    Config,
+}
+
+/// Get the active TTL policy, falling back to the defaults
+pub fn get_config(env: &Env) -> TtlConfig {
+    env.storage()
+        .instance()
+        .get(&TtlStorageKey::Config)
+        .unwrap_or_default()
+}
+
+/// Replace the active TTL policy
+pub fn set_config(env: &Env, config: &TtlConfig) {
+    env.storage().instance().set(&TtlStorageKey::Config, config);
+    extend_instance(env);
+}
+
+/// Extend the contract instance (and with it every instance-stored counter)
+pub fn extend_instance(env: &Env) {
+    let config = get_config(env);
+    env.storage()
+        .instance()
+        .extend_ttl(config.instance_threshold, config.instance_extend_to);
+}
+
+/// Extend a persistent entry that is known to exist
+pub fn extend_persistent<K>(env: &Env, key: &K)
+where
+    K: IntoVal<Env, Val>,
+{
+    let config = get_config(env);
+    env.storage().persistent().extend_ttl(
+        key,
+        config.persistent_threshold,
+        config.persistent_extend_to,
+    );
+}
+
+/// Extend a persistent entry if it exists; returns whether it did
+pub fn extend_persistent_if_present<K>(env: &Env, key: &K) -> bool
+where
+    K: IntoVal<Env, Val>,
+{
+    let present = env.storage().persistent().has(key);
+    if present {
+        extend_persistent(env, key);
+    }
+    present
+}
+
+/// Read a persistent entry, extending its TTL when it exists
+pub fn get_persistent<K, V>(env: &Env, key: &K) -> Option<V>
+where
+    K: IntoVal<Env, Val>,
+    V: TryFromVal<Env, Val>,
+{
+    let value = env.storage().persistent().get(key);
+    if value.is_some() {
+        extend_persistent(env, key);
+    }
+    value
+}
+
+/// Write a persistent entry and extend its TTL
+pub fn set_persistent<K, V>(env: &Env, key: &K, value: &V)
+where
+    K: IntoVal<Env, Val>,
+    V: IntoVal<Env, Val>,
+{
+    env.storage().persistent().set(key, value);
+    extend_persistent(env, key);
+}
+
+/// Read an instance-stored counter
+pub fn get_counter<K>(env: &Env, key: &K, default: u64) -> u64
+where
+    K: IntoVal<Env, Val>,
+{
+    extend_instance(env);
+    env.storage().instance().get(key).unwrap_or(default)
+}
+
+/// Write an instance-stored counter
+pub fn set_counter<K>(env: &Env, key: &K, value: u64)
+where
+    K: IntoVal<Env, Val>,
+{
+    env.storage().instance().set(key, &value);
+    extend_instance(env);
+}
+
+/// Move a counter written by an older release from persistent storage into
+/// instance storage, keeping whichever value is higher
+pub fn promote_counter<L, K>(env: &Env, legacy_key: &L, key: &K)
+where
+    L: IntoVal<Env, Val>,
+    K: IntoVal<Env, Val>,
+{
+    let legacy: Option<u64> = env.storage().persistent().get(legacy_key);
+    if let Some(legacy) = legacy {
+        let current: u64 = env.storage().instance().get(key).unwrap_or(0);
+        if legacy > current {
+            set_counter(env, key, legacy);
+        }
+        env.storage().persistent().remove(legacy_key);
+    }
+}